@@ -13,6 +13,7 @@ use nebula_abe::{
     error::ABEError,
     random::miracl::MiraclRng,
     schemes::isabella24::{decrypt, encrypt, AuthorityPublicKey, Ciphertext, GlobalParams, UserSecretKey},
+    utils::epoch::with_epoch_clauses,
 };
 use nebula_policy::pest::PolicyLanguage;
 use wasm_bindgen::prelude::*;
@@ -128,6 +129,21 @@ pub fn nebula_encrypt(gp: &str, pks: Vec<String>, policy: &str, data: &str) -> R
     Ok(STANDARD.encode(&ciphertext))
 }
 
+/// Encrypts like [`nebula_encrypt`], binding the ciphertext to the current epochs of the authorities referenced by the
+/// policy. `epoch_attributes` are the epoch attributes (`<key>=<epoch>@<authority>-<workspace>#<version>`) of the
+/// authorities serving `GET /workspaces/<workspace>/epoch`.
+#[wasm_bindgen]
+pub fn nebula_encrypt_with_epochs(
+    gp: &str,
+    pks: Vec<String>,
+    policy: &str,
+    data: &str,
+    epoch_attributes: Vec<String>,
+) -> Result<String, NebulaError> {
+    let policy = with_epoch_clauses(policy, &epoch_attributes)?;
+    nebula_encrypt(gp, pks, &policy, data)
+}
+
 #[wasm_bindgen]
 pub fn nebula_decrypt(gp: &str, sk: Vec<String>, ct: &str) -> Result<String, NebulaError> {
     let sk = UserSecretKey::<Bn462Curve>::sum(
@@ -0,0 +1,27 @@
+use nebula_miracl::{
+    bls48581::{
+        big::{BIG, MODBYTES, NLEN},
+        ecp::ECP,
+        ecp8::ECP8,
+        fp48::FP48,
+        pair8,
+        rom::CURVE_ORDER,
+    },
+    hash256::HASH256,
+};
+
+use crate::define_miracl_pairing_curve;
+define_miracl_pairing_curve!(
+    Bls48581Curve,
+    Bls48581Field,
+    BIG,
+    ECP,
+    ECP8,
+    FP48,
+    pair8,
+    HASH256,
+    48,
+    CURVE_ORDER,
+    NLEN,
+    MODBYTES
+);
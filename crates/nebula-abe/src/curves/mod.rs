@@ -1,5 +1,6 @@
 pub mod bls24479;
 pub mod bls48556;
+pub mod bls48581;
 pub mod bn462;
 
 #[macro_use]
@@ -11,11 +12,84 @@ use serde::{Deserialize, Serialize};
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
 use std::{
+    fmt::Display,
     iter::Sum,
     ops::{Add, Div, Mul, Neg, Sub},
+    str::FromStr,
 };
 
-use crate::random::Random;
+use crate::{error::UnknownCurveError, random::Random};
+
+/// Pairing curves on which the global parameters can be generated. Keys and ciphertexts can only be used with the
+/// global parameters of the same curve.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Curve {
+    #[default]
+    Bn462,
+    Bls24479,
+    Bls48556,
+    Bls48581,
+}
+
+impl Curve {
+    pub const ALL: [Curve; 4] = [Curve::Bn462, Curve::Bls24479, Curve::Bls48556, Curve::Bls48581];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Curve::Bn462 => "BN462",
+            Curve::Bls24479 => "BLS24479",
+            Curve::Bls48556 => "BLS48556",
+            Curve::Bls48581 => "BLS48581",
+        }
+    }
+}
+
+impl Display for Curve {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Curve {
+    type Err = UnknownCurveError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Curve::ALL.into_iter().find(|curve| curve.as_str() == s).ok_or_else(|| UnknownCurveError(s.to_owned()))
+    }
+}
+
+/// Evaluates the expression with the type alias bound to the [`PairingCurve`] implementation of the [`Curve`].
+///
+/// ```
+/// use nebula_abe::{curves::Curve, with_curve};
+///
+/// let name = with_curve!(Curve::Bls24479, C => std::any::type_name::<C>());
+/// assert!(name.ends_with("Bls24479Curve"));
+/// ```
+#[macro_export]
+macro_rules! with_curve {
+    ($curve:expr, $alias:ident => $body:expr) => {
+        match $curve {
+            $crate::curves::Curve::Bn462 => {
+                type $alias = $crate::curves::bn462::Bn462Curve;
+                $body
+            }
+            $crate::curves::Curve::Bls24479 => {
+                type $alias = $crate::curves::bls24479::Bls24479Curve;
+                $body
+            }
+            $crate::curves::Curve::Bls48556 => {
+                type $alias = $crate::curves::bls48556::Bls48556Curve;
+                $body
+            }
+            $crate::curves::Curve::Bls48581 => {
+                type $alias = $crate::curves::bls48581::Bls48581Curve;
+                $body
+            }
+        }
+    };
+}
 
 pub trait Pow<Rhs = Self> {
     type Output;
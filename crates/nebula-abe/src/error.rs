@@ -56,3 +56,7 @@ pub enum AESGCMErrorKind {
     #[error("invalid nonce size (expected {expected} bytes, got {actual} bytes)")]
     NonceSizeMismatch { expected: usize, actual: usize },
 }
+
+#[derive(Error, Debug)]
+#[error("unknown curve `{0}`")]
+pub struct UnknownCurveError(pub String);
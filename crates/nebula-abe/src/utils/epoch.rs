@@ -0,0 +1,55 @@
+use std::collections::BTreeSet;
+
+use nebula_policy::pest::{parse, PolicyLanguage};
+
+use crate::{
+    error::{ABEError, InvalidAttributeKind, InvalidPolicyErrorKind},
+    utils::attribute::unpack_attribute,
+};
+
+/// Requires the current epoch attribute of each authority referenced by the human policy, so that the ciphertext can
+/// only be decrypted with user keys issued for the epoch. `epoch_attributes` are in the form of
+/// `<key>=<epoch>@<authority>-<workspace>#<version>`, and the ones of authorities not referenced are ignored.
+pub fn with_epoch_clauses<S: AsRef<str>>(policy: &str, epoch_attributes: &[S]) -> Result<String, ABEError> {
+    let (_, attributes) = parse(policy, PolicyLanguage::HumanPolicy).map_err(InvalidPolicyErrorKind::from)?;
+    let authorities = attributes
+        .keys()
+        .map(|attribute| Ok(unpack_attribute(attribute).map_err(InvalidAttributeKind::from)?.authority))
+        .collect::<Result<BTreeSet<_>, ABEError>>()?;
+
+    let mut clauses = vec![];
+    for epoch_attribute in epoch_attributes {
+        let epoch_attribute = epoch_attribute.as_ref();
+        if authorities.contains(&unpack_attribute(epoch_attribute).map_err(InvalidAttributeKind::from)?.authority) {
+            clauses.push(format!("\"{epoch_attribute}\""));
+        }
+    }
+    if clauses.is_empty() {
+        return Ok(policy.to_owned());
+    }
+
+    Ok(format!("({policy}) AND {}", clauses.join(" AND ")))
+}
+
+#[cfg(test)]
+mod test {
+    use super::with_epoch_clauses;
+
+    #[test]
+    fn when_policy_references_authority_with_epoch_then_epoch_clause_is_added() {
+        let policy = r#""group=admin@A-default#1" OR "group=infra@B-default#2""#;
+
+        let result = with_epoch_clauses(policy, &["epoch=2024-10@A-default#1"]).unwrap();
+
+        assert_eq!(result, format!(r#"({policy}) AND "epoch=2024-10@A-default#1""#));
+    }
+
+    #[test]
+    fn when_policy_does_not_reference_authority_with_epoch_then_policy_is_unchanged() {
+        let policy = r#""group=admin@A-default#1""#;
+
+        let result = with_epoch_clauses(policy, &["epoch=2024-10@C-default#1", "epoch=2024-10@A-default#2"]).unwrap();
+
+        assert_eq!(result, policy);
+    }
+}
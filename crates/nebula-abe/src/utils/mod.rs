@@ -1,3 +1,4 @@
 pub mod aes;
 pub mod attribute;
+pub mod epoch;
 pub mod secret_shares;
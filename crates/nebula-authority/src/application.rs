@@ -1,16 +1,38 @@
 use std::sync::Arc;
 
-use nebula_token::auth::jwks_discovery::JwksDiscovery;
+use nebula_telemetry::health::Readiness;
+use nebula_token::auth::{issuer::TrustedIssuer, jwks_discovery::JwksDiscovery};
 
 use crate::domain::authority::Authority;
 
 pub struct Application {
     pub authority: Authority,
-    pub jwks_discovery: Arc<dyn JwksDiscovery + Send + Sync>,
+    pub jwks_discovery: Option<Arc<dyn JwksDiscovery + Send + Sync>>,
+    pub trusted_issuers: Vec<TrustedIssuer>,
 }
 
 impl Application {
-    pub fn new(authority: Authority, jwks_discovery: Arc<dyn JwksDiscovery + Send + Sync>) -> Self {
-        Self { authority, jwks_discovery }
+    pub fn new(
+        authority: Authority,
+        jwks_discovery: Option<Arc<dyn JwksDiscovery + Send + Sync>>,
+        trusted_issuers: Vec<TrustedIssuer>,
+    ) -> Self {
+        Self { authority, jwks_discovery, trusted_issuers }
+    }
+
+    pub async fn readiness(&self) -> Readiness {
+        let mut jwks_ready = true;
+        for jwks_discovery in self
+            .jwks_discovery
+            .iter()
+            .chain(self.trusted_issuers.iter().map(|trusted_issuer| &trusted_issuer.jwks_discovery))
+        {
+            jwks_ready &= jwks_discovery.jwks().await.is_ok();
+        }
+
+        Readiness::new()
+            .check("database", self.authority.ping_database().await.is_ok())
+            .check("jwks", jwks_ready)
+            .check("shield", !self.authority.is_armored().await)
     }
 }
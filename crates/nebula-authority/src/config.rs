@@ -1,26 +1,61 @@
-use std::path::PathBuf;
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
 
-use config::{Config, File, FileFormat};
-use nebula_config_path::config_dir;
+use config::Config;
+use nebula_config::{config_file, Environment, ENV_PREFIX};
+use nebula_telemetry::trace::TracingConfig;
+use nebula_tls::{ClientTlsConfig, TlsConfig};
+use nebula_token::{claim::ClaimMapping, rate_limit::RateLimitQuota};
 use serde::Deserialize;
 use url::Url;
 
+use crate::domain::epoch::EpochPeriod;
+
 #[derive(Deserialize, Debug)]
-pub(crate) struct ApplicationConfig {
+pub struct ApplicationConfig {
     pub port: u16,
     pub storage: StorageConfig,
     pub backbone: BackboneConfig,
     pub authority: AuthorityConfig,
-    pub jwks_url: Url,
+    /// JWKS of the authorization server, which is trusted for tokens of any issuer not in `trusted_issuers`.
+    pub jwks_url: Option<Url>,
     pub jwks_refresh_interval: Option<u64>,
+    /// TLS settings of the client fetching `jwks_url`.
+    pub jwks_tls: Option<ClientTlsConfig>,
+    #[serde(default)]
+    pub trusted_issuers: Vec<TrustedIssuerConfig>,
     pub disarm_key_shares: Option<Vec<String>>,
     pub path_prefix: Option<String>,
     pub cors: Option<CorsConfig>,
+    pub rate_limit: Option<RateLimitConfig>,
+    pub tracing: Option<TracingConfig>,
+    /// Serves `GET /metrics` on this port, apart from the API. Metrics are not served if not given.
+    pub metrics_port: Option<u16>,
+    pub tls: Option<TlsConfig>,
+}
+
+/// Issuer whose tokens the authority accepts, such as the IdP of the organisation running the authority.
+#[derive(Deserialize, Debug)]
+pub struct TrustedIssuerConfig {
+    /// `iss` claim of the tokens. Tokens of any issuer are accepted if not given, which is allowed for one issuer only.
+    pub issuer: Option<String>,
+    /// Audience which the `aud` claim of the tokens must contain.
+    pub audience: Option<String>,
+    pub jwks_url: Url,
+    pub jwks_refresh_interval: Option<u64>,
+    /// TLS settings of the client fetching `jwks_url`.
+    pub jwks_tls: Option<ClientTlsConfig>,
+    /// Workspaces the tokens are accepted for. Every workspace is allowed if not given.
+    pub workspaces: Option<HashSet<String>>,
+    #[serde(default)]
+    pub claim_mapping: ClaimMapping,
 }
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE", tag = "type")]
-pub(crate) enum StorageConfig {
+pub enum StorageConfig {
     File { path: String },
     Postgres(PostgresConfig),
 }
@@ -35,49 +70,151 @@ pub struct PostgresConfig {
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE", tag = "method")]
-pub(crate) enum PostgresAuthMethod {
+pub enum PostgresAuthMethod {
     Credential { username: String, password: Option<String> },
     RdsIamAuth { username: String },
 }
 
 #[derive(Deserialize, Debug)]
-pub(crate) struct BackboneConfig {
+pub struct BackboneConfig {
     pub host: Url,
+    pub tls: Option<ClientTlsConfig>,
 }
 
 #[derive(Deserialize, Debug)]
-pub(crate) struct AuthorityConfig {
+pub struct AuthorityConfig {
     pub name: String,
+    pub registration: Option<RegistrationConfig>,
+    pub key_rolling: Option<KeyRollingConfig>,
+    /// Attribute keys the authority is authoritative for. No attribute is issued if not given, unless
+    /// `issue_all_attributes` is set.
+    pub attributes: Option<HashMap<String, AttributeRuleConfig>>,
+    /// Issues every attribute resolved from the attribute source. Exclusive with `attributes`.
+    #[serde(default)]
+    pub issue_all_attributes: bool,
+    /// Source of the attributes of users. The attributes in the token are trusted if not given.
+    pub attribute_source: Option<AttributeSourceConfig>,
+    pub epoch: Option<EpochConfig>,
+    pub audit: Option<AuditConfig>,
+    pub auto_armor: Option<AutoArmorConfig>,
+    /// Refuses the user key requests of a user exceeding the limit within a window.
+    pub user_key_burst: Option<UserKeyBurstConfig>,
+}
+
+/// Registers the authority with the backbone on startup.
+#[derive(Deserialize, Debug, Clone)]
+pub struct RegistrationConfig {
+    /// URL at which the backbone reaches the authority.
+    pub host: Url,
+    /// Bootstrap tokens issued by the backbone, keyed by workspace name.
+    pub bootstrap_tokens: HashMap<String, String>,
+}
+
+/// Rolls the key pairs of the workspaces on a schedule.
+#[derive(Deserialize, Debug, Clone)]
+pub struct KeyRollingConfig {
+    /// Interval (in seconds) at which the schedules are checked.
+    pub check_interval: Option<u64>,
+    /// Rolling periods (in seconds), keyed by workspace name.
+    pub workspaces: HashMap<String, u64>,
+}
+
+/// Binds the user keys to epochs, so that a cached user key stops decrypting new secrets once the epoch rolls over.
+#[derive(Deserialize, Debug, Clone)]
+pub struct EpochConfig {
+    /// Key of the epoch attribute. Defaults to `epoch`.
+    pub attribute: Option<String>,
+    pub period: EpochPeriod,
+}
+
+/// Armors the key pair storage without a request, so that a disarmed authority left unattended or under attack is
+/// sealed again.
+#[derive(Deserialize, Debug, Clone)]
+pub struct AutoArmorConfig {
+    /// Armors after no user key has been requested for this long (in seconds).
+    pub idle_timeout: Option<u64>,
+}
+
+/// Limit of the user key requests of each user (gid) of a workspace.
+#[derive(Deserialize, Debug, Clone)]
+pub struct UserKeyBurstConfig {
+    pub max_requests: usize,
+    /// Length of the window (in seconds).
+    pub window: u64,
+}
+
+/// Audit log of the user key issuances, which is always kept in the storage.
+#[derive(Deserialize, Debug, Clone)]
+pub struct AuditConfig {
+    /// File to which each record is also appended as a line of JSON.
+    pub jsonl_path: Option<PathBuf>,
+}
+
+/// Constraints on the values of an attribute the authority issues.
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct AttributeRuleConfig {
+    /// Values allowed for the attribute.
+    pub values: Option<Vec<String>>,
+    /// Regular expression which the whole value must match.
+    pub pattern: Option<String>,
 }
 
 #[derive(Deserialize, Debug)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE", tag = "type")]
+pub enum AttributeSourceConfig {
+    Jwt,
+    /// TOML or JSON file mapping workspace names to gids to their attributes.
+    File {
+        path: PathBuf,
+    },
+    /// Lookup service, given the workspace name and the gid as query parameters.
+    Http {
+        url: Url,
+        timeout: Option<u64>,
+        tls: Option<ClientTlsConfig>,
+    },
+    /// Query given the gid as `$1` and the workspace name as `$2`, returning `key` and `value` columns.
+    Postgres {
+        database: PostgresConfig,
+        query: String,
+    },
+}
+
+#[derive(Deserialize, Debug, Clone)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE", tag = "type", content = "domains")]
-pub(crate) enum CorsConfig {
+pub enum CorsConfig {
     AllowAll,
     AllowList(Vec<String>),
 }
 
-pub(super) fn load_config(
-    path_override: Option<PathBuf>,
-    port_override: Option<u16>,
-) -> anyhow::Result<ApplicationConfig> {
-    let config_file_path = if let Some(path_override) = path_override {
-        path_override
-    } else {
-        let user_config_dir = config_dir().expect("Failed to get user config directory");
-        let nebula_config_dir = user_config_dir.join("nebula");
-        if !nebula_config_dir.exists() {
-            std::fs::create_dir_all(&nebula_config_dir)?;
-        }
-        nebula_config_dir.join("authority_config.toml")
-    };
-
-    let config: ApplicationConfig = Config::builder()
-        .add_source(File::from(config_file_path).format(FileFormat::Toml))
+#[derive(Deserialize, Debug, Clone)]
+pub struct RateLimitConfig {
+    #[serde(flatten)]
+    pub default: RateLimitQuota,
+    #[serde(default)]
+    pub workspaces: HashMap<String, RateLimitQuota>,
+}
+
+pub fn load_config(path_override: Option<PathBuf>, port_override: Option<u16>) -> anyhow::Result<ApplicationConfig> {
+    Ok(build_config(path_override, port_override)?.try_deserialize()?)
+}
+
+pub fn dump_config(path_override: Option<PathBuf>, port_override: Option<u16>) -> anyhow::Result<String> {
+    nebula_config::dump(
+        &build_config(path_override, port_override)?,
+        &["disarm_key_shares", "authority.registration.bootstrap_tokens"],
+    )
+}
+
+/// Merges the configuration sources. Later sources take precedence: config file, `NEBULA_*` environment variables and
+/// command line arguments. The config file is optional unless its path is given.
+fn build_config(path_override: Option<PathBuf>, port_override: Option<u16>) -> anyhow::Result<Config> {
+    let config = Config::builder()
+        .add_source(config_file(path_override, "authority_config.toml")?)
+        .add_source(Environment::new(ENV_PREFIX))
         .set_default("port", 8090)?
         .set_override_option("port", port_override)?
-        .build()?
-        .try_deserialize()?;
+        .build()?;
 
     Ok(config)
 }
@@ -93,6 +93,7 @@ pub async fn connect_to_database(
     if let AuthMethod::RdsIamAuth { host: auth_host, port: auth_port, username } = auth {
         reassign_token_periodically_to_database(connection.clone(), auth_host.clone(), *auth_port, username.clone());
     };
+    nebula_telemetry::metrics::record_database_pool_periodically(connection.clone());
 
     Ok(connection)
 }
@@ -0,0 +1,45 @@
+use std::{fmt, time::Duration};
+
+use tokio::{sync::Mutex, time::Instant};
+
+use crate::config::AutoArmorConfig;
+
+/// Reason why the key pair storage is armored without being requested.
+#[derive(Debug, Clone, Copy)]
+pub enum ArmorTrigger {
+    Idle,
+}
+
+impl ArmorTrigger {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ArmorTrigger::Idle => "idle",
+        }
+    }
+}
+
+impl fmt::Display for ArmorTrigger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Tracks the activity of a disarmed authority to decide when to armor it again.
+pub struct AutoArmor {
+    pub idle_timeout: Option<Duration>,
+    last_activity: Mutex<Instant>,
+}
+
+impl AutoArmor {
+    pub fn new(config: &AutoArmorConfig) -> Self {
+        Self { idle_timeout: config.idle_timeout.map(Duration::from_secs), last_activity: Mutex::new(Instant::now()) }
+    }
+
+    pub async fn touch(&self) {
+        *self.last_activity.lock().await = Instant::now();
+    }
+
+    pub async fn last_activity(&self) -> Instant {
+        *self.last_activity.lock().await
+    }
+}
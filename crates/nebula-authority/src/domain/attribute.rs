@@ -0,0 +1,162 @@
+use std::{collections::HashMap, fmt};
+
+use anyhow::Result;
+use regex::Regex;
+
+use crate::config::AttributeRuleConfig;
+
+/// Attribute keys the authority is authoritative for. Only the attributes under these keys are certified in the user
+/// keys it issues, so that the authority does not vouch for attributes owned by other authorities.
+pub struct AttributeNamespace {
+    /// `None` if the authority is explicitly configured to issue every attribute.
+    rules: Option<HashMap<String, AttributeRule>>,
+}
+
+struct AttributeRule {
+    values: Option<Vec<String>>,
+    pattern: Option<Regex>,
+}
+
+/// Reason why an attribute is not issued.
+#[derive(Debug, PartialEq, Eq)]
+pub enum DroppedReason {
+    /// The authority is not authoritative for the attribute key.
+    OutOfNamespace,
+    /// The value is not one of the allowed values.
+    ValueNotAllowed,
+    /// The value does not match the pattern.
+    PatternMismatch,
+    /// The attribute key is reserved for the epoch, which the authority binds the user keys to.
+    ReservedForEpoch,
+}
+
+impl fmt::Display for DroppedReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DroppedReason::OutOfNamespace => write!(f, "the authority is not authoritative for the attribute key"),
+            DroppedReason::ValueNotAllowed => write!(f, "the value is not one of the allowed values"),
+            DroppedReason::PatternMismatch => write!(f, "the value does not match the pattern"),
+            DroppedReason::ReservedForEpoch => write!(f, "the attribute key is reserved for the epoch"),
+        }
+    }
+}
+
+impl AttributeNamespace {
+    pub fn new(config: &HashMap<String, AttributeRuleConfig>) -> Result<Self> {
+        let rules = config
+            .iter()
+            .map(|(key, rule)| {
+                // The whole value must match, so that a pattern such as `[1-5]` does not accept `15`.
+                let pattern =
+                    rule.pattern.as_ref().map(|pattern| Regex::new(&format!("^(?:{pattern})$"))).transpose()?;
+                Ok((key.clone(), AttributeRule { values: rule.values.clone(), pattern }))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self { rules: Some(rules) })
+    }
+
+    /// Namespace of every attribute key, for an authority which is configured to vouch for any attribute.
+    pub fn unrestricted() -> Self {
+        Self { rules: None }
+    }
+
+    /// Checks whether the attribute can be issued, returning the reason if it can't.
+    pub fn check(&self, key: &str, value: &str) -> std::result::Result<(), DroppedReason> {
+        let Some(rules) = &self.rules else {
+            return Ok(());
+        };
+        let rule = rules.get(key).ok_or(DroppedReason::OutOfNamespace)?;
+        if rule.values.as_ref().is_some_and(|values| !values.iter().any(|allowed| allowed == value)) {
+            return Err(DroppedReason::ValueNotAllowed);
+        }
+        if rule.pattern.as_ref().is_some_and(|pattern| !pattern.is_match(value)) {
+            return Err(DroppedReason::PatternMismatch);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use crate::config::AttributeRuleConfig;
+
+    use super::{AttributeNamespace, DroppedReason};
+
+    fn namespace(key: &str, rule: AttributeRuleConfig) -> AttributeNamespace {
+        AttributeNamespace::new(&HashMap::from([(key.to_owned(), rule)])).expect("namespace should be created")
+    }
+
+    fn values(values: &[&str]) -> AttributeRuleConfig {
+        AttributeRuleConfig { values: Some(values.iter().map(|value| value.to_string()).collect()), pattern: None }
+    }
+
+    fn pattern(pattern: &str) -> AttributeRuleConfig {
+        AttributeRuleConfig { values: None, pattern: Some(pattern.to_owned()) }
+    }
+
+    #[test]
+    fn when_key_is_not_declared_then_attribute_is_out_of_namespace() {
+        let namespace = namespace("department", AttributeRuleConfig::default());
+
+        assert_eq!(namespace.check("department", "engineering"), Ok(()));
+        assert_eq!(namespace.check("role", "admin"), Err(DroppedReason::OutOfNamespace));
+    }
+
+    #[test]
+    fn when_no_key_is_declared_then_no_attribute_is_issued() {
+        let namespace = AttributeNamespace::new(&HashMap::new()).expect("namespace should be created");
+
+        assert_eq!(namespace.check("department", "engineering"), Err(DroppedReason::OutOfNamespace));
+    }
+
+    #[test]
+    fn when_namespace_is_unrestricted_then_every_attribute_is_issued() {
+        let namespace = AttributeNamespace::unrestricted();
+
+        assert_eq!(namespace.check("department", "engineering"), Ok(()));
+        assert_eq!(namespace.check("role", "admin"), Ok(()));
+    }
+
+    #[test]
+    fn when_value_is_not_allowed_then_attribute_is_dropped() {
+        let namespace = namespace("department", values(&["engineering", "finance"]));
+
+        assert_eq!(namespace.check("department", "finance"), Ok(()));
+        assert_eq!(namespace.check("department", "hr"), Err(DroppedReason::ValueNotAllowed));
+    }
+
+    #[test]
+    fn when_pattern_matches_only_part_of_value_then_attribute_is_dropped() {
+        let namespace = namespace("employee_level", pattern("[1-5]"));
+
+        assert_eq!(namespace.check("employee_level", "3"), Ok(()));
+        assert_eq!(namespace.check("employee_level", "15"), Err(DroppedReason::PatternMismatch));
+        assert_eq!(namespace.check("employee_level", "3a"), Err(DroppedReason::PatternMismatch));
+    }
+
+    #[test]
+    fn when_pattern_has_alternation_then_whole_value_must_match_one_of_them() {
+        let namespace = namespace("location", pattern("seoul|tokyo"));
+
+        assert_eq!(namespace.check("location", "tokyo"), Ok(()));
+        assert_eq!(namespace.check("location", "seoul-north"), Err(DroppedReason::PatternMismatch));
+        assert_eq!(namespace.check("location", "north-tokyo"), Err(DroppedReason::PatternMismatch));
+    }
+
+    #[test]
+    fn when_key_is_only_prefix_of_declared_key_then_attribute_is_out_of_namespace() {
+        let namespace = namespace("department", AttributeRuleConfig::default());
+
+        assert_eq!(namespace.check("depart", "engineering"), Err(DroppedReason::OutOfNamespace));
+        assert_eq!(namespace.check("department_head", "alice"), Err(DroppedReason::OutOfNamespace));
+    }
+
+    #[test]
+    fn when_pattern_is_invalid_then_namespace_is_not_created() {
+        assert!(AttributeNamespace::new(&HashMap::from([("location".to_owned(), pattern("("))])).is_err());
+    }
+}
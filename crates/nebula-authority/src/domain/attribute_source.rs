@@ -0,0 +1,274 @@
+use std::{collections::HashMap, path::Path, sync::Arc, time::Duration};
+
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use nebula_tls::ClientTlsConfig;
+use reqwest::StatusCode;
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbBackend, Statement};
+use url::Url;
+
+/// Source of truth of the attributes of users, which the authority issues user keys for.
+#[async_trait]
+pub trait AttributeSource {
+    /// Resolves the attributes of the user with `gid`. `claimed` are the attributes in the token of the user, which
+    /// a source may trust or ignore.
+    async fn attributes(
+        &self,
+        workspace_name: &str,
+        gid: &str,
+        claimed: &HashMap<String, String>,
+    ) -> Result<HashMap<String, String>>;
+}
+
+/// Trusts the attributes in the token issued by the authorization server.
+pub struct JwtAttributeSource;
+
+#[async_trait]
+impl AttributeSource for JwtAttributeSource {
+    async fn attributes(
+        &self,
+        _workspace_name: &str,
+        _gid: &str,
+        claimed: &HashMap<String, String>,
+    ) -> Result<HashMap<String, String>> {
+        Ok(claimed.clone())
+    }
+}
+
+/// Looks the attributes up in a static mapping from workspace names to gids to attributes, loaded from a TOML or JSON
+/// file, so that a gid is issued different attributes in each workspace.
+pub struct FileAttributeSource {
+    attributes: HashMap<String, HashMap<String, HashMap<String, String>>>,
+}
+
+impl FileAttributeSource {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        let attributes = match path.extension().and_then(|extension| extension.to_str()) {
+            Some("json") => serde_json::from_str(&content)?,
+            Some("toml") => toml::from_str(&content)?,
+            _ => bail!("attribute mapping file({}) must be a TOML or JSON file", path.display()),
+        };
+
+        Ok(Self { attributes })
+    }
+}
+
+#[async_trait]
+impl AttributeSource for FileAttributeSource {
+    async fn attributes(
+        &self,
+        workspace_name: &str,
+        gid: &str,
+        _claimed: &HashMap<String, String>,
+    ) -> Result<HashMap<String, String>> {
+        Ok(self.attributes.get(workspace_name).and_then(|users| users.get(gid)).cloned().unwrap_or_default())
+    }
+}
+
+/// Looks the attributes up with `GET <url>?workspace=<workspace_name>&gid=<gid>`, which responds with a JSON object of
+/// string attributes, or `404 Not Found` for an unknown user.
+pub struct HttpAttributeSource {
+    client: reqwest::Client,
+    url: Url,
+}
+
+impl HttpAttributeSource {
+    pub fn new(url: Url, timeout: Duration, tls: Option<&ClientTlsConfig>) -> Result<Self> {
+        let mut builder = reqwest::Client::builder().timeout(timeout);
+        if let Some(tls) = tls {
+            builder = tls.apply(builder)?;
+        }
+        Ok(Self { client: builder.build()?, url })
+    }
+}
+
+#[async_trait]
+impl AttributeSource for HttpAttributeSource {
+    #[tracing::instrument(name = "attribute_source.http", skip(self, _claimed))]
+    async fn attributes(
+        &self,
+        workspace_name: &str,
+        gid: &str,
+        _claimed: &HashMap<String, String>,
+    ) -> Result<HashMap<String, String>> {
+        let response = nebula_telemetry::trace::traced(
+            self.client.get(self.url.clone()).query(&[("workspace", workspace_name), ("gid", gid)]),
+        )
+        .send()
+        .await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(HashMap::new());
+        }
+
+        Ok(response.error_for_status()?.json().await?)
+    }
+}
+
+/// Looks the attributes up with a query, which is given the gid as `$1` and the workspace name as `$2` and returns the
+/// attributes as rows of `key` and `value` text columns.
+pub struct PostgresAttributeSource {
+    database: Arc<DatabaseConnection>,
+    query: String,
+}
+
+impl PostgresAttributeSource {
+    pub fn new(database: Arc<DatabaseConnection>, query: String) -> Result<Self> {
+        // A query ignoring the workspace would issue the same attributes to a gid in every workspace.
+        if !query.contains("$2") {
+            bail!("attribute source query must filter by the workspace name given as $2");
+        }
+        Ok(Self { database, query })
+    }
+}
+
+#[async_trait]
+impl AttributeSource for PostgresAttributeSource {
+    #[tracing::instrument(name = "attribute_source.postgres", skip(self, _claimed))]
+    async fn attributes(
+        &self,
+        workspace_name: &str,
+        gid: &str,
+        _claimed: &HashMap<String, String>,
+    ) -> Result<HashMap<String, String>> {
+        let rows = self
+            .database
+            .query_all(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                &self.query,
+                [gid.into(), workspace_name.into()],
+            ))
+            .await?;
+
+        rows.into_iter()
+            .map(|row| Ok((row.try_get::<String>("", "key")?, row.try_get::<String>("", "value")?)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        collections::{BTreeMap, HashMap},
+        path::PathBuf,
+        sync::Arc,
+        time::Duration,
+    };
+
+    use sea_orm::{DatabaseBackend, MockDatabase, Transaction, Value};
+    use ulid::Ulid;
+
+    use nebula_tls::ClientTlsConfig;
+
+    use super::{
+        AttributeSource, FileAttributeSource, HttpAttributeSource, JwtAttributeSource, PostgresAttributeSource,
+    };
+
+    fn attribute_file(extension: &str, content: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("nebula-authority-test-{}.{extension}", Ulid::new()));
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[tokio::test]
+    async fn when_resolving_with_jwt_source_then_claimed_attributes_are_returned() {
+        let claimed = HashMap::from([("department".to_owned(), "engineering".to_owned())]);
+
+        let attributes = JwtAttributeSource.attributes("testworkspace", "alice", &claimed).await.unwrap();
+
+        assert_eq!(attributes, claimed);
+    }
+
+    #[tokio::test]
+    async fn when_resolving_with_toml_file_source_then_attributes_of_gid_are_returned_instead_of_claimed() {
+        let path = attribute_file("toml", "[testworkspace.alice]\ndepartment = \"engineering\"\n");
+        let source = FileAttributeSource::load(&path).unwrap();
+        let claimed = HashMap::from([("department".to_owned(), "finance".to_owned())]);
+
+        let attributes = source.attributes("testworkspace", "alice", &claimed).await.unwrap();
+
+        assert_eq!(attributes, HashMap::from([("department".to_owned(), "engineering".to_owned())]));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn when_resolving_unknown_gid_with_json_file_source_then_no_attribute_is_returned() {
+        let path = attribute_file("json", r#"{"testworkspace": {"alice": {"department": "engineering"}}}"#);
+        let source = FileAttributeSource::load(&path).unwrap();
+
+        let attributes = source.attributes("testworkspace", "bob", &HashMap::new()).await.unwrap();
+
+        assert!(attributes.is_empty());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn when_resolving_gid_in_other_workspace_with_file_source_then_attributes_of_that_workspace_are_returned() {
+        let path = attribute_file(
+            "toml",
+            "[testworkspace.alice]\ndepartment = \"engineering\"\n[otherworkspace.alice]\ndepartment = \"finance\"\n",
+        );
+        let source = FileAttributeSource::load(&path).unwrap();
+
+        let other_attributes = source.attributes("otherworkspace", "alice", &HashMap::new()).await.unwrap();
+        let unlisted_attributes = source.attributes("unlistedworkspace", "alice", &HashMap::new()).await.unwrap();
+
+        assert_eq!(other_attributes, HashMap::from([("department".to_owned(), "finance".to_owned())]));
+        assert!(unlisted_attributes.is_empty());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn when_loading_file_of_unknown_format_then_file_source_fails() {
+        let path = attribute_file("yaml", "testworkspace:\n  alice:\n    department: engineering\n");
+
+        assert!(FileAttributeSource::load(&path).is_err());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn when_ca_of_tls_is_missing_then_http_source_is_not_created() {
+        let tls = ClientTlsConfig { ca_path: Some(PathBuf::from("/nonexistent/ca.pem")), ..Default::default() };
+
+        let result =
+            HttpAttributeSource::new("https://hr.example.com".parse().unwrap(), Duration::from_secs(5), Some(&tls));
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn when_query_ignores_workspace_then_postgres_source_is_not_created() {
+        let database = Arc::new(MockDatabase::new(DatabaseBackend::Postgres).into_connection());
+
+        let result =
+            PostgresAttributeSource::new(database, "SELECT key, value FROM attributes WHERE gid = $1".to_owned());
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn when_resolving_with_postgres_source_then_query_is_given_gid_and_workspace() {
+        let query = "SELECT key, value FROM attributes WHERE gid = $1 AND workspace = $2";
+        let row = BTreeMap::from([
+            ("key".to_owned(), Value::from("department")),
+            ("value".to_owned(), Value::from("engineering")),
+        ]);
+        let database = MockDatabase::new(DatabaseBackend::Postgres).append_query_results([vec![row]]).into_connection();
+        let database = Arc::new(database);
+        let source = PostgresAttributeSource::new(database.clone(), query.to_owned()).unwrap();
+
+        let attributes = source.attributes("testworkspace", "alice", &HashMap::new()).await.unwrap();
+
+        assert_eq!(attributes, HashMap::from([("department".to_owned(), "engineering".to_owned())]));
+        drop(source);
+        let database = Arc::try_unwrap(database).unwrap_or_else(|_| panic!("connection should not be shared"));
+        assert_eq!(
+            database.into_transaction_log(),
+            vec![Transaction::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                query,
+                ["alice".into(), "testworkspace".into()]
+            )]
+        );
+    }
+}
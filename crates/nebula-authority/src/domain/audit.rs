@@ -0,0 +1,223 @@
+use std::{net::IpAddr, path::PathBuf};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use nebula_storage::{
+    backend::{
+        file::{FileStorage, FileStorageError},
+        postgres::PostgresStorage,
+    },
+    Storage as _,
+};
+use serde::{Deserialize, Serialize};
+use tokio::{fs::OpenOptions, io::AsyncWriteExt as _, sync::Mutex};
+use tracing::warn;
+use ulid::Ulid;
+
+use super::key_pair::KeyVersion;
+
+/// Record of a user key request, kept whether or not the user key is issued.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UserKeyIssuance {
+    pub id: Ulid,
+    pub workspace_name: String,
+    pub gid: String,
+    /// Attributes certified in the user key as `key=value`, which are empty if it fails before they are resolved.
+    pub attributes: Vec<String>,
+    pub key_version: Option<KeyVersion>,
+    pub client_ip: Option<IpAddr>,
+    /// Reason why the user key is not issued, or `None` if it is issued.
+    pub failure: Option<String>,
+    pub requested_at: DateTime<Utc>,
+}
+
+impl UserKeyIssuance {
+    pub fn new(workspace_name: String, gid: String, client_ip: Option<IpAddr>) -> Self {
+        Self {
+            id: Ulid::new(),
+            workspace_name,
+            gid,
+            attributes: vec![],
+            key_version: None,
+            client_ip,
+            failure: None,
+            requested_at: Utc::now(),
+        }
+    }
+}
+
+const USER_KEY_AUDIT_PATH: &str = "/authority/audit/user-key/";
+pub(crate) const USER_KEY_AUDIT_FAILURES_TOTAL: &str = "user_key_audit_failures_total";
+
+#[async_trait]
+pub trait UserKeyAuditService {
+    #[inline(always)]
+    fn workspace_path(&self, workspace_name: &str) -> String {
+        format!("{}{}/", USER_KEY_AUDIT_PATH, workspace_name)
+    }
+    #[inline(always)]
+    fn issuance_path(&self, workspace_name: &str, id: &Ulid) -> String {
+        format!("{}{}", self.workspace_path(workspace_name), id)
+    }
+
+    async fn record(&self, issuance: &UserKeyIssuance) -> Result<()>;
+    /// Returns the records of the workspace from the newest, up to `limit`. Only the records older than `before` are
+    /// returned if it is given, so that the id of the last record pages to the next ones.
+    async fn issuances(&self, workspace_name: &str, before: Option<Ulid>, limit: usize)
+        -> Result<Vec<UserKeyIssuance>>;
+}
+
+/// Ids are ULIDs, so sorting them sorts the records by time. Names which are not ULIDs are skipped.
+fn page(ids: impl IntoIterator<Item = String>, before: Option<Ulid>, limit: usize) -> Vec<Ulid> {
+    let mut ids: Vec<Ulid> = ids.into_iter().filter_map(|id| id.parse().ok()).collect();
+    ids.sort_unstable_by(|a, b| b.cmp(a));
+    ids.into_iter().filter(|id| before.is_none_or(|before| *id < before)).take(limit).collect()
+}
+
+pub struct FileUserKeyAuditService<'a> {
+    storage: FileStorage<'a>,
+}
+
+impl<'a> FileUserKeyAuditService<'a> {
+    pub fn new(storage: FileStorage<'a>) -> Self {
+        Self { storage }
+    }
+}
+
+#[async_trait]
+impl UserKeyAuditService for FileUserKeyAuditService<'_> {
+    async fn record(&self, issuance: &UserKeyIssuance) -> Result<()> {
+        let issuance_path = self.issuance_path(&issuance.workspace_name, &issuance.id);
+        self.storage.set(&issuance_path, &serde_json::to_vec(issuance)?).await?;
+        Ok(())
+    }
+
+    async fn issuances(
+        &self,
+        workspace_name: &str,
+        before: Option<Ulid>,
+        limit: usize,
+    ) -> Result<Vec<UserKeyIssuance>> {
+        let ids = match self.storage.list(&self.workspace_path(workspace_name)).await {
+            Ok(ids) => ids.into_iter().collect(),
+            // The directory of a workspace is created with its first record.
+            Err(FileStorageError::Io(e)) if e.kind() == std::io::ErrorKind::NotFound => vec![],
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut issuances = vec![];
+        for id in page(ids, before, limit) {
+            if let Some(issuance) = self.storage.get(&self.issuance_path(workspace_name, &id)).await? {
+                issuances.push(serde_json::from_slice(&issuance)?);
+            }
+        }
+        Ok(issuances)
+    }
+}
+
+pub struct PostgresUserKeyAuditService {
+    storage: PostgresStorage,
+}
+
+impl PostgresUserKeyAuditService {
+    pub fn new(storage: PostgresStorage) -> Self {
+        Self { storage }
+    }
+}
+
+#[async_trait]
+impl UserKeyAuditService for PostgresUserKeyAuditService {
+    async fn record(&self, issuance: &UserKeyIssuance) -> Result<()> {
+        let issuance_path = self.issuance_path(&issuance.workspace_name, &issuance.id);
+        self.storage.set(&issuance_path, &serde_json::to_vec(issuance)?).await?;
+        Ok(())
+    }
+
+    async fn issuances(
+        &self,
+        workspace_name: &str,
+        before: Option<Ulid>,
+        limit: usize,
+    ) -> Result<Vec<UserKeyIssuance>> {
+        let before = before.map(|before| self.issuance_path(workspace_name, &before));
+        let entries = self.storage.page(&self.workspace_path(workspace_name), before.as_deref(), limit).await?;
+
+        entries.into_iter().map(|(_, issuance)| Ok(serde_json::from_slice(&issuance)?)).collect()
+    }
+}
+
+/// Appends each record as a line of JSON to a file, e.g. to be shipped to a SIEM.
+pub struct JsonlUserKeyAuditSink {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl JsonlUserKeyAuditSink {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, lock: Mutex::new(()) }
+    }
+
+    pub async fn append(&self, issuance: &UserKeyIssuance) {
+        if let Err(e) = self.try_append(issuance).await {
+            metrics::counter!(USER_KEY_AUDIT_FAILURES_TOTAL, "sink" => "jsonl").increment(1);
+            warn!(error = %e, path = %self.path.display(), "failed to append the user key issuance to the audit file.");
+        }
+    }
+
+    async fn try_append(&self, issuance: &UserKeyIssuance) -> Result<()> {
+        let mut line = serde_json::to_vec(issuance)?;
+        line.push(b'\n');
+
+        let _lock = self.lock.lock().await;
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path).await?;
+        file.write_all(&line).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ulid::Ulid;
+
+    use super::page;
+
+    fn ids(count: usize) -> Vec<Ulid> {
+        let mut generator = ulid::Generator::new();
+        (0..count).map(|_| generator.generate().expect("ulid should be generated")).collect()
+    }
+
+    #[test]
+    fn when_paging_then_newest_ids_are_returned_first_up_to_limit() {
+        let ids = ids(5);
+
+        let page = page(ids.iter().map(Ulid::to_string), None, 3);
+
+        assert_eq!(page, vec![ids[4], ids[3], ids[2]]);
+    }
+
+    #[test]
+    fn when_paging_before_id_then_only_older_ids_are_returned() {
+        let ids = ids(5);
+
+        let page = page(ids.iter().map(Ulid::to_string), Some(ids[2]), 10);
+
+        assert_eq!(page, vec![ids[1], ids[0]]);
+    }
+
+    #[test]
+    fn when_names_are_not_ulids_then_they_are_skipped() {
+        let ids = ids(2);
+        let names = vec![ids[0].to_string(), "not-a-ulid".to_owned(), ids[1].to_string(), "nested/".to_owned()];
+
+        let page = page(names, None, 10);
+
+        assert_eq!(page, vec![ids[1], ids[0]]);
+    }
+
+    #[test]
+    fn when_limit_is_zero_then_page_is_empty() {
+        assert!(page(ids(3).iter().map(Ulid::to_string), None, 0).is_empty());
+    }
+}
@@ -1,54 +1,357 @@
-use std::{path::PathBuf, str::FromStr, sync::Arc};
+use std::{collections::HashMap, path::PathBuf, str::FromStr, sync::Arc, time::Duration};
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 
-use nebula_abe::{curves::bn462::Bn462Curve, schemes::isabella24::GlobalParams};
+use chrono::{DateTime, Utc};
 use nebula_secret_sharing::shamir::Share;
 use nebula_storage::backend::{file::FileStorage, postgres::PostgresStorage};
-use sea_orm::DatabaseConnection;
+use sea_orm::{ConnectionTrait, DatabaseConnection, DatabaseTransaction, DbBackend, Statement, TransactionTrait};
+use tokio::{sync::Mutex, time::Instant};
+use tracing::{debug, error, info, warn};
+use ulid::Ulid;
+use url::Url;
 
 use crate::{
-    config::{ApplicationConfig, PostgresAuthMethod, PostgresConfig, StorageConfig},
+    config::{
+        ApplicationConfig, AttributeSourceConfig, KeyRollingConfig, PostgresAuthMethod, PostgresConfig,
+        RegistrationConfig, StorageConfig,
+    },
     database::{connect_to_database, AuthMethod},
 };
 
 use super::{
-    backbone::{BackboneService, WorkspaceBackboneService},
-    key_pair::{FileKeyPairService, KeyPair, KeyVersion, PostgresKeyPairService, ShieldedKeyPairService},
+    armor::{ArmorTrigger, AutoArmor},
+    attribute::{AttributeNamespace, DroppedReason},
+    attribute_source::{
+        AttributeSource, FileAttributeSource, HttpAttributeSource, JwtAttributeSource, PostgresAttributeSource,
+    },
+    audit::{
+        FileUserKeyAuditService, JsonlUserKeyAuditSink, PostgresUserKeyAuditService, UserKeyAuditService,
+        UserKeyIssuance, USER_KEY_AUDIT_FAILURES_TOTAL,
+    },
+    backbone::{BackboneService, Parameter, RegistrationRejected, WorkspaceBackboneService},
+    burst::UserKeyBurstLimiter,
+    disarm::{DisarmProgress, DisarmSessionState, DisarmShareError, ShareHolders},
+    epoch::EpochBinding,
+    key_pair::{
+        FileKeyPairService, KeyPair, KeyVersion, KeyVersionState, KeyVersionStatus, KeyVersionStatusError,
+        PostgresKeyPairService, ShieldedKeyPairService,
+    },
+    registration::{BackboneCredentialService, FileBackboneCredentialService, PostgresBackboneCredentialService},
 };
 
 pub struct Authority {
     pub name: String,
     pub backbone_service: Arc<dyn BackboneService + Send + Sync>,
     pub key_pair_service: Arc<dyn ShieldedKeyPairService + Send + Sync>,
+    registration: Option<RegistrationConfig>,
+    backbone_credential_service: Arc<dyn BackboneCredentialService + Send + Sync>,
+    key_rolling: Option<KeyRollingConfig>,
+    attribute_namespace: AttributeNamespace,
+    attribute_source: Arc<dyn AttributeSource + Send + Sync>,
+    pub epoch_binding: Option<EpochBinding>,
+    user_key_audit_service: Arc<dyn UserKeyAuditService + Send + Sync>,
+    user_key_audit_sink: Option<JsonlUserKeyAuditSink>,
+    auto_armor: Option<AutoArmor>,
+    user_key_burst_limiter: Option<Mutex<UserKeyBurstLimiter>>,
+    disarm_session: Mutex<DisarmSessionState>,
+    database: Option<Arc<DatabaseConnection>>,
 }
 
+const DEFAULT_ATTRIBUTE_SOURCE_TIMEOUT_SECONDS: u64 = 5;
+const REGISTRATION_ATTEMPTS: u32 = 5;
+const REGISTRATION_RETRY_INTERVAL: Duration = Duration::from_secs(5);
+const DEFAULT_KEY_ROLLING_CHECK_INTERVAL_SECONDS: u64 = 60 * 60;
+/// Key of the Postgres advisory lock which the replicas sharing the storage take to roll the key pairs on schedule.
+const KEY_ROLLING_LOCK_KEY: i64 = 0x6e65_6275_6c61_0001;
+
 impl Authority {
     pub async fn new(config: &ApplicationConfig) -> Result<Self> {
-        let key_pair_service: Arc<dyn ShieldedKeyPairService + Send + Sync> = match &config.storage {
+        // The audit log and the backbone credentials are not shielded, so that they are available even while the key
+        // pair storage is armored.
+        let key_pair_service: Arc<dyn ShieldedKeyPairService + Send + Sync>;
+        let user_key_audit_service: Arc<dyn UserKeyAuditService + Send + Sync>;
+        let backbone_credential_service: Arc<dyn BackboneCredentialService + Send + Sync>;
+        let database = match &config.storage {
             StorageConfig::File { path } => {
-                Arc::new(FileKeyPairService::new(FileStorage::new(PathBuf::from_str(path)?)))
+                key_pair_service = Arc::new(FileKeyPairService::new(FileStorage::new(PathBuf::from_str(path)?)));
+                user_key_audit_service =
+                    Arc::new(FileUserKeyAuditService::new(FileStorage::new(PathBuf::from_str(path)?)));
+                backbone_credential_service =
+                    Arc::new(FileBackboneCredentialService::new(FileStorage::new(PathBuf::from_str(path)?)));
+                None
             }
             StorageConfig::Postgres(auth_method) => {
                 let database = init_database_connection(auth_method).await?;
-                Arc::new(PostgresKeyPairService::new(
-                    PostgresStorage::new(database.get_postgres_connection_pool().clone(), "nebula_key_pair_storage")
-                        .await?,
-                ))
+                let pool = database.get_postgres_connection_pool();
+                key_pair_service = Arc::new(PostgresKeyPairService::new(
+                    PostgresStorage::new(pool.clone(), "nebula_key_pair_storage").await?,
+                ));
+                user_key_audit_service = Arc::new(PostgresUserKeyAuditService::new(
+                    PostgresStorage::new(pool.clone(), "nebula_user_key_audit_log").await?,
+                ));
+                backbone_credential_service = Arc::new(PostgresBackboneCredentialService::new(
+                    PostgresStorage::new(pool.clone(), "nebula_backbone_credential").await?,
+                ));
+                Some(database)
             }
         };
 
+        let backbone_client = match &config.backbone.tls {
+            Some(tls) => tls.client()?,
+            None => reqwest::Client::new(),
+        };
         let backbone_service: Arc<dyn BackboneService + Send + Sync> =
-            Arc::new(WorkspaceBackboneService::new(config.backbone.host.clone()));
-        Ok(Self { name: config.authority.name.clone(), key_pair_service, backbone_service })
+            Arc::new(WorkspaceBackboneService::new(config.backbone.host.clone(), backbone_client));
+        let attribute_namespace = match (&config.authority.attributes, config.authority.issue_all_attributes) {
+            (Some(_), true) => bail!("authority.attributes and authority.issue_all_attributes are exclusive"),
+            (Some(attributes), false) => AttributeNamespace::new(attributes)?,
+            (None, true) => AttributeNamespace::unrestricted(),
+            (None, false) => {
+                warn!("no attribute namespace is configured, so no attribute is issued.");
+                AttributeNamespace::new(&HashMap::new())?
+            }
+        };
+        let attribute_source: Arc<dyn AttributeSource + Send + Sync> = match &config.authority.attribute_source {
+            None | Some(AttributeSourceConfig::Jwt) => Arc::new(JwtAttributeSource),
+            Some(AttributeSourceConfig::File { path }) => Arc::new(FileAttributeSource::load(path)?),
+            Some(AttributeSourceConfig::Http { url, timeout, tls }) => Arc::new(HttpAttributeSource::new(
+                url.clone(),
+                Duration::from_secs(timeout.unwrap_or(DEFAULT_ATTRIBUTE_SOURCE_TIMEOUT_SECONDS)),
+                tls.as_ref(),
+            )?),
+            Some(AttributeSourceConfig::Postgres { database, query }) => {
+                Arc::new(PostgresAttributeSource::new(init_database_connection(database).await?, query.clone())?)
+            }
+        };
+        Ok(Self {
+            name: config.authority.name.clone(),
+            key_pair_service,
+            backbone_service,
+            registration: config.authority.registration.clone(),
+            backbone_credential_service,
+            key_rolling: config.authority.key_rolling.clone(),
+            attribute_namespace,
+            attribute_source,
+            epoch_binding: config
+                .authority
+                .epoch
+                .as_ref()
+                .map(|epoch| EpochBinding::new(epoch.attribute.clone(), epoch.period)),
+            user_key_audit_service,
+            user_key_audit_sink: config
+                .authority
+                .audit
+                .as_ref()
+                .and_then(|audit| audit.jsonl_path.clone())
+                .map(JsonlUserKeyAuditSink::new),
+            auto_armor: config.authority.auto_armor.as_ref().map(AutoArmor::new),
+            user_key_burst_limiter: config
+                .authority
+                .user_key_burst
+                .as_ref()
+                .map(|user_key_burst| Mutex::new(UserKeyBurstLimiter::new(user_key_burst))),
+            disarm_session: Mutex::new(DisarmSessionState::default()),
+            database,
+        })
+    }
+
+    /// Registers the authority to each workspace with a configured bootstrap token, unless it has registered itself to
+    /// the workspace before. A registration rejected by the backbone, e.g. for a name mismatch, is not retried.
+    pub async fn register_to_backbone(&self) {
+        let Some(registration) = &self.registration else {
+            return;
+        };
+
+        for (workspace_name, bootstrap_token) in &registration.bootstrap_tokens {
+            match self.backbone_credential_service.credential(workspace_name).await {
+                Ok(Some(_)) => {
+                    debug!(workspace = workspace_name, "authority has already registered itself to the workspace.");
+                    continue;
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    error!(error = %e, workspace = workspace_name, "failed to read the backbone credential.");
+                    continue;
+                }
+            }
+            self.register_to_workspace(workspace_name, bootstrap_token, &registration.host).await;
+        }
+    }
+
+    async fn register_to_workspace(&self, workspace_name: &str, bootstrap_token: &str, host: &Url) {
+        for attempt in 1..=REGISTRATION_ATTEMPTS {
+            match self.backbone_service.register_authority(workspace_name, bootstrap_token, &self.name, host).await {
+                Ok(credential) => {
+                    info!(workspace = workspace_name, "authority is registered to the backbone.");
+                    if let Err(e) = self.backbone_credential_service.set_credential(workspace_name, &credential).await {
+                        // The bootstrap token has been consumed, so a new one has to be issued to register again.
+                        error!(error = %e, workspace = workspace_name, "failed to keep the backbone credential.");
+                    }
+                    return;
+                }
+                Err(e) if e.is::<RegistrationRejected>() => {
+                    error!(error = %e, workspace = workspace_name, "failed to register the authority.");
+                    return;
+                }
+                Err(e) => {
+                    warn!(error = %e, workspace = workspace_name, attempt, "failed to register the authority.");
+                    if attempt < REGISTRATION_ATTEMPTS {
+                        tokio::time::sleep(REGISTRATION_RETRY_INTERVAL).await;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Rolls the key pair of each workspace with a configured schedule whenever its period has passed since the last
+    /// rolling. Runs forever, so it is meant to be spawned.
+    pub async fn roll_key_pairs_periodically(&self) {
+        let Some(key_rolling) = &self.key_rolling else {
+            return;
+        };
+
+        let check_interval =
+            Duration::from_secs(key_rolling.check_interval.unwrap_or(DEFAULT_KEY_ROLLING_CHECK_INTERVAL_SECONDS));
+        loop {
+            if let Err(e) = self.roll_scheduled_key_pairs(key_rolling).await {
+                error!(error = %e, "failed to roll the key pairs on schedule.");
+            }
+            tokio::time::sleep(check_interval).await;
+        }
+    }
+
+    async fn roll_scheduled_key_pairs(&self, key_rolling: &KeyRollingConfig) -> Result<()> {
+        if self.is_armored().await {
+            debug!("key pair storage is armored, so the scheduled key rolling is skipped.");
+            return Ok(());
+        }
+        // Replicas sharing the Postgres storage would roll the same key pairs at once, so only the one holding the lock
+        // rolls them. The file storage is not shared.
+        let lock = match &self.database {
+            Some(database) => match try_key_rolling_lock(database).await? {
+                Some(transaction) => Some(transaction),
+                None => {
+                    debug!("another replica is rolling the key pairs on schedule.");
+                    return Ok(());
+                }
+            },
+            None => None,
+        };
+
+        for (workspace_name, period) in &key_rolling.workspaces {
+            match self.roll_key_pair_if_due(workspace_name, Duration::from_secs(*period)).await {
+                Ok(Some(version)) => info!(workspace = workspace_name, version, "key pair is rolled on schedule."),
+                Ok(None) => {}
+                Err(e) => error!(error = %e, workspace = workspace_name, "failed to roll the key pair on schedule."),
+            }
+        }
+
+        if let Some(transaction) = lock {
+            transaction.commit().await?;
+        }
+        Ok(())
+    }
+
+    /// Rolls the key pair if `period` has passed since the last rolling. A key pair which is not generated yet is left
+    /// to be generated on demand, and the schedule of a key pair without a recorded rolling time starts now.
+    async fn roll_key_pair_if_due(&self, workspace_name: &str, period: Duration) -> Result<Option<KeyVersion>> {
+        let name = &format!("{}-{}", self.name, workspace_name);
+        if self.key_pair_service.latest_key_pair_version(name).await?.is_none() {
+            return Ok(None);
+        }
+        let Some(last_rolled_at) = self.key_pair_service.last_rolled_at(name).await? else {
+            self.key_pair_service.set_last_rolled_at(name, Utc::now()).await?;
+            return Ok(None);
+        };
+        if Utc::now().signed_duration_since(last_rolled_at).to_std().unwrap_or_default() < period {
+            return Ok(None);
+        }
+
+        let parameter = self.backbone_service.parameter(workspace_name, None).await?;
+        let version = self.key_pair_rolling(&parameter, workspace_name).await?;
+        self.notify_key_pair_rolled(workspace_name).await;
+
+        Ok(Some(version))
+    }
+
+    /// Requests the backbone to refresh the public keys of the authority with the credential issued at its registration.
+    /// Without one, the backbone picks up the new public key on its periodic refresh.
+    async fn notify_key_pair_rolled(&self, workspace_name: &str) {
+        let credential = match self.backbone_credential_service.credential(workspace_name).await {
+            Ok(Some(credential)) => credential,
+            Ok(None) => return,
+            Err(e) => {
+                warn!(error = %e, workspace = workspace_name, "failed to read the backbone credential.");
+                return;
+            }
+        };
+
+        if let Err(e) = self.backbone_service.refresh_own_public_keys(workspace_name, &credential).await {
+            warn!(error = %e, workspace = workspace_name, "failed to request the backbone to refresh the public keys.");
+        }
+    }
+
+    /// Resolves the attributes of the user from the attribute source, and returns the ones to issue a user key for as
+    /// `key=value`. Attributes outside of the namespace of the authority, violating its constraints or under the key of
+    /// the epoch attribute are dropped.
+    pub async fn issuable_attributes(
+        &self,
+        workspace_name: &str,
+        gid: &str,
+        claimed: &HashMap<String, String>,
+    ) -> Result<Vec<String>> {
+        let attributes = self.attribute_source.attributes(workspace_name, gid, claimed).await?;
+
+        Ok(attributes
+            .into_iter()
+            .filter(|(key, value)| match self.check_attribute(key, value) {
+                Ok(()) => true,
+                Err(reason) => {
+                    info!(workspace = workspace_name, gid, attribute = key, %reason, "attribute is not issued.");
+                    false
+                }
+            })
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect())
+    }
+
+    fn check_attribute(&self, key: &str, value: &str) -> std::result::Result<(), DroppedReason> {
+        if self.epoch_binding.as_ref().is_some_and(|epoch_binding| epoch_binding.attribute == key) {
+            return Err(DroppedReason::ReservedForEpoch);
+        }
+        self.attribute_namespace.check(key, value)
+    }
+
+    /// Records a user key request in the audit log. A failure to record it is counted and returned, so that a user key
+    /// is never issued without its record.
+    pub async fn record_user_key_issuance(&self, issuance: &UserKeyIssuance) -> Result<()> {
+        if let Some(user_key_audit_sink) = &self.user_key_audit_sink {
+            user_key_audit_sink.append(issuance).await;
+        }
+        self.user_key_audit_service.record(issuance).await.inspect_err(|e| {
+            metrics::counter!(USER_KEY_AUDIT_FAILURES_TOTAL, "sink" => "storage").increment(1);
+            error!(error = %e, workspace = issuance.workspace_name, gid = issuance.gid, "failed to record the user key issuance.");
+        })
+    }
+
+    pub async fn user_key_issuances(
+        &self,
+        workspace_name: &str,
+        before: Option<Ulid>,
+        limit: usize,
+    ) -> Result<Vec<UserKeyIssuance>> {
+        self.user_key_audit_service.issuances(workspace_name, before, limit).await
     }
 
     pub async fn key_pair(&self, workspace_name: &str) -> Result<(KeyPair, KeyVersion)> {
-        let gp = self.backbone_service.global_params(workspace_name).await?;
         let name = &format!("{}-{}", self.name, workspace_name);
         let key_pair = match self.key_pair_service.latest_key_pair(name).await? {
             Some(key_pair) => key_pair,
-            None => self.key_pair_service.generate_latest_key_pair(&gp, name).await?,
+            None => {
+                let parameter = self.backbone_service.parameter(workspace_name, None).await?;
+                self.key_pair_service.generate_latest_key_pair(&parameter, name).await?
+            }
         };
 
         Ok(key_pair)
@@ -66,9 +369,63 @@ impl Authority {
         Ok(key_pair)
     }
 
-    pub async fn key_pair_rolling(&self, gp: &GlobalParams<Bn462Curve>, workspace_name: &str) -> Result<KeyVersion> {
+    /// Returns every version of the key pair of the workspace from the oldest, with when it was generated and its state.
+    pub async fn key_pair_versions(
+        &self,
+        workspace_name: &str,
+    ) -> Result<Vec<(KeyVersion, Option<DateTime<Utc>>, KeyVersionState)>> {
+        let name = &format!("{}-{}", self.name, workspace_name);
+        let latest_version = self.key_pair_service.latest_key_pair_version(name).await?.unwrap_or(0);
+
+        self.key_pair_service.key_pair_versions(name, latest_version).await
+    }
+
+    pub async fn key_pair_state(&self, workspace_name: &str, version: KeyVersion) -> Result<KeyVersionState> {
+        let name = &format!("{}-{}", self.name, workspace_name);
+        self.key_pair_service.key_pair_state(name, version).await
+    }
+
+    /// Retires or revokes a key pair version. The latest version can't be changed, since new secrets are encrypted
+    /// with it, so the key pair has to be rolled first.
+    pub async fn change_key_pair_status(
+        &self,
+        workspace_name: &str,
+        version: KeyVersion,
+        status: KeyVersionStatus,
+        reason: Option<String>,
+    ) -> std::result::Result<KeyVersionState, KeyVersionStatusError> {
+        let name = &format!("{}-{}", self.name, workspace_name);
+        if self.key_pair_service.key_pair_by_version(name, version).await?.is_none() {
+            return Err(KeyVersionStatusError::VersionNotFound(version));
+        }
+        if self.key_pair_service.latest_key_pair_version(name).await? == Some(version) {
+            return Err(KeyVersionStatusError::LatestVersion(version));
+        }
+
+        let state = self.key_pair_service.key_pair_state(name, version).await?.transition(status, reason)?;
+        self.key_pair_service.set_key_pair_state(name, version, &state).await?;
+        warn!(
+            workspace = workspace_name,
+            version,
+            status = ?state.status,
+            reason = state.reason.as_deref().unwrap_or_default(),
+            "key pair version status is changed."
+        );
+
+        Ok(state)
+    }
+
+    /// Generates a new key pair version with `parameter`, which is the latest parameter of the workspace in general so
+    /// that the workspace migrates to it.
+    pub async fn key_pair_rolling(&self, parameter: &Parameter, workspace_name: &str) -> Result<KeyVersion> {
         let name = &format!("{}-{}", self.name, workspace_name);
-        let (_, version) = self.key_pair_service.generate_latest_key_pair(gp, name).await?;
+        let (_, version) = self.key_pair_service.generate_latest_key_pair(parameter, name).await?;
+        metrics::counter!(
+            "key_rollings_total",
+            "authority" => self.name.clone(),
+            "workspace" => workspace_name.to_owned()
+        )
+        .increment(1);
         Ok(version)
     }
 
@@ -76,12 +433,226 @@ impl Authority {
         self.key_pair_service.shield_initialize(share, threshold).await
     }
 
+    /// Armors the key pair storage, which zeroizes the shield key in memory.
     pub async fn armor_key_pair_storage(&self) -> Result<()> {
         self.key_pair_service.storage_armor().await
     }
 
     pub async fn disarm_key_pair_storage(&self, shares: &[Share]) -> Result<()> {
-        self.key_pair_service.storage_disarm(shares).await
+        let result = self.key_pair_service.storage_disarm(shares).await;
+        self.on_disarm_attempt(result.is_ok()).await;
+        if result.is_ok() {
+            self.disarm_session.lock().await.close();
+            if let Err(e) = self.record_missing_share_holders(shares).await {
+                warn!("failed to record the share holders of the key pair storage: {e}");
+            }
+        }
+        result
+    }
+
+    /// Records the share holders from the shares which disarmed the key pair storage, if it was initialized before they
+    /// were recorded, so that it can be armored and disarmed share by share afterward.
+    async fn record_missing_share_holders(&self, shares: &[Share]) -> Result<()> {
+        if self.key_pair_service.share_holders().await?.is_some() {
+            return Ok(());
+        }
+
+        self.key_pair_service.record_share_holders(&ShareHolders::recover(shares)?).await?;
+        info!("share holders of the key pair storage are recorded on disarm.");
+        Ok(())
+    }
+
+    async fn on_disarm_attempt(&self, is_disarmed: bool) {
+        let result_label = if is_disarmed { "success" } else { "failure" };
+        metrics::counter!("disarm_attempts_total", "result" => result_label).increment(1);
+        if is_disarmed {
+            // The idle period starts over, so that an authority idle before being disarmed isn't armored right away.
+            if let Some(auto_armor) = &self.auto_armor {
+                auto_armor.touch().await;
+            }
+        }
+    }
+
+    /// Opens a disarm session with the share of a share holder, and returns its nonce with which the other share
+    /// holders submit their shares. Only the opener is given the nonce.
+    pub async fn open_disarm_session(
+        &self,
+        share: Share,
+    ) -> std::result::Result<(String, DisarmProgress), DisarmShareError> {
+        let share_holders = self.armored_share_holders().await?;
+        share_holders.verify(&share)?;
+
+        let mut disarm_session = self.disarm_session.lock().await;
+        let nonce = disarm_session.open(share, std::time::Instant::now())?;
+        let progress = self.disarm_if_threshold_reached(&mut disarm_session, &share_holders, 1).await?;
+        Ok((nonce, progress))
+    }
+
+    /// Adds the share of a share holder to the disarm session with the nonce, and disarms the key pair storage once the
+    /// threshold is reached.
+    pub async fn submit_disarm_share(
+        &self,
+        nonce: &str,
+        share: Share,
+    ) -> std::result::Result<DisarmProgress, DisarmShareError> {
+        let share_holders = self.armored_share_holders().await?;
+        share_holders.verify(&share)?;
+
+        let mut disarm_session = self.disarm_session.lock().await;
+        let submitted = disarm_session.submit(nonce, share, std::time::Instant::now())?;
+        self.disarm_if_threshold_reached(&mut disarm_session, &share_holders, submitted).await
+    }
+
+    /// Discards the shares submitted in the disarm session with the nonce, which a share holder cancels with their share.
+    pub async fn cancel_disarm_session(&self, nonce: &str, share: &Share) -> std::result::Result<(), DisarmShareError> {
+        let share_holders = self.armored_share_holders().await?;
+        share_holders.verify(share)?;
+
+        self.disarm_session.lock().await.cancel(nonce, std::time::Instant::now())
+    }
+
+    /// Checks that the share is one of the shares of the key pair storage, which is the credential of its holder.
+    pub async fn verify_share_holder(&self, share: &Share) -> std::result::Result<(), DisarmShareError> {
+        let share_holders =
+            self.key_pair_service.share_holders().await?.ok_or(DisarmShareError::ShareHoldersNotRecorded)?;
+        share_holders.verify(share)
+    }
+
+    pub async fn disarm_progress(&self) -> Result<DisarmProgress> {
+        let threshold = self.key_pair_service.shield_threshold().await?;
+        let progress = self.disarm_session.lock().await.progress(std::time::Instant::now());
+
+        Ok(DisarmProgress { progress, threshold, disarmed: !self.is_armored().await })
+    }
+
+    async fn armored_share_holders(&self) -> std::result::Result<ShareHolders, DisarmShareError> {
+        if !self.is_armored().await {
+            return Err(DisarmShareError::NotArmored);
+        }
+        self.key_pair_service.share_holders().await?.ok_or(DisarmShareError::ShareHoldersNotRecorded)
+    }
+
+    async fn disarm_if_threshold_reached(
+        &self,
+        disarm_session: &mut DisarmSessionState,
+        share_holders: &ShareHolders,
+        submitted: usize,
+    ) -> std::result::Result<DisarmProgress, DisarmShareError> {
+        let mut progress =
+            DisarmProgress { progress: submitted, threshold: Some(share_holders.threshold), disarmed: false };
+        if submitted < share_holders.threshold {
+            return Ok(progress);
+        }
+
+        let shares = disarm_session.close();
+        let is_disarmed = self.key_pair_service.storage_disarm(&shares).await.is_ok();
+        self.on_disarm_attempt(is_disarmed).await;
+        if !is_disarmed {
+            return Err(DisarmShareError::InvalidShares);
+        }
+
+        info!("key pair storage is disarmed with the shares submitted one by one.");
+        progress.disarmed = true;
+        Ok(progress)
+    }
+
+    /// Notes a user key request of the user as an activity of the authority, and returns whether the request is
+    /// within the burst limit of the user.
+    pub async fn admit_user_key_request(&self, workspace_name: &str, gid: &str) -> bool {
+        if let Some(auto_armor) = &self.auto_armor {
+            auto_armor.touch().await;
+        }
+        let Some(user_key_burst_limiter) = &self.user_key_burst_limiter else {
+            return true;
+        };
+
+        let is_admitted = user_key_burst_limiter.lock().await.admit(workspace_name, gid, Instant::now());
+        if !is_admitted {
+            metrics::counter!("user_key_bursts_total", "workspace" => workspace_name.to_owned()).increment(1);
+        }
+        is_admitted
+    }
+
+    /// Armors the key pair storage once no user key has been requested for the idle timeout. Runs forever, so it is
+    /// meant to be spawned.
+    pub async fn armor_when_idle(&self) {
+        let Some((auto_armor, idle_timeout)) =
+            self.auto_armor.as_ref().and_then(|auto_armor| Some((auto_armor, auto_armor.idle_timeout?)))
+        else {
+            return;
+        };
+
+        loop {
+            let deadline = auto_armor.last_activity().await + idle_timeout;
+            if Instant::now() < deadline {
+                tokio::time::sleep_until(deadline).await;
+                continue;
+            }
+            if !self.is_armored().await {
+                self.auto_armor_key_pair_storage(ArmorTrigger::Idle).await;
+            }
+            tokio::time::sleep(idle_timeout).await;
+        }
+    }
+
+    async fn auto_armor_key_pair_storage(&self, trigger: ArmorTrigger) {
+        match self.armor_key_pair_storage().await {
+            Ok(()) => {
+                warn!(%trigger, "key pair storage is armored automatically.");
+                metrics::counter!("auto_armors_total", "trigger" => trigger.as_str()).increment(1);
+            }
+            Err(e) => error!(error = %e, %trigger, "failed to armor the key pair storage automatically."),
+        }
+    }
+
+    pub async fn is_initialized(&self) -> Result<bool> {
+        self.key_pair_service.is_shield_initialized().await
+    }
+
+    /// Returns the number of shares required to disarm the key pair storage, if it is known.
+    pub async fn shield_threshold(&self) -> Result<Option<usize>> {
+        self.key_pair_service.shield_threshold().await
+    }
+
+    pub async fn is_armored(&self) -> bool {
+        self.key_pair_service.is_storage_armored().await
+    }
+
+    /// Checks the connectivity of the key pair storage database. Always succeeds with the file storage.
+    pub async fn ping_database(&self) -> Result<()> {
+        if let Some(database) = &self.database {
+            database.ping().await?;
+        }
+        Ok(())
+    }
+
+    pub async fn close(&self) {
+        if let Some(database) = &self.database {
+            database.get_postgres_connection_pool().close().await;
+        }
+    }
+}
+
+/// Takes the key rolling lock, which is held until the returned transaction ends. Returns `None` if another replica
+/// holds it.
+async fn try_key_rolling_lock(database: &DatabaseConnection) -> Result<Option<DatabaseTransaction>> {
+    let transaction = database.begin().await?;
+    let locked = transaction
+        .query_one(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "SELECT pg_try_advisory_xact_lock($1) AS locked",
+            [KEY_ROLLING_LOCK_KEY.into()],
+        ))
+        .await?
+        .map(|row| row.try_get::<bool>("", "locked"))
+        .transpose()?
+        .unwrap_or(false);
+
+    if locked {
+        Ok(Some(transaction))
+    } else {
+        transaction.rollback().await?;
+        Ok(None)
     }
 }
 
@@ -104,3 +675,165 @@ fn create_database_auth_method(config: &PostgresConfig) -> AuthMethod {
         }
     }
 }
+
+#[cfg(test)]
+impl Authority {
+    /// Authority named `authority` on a file storage at `path`, which is initialized and disarmed, with nothing else
+    /// configured.
+    pub(crate) async fn with_file_storage(
+        path: &std::path::Path,
+        backbone_service: Arc<dyn BackboneService + Send + Sync>,
+    ) -> Self {
+        let key_pair_service = FileKeyPairService::new(FileStorage::new(path.to_path_buf()));
+        key_pair_service.shield_initialize(1, 1).await.unwrap();
+
+        Self {
+            name: "authority".to_owned(),
+            backbone_service,
+            key_pair_service: Arc::new(key_pair_service),
+            registration: None,
+            backbone_credential_service: Arc::new(FileBackboneCredentialService::new(FileStorage::new(
+                path.to_path_buf(),
+            ))),
+            key_rolling: None,
+            attribute_namespace: AttributeNamespace::unrestricted(),
+            attribute_source: Arc::new(JwtAttributeSource),
+            epoch_binding: None,
+            user_key_audit_service: Arc::new(FileUserKeyAuditService::new(FileStorage::new(path.to_path_buf()))),
+            user_key_audit_sink: None,
+            auto_armor: None,
+            user_key_burst_limiter: None,
+            disarm_session: Mutex::new(DisarmSessionState::default()),
+            database: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::BTreeMap, sync::Arc, time::Duration};
+
+    use chrono::Utc;
+    use nebula_abe::{
+        curves::{bn462::Bn462Curve, Curve, PairingCurve},
+        schemes::isabella24::GlobalParams,
+    };
+    use sea_orm::{DatabaseBackend, MockDatabase, Value};
+    use ulid::Ulid;
+
+    use nebula_storage::backend::file::FileStorage;
+
+    use crate::{
+        config::KeyRollingConfig,
+        domain::{
+            backbone::{MockBackboneService, Parameter},
+            disarm::DisarmShareError,
+            key_pair::{FileKeyPairService, ShieldedKeyPairService},
+        },
+    };
+
+    use super::Authority;
+
+    const WORKSPACE_NAME: &str = "workspace";
+    const KEY_PAIR_NAME: &str = "authority-workspace";
+
+    fn parameter() -> Parameter {
+        let mut rng = <Bn462Curve as PairingCurve>::Rng::new();
+        rng.seed(&[0u8; 64]);
+        let gp = GlobalParams::<Bn462Curve>::new(&mut rng);
+
+        Parameter { version: 1, curve: Curve::Bn462, value: rmp_serde::to_vec(&gp).unwrap() }
+    }
+
+    /// Authority with the first key pair of the workspace, rolled `rolled_ago`.
+    async fn authority(
+        path: &std::path::Path,
+        backbone_service: MockBackboneService,
+        rolled_ago: Duration,
+    ) -> Authority {
+        let authority = Authority::with_file_storage(path, Arc::new(backbone_service)).await;
+        authority.key_pair_rolling(&parameter(), WORKSPACE_NAME).await.unwrap();
+        let rolled_at = Utc::now() - chrono::Duration::from_std(rolled_ago).unwrap();
+        authority.key_pair_service.set_last_rolled_at(KEY_PAIR_NAME, rolled_at).await.unwrap();
+        authority
+    }
+
+    fn temp_path() -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("nebula-authority-test-{}", Ulid::new()))
+    }
+
+    #[tokio::test]
+    async fn when_rolling_period_has_not_passed_then_key_pair_is_not_rolled() {
+        let path = temp_path();
+        let mut mock_backbone_service = MockBackboneService::new();
+        mock_backbone_service.expect_parameter().never();
+        let authority = authority(&path, mock_backbone_service, Duration::from_secs(60)).await;
+
+        let result = authority.roll_key_pair_if_due(WORKSPACE_NAME, Duration::from_secs(3600)).await;
+        let latest_version = authority.key_pair_service.latest_key_pair_version(KEY_PAIR_NAME).await.unwrap();
+        std::fs::remove_dir_all(path).unwrap();
+
+        assert_eq!(result.unwrap(), None);
+        assert_eq!(latest_version, Some(1));
+    }
+
+    #[tokio::test]
+    async fn when_rolling_period_has_passed_then_key_pair_is_rolled() {
+        let path = temp_path();
+        let mut mock_backbone_service = MockBackboneService::new();
+        mock_backbone_service.expect_parameter().times(1).returning(|_, _| Ok(parameter()));
+        let authority = authority(&path, mock_backbone_service, Duration::from_secs(7200)).await;
+
+        let result = authority.roll_key_pair_if_due(WORKSPACE_NAME, Duration::from_secs(3600)).await;
+        let latest_version = authority.key_pair_service.latest_key_pair_version(KEY_PAIR_NAME).await.unwrap();
+        std::fs::remove_dir_all(path).unwrap();
+
+        assert_eq!(result.unwrap(), Some(2));
+        assert_eq!(latest_version, Some(2));
+    }
+
+    #[tokio::test]
+    async fn when_key_rolling_lock_is_held_by_other_replica_then_key_pairs_are_not_rolled() {
+        let path = temp_path();
+        let mut mock_backbone_service = MockBackboneService::new();
+        mock_backbone_service.expect_parameter().never();
+        let mut authority = authority(&path, mock_backbone_service, Duration::from_secs(7200)).await;
+        let mock_database = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([[BTreeMap::from([("locked".to_owned(), Value::Bool(Some(false)))])]]);
+        authority.database = Some(Arc::new(mock_database.into_connection()));
+        let key_rolling = KeyRollingConfig {
+            check_interval: None,
+            workspaces: [(WORKSPACE_NAME.to_owned(), 3600)].into_iter().collect(),
+        };
+
+        let result = authority.roll_scheduled_key_pairs(&key_rolling).await;
+        let latest_version = authority.key_pair_service.latest_key_pair_version(KEY_PAIR_NAME).await.unwrap();
+        std::fs::remove_dir_all(path).unwrap();
+
+        assert!(result.is_ok());
+        assert_eq!(latest_version, Some(1));
+    }
+
+    #[tokio::test]
+    async fn when_storage_initialized_without_share_holders_is_disarmed_then_share_holders_are_recorded() {
+        let path = temp_path();
+        let mut authority = Authority::with_file_storage(&path, Arc::new(MockBackboneService::new())).await;
+        let key_pair_path = path.join("initialized-before-share-holders");
+        let key_pair_service = FileKeyPairService::new(FileStorage::new(key_pair_path.clone()));
+        let shares = key_pair_service.shield_initialize(3, 2).await.unwrap();
+        key_pair_service.storage_armor().await.unwrap();
+        std::fs::remove_file(key_pair_path.join("authority/shield/threshold")).unwrap();
+        std::fs::remove_file(key_pair_path.join("authority/shield/share_digests")).unwrap();
+        authority.key_pair_service = Arc::new(key_pair_service);
+
+        let before_disarm = authority.verify_share_holder(&shares[0]).await;
+        authority.disarm_key_pair_storage(&shares).await.unwrap();
+        let after_disarm = authority.verify_share_holder(&shares[0]).await;
+        let threshold = authority.key_pair_service.shield_threshold().await.unwrap();
+        std::fs::remove_dir_all(path).unwrap();
+
+        assert!(matches!(before_disarm, Err(DisarmShareError::ShareHoldersNotRecorded)));
+        assert!(after_disarm.is_ok());
+        assert_eq!(threshold, Some(2));
+    }
+}
@@ -2,21 +2,79 @@ use anyhow::Result;
 use async_trait::async_trait;
 use base64::{engine::general_purpose::STANDARD, Engine as _};
 use cached::proc_macro::cached;
-use nebula_abe::{curves::bn462::Bn462Curve, schemes::isabella24::GlobalParams};
-use reqwest::IntoUrl;
-use serde::Deserialize;
+#[cfg(test)]
+use mockall::automock;
+use nebula_abe::curves::Curve;
+use serde::{Deserialize, Serialize};
 use url::Url;
 
+/// Global parameter of a workspace. `value` is the global parameters on `curve` serialized in MessagePack.
+#[derive(Clone)]
+pub struct Parameter {
+    pub version: i32,
+    pub curve: Curve,
+    pub value: Vec<u8>,
+}
+
+#[cfg(test)]
+impl Parameter {
+    /// Parameter on BN-462 generated from a fixed seed.
+    pub(crate) fn fixed() -> Self {
+        use nebula_abe::{
+            curves::{bn462::Bn462Curve, PairingCurve},
+            schemes::isabella24::GlobalParams,
+        };
+
+        let mut rng = <Bn462Curve as PairingCurve>::Rng::new();
+        rng.seed(&[0u8; 64]);
+        let gp = GlobalParams::<Bn462Curve>::new(&mut rng);
+
+        Self { version: 1, curve: Curve::Bn462, value: rmp_serde::to_vec(&gp).unwrap() }
+    }
+}
+
+#[cfg_attr(test, automock)]
 #[async_trait]
 pub trait BackboneService {
-    async fn global_params(&self, workspace_name: &str) -> Result<GlobalParams<Bn462Curve>>;
+    /// Returns the parameter of the version, or the latest parameter if the version is not given.
+    async fn parameter(&self, workspace_name: &str, version: Option<i32>) -> Result<Parameter>;
+    /// Requests the backbone to refresh its cache of the authority public keys, authorized by `authorization` which is
+    /// the value of the `Authorization` header.
+    async fn refresh_authority_public_keys(&self, workspace_name: &str, authorization: &str) -> Result<()>;
+    /// Requests the backbone to refresh the public keys of this authority, authenticated with the credential issued at
+    /// its registration.
+    async fn refresh_own_public_keys(&self, workspace_name: &str, credential: &str) -> Result<()>;
+    /// Registers the authority to the workspace with the one-time bootstrap token, and returns the credential with which
+    /// it authenticates itself afterward. Fails with [`RegistrationRejected`] if the backbone rejects the registration.
+    async fn register_authority(
+        &self,
+        workspace_name: &str,
+        bootstrap_token: &str,
+        name: &str,
+        host: &Url,
+    ) -> Result<String>;
 }
 
 #[async_trait]
 pub trait BackboneClient {
-    async fn get_global_params(&self, workspace_name: &str) -> Result<GlobalParams<Bn462Curve>>;
+    async fn get_parameter(&self, workspace_name: &str, version: Option<i32>) -> Result<Parameter>;
+    async fn refresh_authority_public_keys(&self, workspace_name: &str, authorization: &str) -> Result<()>;
+    async fn refresh_own_public_keys(&self, workspace_name: &str, credential: &str) -> Result<()>;
+    async fn register_authority(
+        &self,
+        workspace_name: &str,
+        bootstrap_token: &str,
+        name: &str,
+        host: &Url,
+    ) -> Result<String>;
 }
 
+/// Registration rejected by the backbone, e.g. for an invalid bootstrap token or a name mismatch, which fails again
+/// if retried.
+#[derive(thiserror::Error, Debug)]
+#[error("backbone rejected the registration: {0}")]
+pub struct RegistrationRejected(String);
+
 pub struct WorkspaceBackboneClient {
     client: reqwest::Client,
     host: Url,
@@ -31,51 +89,153 @@ impl WorkspaceBackboneClient {
         self.client = client;
         self
     }
+}
 
-    pub fn host(mut self, host: impl IntoUrl) -> anyhow::Result<Self> {
-        self.host = host.into_url()?;
-        Ok(self)
-    }
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SelfRegistrationRequest<'a> {
+    bootstrap_token: &'a str,
+    name: &'a str,
+    host: &'a str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SelfRefreshRequest<'a> {
+    credential: &'a str,
+}
+
+#[derive(Deserialize)]
+struct SelfRegistrationResponse {
+    credential: String,
 }
 
 #[derive(Deserialize)]
 struct ParameterResponse {
     version: i32,
+    #[serde(default)]
+    curve: Curve,
     parameter: String,
 }
 
 #[async_trait]
 impl BackboneClient for WorkspaceBackboneClient {
-    async fn get_global_params(&self, workspace_name: &str) -> Result<GlobalParams<Bn462Curve>> {
-        let url = self.host.join(&format!("workspaces/{}/parameter", workspace_name))?;
-        let response = self.client.get(url).send().await?;
+    #[tracing::instrument(name = "backbone.get_parameter", skip(self))]
+    async fn get_parameter(&self, workspace_name: &str, version: Option<i32>) -> Result<Parameter> {
+        let mut url = self.host.join(&format!("workspaces/{}/parameter", workspace_name))?;
+        if let Some(version) = version {
+            url.query_pairs_mut().append_pair("version", &version.to_string());
+        }
+        let response = nebula_telemetry::trace::traced(self.client.get(url)).send().await?.error_for_status()?;
         let parameter: ParameterResponse = response.json().await?;
-        let parameter = STANDARD.decode(parameter.parameter)?;
-        let parameter = rmp_serde::from_slice(&parameter)?;
 
-        Ok(parameter)
+        Ok(Parameter {
+            version: parameter.version,
+            curve: parameter.curve,
+            value: STANDARD.decode(parameter.parameter)?,
+        })
+    }
+
+    #[tracing::instrument(name = "backbone.refresh_authority_public_keys", skip(self, authorization))]
+    async fn refresh_authority_public_keys(&self, workspace_name: &str, authorization: &str) -> Result<()> {
+        let url = self.host.join(&format!("workspaces/{}/authorities/public-keys/refresh", workspace_name))?;
+        nebula_telemetry::trace::traced(self.client.post(url).header(reqwest::header::AUTHORIZATION, authorization))
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "backbone.refresh_own_public_keys", skip(self, credential))]
+    async fn refresh_own_public_keys(&self, workspace_name: &str, credential: &str) -> Result<()> {
+        let url = self
+            .host
+            .join(&format!("workspaces/{}/authorities/self-registration/public-keys/refresh", workspace_name))?;
+        let request = SelfRefreshRequest { credential };
+        nebula_telemetry::trace::traced(self.client.post(url).json(&request)).send().await?.error_for_status()?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "backbone.register_authority", skip(self, bootstrap_token))]
+    async fn register_authority(
+        &self,
+        workspace_name: &str,
+        bootstrap_token: &str,
+        name: &str,
+        host: &Url,
+    ) -> Result<String> {
+        let url = self.host.join(&format!("workspaces/{}/authorities/self-registration", workspace_name))?;
+        let request = SelfRegistrationRequest { bootstrap_token, name, host: host.as_str() };
+        let response = nebula_telemetry::trace::traced(self.client.post(url).json(&request)).send().await?;
+        if response.status().is_client_error() {
+            return Err(RegistrationRejected(response.text().await?).into());
+        }
+        let response: SelfRegistrationResponse = response.error_for_status()?.json().await?;
+
+        Ok(response.credential)
     }
 }
 
 pub struct WorkspaceBackboneService {
     backbone_host: Url,
+    client: reqwest::Client,
 }
 
 impl WorkspaceBackboneService {
-    pub fn new(backbone_host: Url) -> Self {
-        Self { backbone_host }
+    pub fn new(backbone_host: Url, client: reqwest::Client) -> Self {
+        Self { backbone_host, client }
     }
 }
 
 #[async_trait]
 impl BackboneService for WorkspaceBackboneService {
-    async fn global_params(&self, workspace_name: &str) -> Result<GlobalParams<Bn462Curve>> {
-        get_global_params(&self.backbone_host, workspace_name).await
+    async fn parameter(&self, workspace_name: &str, version: Option<i32>) -> Result<Parameter> {
+        match version {
+            Some(version) => get_parameter_by_version(&self.client, &self.backbone_host, workspace_name, version).await,
+            // The latest version changes when a version is added to the workspace, so it is not cached.
+            None => {
+                let client = WorkspaceBackboneClient::new(self.backbone_host.clone()).client(self.client.clone());
+                client.get_parameter(workspace_name, None).await
+            }
+        }
+    }
+
+    async fn refresh_authority_public_keys(&self, workspace_name: &str, authorization: &str) -> Result<()> {
+        let client = WorkspaceBackboneClient::new(self.backbone_host.clone()).client(self.client.clone());
+        client.refresh_authority_public_keys(workspace_name, authorization).await
+    }
+
+    async fn refresh_own_public_keys(&self, workspace_name: &str, credential: &str) -> Result<()> {
+        let client = WorkspaceBackboneClient::new(self.backbone_host.clone()).client(self.client.clone());
+        client.refresh_own_public_keys(workspace_name, credential).await
+    }
+
+    async fn register_authority(
+        &self,
+        workspace_name: &str,
+        bootstrap_token: &str,
+        name: &str,
+        host: &Url,
+    ) -> Result<String> {
+        let client = WorkspaceBackboneClient::new(self.backbone_host.clone()).client(self.client.clone());
+        client.register_authority(workspace_name, bootstrap_token, name, host).await
     }
 }
 
-#[cached(size = 32, result = true, key = "String", convert = r#"{ format!("{}{}", host.as_str(), workspace_name) }"#)]
-async fn get_global_params(host: &Url, workspace_name: &str) -> Result<GlobalParams<Bn462Curve>> {
-    let client = WorkspaceBackboneClient::new(host.clone());
-    client.get_global_params(workspace_name).await
+#[cached(
+    size = 32,
+    result = true,
+    key = "String",
+    convert = r#"{ format!("{}{}#{}", host.as_str(), workspace_name, version) }"#
+)]
+async fn get_parameter_by_version(
+    client: &reqwest::Client,
+    host: &Url,
+    workspace_name: &str,
+    version: i32,
+) -> Result<Parameter> {
+    let client = WorkspaceBackboneClient::new(host.clone()).client(client.clone());
+    client.get_parameter(workspace_name, Some(version)).await
 }
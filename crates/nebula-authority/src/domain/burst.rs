@@ -0,0 +1,97 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    time::Duration,
+};
+
+use tokio::time::Instant;
+
+use crate::config::UserKeyBurstConfig;
+
+/// Limits the user key requests of each user of a workspace within a sliding window, so that a user bursting the user
+/// key endpoint is refused without affecting the other users.
+pub struct UserKeyBurstLimiter {
+    max_requests: usize,
+    window: Duration,
+    requests: HashMap<(String, String), VecDeque<Instant>>,
+}
+
+impl UserKeyBurstLimiter {
+    pub fn new(config: &UserKeyBurstConfig) -> Self {
+        Self { max_requests: config.max_requests, window: Duration::from_secs(config.window), requests: HashMap::new() }
+    }
+
+    /// Records a user key request of the user, and returns whether it is within the limit. Refused requests are not
+    /// counted, so that the user is admitted again once their earlier requests leave the window.
+    pub fn admit(&mut self, workspace_name: &str, gid: &str, now: Instant) -> bool {
+        let window = self.window;
+        let is_in_window = |requested_at: &Instant| now.duration_since(*requested_at) < window;
+        // Users whose requests have all left the window are forgotten, so that the map doesn't grow with every user.
+        self.requests.retain(|_, requests| requests.back().is_some_and(is_in_window));
+
+        let requests = self.requests.entry((workspace_name.to_owned(), gid.to_owned())).or_default();
+        while requests.front().is_some_and(|requested_at| !is_in_window(requested_at)) {
+            requests.pop_front();
+        }
+        if requests.len() >= self.max_requests {
+            return false;
+        }
+        requests.push_back(now);
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use tokio::time::Instant;
+
+    use super::UserKeyBurstLimiter;
+    use crate::config::UserKeyBurstConfig;
+
+    fn limiter() -> UserKeyBurstLimiter {
+        UserKeyBurstLimiter::new(&UserKeyBurstConfig { max_requests: 2, window: 60 })
+    }
+
+    #[test]
+    fn when_requests_exceed_max_requests_within_window_then_limiter_refuses_request() {
+        let now = Instant::now();
+        let mut limiter = limiter();
+
+        let admitted =
+            (0..3).map(|i| limiter.admit("workspace", "gid", now + Duration::from_secs(i))).collect::<Vec<_>>();
+
+        assert_eq!(admitted, vec![true, true, false]);
+    }
+
+    #[test]
+    fn when_earlier_requests_leave_window_then_limiter_admits_request_again() {
+        let now = Instant::now();
+        let mut limiter = limiter();
+        limiter.admit("workspace", "gid", now);
+        limiter.admit("workspace", "gid", now + Duration::from_secs(30));
+
+        let refused = limiter.admit("workspace", "gid", now + Duration::from_secs(59));
+        let admitted = limiter.admit("workspace", "gid", now + Duration::from_secs(60));
+
+        assert!(!refused);
+        assert!(admitted);
+    }
+
+    #[test]
+    fn when_user_bursts_then_limiter_admits_other_users() {
+        let now = Instant::now();
+        let mut limiter = limiter();
+        (0..3).for_each(|_| {
+            limiter.admit("workspace", "gid", now);
+        });
+
+        let other_gid = limiter.admit("workspace", "other", now);
+        let other_workspace = limiter.admit("other", "gid", now);
+        let refused = limiter.admit("workspace", "gid", now);
+
+        assert!(other_gid);
+        assert!(other_workspace);
+        assert!(!refused);
+    }
+}
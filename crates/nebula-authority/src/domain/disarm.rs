@@ -0,0 +1,279 @@
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use nebula_secret_sharing::shamir::{combine, Share};
+use serde::Serialize;
+use sha3::{Digest as _, Sha3_256};
+use ulid::Ulid;
+use zeroize::Zeroizing;
+
+/// A disarm session is discarded once no share has been submitted to it for this long, so that a session whose nonce
+/// is lost doesn't block disarming.
+pub const DISARM_SESSION_TTL: Duration = Duration::from_secs(15 * 60);
+
+/// Only the digests of the shares are stored, with which a share holder is told apart from anyone else without storing
+/// the shares.
+pub fn share_digest(share: &Share) -> Result<String> {
+    let share = Zeroizing::new(rmp_serde::to_vec(share)?);
+    Ok(STANDARD.encode(Sha3_256::digest(&share)))
+}
+
+/// Shares issued on the initialization of the key pair storage.
+pub struct ShareHolders {
+    pub threshold: usize,
+    pub digests: Vec<String>,
+}
+
+impl ShareHolders {
+    /// Recovers the share holders from shares which disarmed the key pair storage, for storages initialized before they
+    /// were recorded. The threshold is the fewest of the shares which combine into the same key, and only the holders of
+    /// the given shares are recovered.
+    pub fn recover(shares: &[Share]) -> Result<Self> {
+        let master_key = Zeroizing::new(combine(shares));
+        let threshold = (1..shares.len())
+            .find(|&count| *Zeroizing::new(combine(&shares[..count])) == *master_key)
+            .unwrap_or(shares.len());
+        let digests = shares.iter().map(share_digest).collect::<Result<Vec<_>>>()?;
+        Ok(Self { threshold, digests })
+    }
+
+    /// Checks that the share is one of the issued shares, which is the credential of its holder.
+    pub fn verify(&self, share: &Share) -> Result<(), DisarmShareError> {
+        if self.digests.contains(&share_digest(share)?) {
+            Ok(())
+        } else {
+            Err(DisarmShareError::UnknownShare)
+        }
+    }
+}
+
+/// Shares submitted one by one by the share holders, kept until enough of them are submitted to disarm the key pair
+/// storage. The shares are zeroized as the session is dropped.
+struct DisarmSession {
+    nonce: String,
+    shares: Vec<Share>,
+    submitted_at: Instant,
+}
+
+/// Disarm session of the authority, if one is open. Only the share holder who opens a session is given its nonce, and
+/// the nonce is required to submit to or cancel it.
+#[derive(Default)]
+pub struct DisarmSessionState {
+    session: Option<DisarmSession>,
+}
+
+impl DisarmSessionState {
+    /// Opens a session with the first share, and returns its nonce.
+    pub fn open(&mut self, share: Share, now: Instant) -> Result<String, DisarmShareError> {
+        if self.session(now).is_some() {
+            return Err(DisarmShareError::SessionInProgress);
+        }
+
+        let nonce = Ulid::new().to_string();
+        self.session = Some(DisarmSession { nonce: nonce.clone(), shares: vec![share], submitted_at: now });
+        Ok(nonce)
+    }
+
+    /// Adds a share to the session with the nonce, and returns the number of shares submitted.
+    pub fn submit(&mut self, nonce: &str, share: Share, now: Instant) -> Result<usize, DisarmShareError> {
+        let session = self.session_with_nonce(nonce, now)?;
+        if session.shares.iter().any(|submitted| submitted.x == share.x) {
+            return Err(DisarmShareError::DuplicateShare);
+        }
+
+        session.shares.push(share);
+        session.submitted_at = now;
+        Ok(session.shares.len())
+    }
+
+    pub fn cancel(&mut self, nonce: &str, now: Instant) -> Result<(), DisarmShareError> {
+        self.session_with_nonce(nonce, now)?;
+        self.session = None;
+        Ok(())
+    }
+
+    /// Takes the shares out of the session, which is closed.
+    pub fn close(&mut self) -> Vec<Share> {
+        self.session.take().map(|session| session.shares).unwrap_or_default()
+    }
+
+    /// Returns the number of shares submitted to the open session.
+    pub fn progress(&mut self, now: Instant) -> usize {
+        self.session(now).map_or(0, |session| session.shares.len())
+    }
+
+    fn session(&mut self, now: Instant) -> Option<&mut DisarmSession> {
+        if self.session.as_ref().is_some_and(|session| now.duration_since(session.submitted_at) >= DISARM_SESSION_TTL) {
+            self.session = None;
+        }
+        self.session.as_mut()
+    }
+
+    fn session_with_nonce(&mut self, nonce: &str, now: Instant) -> Result<&mut DisarmSession, DisarmShareError> {
+        match self.session(now) {
+            Some(session) if session.nonce == nonce => Ok(session),
+            Some(_) => Err(DisarmShareError::NonceMismatch),
+            None => Err(DisarmShareError::NoSession),
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DisarmProgress {
+    pub progress: usize,
+    /// Number of shares required, which is unknown for storages initialized before it was recorded.
+    pub threshold: Option<usize>,
+    pub disarmed: bool,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum DisarmShareError {
+    #[error("key pair storage is not armored")]
+    NotArmored,
+    #[error("share holders are not recorded for the key pair storage, which is initialized before they were recorded")]
+    ShareHoldersNotRecorded,
+    #[error("share is not one of the shares of the key pair storage")]
+    UnknownShare,
+    #[error("another disarm session is in progress")]
+    SessionInProgress,
+    #[error("no disarm session is in progress")]
+    NoSession,
+    #[error("nonce doesn't match the disarm session")]
+    NonceMismatch,
+    #[error("share has already been submitted in the disarm session")]
+    DuplicateShare,
+    #[error("shares don't disarm the key pair storage, so the disarm session is closed")]
+    InvalidShares,
+    #[error(transparent)]
+    Anyhow(#[from] anyhow::Error),
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use nebula_secret_sharing::shamir::{split, Share};
+
+    use super::{share_digest, DisarmSessionState, DisarmShareError, ShareHolders, DISARM_SESSION_TTL};
+
+    fn shares() -> Vec<Share> {
+        split(&[7; 32], 2, 3)
+    }
+
+    #[test]
+    fn when_opening_disarm_session_then_progress_is_one() {
+        let now = Instant::now();
+        let mut state = DisarmSessionState::default();
+
+        let result = state.open(shares().remove(0), now);
+
+        assert!(result.is_ok());
+        assert_eq!(state.progress(now), 1);
+    }
+
+    #[test]
+    fn when_opening_disarm_session_in_progress_then_disarm_session_returns_session_in_progress_err() {
+        let now = Instant::now();
+        let mut shares = shares();
+        let mut state = DisarmSessionState::default();
+        state.open(shares.remove(0), now).unwrap();
+
+        let result = state.open(shares.remove(0), now);
+
+        assert!(matches!(result, Err(DisarmShareError::SessionInProgress)));
+    }
+
+    #[test]
+    fn when_submitting_share_with_nonce_then_shares_are_closed_with_the_session() {
+        let now = Instant::now();
+        let mut shares = shares();
+        let mut state = DisarmSessionState::default();
+        let nonce = state.open(shares.remove(0), now).unwrap();
+
+        let progress = state.submit(&nonce, shares.remove(0), now).unwrap();
+        let submitted = state.close();
+
+        assert_eq!(progress, 2);
+        assert_eq!(submitted.len(), 2);
+        assert_eq!(state.progress(now), 0);
+    }
+
+    #[test]
+    fn when_submitting_share_with_wrong_nonce_then_disarm_session_returns_nonce_mismatch_err() {
+        let now = Instant::now();
+        let mut shares = shares();
+        let mut state = DisarmSessionState::default();
+        state.open(shares.remove(0), now).unwrap();
+
+        let result = state.submit("wrong", shares.remove(0), now);
+
+        assert!(matches!(result, Err(DisarmShareError::NonceMismatch)));
+        assert_eq!(state.progress(now), 1);
+    }
+
+    #[test]
+    fn when_submitting_same_share_twice_then_disarm_session_returns_duplicate_share_err() {
+        let now = Instant::now();
+        let shares = shares();
+        let mut state = DisarmSessionState::default();
+        let nonce = state.open(shares[0].clone(), now).unwrap();
+
+        let result = state.submit(&nonce, shares[0].clone(), now);
+
+        assert!(matches!(result, Err(DisarmShareError::DuplicateShare)));
+    }
+
+    #[test]
+    fn when_cancelling_disarm_session_with_nonce_then_submitted_shares_are_discarded() {
+        let now = Instant::now();
+        let mut state = DisarmSessionState::default();
+        let nonce = state.open(shares().remove(0), now).unwrap();
+
+        let result = state.cancel(&nonce, now);
+
+        assert!(result.is_ok());
+        assert_eq!(state.progress(now), 0);
+    }
+
+    #[test]
+    fn when_cancelling_disarm_session_with_wrong_nonce_then_disarm_session_is_kept() {
+        let now = Instant::now();
+        let mut state = DisarmSessionState::default();
+        state.open(shares().remove(0), now).unwrap();
+
+        let result = state.cancel("wrong", now);
+
+        assert!(matches!(result, Err(DisarmShareError::NonceMismatch)));
+        assert_eq!(state.progress(now), 1);
+    }
+
+    #[test]
+    fn when_disarm_session_expires_then_another_session_can_be_opened() {
+        let now = Instant::now();
+        let mut shares = shares();
+        let mut state = DisarmSessionState::default();
+        let nonce = state.open(shares.remove(0), now).unwrap();
+        let later = now + DISARM_SESSION_TTL + Duration::from_secs(1);
+
+        let submitted = state.submit(&nonce, shares.remove(0), later);
+        let opened = state.open(shares.remove(0), later);
+
+        assert!(matches!(submitted, Err(DisarmShareError::NoSession)));
+        assert!(opened.is_ok());
+    }
+
+    #[test]
+    fn when_verifying_share_of_other_storage_then_share_holders_return_unknown_share_err() {
+        let shares = shares();
+        let share_holders =
+            ShareHolders { threshold: 2, digests: shares.iter().map(|share| share_digest(share).unwrap()).collect() };
+
+        let issued = share_holders.verify(&shares[2]);
+        let other = share_holders.verify(&split(&[8; 32], 2, 3)[2]);
+
+        assert!(issued.is_ok());
+        assert!(matches!(other, Err(DisarmShareError::UnknownShare)));
+    }
+}
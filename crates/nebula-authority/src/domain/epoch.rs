@@ -0,0 +1,177 @@
+use chrono::{DateTime, Datelike, Days, Months, NaiveDate, NaiveTime, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+
+/// Length of an epoch. User keys bound to an epoch only decrypt secrets encrypted in the epoch.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum EpochPeriod {
+    /// `2024-10-31`
+    Day,
+    /// ISO week, e.g. `2024-W44`
+    Week,
+    /// `2024-10`
+    Month,
+}
+
+impl EpochPeriod {
+    pub fn epoch(&self, at: DateTime<Utc>) -> String {
+        let date = at.date_naive();
+        match self {
+            EpochPeriod::Day => date.format("%Y-%m-%d").to_string(),
+            EpochPeriod::Week => {
+                let week = date.iso_week();
+                format!("{}-W{:02}", week.year(), week.week())
+            }
+            EpochPeriod::Month => date.format("%Y-%m").to_string(),
+        }
+    }
+
+    /// Returns the first day of the epoch, or `None` if `epoch` is not an epoch of this period.
+    pub fn start(&self, epoch: &str) -> Option<NaiveDate> {
+        let start = match self {
+            EpochPeriod::Day => NaiveDate::parse_from_str(epoch, "%Y-%m-%d").ok()?,
+            EpochPeriod::Week => {
+                let (year, week) = epoch.split_once("-W")?;
+                NaiveDate::from_isoywd_opt(year.parse().ok()?, week.parse().ok()?, Weekday::Mon)?
+            }
+            EpochPeriod::Month => NaiveDate::parse_from_str(&format!("{epoch}-01"), "%Y-%m-%d").ok()?,
+        };
+
+        // Rejects the forms which parse but are not the canonical one, such as `2024-1`.
+        (self.epoch(start.and_time(NaiveTime::MIN).and_utc()) == epoch).then_some(start)
+    }
+
+    /// Returns when the epoch of `at` ends.
+    pub fn end(&self, at: DateTime<Utc>) -> DateTime<Utc> {
+        let date = at.date_naive();
+        let end = match self {
+            EpochPeriod::Day => date.succ_opt(),
+            EpochPeriod::Week => date.checked_add_days(Days::new(7 - date.weekday().num_days_from_monday() as u64)),
+            EpochPeriod::Month => date.with_day(1).and_then(|date| date.checked_add_months(Months::new(1))),
+        };
+
+        end.unwrap_or(NaiveDate::MAX).and_time(NaiveTime::MIN).and_utc()
+    }
+}
+
+const DEFAULT_EPOCH_ATTRIBUTE: &str = "epoch";
+
+/// Epoch attribute the authority adds to every user key it issues.
+pub struct EpochBinding {
+    pub attribute: String,
+    pub period: EpochPeriod,
+}
+
+#[derive(thiserror::Error, Debug)]
+#[error("epoch({0}) is invalid or has not started yet")]
+pub struct InvalidEpoch(pub String);
+
+impl EpochBinding {
+    pub fn new(attribute: Option<String>, period: EpochPeriod) -> Self {
+        Self { attribute: attribute.unwrap_or_else(|| DEFAULT_EPOCH_ATTRIBUTE.to_owned()), period }
+    }
+
+    /// Returns the epoch attribute as `key=value` for the epoch, or for the current epoch if not given. A past epoch is
+    /// allowed so that the secrets encrypted in it stay readable, but a future one is not.
+    pub fn attribute(&self, epoch: Option<&str>, now: DateTime<Utc>) -> Result<String, InvalidEpoch> {
+        let epoch = match epoch {
+            Some(epoch) => {
+                let start = self.period.start(epoch).ok_or_else(|| InvalidEpoch(epoch.to_owned()))?;
+                if start > now.date_naive() {
+                    return Err(InvalidEpoch(epoch.to_owned()));
+                }
+                epoch.to_owned()
+            }
+            None => self.period.epoch(now),
+        };
+
+        Ok(format!("{}={}", self.attribute, epoch))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+
+    use super::{EpochBinding, EpochPeriod};
+
+    fn at(year: i32, month: u32, day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, 0, 0).unwrap()
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn when_week_spans_years_then_epoch_and_start_follow_iso_week_year() {
+        assert_eq!(EpochPeriod::Week.epoch(at(2024, 12, 30, 0)), "2025-W01");
+        assert_eq!(EpochPeriod::Week.start("2025-W01"), Some(date(2024, 12, 30)));
+        assert_eq!(EpochPeriod::Week.start("2020-W53"), Some(date(2020, 12, 28)));
+    }
+
+    #[test]
+    fn when_epoch_is_not_canonical_form_of_period_then_start_is_none() {
+        assert_eq!(EpochPeriod::Week.start("2024-W1"), None);
+        assert_eq!(EpochPeriod::Week.start("2024-W54"), None);
+        assert_eq!(EpochPeriod::Week.start("2024-10"), None);
+        assert_eq!(EpochPeriod::Month.start("2024-2"), None);
+        assert_eq!(EpochPeriod::Month.start("2024-13"), None);
+        assert_eq!(EpochPeriod::Day.start("2023-02-29"), None);
+    }
+
+    #[test]
+    fn when_month_or_day_epoch_is_given_then_start_is_its_first_day() {
+        assert_eq!(EpochPeriod::Month.start("2024-02"), Some(date(2024, 2, 1)));
+        assert_eq!(EpochPeriod::Day.start("2024-02-29"), Some(date(2024, 2, 29)));
+    }
+
+    #[test]
+    fn when_at_is_last_moment_of_week_then_end_is_next_monday() {
+        assert_eq!(EpochPeriod::Week.end(at(2024, 11, 3, 23)), at(2024, 11, 4, 0));
+        assert_eq!(EpochPeriod::Week.end(at(2024, 11, 4, 0)), at(2024, 11, 11, 0));
+    }
+
+    #[test]
+    fn when_at_is_in_last_month_or_day_of_year_then_end_rolls_over_year() {
+        assert_eq!(EpochPeriod::Month.end(at(2024, 12, 15, 12)), at(2025, 1, 1, 0));
+        assert_eq!(EpochPeriod::Month.end(at(2024, 1, 31, 12)), at(2024, 2, 1, 0));
+        assert_eq!(EpochPeriod::Day.end(at(2024, 12, 31, 23)), at(2025, 1, 1, 0));
+    }
+
+    #[test]
+    fn when_epoch_is_not_given_then_attribute_is_current_epoch() {
+        let binding = EpochBinding::new(None, EpochPeriod::Week);
+
+        let attribute = binding.attribute(None, at(2024, 10, 31, 12)).expect("current epoch should be valid");
+
+        assert_eq!(attribute, "epoch=2024-W44");
+    }
+
+    #[test]
+    fn when_epoch_is_current_or_past_then_attribute_is_bound_to_it() {
+        let binding = EpochBinding::new(Some("period".to_owned()), EpochPeriod::Month);
+        let now = at(2024, 10, 31, 12);
+
+        assert_eq!(binding.attribute(Some("2024-10"), now).unwrap(), "period=2024-10");
+        assert_eq!(binding.attribute(Some("2023-01"), now).unwrap(), "period=2023-01");
+    }
+
+    #[test]
+    fn when_epoch_has_not_started_yet_then_attribute_is_rejected() {
+        let binding = EpochBinding::new(None, EpochPeriod::Week);
+        let now = at(2024, 10, 31, 12);
+
+        assert!(binding.attribute(Some("2024-W45"), now).is_err());
+        assert!(binding.attribute(Some("2024-W44"), now).is_ok());
+    }
+
+    #[test]
+    fn when_epoch_is_invalid_then_attribute_is_rejected() {
+        let binding = EpochBinding::new(None, EpochPeriod::Day);
+
+        let result = binding.attribute(Some("2024-10"), at(2024, 10, 31, 12));
+
+        assert!(result.is_err());
+    }
+}
@@ -1,24 +1,175 @@
 use anyhow::{bail, Result};
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use nebula_abe::{
-    curves::bn462::Bn462Curve,
+    curves::{bn462::Bn462Curve, Curve},
     random::miracl::MiraclRng,
-    schemes::isabella24::{AuthorityKeyPair, GlobalParams},
+    schemes::isabella24::{AuthorityKeyPair, GlobalParams, UserSecretKey},
+    with_curve,
 };
 use nebula_secret_sharing::shamir::{combine, split, Share};
 use nebula_storage::{
     backend::{file::FileStorage, postgres::PostgresStorage},
     shield::{aes::AESShieldStorage, Shield},
-    Storage as _,
+    Storage,
 };
 use rand::{rngs::OsRng, RngCore as _};
+use serde::{Deserialize, Serialize};
 use zeroize::Zeroizing;
 
-pub type KeyPair = AuthorityKeyPair<Bn462Curve>;
+use super::{
+    backbone::Parameter,
+    disarm::{share_digest, ShareHolders},
+};
+
 pub type KeyVersion = u64;
 
+/// Authority key pair generated with a parameter version of the workspace. `key_pair` is the
+/// `AuthorityKeyPair` on `curve` serialized in MessagePack.
+#[derive(Serialize, Deserialize)]
+pub struct KeyPair {
+    pub parameter_version: i32,
+    pub curve: Curve,
+    key_pair: Zeroizing<Vec<u8>>,
+}
+
+impl KeyPair {
+    fn generate(rng: &mut MiraclRng, parameter: &Parameter, name: String) -> Result<Self> {
+        let key_pair = with_curve!(parameter.curve, C => {
+            let gp: GlobalParams<C> = rmp_serde::from_slice(&parameter.value)?;
+            let key_pair = tracing::info_span!("abe.key_pair_generation")
+                .in_scope(|| AuthorityKeyPair::<C>::new(rng, &gp, name));
+            Zeroizing::new(rmp_serde::to_vec(&key_pair)?)
+        });
+
+        Ok(Self { parameter_version: parameter.version, curve: parameter.curve, key_pair })
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        match rmp_serde::from_slice(bytes) {
+            Ok(key_pair) => Ok(key_pair),
+            // Key pairs stored before parameter versions were introduced are bare BN462 key pairs of the first version.
+            Err(_) => {
+                let _: AuthorityKeyPair<Bn462Curve> = rmp_serde::from_slice(bytes)?;
+                Ok(Self { parameter_version: 1, curve: Curve::Bn462, key_pair: Zeroizing::new(bytes.to_vec()) })
+            }
+        }
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>> {
+        Ok(rmp_serde::to_vec_named(self)?)
+    }
+
+    /// Returns the public key serialized in MessagePack.
+    pub fn public_key(&self) -> Result<Vec<u8>> {
+        with_curve!(self.curve, C => {
+            let key_pair: AuthorityKeyPair<C> = rmp_serde::from_slice(&self.key_pair)?;
+            Ok(rmp_serde::to_vec(&key_pair.pk)?)
+        })
+    }
+
+    /// Issues a user key for the attributes and returns it serialized in MessagePack. `parameter` must be the
+    /// parameter with which the key pair is generated.
+    pub fn user_key<S: AsRef<str>>(
+        &self,
+        rng: &mut MiraclRng,
+        parameter: &Parameter,
+        gid: &str,
+        attributes: &[S],
+    ) -> Result<Zeroizing<Vec<u8>>> {
+        if parameter.version != self.parameter_version || parameter.curve != self.curve {
+            bail!(
+                "key pair is generated with parameter version {} on {}, but parameter version {} on {} is given",
+                self.parameter_version,
+                self.curve,
+                parameter.version,
+                parameter.curve
+            );
+        }
+
+        with_curve!(self.curve, C => {
+            let gp: GlobalParams<C> = rmp_serde::from_slice(&parameter.value)?;
+            let key_pair: AuthorityKeyPair<C> = rmp_serde::from_slice(&self.key_pair)?;
+            let user_key = UserSecretKey::<C>::new(rng, &gp, &key_pair.mk, gid, attributes);
+            Ok(Zeroizing::new(rmp_serde::to_vec(&user_key)?))
+        })
+    }
+}
+
+/// Lifecycle of a key pair version. A retired version is no longer used for new encryptions but still issues user
+/// keys to decrypt existing secrets, while a revoked version issues no more user keys.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum KeyVersionStatus {
+    Active,
+    Retired,
+    Revoked,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct KeyVersionState {
+    pub status: KeyVersionStatus,
+    pub reason: Option<String>,
+    pub changed_at: Option<DateTime<Utc>>,
+}
+
+impl Default for KeyVersionState {
+    fn default() -> Self {
+        Self { status: KeyVersionStatus::Active, reason: None, changed_at: None }
+    }
+}
+
+impl KeyVersionState {
+    /// Moves the version to `status`. A version only moves forward from active to retired to revoked.
+    pub fn transition(
+        &self,
+        status: KeyVersionStatus,
+        reason: Option<String>,
+    ) -> std::result::Result<Self, KeyVersionStatusError> {
+        let allowed = matches!(
+            (self.status, status),
+            (KeyVersionStatus::Active, KeyVersionStatus::Retired)
+                | (KeyVersionStatus::Active, KeyVersionStatus::Revoked)
+                | (KeyVersionStatus::Retired, KeyVersionStatus::Revoked)
+        );
+        if !allowed {
+            return Err(KeyVersionStatusError::InvalidTransition { from: self.status, to: status });
+        }
+
+        Ok(Self { status, reason, changed_at: Some(Utc::now()) })
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        Ok(rmp_serde::from_slice(bytes)?)
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>> {
+        Ok(rmp_serde::to_vec_named(self)?)
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum KeyVersionStatusError {
+    #[error("key pair version {0} is not found")]
+    VersionNotFound(KeyVersion),
+    #[error("key pair version {0} is the latest version, so roll the key pair before retiring or revoking it")]
+    LatestVersion(KeyVersion),
+    #[error("key pair version can't be changed from {from:?} to {to:?}")]
+    InvalidTransition { from: KeyVersionStatus, to: KeyVersionStatus },
+    #[error(transparent)]
+    Anyhow(#[from] anyhow::Error),
+}
+
 const KEY_PAIR_PATH: &str = "/authority/keypair/";
 const KEY_PAIR_VERSION_NAME: &str = "version";
+const KEY_PAIR_STATUS_NAME: &str = "status";
+const KEY_PAIR_ROLLED_AT_NAME: &str = "rolled_at";
+const KEY_PAIR_CREATED_AT_NAME: &str = "created_at";
+/// Path of the share threshold, which is stored unencrypted so that it is known while the storage is armored.
+const SHIELD_THRESHOLD_PATH: &str = "/authority/shield/threshold";
+/// Path of the digests of the shares, which are stored unencrypted so that share holders are verified while the storage
+/// is armored.
+const SHIELD_SHARE_DIGESTS_PATH: &str = "/authority/shield/share_digests";
 
 #[async_trait]
 pub trait KeyPairService {
@@ -30,22 +181,83 @@ pub trait KeyPairService {
     fn key_pair_path(&self, name: &str, version: KeyVersion) -> String {
         format!("{}{}/{}", KEY_PAIR_PATH, name, version)
     }
+    #[inline(always)]
+    fn status_path(&self, name: &str, version: KeyVersion) -> String {
+        format!("{}{}/{}/{}", KEY_PAIR_PATH, name, KEY_PAIR_STATUS_NAME, version)
+    }
+    #[inline(always)]
+    fn rolled_at_path(&self, name: &str) -> String {
+        format!("{}{}/{}", KEY_PAIR_PATH, name, KEY_PAIR_ROLLED_AT_NAME)
+    }
+    #[inline(always)]
+    fn created_at_path(&self, name: &str, version: KeyVersion) -> String {
+        format!("{}{}/{}/{}", KEY_PAIR_PATH, name, KEY_PAIR_CREATED_AT_NAME, version)
+    }
 
-    async fn generate_latest_key_pair(
-        &self,
-        gp: &GlobalParams<Bn462Curve>,
-        name: &str,
-    ) -> Result<(KeyPair, KeyVersion)>;
+    async fn generate_latest_key_pair(&self, parameter: &Parameter, name: &str) -> Result<(KeyPair, KeyVersion)>;
     async fn latest_key_pair_version(&self, name: &str) -> Result<Option<KeyVersion>>;
     async fn latest_key_pair(&self, name: &str) -> Result<Option<(KeyPair, KeyVersion)>>;
     async fn key_pair_by_version(&self, name: &str, version: KeyVersion) -> Result<Option<KeyPair>>;
+    /// Returns the state of the version, which is active unless it has been retired or revoked.
+    async fn key_pair_state(&self, name: &str, version: KeyVersion) -> Result<KeyVersionState>;
+    async fn set_key_pair_state(&self, name: &str, version: KeyVersion, state: &KeyVersionState) -> Result<()>;
+    /// Returns when the latest version was generated, which is unknown for key pairs generated before it was recorded.
+    async fn last_rolled_at(&self, name: &str) -> Result<Option<DateTime<Utc>>>;
+    async fn set_last_rolled_at(&self, name: &str, rolled_at: DateTime<Utc>) -> Result<()>;
+    /// Returns every version up to `latest_version` from the oldest, with when it was generated, which is unknown for
+    /// versions generated before it was recorded, and its state.
+    async fn key_pair_versions(
+        &self,
+        name: &str,
+        latest_version: KeyVersion,
+    ) -> Result<Vec<(KeyVersion, Option<DateTime<Utc>>, KeyVersionState)>>;
+}
+
+/// Reads when the versions were generated and their states in a single read of the storage.
+async fn read_key_pair_versions<S>(
+    service: &(impl KeyPairService + Sync + ?Sized),
+    storage: &AESShieldStorage<S>,
+    name: &str,
+    latest_version: KeyVersion,
+) -> Result<Vec<(KeyVersion, Option<DateTime<Utc>>, KeyVersionState)>>
+where
+    S: Storage<Key = str, Value = [u8]> + Sync,
+{
+    let versions: Vec<KeyVersion> = (1..=latest_version).collect();
+    let paths: Vec<String> = versions
+        .iter()
+        .flat_map(|version| [service.created_at_path(name, *version), service.status_path(name, *version)])
+        .collect();
+    let values = storage.get_many(&paths.iter().map(String::as_str).collect::<Vec<_>>()).await?;
+
+    versions
+        .into_iter()
+        .zip(values.chunks(2))
+        .map(|(version, values)| {
+            let created_at = values[0].as_deref().map(parse_timestamp).transpose()?;
+            let state = values[1].as_deref().map(KeyVersionState::from_bytes).transpose()?.unwrap_or_default();
+            Ok((version, created_at, state))
+        })
+        .collect()
+}
+
+fn parse_timestamp(bytes: &[u8]) -> Result<DateTime<Utc>> {
+    Ok(DateTime::parse_from_rfc3339(std::str::from_utf8(bytes)?)?.to_utc())
 }
 
 #[async_trait]
 pub trait ShieldedKeyPairService: KeyPairService {
     async fn shield_initialize(&self, share: usize, threshold: usize) -> Result<Vec<Share>>;
+    async fn is_shield_initialized(&self) -> Result<bool>;
     async fn storage_armor(&self) -> Result<()>;
     async fn storage_disarm(&self, shares: &[Share]) -> Result<()>;
+    async fn is_storage_armored(&self) -> bool;
+    /// Returns the number of shares required to disarm the storage, which is unknown for storages initialized before it
+    /// was recorded.
+    async fn shield_threshold(&self) -> Result<Option<usize>>;
+    /// Returns the holders of the shares, which are unknown for storages initialized before they were recorded.
+    async fn share_holders(&self) -> Result<Option<ShareHolders>>;
+    async fn record_share_holders(&self, share_holders: &ShareHolders) -> Result<()>;
 }
 
 pub struct FileKeyPairService<'a> {
@@ -60,11 +272,7 @@ impl<'a> FileKeyPairService<'a> {
 
 #[async_trait]
 impl KeyPairService for FileKeyPairService<'_> {
-    async fn generate_latest_key_pair(
-        &self,
-        gp: &GlobalParams<Bn462Curve>,
-        name: &str,
-    ) -> Result<(KeyPair, KeyVersion)> {
+    async fn generate_latest_key_pair(&self, parameter: &Parameter, name: &str) -> Result<(KeyPair, KeyVersion)> {
         let mut seed = [0u8; 32];
         OsRng.fill_bytes(&mut seed);
         let mut rng = MiraclRng::new();
@@ -77,11 +285,14 @@ impl KeyPairService for FileKeyPairService<'_> {
         let new_version_path = self.key_pair_path(name, new_version);
 
         let name_with_version = format!("{}#{}", name, new_version);
-        let key_pair = KeyPair::new(&mut rng, gp, name_with_version);
-        let key_pair_bytes = rmp_serde::to_vec(&key_pair)?;
+        let key_pair = KeyPair::generate(&mut rng, parameter, name_with_version)?;
+        let key_pair_bytes = Zeroizing::new(key_pair.to_bytes()?);
 
+        let created_at = Utc::now();
         self.storage.set(&new_version_path, &key_pair_bytes).await?;
+        self.storage.set(&self.created_at_path(name, new_version), created_at.to_rfc3339().as_bytes()).await?;
         self.storage.set(&version_path, new_version.to_string().as_bytes()).await?;
+        self.set_last_rolled_at(name, created_at).await?;
 
         Ok((key_pair, new_version))
     }
@@ -116,12 +327,48 @@ impl KeyPairService for FileKeyPairService<'_> {
         let key_pair = self.storage.get(&key_pair_path).await?;
         match key_pair {
             Some(key_pair) => {
-                let key_pair: KeyPair = rmp_serde::from_slice(&key_pair)?;
+                let key_pair = KeyPair::from_bytes(&key_pair)?;
                 Ok(Some(key_pair))
             }
             None => Ok(None),
         }
     }
+
+    async fn key_pair_state(&self, name: &str, version: KeyVersion) -> Result<KeyVersionState> {
+        let status_path = self.status_path(name, version);
+        match self.storage.get(&status_path).await? {
+            Some(state) => KeyVersionState::from_bytes(&state),
+            None => Ok(KeyVersionState::default()),
+        }
+    }
+
+    async fn set_key_pair_state(&self, name: &str, version: KeyVersion, state: &KeyVersionState) -> Result<()> {
+        let status_path = self.status_path(name, version);
+        self.storage.set(&status_path, &state.to_bytes()?).await?;
+        Ok(())
+    }
+
+    async fn last_rolled_at(&self, name: &str) -> Result<Option<DateTime<Utc>>> {
+        let rolled_at_path = self.rolled_at_path(name);
+        match self.storage.get(&rolled_at_path).await? {
+            Some(rolled_at) => Ok(Some(DateTime::parse_from_rfc3339(&String::from_utf8(rolled_at)?)?.to_utc())),
+            None => Ok(None),
+        }
+    }
+
+    async fn set_last_rolled_at(&self, name: &str, rolled_at: DateTime<Utc>) -> Result<()> {
+        let rolled_at_path = self.rolled_at_path(name);
+        self.storage.set(&rolled_at_path, rolled_at.to_rfc3339().as_bytes()).await?;
+        Ok(())
+    }
+
+    async fn key_pair_versions(
+        &self,
+        name: &str,
+        latest_version: KeyVersion,
+    ) -> Result<Vec<(KeyVersion, Option<DateTime<Utc>>, KeyVersionState)>> {
+        read_key_pair_versions(self, &self.storage, name, latest_version).await
+    }
 }
 
 #[async_trait]
@@ -132,13 +379,19 @@ impl ShieldedKeyPairService for FileKeyPairService<'_> {
         }
 
         let master_key = self.storage.generate_key().await?;
-        let shares = split(&master_key, share, threshold);
+        let shares = split(&master_key, threshold, share);
         self.storage.initialize(&master_key).await?;
+        let digests = shares.iter().map(share_digest).collect::<Result<Vec<_>>>()?;
+        self.record_share_holders(&ShareHolders { threshold, digests }).await?;
 
         self.storage.disarm(&master_key).await?; // Disarm the storage immediately after initialization
         Ok(shares)
     }
 
+    async fn is_shield_initialized(&self) -> Result<bool> {
+        Ok(self.storage.is_initialized().await?)
+    }
+
     async fn storage_armor(&self) -> Result<()> {
         Ok(self.storage.armor().await?)
     }
@@ -147,6 +400,32 @@ impl ShieldedKeyPairService for FileKeyPairService<'_> {
         let master_key = Zeroizing::new(combine(shares));
         Ok(self.storage.disarm(&master_key).await?)
     }
+
+    async fn is_storage_armored(&self) -> bool {
+        self.storage.is_armored().await
+    }
+
+    async fn shield_threshold(&self) -> Result<Option<usize>> {
+        match self.storage.inner().get(SHIELD_THRESHOLD_PATH).await? {
+            Some(threshold) => Ok(Some(String::from_utf8(threshold)?.parse()?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn share_holders(&self) -> Result<Option<ShareHolders>> {
+        let (Some(threshold), Some(digests)) =
+            (self.shield_threshold().await?, self.storage.inner().get(SHIELD_SHARE_DIGESTS_PATH).await?)
+        else {
+            return Ok(None);
+        };
+        Ok(Some(ShareHolders { threshold, digests: serde_json::from_slice(&digests)? }))
+    }
+
+    async fn record_share_holders(&self, share_holders: &ShareHolders) -> Result<()> {
+        self.storage.inner().set(SHIELD_THRESHOLD_PATH, share_holders.threshold.to_string().as_bytes()).await?;
+        self.storage.inner().set(SHIELD_SHARE_DIGESTS_PATH, &serde_json::to_vec(&share_holders.digests)?).await?;
+        Ok(())
+    }
 }
 
 pub struct PostgresKeyPairService {
@@ -161,11 +440,7 @@ impl PostgresKeyPairService {
 
 #[async_trait]
 impl KeyPairService for PostgresKeyPairService {
-    async fn generate_latest_key_pair(
-        &self,
-        gp: &GlobalParams<Bn462Curve>,
-        name: &str,
-    ) -> Result<(KeyPair, KeyVersion)> {
+    async fn generate_latest_key_pair(&self, parameter: &Parameter, name: &str) -> Result<(KeyPair, KeyVersion)> {
         let mut seed = [0u8; 32];
         OsRng.fill_bytes(&mut seed);
         let mut rng = MiraclRng::new();
@@ -178,11 +453,14 @@ impl KeyPairService for PostgresKeyPairService {
         let new_version_path = self.key_pair_path(name, new_version);
 
         let name_with_version = format!("{}#{}", name, new_version);
-        let key_pair = KeyPair::new(&mut rng, gp, name_with_version);
-        let key_pair_bytes = rmp_serde::to_vec(&key_pair)?;
+        let key_pair = KeyPair::generate(&mut rng, parameter, name_with_version)?;
+        let key_pair_bytes = Zeroizing::new(key_pair.to_bytes()?);
 
+        let created_at = Utc::now();
         self.storage.set(&new_version_path, &key_pair_bytes).await?;
+        self.storage.set(&self.created_at_path(name, new_version), created_at.to_rfc3339().as_bytes()).await?;
         self.storage.set(&version_path, new_version.to_string().as_bytes()).await?;
+        self.set_last_rolled_at(name, created_at).await?;
 
         Ok((key_pair, new_version))
     }
@@ -217,12 +495,48 @@ impl KeyPairService for PostgresKeyPairService {
         let key_pair = self.storage.get(&key_pair_path).await?;
         match key_pair {
             Some(key_pair) => {
-                let key_pair: KeyPair = rmp_serde::from_slice(&key_pair)?;
+                let key_pair = KeyPair::from_bytes(&key_pair)?;
                 Ok(Some(key_pair))
             }
             None => Ok(None),
         }
     }
+
+    async fn key_pair_state(&self, name: &str, version: KeyVersion) -> Result<KeyVersionState> {
+        let status_path = self.status_path(name, version);
+        match self.storage.get(&status_path).await? {
+            Some(state) => KeyVersionState::from_bytes(&state),
+            None => Ok(KeyVersionState::default()),
+        }
+    }
+
+    async fn set_key_pair_state(&self, name: &str, version: KeyVersion, state: &KeyVersionState) -> Result<()> {
+        let status_path = self.status_path(name, version);
+        self.storage.set(&status_path, &state.to_bytes()?).await?;
+        Ok(())
+    }
+
+    async fn last_rolled_at(&self, name: &str) -> Result<Option<DateTime<Utc>>> {
+        let rolled_at_path = self.rolled_at_path(name);
+        match self.storage.get(&rolled_at_path).await? {
+            Some(rolled_at) => Ok(Some(DateTime::parse_from_rfc3339(&String::from_utf8(rolled_at)?)?.to_utc())),
+            None => Ok(None),
+        }
+    }
+
+    async fn set_last_rolled_at(&self, name: &str, rolled_at: DateTime<Utc>) -> Result<()> {
+        let rolled_at_path = self.rolled_at_path(name);
+        self.storage.set(&rolled_at_path, rolled_at.to_rfc3339().as_bytes()).await?;
+        Ok(())
+    }
+
+    async fn key_pair_versions(
+        &self,
+        name: &str,
+        latest_version: KeyVersion,
+    ) -> Result<Vec<(KeyVersion, Option<DateTime<Utc>>, KeyVersionState)>> {
+        read_key_pair_versions(self, &self.storage, name, latest_version).await
+    }
 }
 
 #[async_trait]
@@ -233,13 +547,19 @@ impl ShieldedKeyPairService for PostgresKeyPairService {
         }
 
         let master_key = self.storage.generate_key().await?;
-        let shares = split(&master_key, share, threshold);
+        let shares = split(&master_key, threshold, share);
         self.storage.initialize(&master_key).await?;
+        let digests = shares.iter().map(share_digest).collect::<Result<Vec<_>>>()?;
+        self.record_share_holders(&ShareHolders { threshold, digests }).await?;
 
         self.storage.disarm(&master_key).await?; // Disarm the storage immediately after initialization
         Ok(shares)
     }
 
+    async fn is_shield_initialized(&self) -> Result<bool> {
+        Ok(self.storage.is_initialized().await?)
+    }
+
     async fn storage_armor(&self) -> Result<()> {
         Ok(self.storage.armor().await?)
     }
@@ -248,4 +568,92 @@ impl ShieldedKeyPairService for PostgresKeyPairService {
         let master_key = Zeroizing::new(combine(shares));
         Ok(self.storage.disarm(&master_key).await?)
     }
+
+    async fn is_storage_armored(&self) -> bool {
+        self.storage.is_armored().await
+    }
+
+    async fn shield_threshold(&self) -> Result<Option<usize>> {
+        match self.storage.inner().get(SHIELD_THRESHOLD_PATH).await? {
+            Some(threshold) => Ok(Some(String::from_utf8(threshold)?.parse()?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn share_holders(&self) -> Result<Option<ShareHolders>> {
+        let (Some(threshold), Some(digests)) =
+            (self.shield_threshold().await?, self.storage.inner().get(SHIELD_SHARE_DIGESTS_PATH).await?)
+        else {
+            return Ok(None);
+        };
+        Ok(Some(ShareHolders { threshold, digests: serde_json::from_slice(&digests)? }))
+    }
+
+    async fn record_share_holders(&self, share_holders: &ShareHolders) -> Result<()> {
+        self.storage.inner().set(SHIELD_THRESHOLD_PATH, share_holders.threshold.to_string().as_bytes()).await?;
+        self.storage.inner().set(SHIELD_SHARE_DIGESTS_PATH, &serde_json::to_vec(&share_holders.digests)?).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use nebula_storage::backend::file::FileStorage;
+    use ulid::Ulid;
+
+    use super::{FileKeyPairService, KeyVersionState, KeyVersionStatus, KeyVersionStatusError, ShieldedKeyPairService};
+
+    fn state(status: KeyVersionStatus) -> KeyVersionState {
+        KeyVersionState { status, reason: None, changed_at: None }
+    }
+
+    #[test]
+    fn when_version_moves_forward_then_transition_is_allowed() {
+        for (from, to) in [
+            (KeyVersionStatus::Active, KeyVersionStatus::Retired),
+            (KeyVersionStatus::Active, KeyVersionStatus::Revoked),
+            (KeyVersionStatus::Retired, KeyVersionStatus::Revoked),
+        ] {
+            let state = state(from).transition(to, Some("compromised".to_owned())).unwrap();
+
+            assert_eq!(state.status, to);
+            assert_eq!(state.reason.as_deref(), Some("compromised"));
+            assert!(state.changed_at.is_some());
+        }
+    }
+
+    #[test]
+    fn when_version_moves_backward_or_stays_then_transition_is_rejected() {
+        for (from, to) in [
+            (KeyVersionStatus::Revoked, KeyVersionStatus::Active),
+            (KeyVersionStatus::Revoked, KeyVersionStatus::Retired),
+            (KeyVersionStatus::Retired, KeyVersionStatus::Active),
+            (KeyVersionStatus::Active, KeyVersionStatus::Active),
+            (KeyVersionStatus::Retired, KeyVersionStatus::Retired),
+            (KeyVersionStatus::Revoked, KeyVersionStatus::Revoked),
+        ] {
+            let result = state(from).transition(to, None);
+
+            assert!(
+                matches!(result, Err(KeyVersionStatusError::InvalidTransition { from: f, to: t }) if f == from && t == to)
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn when_initializing_shield_with_threshold_less_than_shares_then_threshold_shares_disarm_it() {
+        let path = std::env::temp_dir().join(format!("nebula-authority-test-{}", Ulid::new()));
+        let key_pair_service = FileKeyPairService::new(FileStorage::new(path.clone()));
+
+        let shares = key_pair_service.shield_initialize(3, 2).await.unwrap();
+        key_pair_service.storage_armor().await.unwrap();
+        let result = key_pair_service.storage_disarm(&shares[1..]).await;
+        let threshold = key_pair_service.shield_threshold().await.unwrap();
+        std::fs::remove_dir_all(path).unwrap();
+
+        assert_eq!(shares.len(), 3);
+        assert!(result.is_ok());
+        assert!(!key_pair_service.is_storage_armored().await);
+        assert_eq!(threshold, Some(2));
+    }
 }
@@ -1,3 +1,11 @@
+pub mod armor;
+pub mod attribute;
+pub mod attribute_source;
+pub mod audit;
 pub mod authority;
 pub mod backbone;
+pub mod burst;
+pub mod disarm;
+pub mod epoch;
 pub mod key_pair;
+pub mod registration;
@@ -0,0 +1,70 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use nebula_storage::{
+    backend::{file::FileStorage, postgres::PostgresStorage},
+    Storage as _,
+};
+
+const BACKBONE_CREDENTIAL_PATH: &str = "/authority/backbone/credential/";
+
+/// Keeps the credentials the backbone issues to the authority as it registers itself to the workspaces. The bootstrap
+/// token is consumed by the registration, so the authority authenticates itself with the credential afterward.
+///
+/// The credentials are not shielded, so that the authority registers itself even while the key pair storage is armored.
+/// A credential only lets the authority request the backbone to refresh its public keys.
+#[async_trait]
+pub trait BackboneCredentialService {
+    #[inline(always)]
+    fn credential_path(&self, workspace_name: &str) -> String {
+        format!("{}{}", BACKBONE_CREDENTIAL_PATH, workspace_name)
+    }
+
+    async fn credential(&self, workspace_name: &str) -> Result<Option<String>>;
+    async fn set_credential(&self, workspace_name: &str, credential: &str) -> Result<()>;
+}
+
+pub struct FileBackboneCredentialService<'a> {
+    storage: FileStorage<'a>,
+}
+
+impl<'a> FileBackboneCredentialService<'a> {
+    pub fn new(storage: FileStorage<'a>) -> Self {
+        Self { storage }
+    }
+}
+
+#[async_trait]
+impl BackboneCredentialService for FileBackboneCredentialService<'_> {
+    async fn credential(&self, workspace_name: &str) -> Result<Option<String>> {
+        let credential = self.storage.get(&self.credential_path(workspace_name)).await?;
+        Ok(credential.map(String::from_utf8).transpose()?)
+    }
+
+    async fn set_credential(&self, workspace_name: &str, credential: &str) -> Result<()> {
+        self.storage.set(&self.credential_path(workspace_name), credential.as_bytes()).await?;
+        Ok(())
+    }
+}
+
+pub struct PostgresBackboneCredentialService {
+    storage: PostgresStorage,
+}
+
+impl PostgresBackboneCredentialService {
+    pub fn new(storage: PostgresStorage) -> Self {
+        Self { storage }
+    }
+}
+
+#[async_trait]
+impl BackboneCredentialService for PostgresBackboneCredentialService {
+    async fn credential(&self, workspace_name: &str) -> Result<Option<String>> {
+        let credential = self.storage.get(&self.credential_path(workspace_name)).await?;
+        Ok(credential.map(String::from_utf8).transpose()?)
+    }
+
+    async fn set_credential(&self, workspace_name: &str, credential: &str) -> Result<()> {
+        self.storage.set(&self.credential_path(workspace_name), credential.as_bytes()).await?;
+        Ok(())
+    }
+}
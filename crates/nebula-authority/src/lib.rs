@@ -0,0 +1,25 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+
+mod application;
+pub mod config;
+mod database;
+mod domain;
+mod server;
+
+pub use server::Server;
+
+#[derive(Parser, Debug)]
+#[command(version, about)]
+pub struct Args {
+    /// Sets a custom config file
+    #[arg(short, long, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+    /// Sets a port to start a authority server
+    #[arg(short, long, value_name = "PORT")]
+    pub port: Option<u16>,
+    /// Prints the resolved configuration with secrets redacted, then exits
+    #[arg(long)]
+    pub dump_config: bool,
+}
@@ -1,43 +1,166 @@
-use std::sync::Arc;
+use std::{future::Future, sync::Arc, time::Duration};
 
 use axum::{
-    extract::{Path, Request},
+    extract::{Path, Request, State},
     http::header::{AUTHORIZATION, CONTENT_TYPE, LINK},
     middleware::{self, Next},
     response::Response,
     routing::get,
     Extension, Router,
 };
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use nebula_secret_sharing::shamir::Share;
+use nebula_telemetry::health::Readiness;
+use nebula_tls::{ClientTlsConfig, TlsConfig};
 use nebula_token::{
-    auth::layer::NebulaAuthLayer,
-    claim::{NebulaClaim, Role},
+    auth::{
+        issuer::TrustedIssuer,
+        jwks_discovery::{CachedRemoteJwksDiscovery, JwksDiscovery},
+        layer::NebulaAuthLayer,
+    },
+    claim::{NebulaClaim, Permission, Role},
+    rate_limit::NebulaRateLimitLayer,
 };
 use reqwest::StatusCode;
 use serde::Deserialize;
 use tower_http::cors::AllowOrigin;
 use tower_http::cors::Any;
 use tower_http::cors::CorsLayer;
-use tracing::debug;
+use tracing::{debug, info};
+use url::Url;
 
 mod router;
 
-use crate::config::CorsConfig;
-use crate::{application::Application, config::ApplicationConfig};
+use crate::config::{CorsConfig, RateLimitConfig};
+use crate::{application::Application, config::ApplicationConfig, domain::authority::Authority};
 
 pub(super) struct ServerConfig {
     pub port: u16,
     pub path_prefix: Option<String>,
     pub cors: Option<CorsConfig>,
+    pub rate_limit: Option<RateLimitConfig>,
+    pub tls: Option<TlsConfig>,
 }
 
-impl From<ApplicationConfig> for ServerConfig {
-    fn from(value: ApplicationConfig) -> Self {
-        Self { port: value.port, path_prefix: value.path_prefix, cors: value.cors }
+impl From<&ApplicationConfig> for ServerConfig {
+    fn from(value: &ApplicationConfig) -> Self {
+        Self {
+            port: value.port,
+            path_prefix: value.path_prefix.clone(),
+            cors: value.cors.clone(),
+            rate_limit: value.rate_limit.clone(),
+            tls: value.tls.clone(),
+        }
     }
 }
 
-pub(super) async fn run(application: Application, config: ServerConfig) -> anyhow::Result<()> {
-    let application = Arc::new(application);
+/// Authority server, which can be served on its own port or mounted into another router by [`Server::router`].
+pub struct Server {
+    application: Arc<Application>,
+    config: ServerConfig,
+}
+
+impl Server {
+    /// Initializes the authority and disarms the key pair storage if `disarm_key_shares` is configured.
+    pub async fn init(config: &ApplicationConfig) -> anyhow::Result<Self> {
+        let authority = Authority::new(config).await?;
+        let refresh_interval = Duration::from_secs(config.jwks_refresh_interval.unwrap_or(10));
+        let jwks_discovery = config
+            .jwks_url
+            .as_ref()
+            .map(|jwks_url| -> anyhow::Result<Arc<dyn JwksDiscovery + Send + Sync>> {
+                Ok(Arc::new(remote_jwks_discovery(jwks_url, refresh_interval, config.jwks_tls.as_ref())?))
+            })
+            .transpose()?;
+        if config.trusted_issuers.iter().filter(|trusted_issuer| trusted_issuer.issuer.is_none()).count() > 1 {
+            anyhow::bail!("at most one of trusted_issuers may omit issuer");
+        }
+        let trusted_issuers = config
+            .trusted_issuers
+            .iter()
+            .map(|trusted_issuer| {
+                let refresh_interval = Duration::from_secs(trusted_issuer.jwks_refresh_interval.unwrap_or(10));
+                let jwks_discovery = remote_jwks_discovery(
+                    &trusted_issuer.jwks_url,
+                    refresh_interval,
+                    trusted_issuer.jwks_tls.as_ref(),
+                )?;
+                Ok(TrustedIssuer::builder()
+                    .maybe_issuer(trusted_issuer.issuer.clone())
+                    .maybe_audience(trusted_issuer.audience.clone())
+                    .jwks_discovery(Arc::new(jwks_discovery))
+                    .maybe_workspaces(trusted_issuer.workspaces.clone())
+                    .claim_mapping(trusted_issuer.claim_mapping.clone())
+                    .build())
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        if jwks_discovery.is_none() && trusted_issuers.is_empty() {
+            anyhow::bail!("either jwks_url or trusted_issuers must be configured");
+        }
+        let application = Application::new(authority, jwks_discovery, trusted_issuers);
+        if let Some(key_shares) = &config.disarm_key_shares {
+            let key_shares = key_shares
+                .iter()
+                .map(|s| {
+                    STANDARD
+                        .decode(s.as_bytes())
+                        .map_err(|e| anyhow::anyhow!(e))
+                        .and_then(|decoded| rmp_serde::from_slice(&decoded).map_err(|e| anyhow::anyhow!(e)))
+                })
+                .collect::<Result<Vec<Share>, _>>()?;
+            application.authority.disarm_key_pair_storage(&key_shares).await?;
+        }
+
+        Ok(Self { application: Arc::new(application), config: config.into() })
+    }
+
+    pub fn router(&self) -> Router {
+        router(self.application.clone(), &self.config)
+    }
+
+    /// Serves on the configured port until the shutdown future resolves.
+    pub async fn serve(&self, shutdown: impl Future<Output = ()> + Send + 'static) -> anyhow::Result<()> {
+        debug!("starting authority server on {}", self.config.port);
+        let application = self.application.clone();
+        tokio::spawn(async move { application.authority.register_to_backbone().await });
+        let application = self.application.clone();
+        tokio::spawn(async move { application.authority.roll_key_pairs_periodically().await });
+        let application = self.application.clone();
+        tokio::spawn(async move { application.authority.armor_when_idle().await });
+        nebula_tls::serve(self.config.port, self.router(), self.config.tls.as_ref(), shutdown).await
+    }
+
+    pub async fn run(self) -> anyhow::Result<()> {
+        self.serve(nebula_tls::shutdown_signal()).await?;
+        self.close().await;
+        info!("authority server is stopped");
+        Ok(())
+    }
+
+    pub async fn close(&self) {
+        self.application.authority.close().await;
+    }
+
+    /// Initializes the key pair storage and returns the encoded key shares, which `disarm_key_shares` accepts.
+    pub async fn initialize_key_pair_storage(&self, share: usize, threshold: usize) -> anyhow::Result<Vec<String>> {
+        let shares = self.application.authority.init_key_pair_storage(share, threshold).await?;
+        shares.iter().map(|share| Ok(STANDARD.encode(rmp_serde::to_vec(share)?))).collect()
+    }
+}
+
+fn remote_jwks_discovery(
+    jwks_url: &Url,
+    refresh_interval: Duration,
+    tls: Option<&ClientTlsConfig>,
+) -> anyhow::Result<CachedRemoteJwksDiscovery> {
+    let discovery = CachedRemoteJwksDiscovery::new(jwks_url.clone(), refresh_interval);
+    Ok(match tls {
+        Some(tls) => discovery.client(tls.client()?),
+        None => discovery,
+    })
+}
+
+fn router(application: Arc<Application>, config: &ServerConfig) -> Router {
     let protected_router = Router::new()
         .nest(
             "/workspaces/:workspace_name/",
@@ -49,21 +172,46 @@ pub(super) async fn run(application: Application, config: ServerConfig) -> anyho
                 .route_layer(middleware::from_fn(check_admin_role))
                 .route_layer(middleware::from_fn(check_workspace_name)),
         )
-        .layer(NebulaAuthLayer::builder().jwk_discovery(application.jwks_discovery.clone()).build());
+        .nest(
+            "/workspaces/:workspace_name/",
+            router::audit::router(application.clone())
+                .route_layer(middleware::from_fn_with_state(Permission::AuditRead, check_permission))
+                .route_layer(middleware::from_fn(check_workspace_name)),
+        );
+    let protected_router = if let Some(rate_limit) = config.rate_limit.clone() {
+        protected_router.layer(NebulaRateLimitLayer::new(rate_limit.default, rate_limit.workspaces))
+    } else {
+        protected_router
+    };
+    let protected_router = protected_router.layer(
+        NebulaAuthLayer::builder()
+            .maybe_jwk_discovery(application.jwks_discovery.clone())
+            .issuers(application.trusted_issuers.clone())
+            .build(),
+    );
     let public_router = Router::new()
         .nest("/workspaces/:workspace_name/", router::pubkey::router(application.clone()))
+        .nest("/workspaces/:workspace_name/", router::epoch::router(application.clone()))
         .nest("/", router::init::router(application.clone()))
-        .nest("/", router::disarm::router(application.clone()));
+        .nest("/", router::disarm::router(application.clone()))
+        .nest("/", router::armor::router(application.clone()))
+        .nest("/", router::status::router(application.clone()));
 
-    let app = Router::new().route("/health", get(|| async { "OK" }));
-    let app = if let Some(path_prefix) = config.path_prefix {
+    let app = Router::new()
+        .route("/health", get(|| async { "OK" }))
+        .route("/ready", get(handle_ready).with_state(application.clone()));
+    let app = if let Some(path_prefix) = &config.path_prefix {
         let path_prefix = format!("/{}/", path_prefix.trim_matches('/'));
         app.nest(&path_prefix, protected_router).nest(&path_prefix, public_router)
     } else {
         app.merge(protected_router).merge(public_router)
     };
 
-    let app = if let Some(cors) = config.cors {
+    let app = app
+        .layer(middleware::from_fn(nebula_telemetry::metrics::track_http_requests))
+        .layer(middleware::from_fn(nebula_telemetry::trace::propagate_trace_context));
+
+    if let Some(cors) = config.cors.clone() {
         let cors = CorsLayer::new()
             .allow_methods(Any)
             .allow_origin(match cors {
@@ -86,12 +234,11 @@ pub(super) async fn run(application: Application, config: ServerConfig) -> anyho
         app.layer(cors)
     } else {
         app
-    };
+    }
+}
 
-    let listener = tokio::net::TcpListener::bind(("0.0.0.0", config.port)).await?;
-    debug!("starting authority server on {}", config.port);
-    axum::serve(listener, app).await?;
-    Ok(())
+async fn handle_ready(State(application): State<Arc<Application>>) -> Readiness {
+    application.readiness().await
 }
 
 #[derive(Deserialize)]
@@ -112,6 +259,21 @@ pub(crate) async fn check_workspace_name(
     }
 }
 
+/// Allows the request only when the built-in role in the claim has the permission. Custom roles are defined in the
+/// workspaces of the Backbone server, so they have no permission here.
+pub(crate) async fn check_permission(
+    State(permission): State<Permission>,
+    Extension(claim): Extension<NebulaClaim>,
+    req: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    if claim.role.builtin_permissions().is_some_and(|permissions| permissions.contains(&permission)) {
+        Ok(next.run(req).await)
+    } else {
+        Err(StatusCode::FORBIDDEN)
+    }
+}
+
 pub(crate) async fn check_admin_role(
     Extension(claim): Extension<NebulaClaim>,
     req: Request,
@@ -123,3 +285,109 @@ pub(crate) async fn check_admin_role(
         Err(StatusCode::FORBIDDEN)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::{collections::HashMap, sync::Arc};
+
+    use axum::{
+        body::{to_bytes, Body},
+        extract::Request,
+        http::StatusCode,
+        middleware,
+        routing::get,
+        Extension, Router,
+    };
+    use nebula_token::claim::{NebulaClaim, Permission, Role};
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+    use ulid::Ulid;
+
+    use crate::{
+        application::Application,
+        domain::{authority::Authority, backbone::MockBackboneService},
+    };
+
+    use super::{check_permission, router, ServerConfig};
+
+    async fn request(application: Arc<Application>, uri: &str) -> (StatusCode, Vec<u8>) {
+        let config = ServerConfig { port: 0, path_prefix: None, cors: None, rate_limit: None, tls: None };
+        let request = Request::builder().uri(uri).body(Body::empty()).expect("request should be built");
+        let response = router(application, &config).oneshot(request).await.expect("request should be handled");
+        let status = response.status();
+        (status, to_bytes(response.into_body(), usize::MAX).await.expect("body should be read").to_vec())
+    }
+
+    async fn application(path: &std::path::Path) -> Arc<Application> {
+        let authority = Authority::with_file_storage(path, Arc::new(MockBackboneService::new())).await;
+        Arc::new(Application::new(authority, None, vec![]))
+    }
+
+    #[tokio::test]
+    async fn when_health_is_requested_then_ok_is_returned() {
+        let path = std::env::temp_dir().join(format!("nebula-authority-test-{}", Ulid::new()));
+
+        let (status, _) = request(application(&path).await, "/health").await;
+
+        assert_eq!(status, StatusCode::OK);
+        std::fs::remove_dir_all(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn when_key_pair_storage_is_disarmed_then_server_is_ready() {
+        let path = std::env::temp_dir().join(format!("nebula-authority-test-{}", Ulid::new()));
+
+        let (status, body) = request(application(&path).await, "/ready").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            serde_json::from_slice::<Value>(&body).unwrap(),
+            json!({ "status": "READY", "checks": { "database": "UP", "jwks": "UP", "shield": "UP" } })
+        );
+        std::fs::remove_dir_all(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn when_key_pair_storage_is_armored_then_server_is_not_ready() {
+        let path = std::env::temp_dir().join(format!("nebula-authority-test-{}", Ulid::new()));
+        let application = application(&path).await;
+        application.authority.armor_key_pair_storage().await.unwrap();
+
+        let (status, body) = request(application, "/ready").await;
+
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(
+            serde_json::from_slice::<Value>(&body).unwrap(),
+            json!({ "status": "NOT_READY", "checks": { "database": "UP", "jwks": "UP", "shield": "DOWN" } })
+        );
+        std::fs::remove_dir_all(path).unwrap();
+    }
+
+    async fn request_audit_log(role: Role) -> StatusCode {
+        let claim = NebulaClaim {
+            gid: "gid".to_owned(),
+            workspace_name: "testworkspace".to_owned(),
+            attributes: HashMap::new(),
+            role,
+        };
+        let router = Router::new()
+            .route("/audit/user-keys", get(|| async { "OK" }))
+            .route_layer(middleware::from_fn_with_state(Permission::AuditRead, check_permission))
+            .layer(Extension(claim));
+
+        let request = Request::builder().uri("/audit/user-keys").body(Body::empty()).expect("request should be built");
+        router.oneshot(request).await.expect("request should be handled").status()
+    }
+
+    #[tokio::test]
+    async fn when_role_has_audit_read_permission_then_audit_log_is_readable() {
+        assert_eq!(request_audit_log(Role::Auditor).await, StatusCode::OK);
+        assert_eq!(request_audit_log(Role::Admin).await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn when_role_lacks_audit_read_permission_then_audit_log_is_forbidden() {
+        assert_eq!(request_audit_log(Role::Member).await, StatusCode::FORBIDDEN);
+        assert_eq!(request_audit_log(Role::Custom("auditor-like".to_owned())).await, StatusCode::FORBIDDEN);
+    }
+}
@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use axum::{extract::State, http::StatusCode, response::IntoResponse, routing::post, Json, Router};
+use axum_thiserror::ErrorStatus;
+use serde::Deserialize;
+use thiserror::Error;
+use tracing::warn;
+use zeroize::Zeroizing;
+
+use crate::{application::Application, domain::disarm::DisarmShareError};
+
+use super::disarm::decode_share;
+
+pub(crate) fn router(application: Arc<Application>) -> axum::Router {
+    Router::new().route("/armor", post(handle_armor_authority)).with_state(application)
+}
+
+/// Armors the authority for every workspace. It is authorized by the share of a share holder rather than by an admin
+/// token, since an admin of a single workspace must not armor the authority for the others.
+async fn handle_armor_authority(
+    State(application): State<Arc<Application>>,
+    Json(ArmorRequest { share }): Json<ArmorRequest>,
+) -> Result<impl IntoResponse, ArmorError> {
+    let share = decode_share(&share).map_err(|_| ArmorError::InvalidShare)?;
+    application.authority.verify_share_holder(&share).await?;
+
+    application.authority.armor_key_pair_storage().await.map_err(|_| ArmorError::FailedToArmor)?;
+    warn!("key pair storage is armored.");
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArmorRequest {
+    share: Zeroizing<String>,
+}
+
+#[derive(Error, Debug, ErrorStatus)]
+pub enum ArmorError {
+    #[error("Unable to decode the share")]
+    #[status(StatusCode::BAD_REQUEST)]
+    InvalidShare,
+
+    #[error("The share holders are not recorded for the authority key pair")]
+    #[status(StatusCode::CONFLICT)]
+    ShareHoldersNotRecorded,
+
+    #[error("The share is not one of the shares of the authority key pair")]
+    #[status(StatusCode::FORBIDDEN)]
+    UnknownShare,
+
+    #[error("Unable to armor the authority key pair")]
+    #[status(StatusCode::INTERNAL_SERVER_ERROR)]
+    FailedToArmor,
+}
+
+impl From<DisarmShareError> for ArmorError {
+    fn from(value: DisarmShareError) -> Self {
+        match value {
+            DisarmShareError::ShareHoldersNotRecorded => Self::ShareHoldersNotRecorded,
+            DisarmShareError::UnknownShare => Self::UnknownShare,
+            _ => Self::FailedToArmor,
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::get,
+    Json, Router,
+};
+use axum_thiserror::ErrorStatus;
+use serde::Deserialize;
+use thiserror::Error;
+use ulid::Ulid;
+
+use crate::{application::Application, domain::audit::UserKeyIssuance};
+
+const DEFAULT_LIMIT: usize = 100;
+const MAX_LIMIT: usize = 1000;
+
+pub(crate) fn router(application: Arc<Application>) -> axum::Router {
+    Router::new().route("/audit/user-keys", get(handle_get_user_key_issuances)).with_state(application)
+}
+
+async fn handle_get_user_key_issuances(
+    Path(workspace_name): Path<String>,
+    Query(query_params): Query<GetUserKeyIssuancesQueryParam>,
+    State(application): State<Arc<Application>>,
+) -> Result<Json<Vec<UserKeyIssuance>>, GetUserKeyIssuancesError> {
+    let limit = query_params.limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT);
+    let issuances = application
+        .authority
+        .user_key_issuances(&workspace_name, query_params.before, limit)
+        .await
+        .map_err(|_| GetUserKeyIssuancesError::GetUserKeyIssuances)?;
+
+    Ok(Json(issuances))
+}
+
+#[derive(Deserialize)]
+pub struct GetUserKeyIssuancesQueryParam {
+    /// Returns only the records older than the record with this id.
+    before: Option<Ulid>,
+    limit: Option<usize>,
+}
+
+#[derive(Error, Debug, ErrorStatus)]
+pub enum GetUserKeyIssuancesError {
+    #[error("Unable to get the user key issuances")]
+    #[status(StatusCode::INTERNAL_SERVER_ERROR)]
+    GetUserKeyIssuances,
+}
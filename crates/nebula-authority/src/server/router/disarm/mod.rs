@@ -1,43 +1,117 @@
 use std::sync::Arc;
 
-use axum::{extract::State, http::StatusCode, response::IntoResponse, routing::post, Json, Router};
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
 use axum_thiserror::ErrorStatus;
 use base64::{engine::general_purpose::STANDARD, Engine as _};
 use nebula_secret_sharing::shamir::Share;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use zeroize::Zeroizing;
 
-use crate::application::Application;
+use crate::{
+    application::Application,
+    domain::disarm::{DisarmProgress, DisarmShareError},
+};
 
 pub(crate) fn router(application: Arc<Application>) -> axum::Router {
-    Router::new().route("/disarm", post(handle_disarm_authority)).with_state(application)
+    Router::new()
+        .route("/disarm", post(handle_disarm_authority))
+        .route(
+            "/disarm/session",
+            get(handle_get_disarm_session).post(handle_open_disarm_session).delete(handle_delete_disarm_session),
+        )
+        .route("/disarm/session/shares", post(handle_post_disarm_share))
+        .with_state(application)
 }
 
 async fn handle_disarm_authority(
     State(application): State<Arc<Application>>,
     Json(DisarmRequest { shares }): Json<DisarmRequest>,
 ) -> Result<impl IntoResponse, DisarmError> {
-    let shares: Vec<Share> = shares
-        .iter()
-        .map(|share| {
-            let share = Zeroizing::new(STANDARD.decode(share).map_err(DisarmError::Decode)?);
-            let share = rmp_serde::from_slice(&share).map_err(DisarmError::Deserialize)?;
-            Ok(share)
-        })
-        .collect::<Result<_, DisarmError>>()?;
+    let shares: Vec<Share> = shares.iter().map(|share| decode_share(share)).collect::<Result<_, DisarmError>>()?;
 
     application.authority.disarm_key_pair_storage(&shares).await.map_err(|_| DisarmError::FailedToDisarm)?;
 
     Ok(StatusCode::NO_CONTENT)
 }
 
+pub(crate) fn decode_share(share: &str) -> Result<Share, DisarmError> {
+    let share = Zeroizing::new(STANDARD.decode(share).map_err(DisarmError::Decode)?);
+    rmp_serde::from_slice(&share).map_err(DisarmError::Deserialize)
+}
+
+async fn handle_get_disarm_session(
+    State(application): State<Arc<Application>>,
+) -> Result<Json<DisarmProgress>, DisarmShareRequestError> {
+    let progress = application.authority.disarm_progress().await.map_err(DisarmShareError::from)?;
+
+    Ok(Json(progress))
+}
+
+async fn handle_open_disarm_session(
+    State(application): State<Arc<Application>>,
+    Json(OpenDisarmSessionRequest { share }): Json<OpenDisarmSessionRequest>,
+) -> Result<Json<OpenDisarmSessionResponse>, DisarmShareRequestError> {
+    let share = decode_share(&share).map_err(|_| DisarmShareRequestError::InvalidShare)?;
+    let (nonce, progress) = application.authority.open_disarm_session(share).await?;
+
+    Ok(Json(OpenDisarmSessionResponse { nonce, progress }))
+}
+
+async fn handle_post_disarm_share(
+    State(application): State<Arc<Application>>,
+    Json(DisarmShareRequest { share, nonce }): Json<DisarmShareRequest>,
+) -> Result<Json<DisarmProgress>, DisarmShareRequestError> {
+    let share = decode_share(&share).map_err(|_| DisarmShareRequestError::InvalidShare)?;
+    let progress = application.authority.submit_disarm_share(&nonce, share).await?;
+
+    Ok(Json(progress))
+}
+
+async fn handle_delete_disarm_session(
+    State(application): State<Arc<Application>>,
+    Json(DisarmShareRequest { share, nonce }): Json<DisarmShareRequest>,
+) -> Result<impl IntoResponse, DisarmShareRequestError> {
+    let share = decode_share(&share).map_err(|_| DisarmShareRequestError::InvalidShare)?;
+    application.authority.cancel_disarm_session(&nonce, &share).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DisarmRequest {
     shares: Zeroizing<Vec<String>>,
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenDisarmSessionRequest {
+    share: Zeroizing<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenDisarmSessionResponse {
+    /// Nonce of the opened disarm session, which is only given to the opener.
+    nonce: String,
+    #[serde(flatten)]
+    progress: DisarmProgress,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisarmShareRequest {
+    share: Zeroizing<String>,
+    nonce: String,
+}
+
 #[derive(Error, Debug, ErrorStatus)]
 pub enum DisarmError {
     #[error("Unable to decode the shares")]
@@ -52,3 +126,62 @@ pub enum DisarmError {
     #[status(StatusCode::INTERNAL_SERVER_ERROR)]
     FailedToDisarm,
 }
+
+#[derive(Error, Debug, ErrorStatus)]
+pub enum DisarmShareRequestError {
+    #[error("Unable to decode the share")]
+    #[status(StatusCode::BAD_REQUEST)]
+    InvalidShare,
+
+    #[error("The authority key pair is not armored")]
+    #[status(StatusCode::CONFLICT)]
+    NotArmored,
+
+    #[error("The share holders are not recorded for the authority key pair, so disarm it with all shares")]
+    #[status(StatusCode::CONFLICT)]
+    ShareHoldersNotRecorded,
+
+    #[error("The share is not one of the shares of the authority key pair")]
+    #[status(StatusCode::FORBIDDEN)]
+    UnknownShare,
+
+    #[error("Another disarm session is in progress")]
+    #[status(StatusCode::CONFLICT)]
+    SessionInProgress,
+
+    #[error("No disarm session is in progress")]
+    #[status(StatusCode::NOT_FOUND)]
+    NoSession,
+
+    #[error("The nonce doesn't match the ongoing disarm session")]
+    #[status(StatusCode::FORBIDDEN)]
+    NonceMismatch,
+
+    #[error("The share has already been submitted")]
+    #[status(StatusCode::BAD_REQUEST)]
+    DuplicateShare,
+
+    #[error("The shares don't disarm the authority key pair, so the disarm session is closed")]
+    #[status(StatusCode::BAD_REQUEST)]
+    InvalidShares,
+
+    #[error("Unable to disarm the authority key pair")]
+    #[status(StatusCode::INTERNAL_SERVER_ERROR)]
+    FailedToDisarm,
+}
+
+impl From<DisarmShareError> for DisarmShareRequestError {
+    fn from(value: DisarmShareError) -> Self {
+        match value {
+            DisarmShareError::NotArmored => Self::NotArmored,
+            DisarmShareError::ShareHoldersNotRecorded => Self::ShareHoldersNotRecorded,
+            DisarmShareError::UnknownShare => Self::UnknownShare,
+            DisarmShareError::SessionInProgress => Self::SessionInProgress,
+            DisarmShareError::NoSession => Self::NoSession,
+            DisarmShareError::NonceMismatch => Self::NonceMismatch,
+            DisarmShareError::DuplicateShare => Self::DuplicateShare,
+            DisarmShareError::InvalidShares => Self::InvalidShares,
+            DisarmShareError::Anyhow(_) => Self::FailedToDisarm,
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use axum::{extract::State, http::StatusCode, response::IntoResponse, routing::get, Json, Router};
+use axum_thiserror::ErrorStatus;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::{application::Application, domain::epoch::EpochPeriod};
+
+pub(crate) fn router(application: Arc<Application>) -> axum::Router {
+    Router::new().route("/epoch", get(handle_get_epoch)).with_state(application)
+}
+
+async fn handle_get_epoch(State(application): State<Arc<Application>>) -> Result<impl IntoResponse, GetEpochError> {
+    let epoch_binding = application.authority.epoch_binding.as_ref().ok_or(GetEpochError::NotBound)?;
+    let now = Utc::now();
+
+    Ok(Json(GetEpochResponse {
+        attribute: epoch_binding.attribute.clone(),
+        period: epoch_binding.period,
+        epoch: epoch_binding.period.epoch(now),
+        ends_at: epoch_binding.period.end(now),
+    }))
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetEpochResponse {
+    attribute: String,
+    period: EpochPeriod,
+    epoch: String,
+    ends_at: DateTime<Utc>,
+}
+
+#[derive(Error, Debug, ErrorStatus)]
+pub enum GetEpochError {
+    #[error("The authority does not bind user keys to epochs")]
+    #[status(StatusCode::NOT_FOUND)]
+    NotBound,
+}
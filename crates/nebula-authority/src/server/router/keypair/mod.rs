@@ -2,54 +2,67 @@ use std::sync::Arc;
 
 use axum::{
     extract::{Path, State},
-    http::StatusCode,
+    http::{header::AUTHORIZATION, HeaderMap, StatusCode},
     response::IntoResponse,
-    routing::patch,
+    routing::{get, patch, put},
     Json, Router,
 };
 use axum_thiserror::ErrorStatus;
-
-use nebula_abe::random::miracl::MiraclRng;
-
-use rand::{rngs::OsRng, Rng as _};
-use serde::Serialize;
+use chrono::{DateTime, Utc};
+use nebula_abe::curves::Curve;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use tracing::warn;
 
-use crate::application::Application;
+use crate::{
+    application::Application,
+    domain::key_pair::{KeyVersion, KeyVersionStatus, KeyVersionStatusError},
+};
 
 pub(crate) fn router(application: Arc<Application>) -> axum::Router {
-    Router::new().route("/key-pair", patch(handle_key_pair_rolling)).with_state(application)
+    Router::new()
+        .route("/key-pairs", get(handle_get_key_pairs))
+        .route("/key-pair", patch(handle_key_pair_rolling))
+        .route("/key-pair/:version/status", put(handle_put_key_pair_status))
+        .with_state(application)
 }
 
 async fn handle_key_pair_rolling(
     Path(workspace_name): Path<String>,
     State(application): State<Arc<Application>>,
+    headers: HeaderMap,
 ) -> Result<impl IntoResponse, KeyPairRollingError> {
-    let gp = application
+    let parameter = application
         .authority
         .backbone_service
-        .global_params(&workspace_name)
+        .parameter(&workspace_name, None)
         .await
         .map_err(|_| KeyPairRollingError::GetGlobalParams)?;
 
-    let mut rng = MiraclRng::new();
-    let mut seed = [0u8; 64];
-    OsRng.fill(&mut seed);
-    rng.seed(&seed);
-
     let version = application
         .authority
-        .key_pair_rolling(&gp, &workspace_name)
+        .key_pair_rolling(&parameter, &workspace_name)
         .await
         .map_err(|_| KeyPairRollingError::FailedToRollKeyPair)?;
 
-    Ok(Json(KeyPairRollingResponse { version }))
+    // The backbone also refreshes the public keys periodically, so the key pair is rolled even if this fails.
+    if let Some(authorization) = headers.get(AUTHORIZATION).and_then(|value| value.to_str().ok()) {
+        if let Err(e) =
+            application.authority.backbone_service.refresh_authority_public_keys(&workspace_name, authorization).await
+        {
+            warn!(error = %e, workspace = workspace_name, "failed to request the backbone to refresh the public keys.");
+        }
+    }
+
+    Ok(Json(KeyPairRollingResponse { version, parameter_version: parameter.version, curve: parameter.curve }))
 }
 
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct KeyPairRollingResponse {
     version: u64,
+    parameter_version: i32,
+    curve: Curve,
 }
 
 #[derive(Error, Debug, ErrorStatus)]
@@ -62,3 +75,183 @@ pub enum KeyPairRollingError {
     #[status(StatusCode::INTERNAL_SERVER_ERROR)]
     FailedToRollKeyPair,
 }
+
+async fn handle_get_key_pairs(
+    Path(workspace_name): Path<String>,
+    State(application): State<Arc<Application>>,
+) -> Result<impl IntoResponse, GetKeyPairsError> {
+    if application.authority.is_armored().await {
+        return Err(GetKeyPairsError::Armored);
+    }
+    let versions =
+        application.authority.key_pair_versions(&workspace_name).await.map_err(|_| GetKeyPairsError::GetKeyPairs)?;
+
+    Ok(Json(
+        versions
+            .into_iter()
+            .map(|(version, created_at, state)| KeyPairVersionResponse {
+                version,
+                created_at,
+                status: state.status,
+                status_reason: state.reason,
+                status_changed_at: state.changed_at,
+            })
+            .collect::<Vec<_>>(),
+    ))
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyPairVersionResponse {
+    version: KeyVersion,
+    /// Unknown for versions generated before it was recorded.
+    created_at: Option<DateTime<Utc>>,
+    status: KeyVersionStatus,
+    status_reason: Option<String>,
+    status_changed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Error, Debug, ErrorStatus)]
+pub enum GetKeyPairsError {
+    #[error("The authority is armored")]
+    #[status(StatusCode::SERVICE_UNAVAILABLE)]
+    Armored,
+
+    #[error("Unable to get the key pairs")]
+    #[status(StatusCode::INTERNAL_SERVER_ERROR)]
+    GetKeyPairs,
+}
+
+async fn handle_put_key_pair_status(
+    Path((workspace_name, version)): Path<(String, KeyVersion)>,
+    State(application): State<Arc<Application>>,
+    Json(payload): Json<PutKeyPairStatusRequest>,
+) -> Result<impl IntoResponse, KeyPairStatusError> {
+    let state = application
+        .authority
+        .change_key_pair_status(&workspace_name, version, payload.status, payload.reason)
+        .await
+        .map_err(|e| match e {
+            KeyVersionStatusError::VersionNotFound(_) => KeyPairStatusError::VersionNotFound,
+            KeyVersionStatusError::LatestVersion(_) => KeyPairStatusError::LatestVersion,
+            KeyVersionStatusError::InvalidTransition { .. } => KeyPairStatusError::InvalidTransition,
+            KeyVersionStatusError::Anyhow(_) => KeyPairStatusError::FailedToChangeStatus,
+        })?;
+
+    Ok(Json(KeyPairStatusResponse {
+        version,
+        status: state.status,
+        reason: state.reason,
+        changed_at: state.changed_at,
+    }))
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PutKeyPairStatusRequest {
+    status: KeyVersionStatus,
+    reason: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyPairStatusResponse {
+    version: KeyVersion,
+    status: KeyVersionStatus,
+    reason: Option<String>,
+    changed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Error, Debug, ErrorStatus)]
+pub enum KeyPairStatusError {
+    #[error("The key pair version is not found")]
+    #[status(StatusCode::NOT_FOUND)]
+    VersionNotFound,
+
+    #[error("The latest key pair version can't be retired or revoked. Roll the key pair first")]
+    #[status(StatusCode::CONFLICT)]
+    LatestVersion,
+
+    #[error("The key pair version can't be changed to the status")]
+    #[status(StatusCode::CONFLICT)]
+    InvalidTransition,
+
+    #[error("Unable to change the status of the key pair version")]
+    #[status(StatusCode::INTERNAL_SERVER_ERROR)]
+    FailedToChangeStatus,
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use axum::{
+        body::to_bytes,
+        extract::{Path, State},
+        http::StatusCode,
+        response::IntoResponse as _,
+    };
+    use serde_json::{json, Value};
+    use ulid::Ulid;
+
+    use crate::{
+        application::Application,
+        domain::{
+            authority::Authority,
+            backbone::{MockBackboneService, Parameter},
+            key_pair::KeyVersionStatus,
+        },
+    };
+
+    use super::handle_get_key_pairs;
+
+    const WORKSPACE_NAME: &str = "workspace";
+
+    #[tokio::test]
+    async fn when_getting_key_pairs_then_every_version_is_listed_with_its_state() {
+        let path = std::env::temp_dir().join(format!("nebula-authority-test-{}", Ulid::new()));
+        let authority = Authority::with_file_storage(&path, Arc::new(MockBackboneService::new())).await;
+        let parameter = Parameter::fixed();
+        for _ in 0..3 {
+            authority.key_pair_rolling(&parameter, WORKSPACE_NAME).await.unwrap();
+        }
+        authority
+            .change_key_pair_status(WORKSPACE_NAME, 1, KeyVersionStatus::Revoked, Some("compromised".to_owned()))
+            .await
+            .unwrap();
+        authority.change_key_pair_status(WORKSPACE_NAME, 2, KeyVersionStatus::Retired, None).await.unwrap();
+        let application = Arc::new(Application::new(authority, None, vec![]));
+
+        let response =
+            handle_get_key_pairs(Path(WORKSPACE_NAME.to_owned()), State(application)).await.unwrap().into_response();
+        std::fs::remove_dir_all(path).unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+        let versions = body.as_array().unwrap();
+        assert_eq!(versions.len(), 3);
+        for (index, (status, reason)) in
+            [("REVOKED", json!("compromised")), ("RETIRED", Value::Null), ("ACTIVE", Value::Null)]
+                .into_iter()
+                .enumerate()
+        {
+            assert_eq!(versions[index]["version"], json!(index + 1));
+            assert!(versions[index]["createdAt"].is_string());
+            assert_eq!(versions[index]["status"], json!(status));
+            assert_eq!(versions[index]["statusReason"], reason);
+        }
+    }
+
+    #[tokio::test]
+    async fn when_getting_key_pairs_of_armored_authority_then_service_unavailable_is_responded() {
+        let path = std::env::temp_dir().join(format!("nebula-authority-test-{}", Ulid::new()));
+        let authority = Authority::with_file_storage(&path, Arc::new(MockBackboneService::new())).await;
+        authority.armor_key_pair_storage().await.unwrap();
+        let application = Arc::new(Application::new(authority, None, vec![]));
+
+        let result = handle_get_key_pairs(Path(WORKSPACE_NAME.to_owned()), State(application)).await;
+        std::fs::remove_dir_all(path).unwrap();
+
+        assert_eq!(result.err().unwrap().into_response().status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+}
@@ -1,5 +1,9 @@
+pub mod armor;
+pub mod audit;
 pub mod disarm;
+pub mod epoch;
 pub mod init;
 pub mod keypair;
 pub mod pubkey;
+pub mod status;
 pub mod userkey;
@@ -9,10 +9,12 @@ use axum::{
 };
 use axum_thiserror::ErrorStatus;
 use base64::{engine::general_purpose::STANDARD, Engine as _};
+use chrono::{DateTime, Utc};
+use nebula_abe::curves::Curve;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::application::Application;
+use crate::{application::Application, domain::key_pair::KeyVersionStatus};
 
 pub(crate) fn router(application: Arc<Application>) -> axum::Router {
     Router::new().route("/public-key", get(handle_get_public_key)).with_state(application)
@@ -29,11 +31,24 @@ async fn handle_get_public_key(
         application.authority.key_pair(&workspace_name).await
     }
     .map_err(|_| GetPublicKeyError::GetPublicKey)?;
+    let state = application
+        .authority
+        .key_pair_state(&workspace_name, version)
+        .await
+        .map_err(|_| GetPublicKeyError::GetPublicKey)?;
 
-    let public_key = rmp_serde::to_vec(&key_pair.pk).map_err(GetPublicKeyError::Serialization)?;
+    let public_key = key_pair.public_key().map_err(|_| GetPublicKeyError::Serialization)?;
     let public_key = STANDARD.encode(&public_key);
 
-    Ok(Json(GetPublicKeyResponse { public_key, version }))
+    Ok(Json(GetPublicKeyResponse {
+        public_key,
+        version,
+        parameter_version: key_pair.parameter_version,
+        curve: key_pair.curve,
+        status: state.status,
+        status_reason: state.reason,
+        status_changed_at: state.changed_at,
+    }))
 }
 
 #[derive(Deserialize)]
@@ -47,6 +62,11 @@ pub struct GetPublicKeyQueryParam {
 pub struct GetPublicKeyResponse {
     public_key: String,
     version: u64,
+    parameter_version: i32,
+    curve: Curve,
+    status: KeyVersionStatus,
+    status_reason: Option<String>,
+    status_changed_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Error, Debug, ErrorStatus)]
@@ -57,5 +77,5 @@ pub enum GetPublicKeyError {
 
     #[error("Unable to serialize the public key")]
     #[status(StatusCode::INTERNAL_SERVER_ERROR)]
-    Serialization(#[from] rmp_serde::encode::Error),
+    Serialization,
 }
@@ -0,0 +1,91 @@
+use std::sync::Arc;
+
+use axum::{extract::State, http::StatusCode, routing::get, Json, Router};
+use axum_thiserror::ErrorStatus;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::application::Application;
+
+pub(crate) fn router(application: Arc<Application>) -> axum::Router {
+    Router::new().route("/status", get(handle_get_status)).with_state(application)
+}
+
+async fn handle_get_status(
+    State(application): State<Arc<Application>>,
+) -> Result<Json<GetStatusResponse>, GetStatusError> {
+    let initialized = application.authority.is_initialized().await.map_err(|_| GetStatusError::GetStatus)?;
+    let threshold = application.authority.shield_threshold().await.map_err(|_| GetStatusError::GetStatus)?;
+
+    Ok(Json(GetStatusResponse {
+        name: application.authority.name.clone(),
+        initialized,
+        armored: application.authority.is_armored().await,
+        threshold,
+    }))
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetStatusResponse {
+    name: String,
+    initialized: bool,
+    armored: bool,
+    /// Number of shares required to disarm, which is unknown for authorities initialized before it was recorded.
+    threshold: Option<usize>,
+}
+
+#[derive(Error, Debug, ErrorStatus)]
+pub enum GetStatusError {
+    #[error("Unable to get the status of the authority")]
+    #[status(StatusCode::INTERNAL_SERVER_ERROR)]
+    GetStatus,
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use axum::extract::State;
+    use ulid::Ulid;
+
+    use crate::{
+        application::Application,
+        domain::{authority::Authority, backbone::MockBackboneService},
+    };
+
+    use super::handle_get_status;
+
+    async fn application(path: &std::path::Path) -> Arc<Application> {
+        let authority = Authority::with_file_storage(path, Arc::new(MockBackboneService::new())).await;
+        Arc::new(Application::new(authority, None, vec![]))
+    }
+
+    #[tokio::test]
+    async fn when_authority_is_disarmed_then_status_is_not_armored() {
+        let path = std::env::temp_dir().join(format!("nebula-authority-test-{}", Ulid::new()));
+        let application = application(&path).await;
+
+        let status = handle_get_status(State(application)).await.unwrap().0;
+        std::fs::remove_dir_all(path).unwrap();
+
+        assert_eq!(status.name, "authority");
+        assert!(status.initialized);
+        assert!(!status.armored);
+        assert_eq!(status.threshold, Some(1));
+    }
+
+    #[tokio::test]
+    async fn when_authority_is_armored_then_status_is_armored_with_threshold() {
+        let path = std::env::temp_dir().join(format!("nebula-authority-test-{}", Ulid::new()));
+        let application = application(&path).await;
+        application.authority.armor_key_pair_storage().await.unwrap();
+
+        let status = handle_get_status(State(application)).await.unwrap().0;
+        std::fs::remove_dir_all(path).unwrap();
+
+        assert!(status.initialized);
+        assert!(status.armored);
+        assert_eq!(status.threshold, Some(1));
+    }
+}
@@ -1,22 +1,26 @@
-use std::sync::Arc;
+use std::{net::SocketAddr, sync::Arc};
 
 use axum::{
-    extract::{Path, Query, State},
+    extract::{ConnectInfo, Path, Query, State},
     http::StatusCode,
-    response::IntoResponse,
     routing::get,
     Extension, Json, Router,
 };
 use axum_thiserror::ErrorStatus;
 use base64::{engine::general_purpose::STANDARD, Engine as _};
-use nebula_abe::{random::miracl::MiraclRng, schemes::isabella24::UserSecretKey};
+use chrono::Utc;
+use nebula_abe::{curves::Curve, random::miracl::MiraclRng};
 use nebula_token::claim::NebulaClaim;
 use rand::{rngs::OsRng, Rng as _};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use tracing::warn;
 use zeroize::Zeroizing;
 
-use crate::application::Application;
+use crate::{
+    application::Application,
+    domain::{audit::UserKeyIssuance, key_pair::KeyVersionStatus},
+};
 
 pub(crate) fn router(application: Arc<Application>) -> axum::Router {
     Router::new().route("/user-key", get(handle_get_user_key)).with_state(application)
@@ -27,18 +31,51 @@ async fn handle_get_user_key(
     Query(query_params): Query<GetUserKeyQueryParam>,
     State(application): State<Arc<Application>>,
     Extension(claim): Extension<NebulaClaim>,
-) -> Result<impl IntoResponse, GetUserKeyError> {
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+) -> Result<Json<GetUserKeyResponse>, GetUserKeyError> {
+    let mut issuance =
+        UserKeyIssuance::new(workspace_name, claim.gid.clone(), connect_info.map(|ConnectInfo(address)| address.ip()));
+    let result = if application.authority.admit_user_key_request(&issuance.workspace_name, &claim.gid).await {
+        issue_user_key(&application, &query_params, &claim, &mut issuance).await
+    } else {
+        Err(GetUserKeyError::TooManyRequests)
+    };
+    if let Err(e) = &result {
+        issuance.failure = Some(e.to_string());
+    }
+    application.authority.record_user_key_issuance(&issuance).await.map_err(|_| GetUserKeyError::RecordIssuance)?;
+
+    result
+}
+
+/// Issues the user key, filling `issuance` in with the key version and the attributes as they are decided.
+async fn issue_user_key(
+    application: &Application,
+    query_params: &GetUserKeyQueryParam,
+    claim: &NebulaClaim,
+    issuance: &mut UserKeyIssuance,
+) -> Result<Json<GetUserKeyResponse>, GetUserKeyError> {
+    let workspace_name = issuance.workspace_name.clone();
     let (key_pair, version) = if let Some(version) = query_params.version {
         application.authority.key_pair_by_version(&workspace_name, version).await.map(|key_pair| (key_pair, version))
     } else {
         application.authority.key_pair(&workspace_name).await
     }
     .map_err(|_| GetUserKeyError::GetUserKey)?;
+    issuance.key_version = Some(version);
+    let state = application
+        .authority
+        .key_pair_state(&workspace_name, version)
+        .await
+        .map_err(|_| GetUserKeyError::GetUserKey)?;
+    if state.status == KeyVersionStatus::Revoked {
+        return Err(GetUserKeyError::KeyVersionRevoked);
+    }
 
-    let gp = application
+    let parameter = application
         .authority
         .backbone_service
-        .global_params(&workspace_name)
+        .parameter(&workspace_name, Some(key_pair.parameter_version))
         .await
         .map_err(|_| GetUserKeyError::GetGlobalParams)?;
 
@@ -47,24 +84,50 @@ async fn handle_get_user_key(
     OsRng.fill(&mut seed);
     rng.seed(&seed);
 
-    let user_key = UserSecretKey::new(
-        &mut rng,
-        &gp,
-        &key_pair.mk,
-        &claim.gid,
-        &claim.attributes.iter().map(|(k, v)| format!("{k}={v}")).collect::<Vec<_>>(),
-    );
+    let epoch_attribute = application
+        .authority
+        .epoch_binding
+        .as_ref()
+        .map(|epoch_binding| epoch_binding.attribute(query_params.epoch.as_deref(), Utc::now()))
+        .transpose()
+        .map_err(|_| GetUserKeyError::InvalidEpoch)?;
+    let mut attributes =
+        application.authority.issuable_attributes(&workspace_name, &claim.gid, &claim.attributes).await.map_err(
+            |e| {
+                warn!(error = %e, workspace = workspace_name, gid = claim.gid, "failed to resolve the attributes.");
+                GetUserKeyError::ResolveAttributes
+            },
+        )?;
+    attributes.extend(epoch_attribute);
+    issuance.attributes = attributes.clone();
+    let user_key = tracing::info_span!("abe.user_key_generation", workspace = %workspace_name)
+        .in_scope(|| key_pair.user_key(&mut rng, &parameter, &claim.gid, &attributes));
 
-    let user_key = Zeroizing::new(rmp_serde::to_vec(&user_key).map_err(GetUserKeyError::Serialization)?);
+    let user_key = user_key.map_err(|_| GetUserKeyError::Serialization)?;
     let user_key = Zeroizing::new(STANDARD.encode(&user_key));
 
-    Ok(Json(GetUserKeyResponse { user_key, version }))
+    metrics::counter!(
+        "user_keys_issued_total",
+        "authority" => application.authority.name.clone(),
+        "workspace" => workspace_name
+    )
+    .increment(1);
+
+    Ok(Json(GetUserKeyResponse {
+        user_key,
+        version,
+        parameter_version: key_pair.parameter_version,
+        curve: key_pair.curve,
+    }))
 }
 
 #[derive(Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct GetUserKeyQueryParam {
     version: Option<u64>,
+    /// Epoch to bind the user key to, which defaults to the current epoch. Ignored unless the authority binds user keys
+    /// to epochs.
+    epoch: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -72,6 +135,8 @@ pub struct GetUserKeyQueryParam {
 pub struct GetUserKeyResponse {
     user_key: Zeroizing<String>,
     version: u64,
+    parameter_version: i32,
+    curve: Curve,
 }
 
 #[derive(Error, Debug, ErrorStatus)]
@@ -84,7 +149,91 @@ pub enum GetUserKeyError {
     #[status(StatusCode::INTERNAL_SERVER_ERROR)]
     GetGlobalParams,
 
-    #[error("Unable to serialize the user key")]
+    #[error("Unable to resolve the attributes")]
+    #[status(StatusCode::BAD_GATEWAY)]
+    ResolveAttributes,
+
+    #[error("Unable to generate the user key")]
     #[status(StatusCode::INTERNAL_SERVER_ERROR)]
-    Serialization(#[from] rmp_serde::encode::Error),
+    Serialization,
+
+    #[error("The epoch is invalid or has not started yet")]
+    #[status(StatusCode::BAD_REQUEST)]
+    InvalidEpoch,
+
+    #[error("The key pair version is revoked")]
+    #[status(StatusCode::GONE)]
+    KeyVersionRevoked,
+
+    #[error("Too many user keys are requested")]
+    #[status(StatusCode::TOO_MANY_REQUESTS)]
+    TooManyRequests,
+
+    #[error("Unable to record the user key request")]
+    #[status(StatusCode::INTERNAL_SERVER_ERROR)]
+    RecordIssuance,
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::HashMap, sync::Arc};
+
+    use axum::{
+        extract::{Path, Query, State},
+        http::StatusCode,
+        response::IntoResponse as _,
+        Extension,
+    };
+    use nebula_token::claim::{NebulaClaim, Role};
+    use ulid::Ulid;
+
+    use crate::{
+        application::Application,
+        domain::{
+            authority::Authority,
+            backbone::{MockBackboneService, Parameter},
+            key_pair::KeyVersionStatus,
+        },
+    };
+
+    use super::{handle_get_user_key, GetUserKeyError, GetUserKeyQueryParam};
+
+    const WORKSPACE_NAME: &str = "workspace";
+
+    #[tokio::test]
+    async fn when_requesting_user_key_of_revoked_version_then_gone_is_responded() {
+        let path = std::env::temp_dir().join(format!("nebula-authority-test-{}", Ulid::new()));
+        let mut mock_backbone_service = MockBackboneService::new();
+        mock_backbone_service.expect_parameter().never();
+        let authority = Authority::with_file_storage(&path, Arc::new(mock_backbone_service)).await;
+        let parameter = Parameter::fixed();
+        authority.key_pair_rolling(&parameter, WORKSPACE_NAME).await.unwrap();
+        authority.key_pair_rolling(&parameter, WORKSPACE_NAME).await.unwrap();
+        authority.change_key_pair_status(WORKSPACE_NAME, 1, KeyVersionStatus::Revoked, None).await.unwrap();
+        let application = Arc::new(Application::new(authority, None, vec![]));
+        let claim = NebulaClaim {
+            gid: "alice".to_owned(),
+            workspace_name: WORKSPACE_NAME.to_owned(),
+            attributes: HashMap::new(),
+            role: Role::Member,
+        };
+
+        let result = handle_get_user_key(
+            Path(WORKSPACE_NAME.to_owned()),
+            Query(GetUserKeyQueryParam { version: Some(1), epoch: None }),
+            State(application.clone()),
+            Extension(claim),
+            None,
+        )
+        .await;
+        let issuances = application.authority.user_key_issuances(WORKSPACE_NAME, None, 10).await.unwrap();
+        std::fs::remove_dir_all(path).unwrap();
+
+        let error = result.err().expect("user key of revoked version should not be issued");
+        assert!(matches!(error, GetUserKeyError::KeyVersionRevoked));
+        assert_eq!(error.into_response().status(), StatusCode::GONE);
+        assert_eq!(issuances.len(), 1);
+        assert_eq!(issuances[0].key_version, Some(1));
+        assert_eq!(issuances[0].failure.as_deref(), Some("The key pair version is revoked"));
+    }
 }
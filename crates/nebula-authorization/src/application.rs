@@ -1,8 +1,10 @@
 use std::sync::Arc;
 
 use crate::{
-    config::{ApplicationConfig, StaticWorkspaceConfig, UpstreamIdpConfig, WorkspaceConfig},
-    database::{self, connect_to_database, AuthMethod},
+    config::{
+        ApplicationConfig, DatabaseConfig, PostgresConfig, StaticWorkspaceConfig, UpstreamIdpConfig, WorkspaceConfig,
+    },
+    database::{self, connect_to_database, AuthMethod, DatabaseLocation},
     domain::{
         connector::saml::{SAMLConnector, SAMLConnertorConfig},
         machine_identity::MachineIdentityService,
@@ -11,6 +13,7 @@ use crate::{
     },
 };
 
+use nebula_telemetry::health::Readiness;
 use nebula_token::jwk::jwk_set::{JwkSet, JWK_SET_DEFAULT_KEY_ID};
 use sea_orm::{DatabaseConnection, TransactionTrait};
 
@@ -28,22 +31,12 @@ impl Application {
 
         match config.workspace {
             WorkspaceConfig::Static(StaticWorkspaceConfig { ref name }) => {
-                database::migrate_workspace(
-                    name,
-                    &config.database.host,
-                    config.database.port,
-                    &config.database.database_name,
-                    &create_database_auth_method(config),
-                )
-                .await?;
+                database::migrate_workspace(name, &create_database_location(config)).await?;
             }
             WorkspaceConfig::Claim(_) => {
                 database::migrate_all_workspaces(
                     &database_connection.begin().await?,
-                    &config.database.host,
-                    config.database.port,
-                    &config.database.database_name,
-                    &create_database_auth_method(config),
+                    &create_database_location(config),
                 )
                 .await?;
             }
@@ -67,6 +60,7 @@ impl Application {
                     .attributes_config(saml.attributes.clone())
                     .workspace_config(config.workspace.clone())
                     .admin_role_config(saml.admin_role.clone())
+                    .maybe_role_mapping_config(saml.role_mapping.clone())
                     .build()
             }
         };
@@ -91,33 +85,45 @@ impl Application {
             machine_identity_service: Arc::new(MachineIdentityService {}),
             workspace_service: Arc::new(WorkspaceService::new(
                 database_connection.clone(),
-                config.database.host.to_owned(),
-                config.database.port,
-                config.database.database_name.to_owned(),
-                create_database_auth_method(config),
+                create_database_location(config),
             )),
         })
     }
+
+    pub async fn readiness(&self) -> Readiness {
+        Readiness::new()
+            .check("database", self.database_connection.ping().await.is_ok())
+            .check("jwks", self.token_service.jwks.get(&self.token_service.jwk_kid).is_some())
+    }
+
+    pub async fn close(&self) {
+        database::close_database(&self.database_connection).await;
+    }
 }
 
 async fn init_database_connection(config: &ApplicationConfig) -> anyhow::Result<Arc<DatabaseConnection>> {
-    let database_host = &config.database.host;
-    let database_port = config.database.port;
-    let database_name = &config.database.database_name;
-    let auth_method = create_database_auth_method(config);
+    connect_to_database(&create_database_location(config)).await
+}
 
-    connect_to_database(database_host, database_port, database_name, &auth_method).await
+fn create_database_location(config: &ApplicationConfig) -> DatabaseLocation {
+    match &config.database {
+        DatabaseConfig::Postgres(postgres) => DatabaseLocation::Postgres {
+            host: postgres.host.to_owned(),
+            port: postgres.port,
+            database_name: postgres.database_name.to_owned(),
+            auth: create_database_auth_method(postgres),
+        },
+        DatabaseConfig::Sqlite { path } => DatabaseLocation::Sqlite { path: path.to_owned() },
+    }
 }
 
-fn create_database_auth_method(config: &ApplicationConfig) -> AuthMethod {
-    match &config.database.auth {
+fn create_database_auth_method(config: &PostgresConfig) -> AuthMethod {
+    match &config.auth {
         crate::config::DatabaseAuthConfig::Credential { username, password } => {
             AuthMethod::Credential { username: username.to_owned(), password: password.to_owned() }
         }
-        crate::config::DatabaseAuthConfig::RdsIamAuth { username } => AuthMethod::RdsIamAuth {
-            host: config.database.host.to_owned(),
-            port: config.database.port,
-            username: username.to_owned(),
-        },
+        crate::config::DatabaseAuthConfig::RdsIamAuth { username } => {
+            AuthMethod::RdsIamAuth { host: config.host.to_owned(), port: config.port, username: username.to_owned() }
+        }
     }
 }
@@ -1,5 +1,9 @@
-use config::{Config, File, FileFormat};
-use nebula_config_path::config_dir;
+use std::path::PathBuf;
+
+use config::Config;
+use nebula_config::{config_file, Environment, ENV_PREFIX};
+use nebula_telemetry::trace::TracingConfig;
+use nebula_tls::TlsConfig;
 use nebula_token::jwk::jwk_set::JwkSet;
 use serde::Deserialize;
 use url::Url;
@@ -7,7 +11,7 @@ use url::Url;
 use crate::Args;
 
 #[derive(Deserialize, Debug)]
-pub(crate) struct ApplicationConfig {
+pub struct ApplicationConfig {
     pub port: u16,
     pub base_url: Url,
     pub database: DatabaseConfig,
@@ -16,10 +20,24 @@ pub(crate) struct ApplicationConfig {
     pub workspace: WorkspaceConfig,
     pub path_prefix: Option<String>,
     pub cors: Option<CorsConfig>,
+    pub tracing: Option<TracingConfig>,
+    /// Serves `GET /metrics` on this port, apart from the API. Metrics are not served if not given.
+    pub metrics_port: Option<u16>,
+    pub tls: Option<TlsConfig>,
 }
 
 #[derive(Deserialize, Debug)]
-pub struct DatabaseConfig {
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DatabaseConfig {
+    Postgres(PostgresConfig),
+    /// Directory of the SQLite databases, which must be the same as the one of the backbone.
+    Sqlite {
+        path: PathBuf,
+    },
+}
+
+#[derive(Deserialize, Debug)]
+pub struct PostgresConfig {
     pub host: String,
     pub port: u16,
     pub database_name: String,
@@ -47,6 +65,7 @@ pub struct SAMLConfig {
     pub ca: String,
     pub attributes: AttributesConfig,
     pub admin_role: SAMLAdminRoleConfig,
+    pub role_mapping: Option<SAMLRoleMappingConfig>,
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -58,11 +77,19 @@ pub enum AttributesConfig {
 
 #[derive(Deserialize, Debug, Clone)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE", tag = "type")]
-pub(crate) enum SAMLAdminRoleConfig {
+pub enum SAMLAdminRoleConfig {
     All,
     Group { attribute_name: String, admin_groups: Vec<String> },
 }
 
+/// Maps groups of the user to a role other than admin, such as `auditor` or a role defined by the workspace.
+/// The first matched group wins, and a user in none of the groups becomes a member.
+#[derive(Deserialize, Debug, Clone)]
+pub struct SAMLRoleMappingConfig {
+    pub attribute_name: String,
+    pub groups: Vec<(String, String)>,
+}
+
 #[derive(Deserialize, Debug)]
 pub struct TokenConfig {
     pub lifetime: u64,
@@ -87,27 +114,28 @@ pub struct ClaimWorkspaceConfig {
     pub claim: String,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Clone)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE", tag = "type", content = "domains")]
-pub(crate) enum CorsConfig {
+pub enum CorsConfig {
     AllowAll,
     AllowList(Vec<String>),
 }
 
-pub(super) fn load_config(args: Args) -> anyhow::Result<ApplicationConfig> {
-    let config_file_path = if let Some(path_override) = args.config {
-        path_override
-    } else {
-        let user_config_dir = config_dir().expect("Failed to get config directory");
-        let nebula_config_dir = user_config_dir.join("nebula");
-        if !nebula_config_dir.exists() {
-            std::fs::create_dir_all(&nebula_config_dir)?;
-        }
-        nebula_config_dir.join("authorization_config.toml")
-    };
-
-    let config: ApplicationConfig = Config::builder()
-        .add_source(File::from(config_file_path).format(FileFormat::Toml))
+pub fn load_config(args: Args) -> anyhow::Result<ApplicationConfig> {
+    Ok(build_config(args)?.try_deserialize()?)
+}
+
+pub fn dump_config(args: Args) -> anyhow::Result<String> {
+    nebula_config::dump(&build_config(args)?, &["token.jwks"])
+}
+
+/// Merges the configuration sources. Later sources take precedence: config file, `NEBULA_*` environment variables and
+/// command line arguments. The config file is optional unless its path is given.
+fn build_config(args: Args) -> anyhow::Result<Config> {
+    let config = Config::builder()
+        .add_source(config_file(args.config, "authorization_config.toml")?)
+        .add_source(Environment::new(ENV_PREFIX))
+        .set_default("database.type", "POSTGRES")?
         .set_override_option("port", args.port.map(|port| port.to_string()))?
         .set_override_option("database.host", args.database_host)?
         .set_override_option("database.port", args.database_port)?
@@ -115,8 +143,7 @@ pub(super) fn load_config(args: Args) -> anyhow::Result<ApplicationConfig> {
         .set_override_option("database.auth.username", args.database_username)?
         .set_override_option("database.auth.password", args.database_password)?
         .set_default("token.lifetime", 6 * 3600)?
-        .build()?
-        .try_deserialize()?;
+        .build()?;
 
     Ok(config)
 }
@@ -10,6 +10,7 @@ pub struct Model {
     pub id: UlidId,
     pub owner_gid: String,
     pub label: String,
+    pub role: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -1,11 +1,14 @@
 use std::{
     borrow::Borrow,
+    collections::HashMap,
     fmt::Display,
     ops::Deref,
+    path::{Path, PathBuf},
     sync::Arc,
     time::{Duration, SystemTime},
 };
 
+use anyhow::bail;
 use async_trait::async_trait;
 use aws_config::BehaviorVersion;
 use aws_credential_types::provider::ProvideCredentials;
@@ -13,13 +16,19 @@ use aws_sigv4::{
     http_request::{sign, SignableBody, SignableRequest, SigningSettings},
     sign::v4::SigningParams,
 };
+use lazy_static::lazy_static;
 use nebula_common::validate_workspace_name;
-use sea_orm::{sqlx::postgres::PgConnectOptions, ConnectionTrait, DatabaseBackend, Statement, TransactionTrait};
+use sea_orm::sqlx::{
+    postgres::PgConnectOptions,
+    sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions},
+};
 use sea_orm::{ConnectOptions, Database, DatabaseConnection, DatabaseTransaction, DbErr, TryFromU64, TryGetError};
+use sea_orm::{ConnectionTrait, DatabaseBackend, SqlxSqliteConnector, Statement, TransactionTrait};
+use tokio::sync::Mutex;
 use ulid::Ulid;
 use url::Url;
 
-pub use workspace_migration::{migrate_all_workspaces, migrate_workspace};
+pub(crate) use workspace_migration::{migrate_all_workspaces, migrate_workspace};
 
 pub(crate) mod machine_identity;
 pub(crate) mod machine_identity_attribute;
@@ -28,6 +37,23 @@ pub(crate) mod types;
 pub(crate) mod workspace;
 mod workspace_migration;
 
+/// Same layout as the backbone, whose public database file holds the `workspace` table.
+const SQLITE_PUBLIC_DATABASE_FILE: &str = "nebula.db";
+const SQLITE_WORKSPACE_DIRECTORY: &str = "workspaces";
+
+lazy_static! {
+    static ref SQLITE_WORKSPACE_CONNECTIONS: Mutex<HashMap<PathBuf, Arc<DatabaseConnection>>> = Default::default();
+}
+
+/// Location of the database shared with the backbone. Workspaces are isolated by schemas on Postgres, and by database
+/// files on SQLite.
+#[derive(Clone)]
+pub(crate) enum DatabaseLocation {
+    Postgres { host: String, port: u16, database_name: String, auth: AuthMethod },
+    Sqlite { path: PathBuf },
+}
+
+#[derive(Clone)]
 pub(crate) enum AuthMethod {
     Credential { username: String, password: Option<String> },
     RdsIamAuth { host: String, port: u16, username: String },
@@ -79,13 +105,28 @@ async fn generate_rds_iam_token(db_hostname: &str, port: u16, db_username: &str)
     Ok(response)
 }
 
-pub async fn connect_to_database(
-    host: &str,
-    port: u16,
-    database_name: &str,
-    auth: &AuthMethod,
+pub(crate) async fn connect_to_database(location: &DatabaseLocation) -> anyhow::Result<Arc<DatabaseConnection>> {
+    let connection = match location {
+        DatabaseLocation::Postgres { host, port, database_name, auth } => {
+            connect_to_database_with_search_path(host, *port, database_name, auth, None).await?
+        }
+        DatabaseLocation::Sqlite { path } => connect_to_sqlite_database(path, None).await?,
+    };
+    nebula_telemetry::metrics::record_database_pool_periodically(connection.clone());
+
+    Ok(connection)
+}
+
+async fn connect_to_workspace_database(
+    location: &DatabaseLocation,
+    workspace_slug: &str,
 ) -> anyhow::Result<Arc<DatabaseConnection>> {
-    connect_to_database_with_search_path(host, port, database_name, auth, None).await
+    match location {
+        DatabaseLocation::Postgres { host, port, database_name, auth } => {
+            connect_to_database_with_search_path(host, *port, database_name, auth, Some(workspace_slug)).await
+        }
+        DatabaseLocation::Sqlite { path } => sqlite_workspace_connection(path, workspace_slug).await,
+    }
 }
 
 async fn connect_to_database_with_search_path(
@@ -129,6 +170,112 @@ async fn connect_to_database_with_search_path(
     Ok(connection)
 }
 
+/// Connects to the public database file, or to the database file of the workspace which attaches the public one as
+/// `public`, so that the public tables are accessible in the workspace scope like the `public` schema on Postgres.
+async fn connect_to_sqlite_database(
+    path: &Path,
+    workspace_slug: Option<&str>,
+) -> anyhow::Result<Arc<DatabaseConnection>> {
+    let public_database_file = path.join(SQLITE_PUBLIC_DATABASE_FILE);
+    let database_file = if let Some(workspace_slug) = workspace_slug {
+        if !validate_workspace_name(workspace_slug) {
+            bail!("workspace slug is invalid");
+        }
+        sqlite_workspace_file(path, workspace_slug)
+    } else {
+        public_database_file.clone()
+    };
+    if let Some(directory) = database_file.parent() {
+        std::fs::create_dir_all(directory)?;
+    }
+
+    let options = SqliteConnectOptions::new()
+        .filename(&database_file)
+        .create_if_missing(true)
+        // The database files are written by the backbone at the same time.
+        .journal_mode(SqliteJournalMode::Wal)
+        .foreign_keys(true);
+    let options = sea_orm::sqlx::ConnectOptions::log_statements(options, tracing::log::LevelFilter::Debug);
+
+    let mut pool_options = SqlitePoolOptions::new();
+    if workspace_slug.is_some() {
+        let attach_statement =
+            format!("ATTACH DATABASE '{}' AS public;", public_database_file.to_string_lossy().replace('\'', "''"));
+        pool_options = pool_options.after_connect(move |connection, _| {
+            let attach_statement = attach_statement.clone();
+            Box::pin(async move {
+                sea_orm::sqlx::query(&attach_statement).execute(connection).await?;
+                Ok(())
+            })
+        });
+    }
+    let pool = pool_options.connect_with(options).await?;
+
+    Ok(Arc::new(SqlxSqliteConnector::from_sqlx_sqlite_pool(pool)))
+}
+
+fn sqlite_workspace_file(path: &Path, workspace_slug: &str) -> PathBuf {
+    path.join(SQLITE_WORKSPACE_DIRECTORY).join(format!("{workspace_slug}.db"))
+}
+
+/// Returns the connection to the database file of the workspace, which is shared until the database is closed.
+async fn sqlite_workspace_connection(path: &Path, workspace_slug: &str) -> anyhow::Result<Arc<DatabaseConnection>> {
+    let mut connections = SQLITE_WORKSPACE_CONNECTIONS.lock().await;
+    let database_file = sqlite_workspace_file(path, workspace_slug);
+    if let Some(connection) = connections.get(&database_file) {
+        return Ok(connection.clone());
+    }
+
+    let connection = connect_to_sqlite_database(path, Some(workspace_slug)).await?;
+    connections.insert(database_file, connection.clone());
+    Ok(connection)
+}
+
+/// Finds the directory of the SQLite databases from the public database file of the connection.
+async fn sqlite_path<C: ConnectionTrait>(connection: &C) -> Result<PathBuf, DbErr> {
+    let public_database_file: String = connection
+        .query_one(Statement::from_string(
+            DatabaseBackend::Sqlite,
+            "SELECT file FROM pragma_database_list WHERE name = 'main';",
+        ))
+        .await?
+        .ok_or_else(|| DbErr::Custom("sqlite database file is not found".to_owned()))?
+        .try_get("", "file")?;
+
+    Path::new(&public_database_file)
+        .parent()
+        .map(Path::to_path_buf)
+        .ok_or_else(|| DbErr::Custom("sqlite database directory is not found".to_owned()))
+}
+
+/// Creates the schema of the workspace. On SQLite, the database file of the workspace is created by its migration.
+pub(crate) async fn create_workspace_scope<C: ConnectionTrait>(
+    connection: &C,
+    workspace_slug: &str,
+) -> Result<(), DbErr> {
+    match connection.get_database_backend() {
+        DatabaseBackend::Sqlite => {}
+        backend => {
+            connection
+                .execute(Statement::from_string(backend, format!("CREATE SCHEMA IF NOT EXISTS \"{workspace_slug}\";")))
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+pub(crate) async fn close_database(connection: &DatabaseConnection) {
+    match connection.get_database_backend() {
+        DatabaseBackend::Sqlite => {
+            for (_, workspace_connection) in SQLITE_WORKSPACE_CONNECTIONS.lock().await.drain() {
+                workspace_connection.get_sqlite_connection_pool().close().await;
+            }
+            connection.get_sqlite_connection_pool().close().await;
+        }
+        _ => connection.get_postgres_connection_pool().close().await,
+    }
+}
+
 fn reassign_token_periodically_to_database(
     database: Arc<DatabaseConnection>,
     database_host: String,
@@ -265,11 +412,19 @@ pub trait WorkspaceScopedTransaction {
 
 #[async_trait]
 impl WorkspaceScopedTransaction for DatabaseConnection {
+    #[tracing::instrument(name = "db.transaction.begin", skip(self))]
     async fn begin_with_workspace_scope(&self, workspace_slug: &str) -> Result<DatabaseTransaction, DbErr> {
-        let transaction = self.begin().await?;
         if !validate_workspace_name(workspace_slug) {
             return Err(DbErr::Custom("workspace slug is invalid".to_string()));
         }
+        if self.get_database_backend() == DatabaseBackend::Sqlite {
+            let connection = sqlite_workspace_connection(&sqlite_path(self).await?, workspace_slug)
+                .await
+                .map_err(|e| DbErr::Custom(e.to_string()))?;
+            return connection.begin().await;
+        }
+
+        let transaction = self.begin().await?;
         transaction
             .execute(Statement::from_string(
                 DatabaseBackend::Postgres,
@@ -0,0 +1,31 @@
+use async_trait::async_trait;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveIden)]
+pub enum MachineIdentity {
+    Table,
+    Role,
+}
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(MachineIdentity::Table)
+                    .add_column_if_not_exists(string_len(MachineIdentity::Role, 255).default("member"))
+                    .take(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(Table::alter().table(MachineIdentity::Table).drop_column(MachineIdentity::Role).take())
+            .await
+    }
+}
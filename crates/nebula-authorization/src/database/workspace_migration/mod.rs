@@ -6,16 +6,20 @@ use sea_orm::{
 };
 use sea_orm_migration::{IntoSchemaManagerConnection, MigrationTrait, MigratorTrait};
 
-use super::{workspace, AuthMethod};
+use super::{workspace, DatabaseLocation};
 
 mod m20241128_001_init_authorization;
+mod m20241226_001_add_machine_identity_role;
 
 pub struct Migrator;
 
 #[async_trait]
 impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-        vec![Box::new(m20241128_001_init_authorization::Migration)]
+        vec![
+            Box::new(m20241128_001_init_authorization::Migration),
+            Box::new(m20241226_001_add_machine_identity_role::Migration),
+        ]
     }
 
     fn migration_table_name() -> sea_orm::DynIden {
@@ -23,19 +27,13 @@ impl MigratorTrait for Migrator {
     }
 }
 
-pub async fn migrate_all_workspaces(
+pub(crate) async fn migrate_all_workspaces(
     transaction: &DatabaseTransaction,
-    host: &str,
-    port: u16,
-    database_name: &str,
-    auth: &AuthMethod,
+    location: &DatabaseLocation,
 ) -> anyhow::Result<()> {
     let workspaces = workspace::Entity::find().all(transaction).await?;
 
-    let results = join_all(
-        workspaces.iter().map(|workspace| migrate_workspace(&workspace.name, host, port, database_name, auth)),
-    )
-    .await;
+    let results = join_all(workspaces.iter().map(|workspace| migrate_workspace(&workspace.name, location))).await;
 
     for result in results {
         result?;
@@ -44,15 +42,8 @@ pub async fn migrate_all_workspaces(
     Ok(())
 }
 
-pub async fn migrate_workspace(
-    workspace_slug: &str,
-    host: &str,
-    port: u16,
-    database_name: &str,
-    auth: &AuthMethod,
-) -> anyhow::Result<()> {
-    let connection =
-        super::connect_to_database_with_search_path(host, port, database_name, auth, Some(workspace_slug)).await?;
+pub(crate) async fn migrate_workspace(workspace_slug: &str, location: &DatabaseLocation) -> anyhow::Result<()> {
+    let connection = super::connect_to_workspace_database(location, workspace_slug).await?;
 
     migrate(connection.as_ref()).await?;
 
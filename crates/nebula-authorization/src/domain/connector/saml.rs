@@ -10,7 +10,7 @@ use samael::{
 };
 use thiserror::Error;
 
-use crate::config::{AttributesConfig, SAMLAdminRoleConfig, WorkspaceConfig};
+use crate::config::{AttributesConfig, SAMLAdminRoleConfig, SAMLRoleMappingConfig, WorkspaceConfig};
 
 use super::Identity;
 
@@ -27,6 +27,7 @@ pub struct SAMLConnertorConfig {
     attributes_config: AttributesConfig,
     workspace_config: WorkspaceConfig,
     admin_role_config: SAMLAdminRoleConfig,
+    role_mapping_config: Option<SAMLRoleMappingConfig>,
 }
 
 pub struct SAMLConnector {
@@ -36,6 +37,7 @@ pub struct SAMLConnector {
     attributes_config: AttributesConfig,
     workspace_config: WorkspaceConfig,
     admin_role_config: SAMLAdminRoleConfig,
+    role_mapping_config: Option<SAMLRoleMappingConfig>,
 }
 
 #[derive(Error, Debug)]
@@ -129,6 +131,7 @@ impl SAMLConnector {
             attributes_config: config.attributes_config,
             workspace_config: config.workspace_config,
             admin_role_config: config.admin_role_config,
+            role_mapping_config: config.role_mapping_config,
         })
     }
 
@@ -184,12 +187,25 @@ impl SAMLConnector {
                 get_all_attribute(&attributes, attribute_name)?
                     .iter()
                     .find_map(|group| if admin_groups.contains(group) { Some(Role::Admin) } else { None })
-                    .unwrap_or(Role::Member)
+                    .unwrap_or_else(|| self.mapped_role(&attributes))
             }
         };
 
         Ok(Identity { user_id, claims, workspace_name, role })
     }
+
+    fn mapped_role(&self, attributes: &[AttributeStatement]) -> Role {
+        let Some(SAMLRoleMappingConfig { attribute_name, groups: role_groups }) = &self.role_mapping_config else {
+            return Role::Member;
+        };
+        let groups = get_all_attribute(attributes, attribute_name).unwrap_or_default();
+
+        role_groups
+            .iter()
+            .find(|(group, _)| groups.contains(group))
+            .map(|(_, role)| Role::from(role.clone()))
+            .unwrap_or(Role::Member)
+    }
 }
 
 fn get_attribute(attribute_statements: &[AttributeStatement], name: &str) -> Result<String, SAMLHandlerError> {
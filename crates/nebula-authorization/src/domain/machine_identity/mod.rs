@@ -2,7 +2,7 @@ use std::collections::HashSet;
 
 use axum::async_trait;
 use chrono::Utc;
-use nebula_token::claim::NebulaClaim;
+use nebula_token::claim::{NebulaClaim, Role};
 use rand::{distributions::Alphanumeric, Rng};
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, DatabaseTransaction, DbErr, EntityTrait, LoaderTrait, QueryFilter, QuerySelect as _,
@@ -18,7 +18,9 @@ pub struct MachineIdentity {
     pub owner_gid: String,
     pub label: String,
     pub attributes: Vec<(String, String)>,
+    pub role: Role,
     updated_attributes: Option<Vec<(String, String)>>,
+    updated_role: Option<Role>,
     deleted: bool,
 }
 
@@ -39,6 +41,14 @@ impl MachineIdentity {
             Some(attributes.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect());
     }
 
+    pub(crate) fn update_role(&mut self, role: Role) {
+        if self.role == role {
+            return;
+        }
+
+        self.updated_role = Some(role);
+    }
+
     pub(crate) fn delete(&mut self) {
         self.deleted = true
     }
@@ -79,6 +89,18 @@ impl Persistable for MachineIdentity {
             }
         }
 
+        if let Some(updated_role) = self.updated_role {
+            machine_identity::Entity::update_many()
+                .set(machine_identity::ActiveModel {
+                    role: Set(updated_role.into()),
+                    updated_at: Set(Utc::now()),
+                    ..Default::default()
+                })
+                .filter(machine_identity::Column::Id.eq(UlidId::new(self.id)))
+                .exec(transaction)
+                .await?;
+        }
+
         Ok(())
     }
 }
@@ -131,7 +153,9 @@ impl From<(machine_identity::Model, Vec<machine_identity_attribute::Model>)> for
                 .into_iter()
                 .map(|attribute| (attribute.key, attribute.value))
                 .collect(),
+            role: Role::from(machine_identity_model.role),
             updated_attributes: None,
+            updated_role: None,
             deleted: false,
         }
     }
@@ -143,6 +167,7 @@ impl MachineIdentityService {
         transaction: &DatabaseTransaction,
         owner_claim: &NebulaClaim,
         label: &str,
+        role: Role,
     ) -> Result<MachineIdentityCreationResult> {
         let machine_identity_id = Ulid::new();
         let now = Utc::now();
@@ -150,6 +175,7 @@ impl MachineIdentityService {
             id: Set(UlidId::new(machine_identity_id)),
             owner_gid: Set(owner_claim.gid.to_owned()),
             label: Set(label.to_owned()),
+            role: Set(role.into()),
             created_at: Set(now),
             updated_at: Set(now),
         }
@@ -2,7 +2,7 @@ use std::time::{Duration, SystemTime};
 
 use anyhow::Result;
 use nebula_token::{
-    claim::{ATTRIBUTES_CLAIM, ROLE_CLAIM, WORKSPACE_NAME_CLAIM},
+    claim::{ATTRIBUTES_CLAIM, AUTHORIZATION_SERVER_ISSUER, ROLE_CLAIM, WORKSPACE_NAME_CLAIM},
     jwk::jwk_set::JwkSet,
     jwt::Jwt,
     JwsHeader, JwtPayload, Map, Value,
@@ -19,7 +19,6 @@ pub struct TokenService {
 }
 
 const DEFAULT_ALGORITHM: &str = "ES256";
-const ISSUER: &str = "nebula-authorization";
 
 impl TokenService {
     pub fn new(base_url: Url, lifetime: u64, jwks: JwkSet, jwk_kid: String) -> Self {
@@ -40,7 +39,7 @@ impl TokenService {
             )),
         )?;
         jwt_payload.set_subject(&identity.user_id);
-        jwt_payload.set_issuer(ISSUER);
+        jwt_payload.set_issuer(AUTHORIZATION_SERVER_ISSUER);
         jwt_payload.set_claim(WORKSPACE_NAME_CLAIM, Some(identity.workspace_name.clone().into())).unwrap();
         jwt_payload.set_claim(ROLE_CLAIM, Some(String::from(identity.role.clone()).into())).unwrap();
 
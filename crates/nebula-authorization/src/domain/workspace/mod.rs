@@ -3,13 +3,13 @@ use std::sync::Arc;
 use chrono::Utc;
 use nebula_common::validate_workspace_name;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseBackend, DatabaseConnection, DatabaseTransaction, DbErr,
-    EntityTrait, PaginatorTrait, QueryFilter, RuntimeErr, SqlxError, Statement,
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DatabaseTransaction, DbErr, EntityTrait, PaginatorTrait,
+    QueryFilter, RuntimeErr, SqlxError,
 };
 use tracing::info;
 use ulid::Ulid;
 
-use crate::database::{migrate_workspace, AuthMethod};
+use crate::database::{create_workspace_scope, migrate_workspace, DatabaseLocation};
 
 #[derive(thiserror::Error, Debug)]
 pub(crate) enum Error {
@@ -27,23 +27,14 @@ pub(crate) type Result<T> = std::result::Result<T, Error>;
 
 pub(crate) struct WorkspaceService {
     connection: Arc<DatabaseConnection>,
-    database_host: String,
-    database_port: u16,
-    database_name: String,
-    database_auth: AuthMethod,
+    database_location: DatabaseLocation,
 }
 
 impl WorkspaceService {}
 
 impl WorkspaceService {
-    pub(crate) fn new(
-        connection: Arc<DatabaseConnection>,
-        database_host: String,
-        database_port: u16,
-        database_name: String,
-        database_auth: AuthMethod,
-    ) -> Self {
-        Self { connection, database_host, database_port, database_name, database_auth }
+    pub(crate) fn new(connection: Arc<DatabaseConnection>, database_location: DatabaseLocation) -> Self {
+        Self { connection, database_location }
     }
 
     async fn exists_by_name(&self, transaction: &DatabaseTransaction, name: &str) -> Result<bool> {
@@ -59,15 +50,9 @@ impl WorkspaceService {
             return Err(Error::InvalidWorkspaceName);
         }
 
-        self.connection
-            .execute(Statement::from_string(
-                DatabaseBackend::Postgres,
-                format!("CREATE SCHEMA IF NOT EXISTS \"{name}\";"),
-            ))
-            .await?;
+        create_workspace_scope(self.connection.as_ref(), name).await?;
 
-        migrate_workspace(name, &self.database_host, self.database_port, &self.database_name, &self.database_auth)
-            .await?;
+        migrate_workspace(name, &self.database_location).await?;
 
         if self.exists_by_name(transaction, name).await? {
             return Err(Error::WorkspaceNameConflicted);
@@ -93,7 +78,7 @@ impl WorkspaceService {
 impl From<DbErr> for Error {
     fn from(value: DbErr) -> Self {
         if let DbErr::Query(RuntimeErr::SqlxError(SqlxError::Database(e))) = value {
-            if e.code().as_deref() == Some("23505") {
+            if e.is_unique_violation() {
                 Self::WorkspaceNameConflicted
             } else {
                 Self::Anyhow(e.into())
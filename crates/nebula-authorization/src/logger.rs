@@ -1,8 +1,10 @@
-use tracing_subscriber::fmt::SubscriberBuilder;
+use nebula_telemetry::trace::TracingConfig;
+use tracing_subscriber::{filter::LevelFilter, layer::SubscriberExt, util::SubscriberInitExt};
 
 #[derive(Default)]
 pub(super) struct LoggerConfig {
     pub format: LoggerFormat,
+    pub tracing: Option<TracingConfig>,
 }
 
 #[derive(Default)]
@@ -11,12 +13,16 @@ pub(super) enum LoggerFormat {
     Json,
 }
 
-pub(super) fn init_logger(config: LoggerConfig) {
-    let builder = SubscriberBuilder::default();
-
-    let builder = match config.format {
-        LoggerFormat::Json => builder.json(),
+pub(super) fn init_logger(config: LoggerConfig) -> anyhow::Result<()> {
+    let fmt_layer = match config.format {
+        LoggerFormat::Json => tracing_subscriber::fmt::layer().json(),
     };
+    let otel_layer = config
+        .tracing
+        .as_ref()
+        .map(|tracing| nebula_telemetry::trace::layer(env!("CARGO_PKG_NAME"), tracing))
+        .transpose()?;
 
-    builder.init();
+    tracing_subscriber::registry().with(LevelFilter::INFO).with(fmt_layer).with(otel_layer).try_init()?;
+    Ok(())
 }
@@ -1,11 +1,13 @@
-use std::sync::Arc;
+use std::{future::Future, sync::Arc};
 
 use axum::http::header::{AUTHORIZATION, CONTENT_TYPE, LINK};
-use axum::{routing::get, Router};
+use axum::{extract::State, middleware, routing::get, Router};
+use nebula_telemetry::health::Readiness;
+use nebula_tls::TlsConfig;
 use tower_http::cors::AllowOrigin;
 use tower_http::cors::Any;
 use tower_http::cors::CorsLayer;
-use tracing::debug;
+use tracing::{debug, info};
 
 use crate::config::CorsConfig;
 use crate::{application::Application, config::ApplicationConfig};
@@ -16,26 +18,70 @@ pub(super) struct ServerConfig {
     pub port: u16,
     pub path_prefix: Option<String>,
     pub cors: Option<CorsConfig>,
+    pub tls: Option<TlsConfig>,
 }
 
-impl From<ApplicationConfig> for ServerConfig {
-    fn from(value: ApplicationConfig) -> Self {
-        Self { port: value.port, path_prefix: value.path_prefix, cors: value.cors }
+impl From<&ApplicationConfig> for ServerConfig {
+    fn from(value: &ApplicationConfig) -> Self {
+        Self {
+            port: value.port,
+            path_prefix: value.path_prefix.clone(),
+            cors: value.cors.clone(),
+            tls: value.tls.clone(),
+        }
     }
 }
 
-pub(super) async fn run(application: Application, config: ServerConfig) -> anyhow::Result<()> {
-    let application = Arc::new(application);
-    let app = Router::new().route("/health", get(|| async { "" }));
+/// Authorization server, which can be served on its own port or mounted into another router by [`Server::router`].
+pub struct Server {
+    application: Arc<Application>,
+    config: ServerConfig,
+}
+
+impl Server {
+    pub async fn init(config: &ApplicationConfig) -> anyhow::Result<Self> {
+        let application = Application::init(config).await?;
+        Ok(Self { application: Arc::new(application), config: config.into() })
+    }
+
+    pub fn router(&self) -> Router {
+        router(self.application.clone(), &self.config)
+    }
+
+    /// Serves on the configured port until the shutdown future resolves.
+    pub async fn serve(&self, shutdown: impl Future<Output = ()> + Send + 'static) -> anyhow::Result<()> {
+        debug!("starting authz server on {}", self.config.port);
+        nebula_tls::serve(self.config.port, self.router(), self.config.tls.as_ref(), shutdown).await
+    }
+
+    pub async fn run(self) -> anyhow::Result<()> {
+        self.serve(nebula_tls::shutdown_signal()).await?;
+        self.close().await;
+        info!("authz server is stopped");
+        Ok(())
+    }
+
+    pub async fn close(&self) {
+        self.application.close().await;
+    }
+}
+
+fn router(application: Arc<Application>, config: &ServerConfig) -> Router {
+    let app = Router::new()
+        .route("/health", get(|| async { "" }))
+        .route("/ready", get(handle_ready).with_state(application.clone()));
     let path_prefix = if let Some(ref path_prefix) = config.path_prefix {
         let path_prefix = format!("/{}/", path_prefix.trim_matches('/'));
         path_prefix
     } else {
         "/".to_string()
     };
-    let app = app.nest(&path_prefix, router::router(application.clone()));
+    let app = app
+        .nest(&path_prefix, router::router(application.clone()))
+        .layer(middleware::from_fn(nebula_telemetry::metrics::track_http_requests))
+        .layer(middleware::from_fn(nebula_telemetry::trace::propagate_trace_context));
 
-    let app = if let Some(cors) = config.cors {
+    if let Some(cors) = config.cors.clone() {
         let cors = CorsLayer::new()
             .allow_methods(Any)
             .allow_origin(match cors {
@@ -58,10 +104,9 @@ pub(super) async fn run(application: Application, config: ServerConfig) -> anyho
         app.layer(cors)
     } else {
         app
-    };
+    }
+}
 
-    let listener = tokio::net::TcpListener::bind(("0.0.0.0", config.port)).await?;
-    debug!("starting authz server on {}", config.port);
-    axum::serve(listener, app).await?;
-    Ok(())
+async fn handle_ready(State(application): State<Arc<Application>>) -> Readiness {
+    application.readiness().await
 }
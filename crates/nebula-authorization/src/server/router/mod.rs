@@ -141,6 +141,8 @@ pub(crate) async fn check_machine_identity_owner_or_admin_role(
 }
 
 const TOKEN_HEADER_NAME: &str = "token";
+const MACHINE_IDENTITY_LOGINS_TOTAL: &str = "machine_identity_logins_total";
+const SAML_LOGINS_TOTAL: &str = "saml_logins_total";
 
 async fn handle_machine_identity_login(
     Path(workspace_name): Path<String>,
@@ -159,14 +161,19 @@ async fn handle_machine_identity_login(
         .get_machine_identity_by_token(&transaction, machine_token)
         .await
         .map_err(|_| MachineIdentityLoginError::FailedToGetMachineIdentityToken)?
-        .ok_or(MachineIdentityLoginError::InvalidToken)?;
+        .ok_or(MachineIdentityLoginError::InvalidToken)
+        .inspect_err(|_| {
+            metrics::counter!(MACHINE_IDENTITY_LOGINS_TOTAL, "result" => "failure").increment(1);
+        })?;
 
     transaction.commit().await?;
 
-    let identity = Identity::new(token.id.into(), workspace_name, Role::Member, token.attributes.into_iter().collect());
+    let identity = Identity::new(token.id.into(), workspace_name, token.role, token.attributes.into_iter().collect());
     let jwt =
         application.token_service.create_jwt(&identity).map_err(|_| MachineIdentityLoginError::FailedToCreateJWT)?;
 
+    metrics::counter!(MACHINE_IDENTITY_LOGINS_TOTAL, "result" => "success").increment(1);
+
     Ok(Json(MachineIdentityLoginResponse { access_token: jwt }))
 }
 
@@ -295,13 +302,14 @@ async fn handle_saml_connector_callback(
     State(application): State<Arc<Application>>,
     Form(payload): Form<SAMLConnectorCallbackRequest>,
 ) -> Result<Response, SAMLConnectorCallbackError> {
-    let identity = application
-        .connector
-        .identity(&payload.saml_response, &payload.relay_state)
-        .map_err(|_| SAMLConnectorCallbackError::FailedToCreateSAMLIdentity)?;
+    let identity = application.connector.identity(&payload.saml_response, &payload.relay_state).map_err(|_| {
+        metrics::counter!(SAML_LOGINS_TOTAL, "result" => "failure").increment(1);
+        SAMLConnectorCallbackError::FailedToCreateSAMLIdentity
+    })?;
 
     let jwt =
         application.token_service.create_jwt(&identity).map_err(|_| SAMLConnectorCallbackError::FailedToCreateJWT)?;
+    metrics::counter!(SAML_LOGINS_TOTAL, "result" => "success").increment(1);
     if payload.relay_state.starts_with("nebula-callback-port=") {
         let relay_state = payload.relay_state.trim_start_matches("nebula-callback-port=");
         let url = format!(
@@ -359,6 +367,7 @@ pub struct Attribute {
 #[serde(rename_all = "camelCase")]
 pub struct PostMachineIdentityRequest {
     label: String,
+    role: Option<Role>,
 }
 
 #[derive(Error, Debug, ErrorStatus)]
@@ -375,6 +384,9 @@ enum MachineIdentityError {
     #[error("failed to create location header")]
     #[status(StatusCode::INTERNAL_SERVER_ERROR)]
     FailedToCreateLocationHeader,
+    #[error("only admin can assign a role other than member")]
+    #[status(StatusCode::FORBIDDEN)]
+    RoleNotAssignable,
 }
 
 impl From<machine_identity::Error> for MachineIdentityError {
@@ -392,10 +404,17 @@ async fn handle_post_machine_identity(
     Extension(claim): Extension<NebulaClaim>,
     Json(payload): Json<PostMachineIdentityRequest>,
 ) -> Result<impl IntoResponse, MachineIdentityError> {
+    let role = payload.role.unwrap_or(Role::Member);
+    if role != Role::Member && claim.role != Role::Admin {
+        return Err(MachineIdentityError::RoleNotAssignable);
+    }
+
     let transaction = application.database_connection.begin_with_workspace_scope(&workspace_name).await?;
 
-    let result =
-        application.machine_identity_service.register_machine_identity(&transaction, &claim, &payload.label).await?;
+    let result = application
+        .machine_identity_service
+        .register_machine_identity(&transaction, &claim, &payload.label, role)
+        .await?;
 
     let mut parts = uri.into_parts();
 
@@ -431,6 +450,7 @@ pub struct MachineIdentityResponse {
     id: Ulid,
     label: String,
     attributes: Vec<Attribute>,
+    role: Role,
 }
 
 impl From<MachineIdentity> for MachineIdentityResponse {
@@ -439,6 +459,7 @@ impl From<MachineIdentity> for MachineIdentityResponse {
             id: value.id,
             label: value.label,
             attributes: value.attributes.into_iter().map(|(key, value)| Attribute { key, value }).collect(),
+            role: value.role,
         }
     }
 }
@@ -488,6 +509,7 @@ async fn handle_get_machine_identity(
 #[derive(Deserialize)]
 struct PatchMachineIdentityRequest {
     attributes: Option<Vec<Attribute>>,
+    role: Option<Role>,
 }
 
 async fn handle_patch_machine_identity(
@@ -504,6 +526,9 @@ async fn handle_patch_machine_identity(
             attributes.iter().map(|attribute| (attribute.key.as_str(), attribute.value.as_str())).collect();
         machine_identity.update_attributes(&attributes);
     }
+    if let Some(role) = payload.role {
+        machine_identity.update_role(role);
+    }
     machine_identity.persist(&transaction).await?;
 
     transaction.commit().await?;
@@ -1,14 +1,22 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use nebula_abe::curves::Curve;
 use sea_orm::{DatabaseConnection, DatabaseTransaction, DbErr};
+use tracing::{debug, info, warn};
 use ulid::Ulid;
 
 use crate::{
     database::{Persistable, WorkspaceScopedTransaction},
     domain::{
         self,
-        authority::{Authority, AuthorityService},
+        authority::{
+            client::{AuthorityClient, FetchedPublicKey},
+            Authority, AuthorityPublicKey, AuthorityService, AuthorityStatus, PublicKeyStatus,
+        },
+        parameter::{Parameter, ParameterService},
+        workspace::WorkspaceService,
     },
 };
 
@@ -17,14 +25,50 @@ pub struct AuthorityData {
     pub name: String,
     pub host: String,
     pub public_key: Option<String>,
+    pub status: Option<AuthorityStatus>,
+    pub latest_key_version: Option<u64>,
+    pub status_checked_at: Option<DateTime<Utc>>,
 }
 
 impl From<domain::authority::Authority> for AuthorityData {
     fn from(value: domain::authority::Authority) -> Self {
-        Self { id: value.id, name: value.name, host: value.host, public_key: value.public_key }
+        Self {
+            id: value.id,
+            name: value.name,
+            host: value.host,
+            public_key: value.public_key,
+            status: value.status,
+            latest_key_version: value.latest_key_version,
+            status_checked_at: value.status_checked_at,
+        }
     }
 }
 
+/// Authority registered by itself, with the credential with which it authenticates itself afterward.
+pub struct SelfRegistrationData {
+    pub authority: AuthorityData,
+    pub credential: String,
+}
+
+pub struct BootstrapTokenData {
+    pub token: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+pub struct AuthorityPublicKeyData {
+    pub authority_id: Ulid,
+    pub authority_name: String,
+    pub version: u64,
+    pub parameter_version: i32,
+    pub curve: Curve,
+    pub public_key: String,
+    pub status: PublicKeyStatus,
+}
+
+/// Requests an authority is sent on a refresh of its public keys besides the one for the latest public key, so that an
+/// authority reporting an excessive version can't make the backbone flood it with requests.
+const MAX_PUBLIC_KEY_REQUESTS_PER_REFRESH: usize = 16;
+
 #[async_trait]
 pub trait AuthorityUseCase {
     async fn register_authority(&self, name: &str, host: &str) -> Result<()>;
@@ -37,21 +81,189 @@ pub trait AuthorityUseCase {
         new_public_key: Option<&str>,
     ) -> Result<()>;
     async fn delete_authority(&self, authority_id: &Ulid) -> Result<()>;
+    /// Fetches the public keys newer than the cached ones from every authority, and caches them once verified along
+    /// with the refreshed statuses of the cached ones. Authorities which are unreachable or serve an invalid public key are skipped, keeping their cached keys.
+    async fn refresh_public_keys(&self) -> Result<()>;
+    /// Gets the cached public keys of all authorities, or only the latest one of each authority if `latest_only`.
+    async fn get_public_keys(&self, latest_only: bool) -> Result<Vec<AuthorityPublicKeyData>>;
+    /// Probes the readiness and the latest key pair version of every authority, and records them.
+    async fn check_health(&self) -> Result<()>;
+    async fn issue_bootstrap_token(&self, expires_in: Duration) -> Result<BootstrapTokenData>;
+    /// Registers the authority with the one-time bootstrap token, and issues the credential with which it authenticates
+    /// itself afterward. An authority registered by an admin adopts the token only if it is served at the same host.
+    async fn register_self(&self, bootstrap_token: &str, name: &str, host: &str) -> Result<SelfRegistrationData>;
+    /// Refreshes the public keys of the authority which authenticates itself with the credential, so that a key pair it
+    /// has rolled is picked up without waiting for the periodic refresh.
+    async fn refresh_own_public_keys(&self, credential: &str) -> Result<()>;
 }
 
 pub struct AuthorityUseCaseImpl {
     workspace_name: String,
     database_connection: Arc<DatabaseConnection>,
+    workspace_service: Arc<dyn WorkspaceService + Sync + Send>,
     authority_service: Arc<dyn AuthorityService + Sync + Send>,
+    parameter_service: Arc<dyn ParameterService + Sync + Send>,
+    authority_client: Arc<dyn AuthorityClient + Sync + Send>,
 }
 
 impl AuthorityUseCaseImpl {
     pub fn new(
         workspace_name: String,
         database_connection: Arc<DatabaseConnection>,
+        workspace_service: Arc<dyn WorkspaceService + Sync + Send>,
         authority_service: Arc<dyn AuthorityService + Sync + Send>,
+        parameter_service: Arc<dyn ParameterService + Sync + Send>,
+        authority_client: Arc<dyn AuthorityClient + Sync + Send>,
     ) -> Self {
-        Self { workspace_name, database_connection, authority_service }
+        Self {
+            workspace_name,
+            database_connection,
+            workspace_service,
+            authority_service,
+            parameter_service,
+            authority_client,
+        }
+    }
+
+    async fn probe(&self, authority: &Authority) -> (AuthorityStatus, Option<u64>) {
+        let status = match self.authority_client.get_status(&authority.host).await {
+            Ok(status) => status,
+            Err(e) => {
+                debug!(error = %e, workspace = self.workspace_name, authority = authority.name, "failed to probe the authority.");
+                AuthorityStatus::Unavailable
+            }
+        };
+        let latest_key_version = match status {
+            AuthorityStatus::Available => self
+                .authority_client
+                .get_public_key(&authority.host, &self.workspace_name, None)
+                .await
+                .ok()
+                .map(|public_key| public_key.version),
+            AuthorityStatus::Armored | AuthorityStatus::Unavailable => None,
+        };
+
+        (status, latest_key_version)
+    }
+
+    /// Fetches the public keys of the authority above the latest cached version, up to its latest key pair version,
+    /// and refreshes the status of the cached versions which are not revoked yet. At most
+    /// [`MAX_PUBLIC_KEY_REQUESTS_PER_REFRESH`] requests are made besides the one for the latest public key, and the
+    /// rest is left to the following refreshes. Returns the public keys verified along with the latest public key.
+    async fn fetch_public_keys(
+        &self,
+        authority: &Authority,
+        cached_public_keys: &[&AuthorityPublicKey],
+        parameters: &[Parameter],
+    ) -> anyhow::Result<(Vec<AuthorityPublicKey>, String)> {
+        let latest = self.authority_client.get_public_key(&authority.host, &self.workspace_name, None).await?;
+        let cached_version = cached_public_keys.iter().map(|public_key| public_key.version).max().unwrap_or_default();
+        if latest.version < cached_version {
+            anyhow::bail!("latest version {} is older than the cached version {cached_version}", latest.version);
+        }
+
+        let mut requests = 0;
+        let mut public_keys = vec![];
+        for version in cached_version + 1..=latest.version {
+            let fetched = if version == latest.version {
+                latest.clone()
+            } else if requests < MAX_PUBLIC_KEY_REQUESTS_PER_REFRESH {
+                requests += 1;
+                self.fetch_public_key(authority, version).await?
+            } else {
+                break;
+            };
+            let public_key = AuthorityPublicKey {
+                authority_id: authority.id,
+                version,
+                parameter_version: fetched.parameter_version,
+                curve: fetched.curve,
+                public_key: fetched.public_key,
+                status: fetched.status,
+                status_checked_at: Some(Utc::now()),
+            };
+            public_key.verify(
+                parameters,
+                &AuthorityPublicKey::key_pair_name(&authority.name, &self.workspace_name, version),
+            )?;
+            public_keys.push(public_key);
+        }
+
+        // The statuses checked least recently are refreshed first, so that every version is refreshed in turn.
+        let mut unrevoked: Vec<_> =
+            cached_public_keys.iter().filter(|public_key| public_key.status != PublicKeyStatus::Revoked).collect();
+        unrevoked.sort_by_key(|public_key| public_key.status_checked_at);
+        for cached in unrevoked.into_iter().take(MAX_PUBLIC_KEY_REQUESTS_PER_REFRESH.saturating_sub(requests)) {
+            let fetched = self.fetch_public_key(authority, cached.version).await?;
+            if fetched.public_key != cached.public_key {
+                anyhow::bail!("public key of version {} differs from the cached one", cached.version);
+            }
+            public_keys.push(AuthorityPublicKey {
+                status: fetched.status,
+                status_checked_at: Some(Utc::now()),
+                ..(*cached).clone()
+            });
+        }
+
+        Ok((public_keys, latest.public_key))
+    }
+
+    async fn fetch_public_key(&self, authority: &Authority, version: u64) -> anyhow::Result<FetchedPublicKey> {
+        let fetched =
+            self.authority_client.get_public_key(&authority.host, &self.workspace_name, Some(version)).await?;
+        if fetched.version != version {
+            anyhow::bail!("version {} is served for version {version}", fetched.version);
+        }
+
+        Ok(fetched)
+    }
+
+    /// Refreshes the public keys of every authority, or only of the authority with `authority_id` if given.
+    #[tracing::instrument(skip_all, fields(workspace_name = %self.workspace_name))]
+    async fn refresh_public_keys_of(&self, authority_id: Option<Ulid>) -> Result<()> {
+        let transaction = self.database_connection.begin_with_workspace_scope(&self.workspace_name).await?;
+        let authorities: Vec<_> = self
+            .authority_service
+            .get_authorities(&transaction)
+            .await?
+            .into_iter()
+            .filter(|authority| authority_id.is_none_or(|authority_id| authority.id == authority_id))
+            .collect();
+        let cached_public_keys = self.authority_service.get_public_keys(&transaction).await?;
+        let parameters = self.parameter_service.get_all(&transaction).await.map_err(anyhow::Error::from)?;
+        transaction.commit().await?;
+
+        // Authorities are contacted outside of a transaction not to hold it while waiting for them.
+        let mut public_keys = vec![];
+        let mut latest_public_keys = HashMap::new();
+        for authority in &authorities {
+            let cached_public_keys: Vec<_> =
+                cached_public_keys.iter().filter(|public_key| public_key.authority_id == authority.id).collect();
+            match self.fetch_public_keys(authority, &cached_public_keys, &parameters).await {
+                Ok((fetched, latest)) => {
+                    public_keys.extend(fetched);
+                    latest_public_keys.insert(authority.id, latest);
+                }
+                Err(e) => warn!(
+                    error = %e,
+                    workspace = self.workspace_name,
+                    authority = authority.name,
+                    "failed to refresh the public keys of the authority."
+                ),
+            }
+        }
+
+        let transaction = self.database_connection.begin_with_workspace_scope(&self.workspace_name).await?;
+        self.authority_service.save_public_keys(&transaction, &public_keys).await?;
+        for mut authority in authorities {
+            if let Some(latest) = latest_public_keys.get(&authority.id) {
+                authority.update_public_key(latest);
+                authority.persist(&transaction).await?;
+            }
+        }
+        transaction.commit().await?;
+
+        Ok(())
     }
 
     async fn get_authority_model(&self, transaction: &DatabaseTransaction, authority_id: &Ulid) -> Result<Authority> {
@@ -64,13 +276,25 @@ impl AuthorityUseCaseImpl {
 
 #[async_trait]
 impl AuthorityUseCase for AuthorityUseCaseImpl {
+    #[tracing::instrument(skip_all, fields(workspace_name = %self.workspace_name))]
     async fn register_authority(&self, name: &str, host: &str) -> Result<()> {
         let transaction = self.database_connection.begin_with_workspace_scope(&self.workspace_name).await?;
+
+        let workspace = self
+            .workspace_service
+            .get_by_name(&transaction, &self.workspace_name)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("workspace({}) is not exists", self.workspace_name))?;
+        if !workspace.settings.is_authority_host_allowed(host) {
+            return Err(Error::HostNotAllowed { entered_host: host.to_owned() });
+        }
+
         self.authority_service.register_authority(&transaction, name, host).await?;
         transaction.commit().await?;
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, fields(workspace_name = %self.workspace_name))]
     async fn get_authorities(&self) -> Result<Vec<AuthorityData>> {
         let transaction = self.database_connection.begin_with_workspace_scope(&self.workspace_name).await?;
         let authorities = self.authority_service.get_authorities(&transaction).await?;
@@ -79,6 +303,7 @@ impl AuthorityUseCase for AuthorityUseCaseImpl {
         Ok(authorities.into_iter().map(Into::into).collect())
     }
 
+    #[tracing::instrument(skip_all, fields(workspace_name = %self.workspace_name))]
     async fn get_authority(&self, authority_id: &Ulid) -> Result<AuthorityData> {
         let transaction = self.database_connection.begin_with_workspace_scope(&self.workspace_name).await?;
         let authority = self.get_authority_model(&transaction, authority_id).await?;
@@ -87,6 +312,7 @@ impl AuthorityUseCase for AuthorityUseCaseImpl {
         Ok(authority.into())
     }
 
+    #[tracing::instrument(skip_all, fields(workspace_name = %self.workspace_name))]
     async fn update_authority(
         &self,
         authority_id: &Ulid,
@@ -109,6 +335,7 @@ impl AuthorityUseCase for AuthorityUseCaseImpl {
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, fields(workspace_name = %self.workspace_name))]
     async fn delete_authority(&self, authority_id: &Ulid) -> Result<()> {
         let transaction = self.database_connection.begin_with_workspace_scope(&self.workspace_name).await?;
 
@@ -120,14 +347,178 @@ impl AuthorityUseCase for AuthorityUseCaseImpl {
 
         Ok(())
     }
+
+    async fn refresh_public_keys(&self) -> Result<()> {
+        self.refresh_public_keys_of(None).await
+    }
+
+    #[tracing::instrument(skip_all, fields(workspace_name = %self.workspace_name))]
+    async fn get_public_keys(&self, latest_only: bool) -> Result<Vec<AuthorityPublicKeyData>> {
+        let transaction = self.database_connection.begin_with_workspace_scope(&self.workspace_name).await?;
+        let authorities = self.authority_service.get_authorities(&transaction).await?;
+        let public_keys = self.authority_service.get_public_keys(&transaction).await?;
+        transaction.commit().await?;
+
+        let authority_names: HashMap<_, _> =
+            authorities.into_iter().map(|authority| (authority.id, authority.name)).collect();
+        let latest_versions = public_keys.iter().fold(HashMap::new(), |mut latest_versions, public_key| {
+            let latest_version = latest_versions.entry(public_key.authority_id).or_insert(public_key.version);
+            *latest_version = (*latest_version).max(public_key.version);
+            latest_versions
+        });
+
+        Ok(public_keys
+            .into_iter()
+            .filter(|public_key| {
+                !latest_only || latest_versions.get(&public_key.authority_id) == Some(&public_key.version)
+            })
+            .filter_map(|public_key| {
+                Some(AuthorityPublicKeyData {
+                    authority_name: authority_names.get(&public_key.authority_id)?.to_owned(),
+                    authority_id: public_key.authority_id,
+                    version: public_key.version,
+                    parameter_version: public_key.parameter_version,
+                    curve: public_key.curve,
+                    public_key: public_key.public_key,
+                    status: public_key.status,
+                })
+            })
+            .collect())
+    }
+
+    #[tracing::instrument(skip_all, fields(workspace_name = %self.workspace_name))]
+    async fn check_health(&self) -> Result<()> {
+        let transaction = self.database_connection.begin_with_workspace_scope(&self.workspace_name).await?;
+        let authorities = self.authority_service.get_authorities(&transaction).await?;
+        transaction.commit().await?;
+
+        let mut statuses = HashMap::new();
+        for authority in &authorities {
+            let (status, latest_key_version) = self.probe(authority).await;
+
+            let labels = [("workspace", self.workspace_name.clone()), ("authority", authority.name.clone())];
+            metrics::gauge!(AUTHORITY_UP, &labels).set(if status == AuthorityStatus::Available { 1.0 } else { 0.0 });
+            if authority.status != Some(status) {
+                if status == AuthorityStatus::Available {
+                    info!(workspace = self.workspace_name, authority = authority.name, "authority became available.");
+                } else {
+                    metrics::counter!(AUTHORITY_UNAVAILABLE_TOTAL, &labels).increment(1);
+                    warn!(
+                        workspace = self.workspace_name,
+                        authority = authority.name,
+                        status = ?status,
+                        "authority became unavailable."
+                    );
+                }
+            }
+
+            statuses.insert(authority.id, (status, latest_key_version));
+        }
+
+        let transaction = self.database_connection.begin_with_workspace_scope(&self.workspace_name).await?;
+        for mut authority in authorities {
+            if let Some((status, latest_key_version)) = statuses.remove(&authority.id) {
+                authority.update_status(status, latest_key_version);
+                authority.persist(&transaction).await?;
+            }
+        }
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip_all, fields(workspace_name = %self.workspace_name))]
+    async fn issue_bootstrap_token(&self, expires_in: Duration) -> Result<BootstrapTokenData> {
+        let expires_at = Utc::now() + expires_in;
+
+        let transaction = self.database_connection.begin_with_workspace_scope(&self.workspace_name).await?;
+        let token = self.authority_service.issue_bootstrap_token(&transaction, expires_at).await?;
+        transaction.commit().await?;
+
+        Ok(BootstrapTokenData { token, expires_at })
+    }
+
+    #[tracing::instrument(skip_all, fields(workspace_name = %self.workspace_name))]
+    async fn register_self(&self, bootstrap_token: &str, name: &str, host: &str) -> Result<SelfRegistrationData> {
+        let transaction = self.database_connection.begin_with_workspace_scope(&self.workspace_name).await?;
+
+        let mut bootstrap_token = self
+            .authority_service
+            .get_bootstrap_token(&transaction, bootstrap_token)
+            .await?
+            .filter(|bootstrap_token| bootstrap_token.is_valid())
+            .ok_or(Error::InvalidBootstrapToken)?;
+
+        let workspace = self
+            .workspace_service
+            .get_by_name(&transaction, &self.workspace_name)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("workspace({}) is not exists", self.workspace_name))?;
+        if !workspace.settings.is_authority_host_allowed(host) {
+            return Err(Error::HostNotAllowed { entered_host: host.to_owned() });
+        }
+
+        let authorities = self.authority_service.get_authorities(&transaction).await?;
+        let authority_id = if let Some(authority) = authorities.iter().find(|authority| authority.name == name) {
+            if authority.host != host {
+                return Err(Error::NameAlreadyInUse { entered_authority_name: name.to_owned() });
+            }
+            authority.id
+        } else if let Some(authority) = authorities.iter().find(|authority| authority.host == host) {
+            // The name of an authority is a part of its attribute names, so an authority cannot register itself under a
+            // name other than the one it is registered by.
+            return Err(Error::NameMismatch {
+                registered_authority_name: authority.name.clone(),
+                entered_authority_name: name.to_owned(),
+            });
+        } else {
+            self.authority_service.register_authority(&transaction, name, host).await?
+        };
+        bootstrap_token.bind(authority_id);
+        bootstrap_token.persist(&transaction).await?;
+
+        let mut authority = self.get_authority_model(&transaction, &authority_id).await?;
+        let credential = authority.issue_credential();
+        authority.persist(&transaction).await?;
+        let authority = self.get_authority_model(&transaction, &authority_id).await?;
+        transaction.commit().await?;
+
+        info!(workspace = self.workspace_name, authority = name, host, "authority registered itself.");
+
+        Ok(SelfRegistrationData { authority: authority.into(), credential })
+    }
+
+    #[tracing::instrument(skip_all, fields(workspace_name = %self.workspace_name))]
+    async fn refresh_own_public_keys(&self, credential: &str) -> Result<()> {
+        let transaction = self.database_connection.begin_with_workspace_scope(&self.workspace_name).await?;
+        let authority = self
+            .authority_service
+            .get_authority_by_credential(&transaction, credential)
+            .await?
+            .ok_or(Error::InvalidAuthorityCredential)?;
+        transaction.commit().await?;
+
+        self.refresh_public_keys_of(Some(authority.id)).await
+    }
 }
 
+const AUTHORITY_UP: &str = "authority_up";
+const AUTHORITY_UNAVAILABLE_TOTAL: &str = "authority_unavailable_total";
+
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error("Authority({entered_authority_id}) is not exists")]
     AuthorityNotExists { entered_authority_id: Ulid },
     #[error("Authority name is already in use")]
     NameAlreadyInUse { entered_authority_name: String },
+    #[error("Authority host({entered_host}) is not allowed in this workspace")]
+    HostNotAllowed { entered_host: String },
+    #[error("Bootstrap token is invalid, expired or already used")]
+    InvalidBootstrapToken,
+    #[error("Authority credential is invalid")]
+    InvalidAuthorityCredential,
+    #[error("Authority is registered as {registered_authority_name}, not {entered_authority_name}")]
+    NameMismatch { registered_authority_name: String, entered_authority_name: String },
     #[error(transparent)]
     Anyhow(#[from] anyhow::Error),
 }
@@ -138,15 +529,503 @@ impl From<DbErr> for Error {
     }
 }
 
+impl From<domain::workspace::Error> for Error {
+    fn from(value: domain::workspace::Error) -> Self {
+        match value {
+            domain::workspace::Error::Anyhow(e) => Self::Anyhow(e),
+            _ => Self::Anyhow(value.into()),
+        }
+    }
+}
+
 impl From<domain::authority::Error> for Error {
     fn from(value: domain::authority::Error) -> Self {
         match value {
             domain::authority::Error::NameAlreadyInUse { entered_authority_name } => {
                 Self::NameAlreadyInUse { entered_authority_name }
             }
+            domain::authority::Error::InvalidPublicKey(_) => Self::Anyhow(value.into()),
+            domain::authority::Error::BootstrapTokenAlreadyUsed => Self::InvalidBootstrapToken,
             domain::authority::Error::Anyhow(e) => Self::Anyhow(e),
         }
     }
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use base64::{engine::general_purpose::STANDARD, Engine as _};
+    use chrono::{Duration, Utc};
+    use metrics_exporter_prometheus::PrometheusBuilder;
+    use nebula_abe::{
+        curves::{bn462::Bn462Curve, Curve, PairingCurve},
+        schemes::isabella24::{AuthorityKeyPair, GlobalParams},
+    };
+    use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+    use ulid::Ulid;
+
+    use crate::{
+        database::{authority, authority_bootstrap_token, UlidId},
+        domain::{
+            authority::{
+                client::{FetchedPublicKey, MockAuthorityClient},
+                Authority, AuthorityPublicKey, AuthorityStatus, BootstrapToken, MockAuthorityService, PublicKeyStatus,
+            },
+            parameter::{MockParameterService, Parameter},
+            workspace::{MockWorkspaceService, Workspace},
+        },
+    };
+
+    use super::MAX_PUBLIC_KEY_REQUESTS_PER_REFRESH;
+
+    use super::{AuthorityUseCase, AuthorityUseCaseImpl, Error};
+
+    fn authority(id: Ulid, name: &str, host: &str) -> Authority {
+        let now = Utc::now();
+        authority::Model {
+            id: UlidId::new(id),
+            name: name.to_owned(),
+            host: host.to_owned(),
+            public_key: None,
+            status: None,
+            latest_key_version: None,
+            status_checked_at: None,
+            credential_hash: None,
+            created_at: now,
+            updated_at: now,
+        }
+        .into()
+    }
+
+    fn bootstrap_token(authority_id: Option<Ulid>, expires_at: chrono::DateTime<Utc>) -> BootstrapToken {
+        let now = Utc::now();
+        authority_bootstrap_token::Model {
+            id: UlidId::new(Ulid::new()),
+            token_hash: "hash".to_owned(),
+            authority_id: authority_id.map(UlidId::new),
+            expires_at,
+            created_at: now,
+            updated_at: now,
+        }
+        .into()
+    }
+
+    fn authority_usecase(
+        mock_authority_service: MockAuthorityService,
+        mock_workspace_service: MockWorkspaceService,
+    ) -> AuthorityUseCaseImpl {
+        let mock_database = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 1 }]);
+
+        AuthorityUseCaseImpl::new(
+            "testworkspace".to_owned(),
+            Arc::new(mock_database.into_connection()),
+            Arc::new(mock_workspace_service),
+            Arc::new(mock_authority_service),
+            Arc::new(MockParameterService::new()),
+            Arc::new(MockAuthorityClient::new()),
+        )
+    }
+
+    fn mock_workspace_service() -> MockWorkspaceService {
+        let mut mock_workspace_service = MockWorkspaceService::new();
+        mock_workspace_service
+            .expect_get_by_name()
+            .times(1)
+            .returning(|_, name| Ok(Some(Workspace::new(Ulid::new(), name.to_owned()))));
+        mock_workspace_service
+    }
+
+    #[tokio::test]
+    async fn when_registering_self_with_expired_bootstrap_token_then_authority_usecase_returns_invalid_token_err() {
+        let mut mock_authority_service = MockAuthorityService::new();
+        mock_authority_service
+            .expect_get_bootstrap_token()
+            .times(1)
+            .returning(|_, _| Ok(Some(bootstrap_token(None, Utc::now() - Duration::minutes(1)))));
+
+        let authority_usecase = authority_usecase(mock_authority_service, MockWorkspaceService::new());
+
+        let result = authority_usecase.register_self("token", "authority", "http://authority/").await;
+
+        assert!(matches!(result, Err(Error::InvalidBootstrapToken)));
+    }
+
+    #[tokio::test]
+    async fn when_refreshing_own_public_keys_with_unknown_credential_then_authority_usecase_returns_invalid_credential_err(
+    ) {
+        let mut mock_authority_service = MockAuthorityService::new();
+        mock_authority_service.expect_get_authority_by_credential().times(1).returning(|_, _| Ok(None));
+
+        let authority_usecase = authority_usecase(mock_authority_service, MockWorkspaceService::new());
+
+        let result = authority_usecase.refresh_own_public_keys("credential").await;
+
+        assert!(matches!(result, Err(Error::InvalidAuthorityCredential)));
+    }
+
+    #[tokio::test]
+    async fn when_registering_self_with_used_bootstrap_token_then_authority_usecase_returns_invalid_token_err() {
+        let authority_id = Ulid::new();
+        let mut mock_authority_service = MockAuthorityService::new();
+        mock_authority_service
+            .expect_get_bootstrap_token()
+            .times(1)
+            .returning(move |_, _| Ok(Some(bootstrap_token(Some(authority_id), Utc::now() + Duration::days(1)))));
+        mock_authority_service.expect_get_authorities().never();
+
+        let authority_usecase = authority_usecase(mock_authority_service, MockWorkspaceService::new());
+
+        let result = authority_usecase.register_self("token", "authority", "http://other-authority/").await;
+
+        assert!(matches!(result, Err(Error::InvalidBootstrapToken)));
+    }
+
+    #[tokio::test]
+    async fn when_registering_self_with_bootstrap_token_then_authority_usecase_issues_credential() {
+        let authority_id = Ulid::new();
+        let mut mock_authority_service = MockAuthorityService::new();
+        mock_authority_service
+            .expect_get_bootstrap_token()
+            .times(1)
+            .returning(|_, _| Ok(Some(bootstrap_token(None, Utc::now() + Duration::days(1)))));
+        mock_authority_service.expect_get_authorities().times(1).returning(|_| Ok(vec![]));
+        mock_authority_service.expect_register_authority().times(1).returning(move |_, _, _| Ok(authority_id));
+        mock_authority_service
+            .expect_get_authority()
+            .times(2)
+            .returning(move |_, _| Ok(Some(authority(authority_id, "authority", "http://authority/"))));
+        let mock_database = MockDatabase::new(DatabaseBackend::Postgres).append_exec_results(
+            (0..3).map(|_| MockExecResult { last_insert_id: 0, rows_affected: 1 }).collect::<Vec<_>>(),
+        );
+        let authority_usecase = AuthorityUseCaseImpl::new(
+            "testworkspace".to_owned(),
+            Arc::new(mock_database.into_connection()),
+            Arc::new(mock_workspace_service()),
+            Arc::new(mock_authority_service),
+            Arc::new(MockParameterService::new()),
+            Arc::new(MockAuthorityClient::new()),
+        );
+
+        let result = authority_usecase.register_self("token", "authority", "http://authority/").await;
+
+        let registration = result.unwrap();
+        assert_eq!(registration.authority.id, authority_id);
+        assert!(!registration.credential.is_empty());
+    }
+
+    #[tokio::test]
+    async fn when_bootstrap_token_is_used_by_concurrent_registration_then_authority_usecase_returns_invalid_token_err()
+    {
+        let authority_id = Ulid::new();
+        let mut mock_authority_service = MockAuthorityService::new();
+        mock_authority_service
+            .expect_get_bootstrap_token()
+            .times(1)
+            .returning(|_, _| Ok(Some(bootstrap_token(None, Utc::now() + Duration::days(1)))));
+        mock_authority_service.expect_get_authorities().times(1).returning(|_| Ok(vec![]));
+        mock_authority_service.expect_register_authority().times(1).returning(move |_, _, _| Ok(authority_id));
+        mock_authority_service.expect_get_authority().never();
+        // The other registration has bound the token after it is read, so binding it updates no row.
+        let mock_database = MockDatabase::new(DatabaseBackend::Postgres).append_exec_results([
+            MockExecResult { last_insert_id: 0, rows_affected: 1 },
+            MockExecResult { last_insert_id: 0, rows_affected: 0 },
+        ]);
+        let authority_usecase = AuthorityUseCaseImpl::new(
+            "testworkspace".to_owned(),
+            Arc::new(mock_database.into_connection()),
+            Arc::new(mock_workspace_service()),
+            Arc::new(mock_authority_service),
+            Arc::new(MockParameterService::new()),
+            Arc::new(MockAuthorityClient::new()),
+        );
+
+        let result = authority_usecase.register_self("token", "authority", "http://leaked-token-user/").await;
+
+        assert!(matches!(result, Err(Error::InvalidBootstrapToken)));
+    }
+
+    #[tokio::test]
+    async fn when_registering_self_at_host_of_authority_with_other_name_then_authority_usecase_returns_name_mismatch_err(
+    ) {
+        let mut mock_authority_service = MockAuthorityService::new();
+        mock_authority_service
+            .expect_get_bootstrap_token()
+            .times(1)
+            .returning(|_, _| Ok(Some(bootstrap_token(None, Utc::now() + Duration::days(1)))));
+        mock_authority_service
+            .expect_get_authorities()
+            .times(1)
+            .returning(|_| Ok(vec![authority(Ulid::new(), "authority", "http://authority/")]));
+        mock_authority_service.expect_register_authority().never();
+
+        let authority_usecase = authority_usecase(mock_authority_service, mock_workspace_service());
+
+        let result = authority_usecase.register_self("token", "renamed", "http://authority/").await;
+
+        assert!(matches!(result, Err(Error::NameMismatch { .. })));
+    }
+
+    #[tokio::test]
+    async fn when_registering_self_under_name_in_use_at_other_host_then_authority_usecase_returns_name_in_use_err() {
+        let mut mock_authority_service = MockAuthorityService::new();
+        mock_authority_service
+            .expect_get_bootstrap_token()
+            .times(1)
+            .returning(|_, _| Ok(Some(bootstrap_token(None, Utc::now() + Duration::days(1)))));
+        mock_authority_service
+            .expect_get_authorities()
+            .times(1)
+            .returning(|_| Ok(vec![authority(Ulid::new(), "authority", "http://authority/")]));
+        mock_authority_service.expect_register_authority().never();
+
+        let authority_usecase = authority_usecase(mock_authority_service, mock_workspace_service());
+
+        let result = authority_usecase.register_self("token", "authority", "http://other-authority/").await;
+
+        assert!(matches!(result, Err(Error::NameAlreadyInUse { .. })));
+    }
+
+    fn fetched_public_key(version: u64, status: PublicKeyStatus) -> FetchedPublicKey {
+        let mut rng = <Bn462Curve as PairingCurve>::Rng::new();
+        rng.seed(&[0u8; 64]);
+        let gp = GlobalParams::<Bn462Curve>::new(&mut rng);
+        let key_pair = AuthorityKeyPair::new(&mut rng, &gp, format!("authority-testworkspace#{version}"));
+
+        FetchedPublicKey {
+            public_key: STANDARD.encode(rmp_serde::to_vec(&key_pair.pk).unwrap()),
+            version,
+            parameter_version: 1,
+            curve: Curve::Bn462,
+            status,
+        }
+    }
+
+    fn cached_public_key(authority_id: Ulid, version: u64, status: PublicKeyStatus) -> AuthorityPublicKey {
+        let fetched = fetched_public_key(version, PublicKeyStatus::Active);
+        AuthorityPublicKey {
+            authority_id,
+            version,
+            parameter_version: fetched.parameter_version,
+            curve: fetched.curve,
+            public_key: fetched.public_key,
+            status,
+            status_checked_at: None,
+        }
+    }
+
+    fn authority_usecase_with_client(mock_authority_client: MockAuthorityClient) -> AuthorityUseCaseImpl {
+        AuthorityUseCaseImpl::new(
+            "testworkspace".to_owned(),
+            Arc::new(MockDatabase::new(DatabaseBackend::Postgres).into_connection()),
+            Arc::new(MockWorkspaceService::new()),
+            Arc::new(MockAuthorityService::new()),
+            Arc::new(MockParameterService::new()),
+            Arc::new(mock_authority_client),
+        )
+    }
+
+    fn parameters() -> Vec<Parameter> {
+        vec![Parameter { version: 1, curve: Curve::Bn462, value: vec![] }]
+    }
+
+    #[tokio::test]
+    async fn when_authority_reports_excessive_version_then_authority_usecase_fetches_public_keys_up_to_limit() {
+        let authority = authority(Ulid::new(), "authority", "http://authority/");
+        let mut mock_authority_client = MockAuthorityClient::new();
+        mock_authority_client.expect_get_public_key().withf(|_, _, version| version.is_none()).times(1).returning(
+            |_, _, _| Ok(FetchedPublicKey { version: 1_000_000, ..fetched_public_key(1, PublicKeyStatus::Active) }),
+        );
+        mock_authority_client
+            .expect_get_public_key()
+            .withf(|_, _, version| version.is_some())
+            .times(MAX_PUBLIC_KEY_REQUESTS_PER_REFRESH)
+            .returning(|_, _, version| Ok(fetched_public_key(version.unwrap(), PublicKeyStatus::Active)));
+
+        let (public_keys, _) = authority_usecase_with_client(mock_authority_client)
+            .fetch_public_keys(&authority, &[], &parameters())
+            .await
+            .unwrap();
+
+        assert_eq!(public_keys.len(), MAX_PUBLIC_KEY_REQUESTS_PER_REFRESH);
+        assert_eq!(public_keys.last().unwrap().version, MAX_PUBLIC_KEY_REQUESTS_PER_REFRESH as u64);
+    }
+
+    #[tokio::test]
+    async fn when_fetching_public_keys_then_authority_usecase_refreshes_status_of_unrevoked_cached_versions() {
+        let authority = authority(Ulid::new(), "authority", "http://authority/");
+        let revoked = cached_public_key(authority.id, 1, PublicKeyStatus::Revoked);
+        let active = cached_public_key(authority.id, 2, PublicKeyStatus::Active);
+        let mut mock_authority_client = MockAuthorityClient::new();
+        mock_authority_client
+            .expect_get_public_key()
+            .withf(|_, _, version| version.is_none())
+            .times(1)
+            .returning(|_, _, _| Ok(fetched_public_key(3, PublicKeyStatus::Active)));
+        mock_authority_client
+            .expect_get_public_key()
+            .withf(|_, _, version| *version == Some(2))
+            .times(1)
+            .returning(|_, _, _| Ok(fetched_public_key(2, PublicKeyStatus::Revoked)));
+
+        let (public_keys, _) = authority_usecase_with_client(mock_authority_client)
+            .fetch_public_keys(&authority, &[&revoked, &active], &parameters())
+            .await
+            .unwrap();
+
+        let statuses: Vec<_> = public_keys.iter().map(|public_key| (public_key.version, public_key.status)).collect();
+        assert_eq!(statuses, vec![(3, PublicKeyStatus::Active), (2, PublicKeyStatus::Revoked)]);
+    }
+
+    #[tokio::test]
+    async fn when_authority_serves_other_version_than_requested_then_authority_usecase_fails_to_fetch_public_keys() {
+        let authority = authority(Ulid::new(), "authority", "http://authority/");
+        let mut mock_authority_client = MockAuthorityClient::new();
+        mock_authority_client
+            .expect_get_public_key()
+            .withf(|_, _, version| version.is_none())
+            .times(1)
+            .returning(|_, _, _| Ok(fetched_public_key(2, PublicKeyStatus::Active)));
+        mock_authority_client
+            .expect_get_public_key()
+            .withf(|_, _, version| *version == Some(1))
+            .times(1)
+            .returning(|_, _, _| Ok(fetched_public_key(2, PublicKeyStatus::Active)));
+
+        let result = authority_usecase_with_client(mock_authority_client)
+            .fetch_public_keys(&authority, &[], &parameters())
+            .await;
+
+        assert!(result.is_err());
+    }
+
+    /// Authority last checked with `status` and `latest_key_version`.
+    fn checked_authority(status: AuthorityStatus, latest_key_version: u64) -> Authority {
+        let now = Utc::now();
+        authority::Model {
+            id: UlidId::new(Ulid::new()),
+            name: "authority".to_owned(),
+            host: "http://authority/".to_owned(),
+            public_key: None,
+            status: Some(status.into()),
+            latest_key_version: Some(latest_key_version as i64),
+            status_checked_at: Some(now),
+            credential_hash: None,
+            created_at: now,
+            updated_at: now,
+        }
+        .into()
+    }
+
+    /// Checks the health of the authority, and returns the rendered metrics and the transaction log.
+    fn check_health(authority: Authority, mock_authority_client: MockAuthorityClient) -> (String, String) {
+        let mut authority = Some(authority);
+        let mut mock_authority_service = MockAuthorityService::new();
+        mock_authority_service
+            .expect_get_authorities()
+            .times(1)
+            .returning(move |_| Ok(authority.take().into_iter().collect()));
+        let mock_database = MockDatabase::new(DatabaseBackend::Postgres).append_exec_results(
+            (0..3).map(|_| MockExecResult { last_insert_id: 0, rows_affected: 1 }).collect::<Vec<_>>(),
+        );
+        let connection = Arc::new(mock_database.into_connection());
+        let authority_usecase = AuthorityUseCaseImpl::new(
+            "testworkspace".to_owned(),
+            connection.clone(),
+            Arc::new(MockWorkspaceService::new()),
+            Arc::new(mock_authority_service),
+            Arc::new(MockParameterService::new()),
+            Arc::new(mock_authority_client),
+        );
+        let recorder = PrometheusBuilder::new().build_recorder();
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+
+        // The recorder is local to the thread, on which the current thread runtime runs the check.
+        metrics::with_local_recorder(&recorder, || runtime.block_on(authority_usecase.check_health())).unwrap();
+
+        drop(authority_usecase);
+        let connection = Arc::try_unwrap(connection).unwrap_or_else(|_| panic!("connection should not be shared"));
+        (recorder.handle().render(), format!("{:?}", connection.into_transaction_log()))
+    }
+
+    const AUTHORITY_UP_METRIC: &str = r#"authority_up{workspace="testworkspace",authority="authority"}"#;
+    const AUTHORITY_UNAVAILABLE_TOTAL_METRIC: &str =
+        r#"authority_unavailable_total{workspace="testworkspace",authority="authority"}"#;
+
+    #[test]
+    fn when_available_authority_is_ready_then_authority_usecase_records_its_latest_key_version() {
+        let mut mock_authority_client = MockAuthorityClient::new();
+        mock_authority_client.expect_get_status().times(1).returning(|_| Ok(AuthorityStatus::Available));
+        mock_authority_client
+            .expect_get_public_key()
+            .withf(|_, _, version| version.is_none())
+            .times(1)
+            .returning(|_, _, _| Ok(fetched_public_key(3, PublicKeyStatus::Active)));
+
+        let (metrics, transaction_log) =
+            check_health(checked_authority(AuthorityStatus::Available, 2), mock_authority_client);
+
+        assert!(metrics.contains(&format!("{AUTHORITY_UP_METRIC} 1")));
+        assert!(!metrics.contains(AUTHORITY_UNAVAILABLE_TOTAL_METRIC));
+        assert!(transaction_log.contains(r#""AVAILABLE""#));
+        assert!(transaction_log.contains("BigInt(Some(3))"));
+    }
+
+    #[test]
+    fn when_available_authority_becomes_not_ready_then_authority_usecase_records_it_unavailable() {
+        let mut mock_authority_client = MockAuthorityClient::new();
+        mock_authority_client.expect_get_status().times(1).returning(|_| Ok(AuthorityStatus::Unavailable));
+        mock_authority_client.expect_get_public_key().never();
+
+        let (metrics, transaction_log) =
+            check_health(checked_authority(AuthorityStatus::Available, 2), mock_authority_client);
+
+        assert!(metrics.contains(&format!("{AUTHORITY_UP_METRIC} 0")));
+        assert!(metrics.contains(&format!("{AUTHORITY_UNAVAILABLE_TOTAL_METRIC} 1")));
+        assert!(transaction_log.contains(r#""UNAVAILABLE""#));
+        // The latest key version is kept while the authority can't tell it.
+        assert!(transaction_log.contains("BigInt(Some(2))"));
+    }
+
+    #[test]
+    fn when_available_authority_becomes_armored_then_authority_usecase_records_it_armored() {
+        let mut mock_authority_client = MockAuthorityClient::new();
+        mock_authority_client.expect_get_status().times(1).returning(|_| Ok(AuthorityStatus::Armored));
+        mock_authority_client.expect_get_public_key().never();
+
+        let (metrics, transaction_log) =
+            check_health(checked_authority(AuthorityStatus::Available, 2), mock_authority_client);
+
+        assert!(metrics.contains(&format!("{AUTHORITY_UP_METRIC} 0")));
+        assert!(metrics.contains(&format!("{AUTHORITY_UNAVAILABLE_TOTAL_METRIC} 1")));
+        assert!(transaction_log.contains(r#""ARMORED""#));
+    }
+
+    #[test]
+    fn when_authority_host_is_unreachable_then_authority_usecase_records_it_unavailable() {
+        let mut mock_authority_client = MockAuthorityClient::new();
+        mock_authority_client.expect_get_status().times(1).returning(|_| Err(anyhow::anyhow!("connection refused")));
+        mock_authority_client.expect_get_public_key().never();
+
+        let (metrics, transaction_log) =
+            check_health(checked_authority(AuthorityStatus::Available, 2), mock_authority_client);
+
+        assert!(metrics.contains(&format!("{AUTHORITY_UP_METRIC} 0")));
+        assert!(metrics.contains(&format!("{AUTHORITY_UNAVAILABLE_TOTAL_METRIC} 1")));
+        assert!(transaction_log.contains(r#""UNAVAILABLE""#));
+    }
+
+    #[test]
+    fn when_unavailable_authority_stays_unavailable_then_authority_usecase_does_not_count_it_again() {
+        let mut mock_authority_client = MockAuthorityClient::new();
+        mock_authority_client.expect_get_status().times(1).returning(|_| Err(anyhow::anyhow!("connection refused")));
+
+        let (metrics, _) = check_health(checked_authority(AuthorityStatus::Unavailable, 2), mock_authority_client);
+
+        assert!(metrics.contains(&format!("{AUTHORITY_UP_METRIC} 0")));
+        assert!(!metrics.contains(AUTHORITY_UNAVAILABLE_TOTAL_METRIC));
+    }
+}
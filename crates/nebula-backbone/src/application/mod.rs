@@ -1,19 +1,25 @@
-use std::{sync::Arc, time::Duration};
+use std::{future::Future, sync::Arc, time::Duration};
 
 use anyhow::bail;
+use nebula_telemetry::health::Readiness;
 use nebula_token::auth::jwks_discovery::{CachedRemoteJwksDiscovery, JwksDiscovery};
 use parameter::{ParameterUseCase, ParameterUseCaseImpl};
 use sea_orm::{DatabaseConnection, TransactionTrait};
+use tracing::{error, info};
 
 use crate::{
-    config::{ApplicationConfig, WorkspaceConfig},
-    database::{self, connect_to_database, AuthMethod},
+    config::{ApplicationConfig, DatabaseConfig, PostgresConfig, QuotaConfig, WorkspaceConfig},
+    database::{self, connect_to_database, AuthMethod, DatabaseLocation},
     domain::{
-        authority::{AuthorityService, PostgresAuthorityService},
+        authority::{
+            client::{AuthorityClient, HttpAuthorityClient},
+            AuthorityService, PostgresAuthorityService,
+        },
         parameter::{ParameterService, PostgresParameterService},
         policy::{PolicyService, PostgresPolicyService},
+        role::{PostgresRoleService, RoleService},
         secret::{PostgresSecretService, SecretService},
-        workspace::{WorkspaceService, WorkspaceServiceImpl},
+        workspace::{WorkspaceQuota, WorkspaceService, WorkspaceServiceImpl},
     },
 };
 
@@ -24,6 +30,7 @@ use self::{
     database::WorkspaceScopedTransaction,
     path::{PathUseCase, PathUseCaseImpl},
     policy::{PolicyUseCase, PolicyUseCaseImpl},
+    role::{RolePermissionCache, RoleUseCase, RoleUseCaseImpl},
     secret::{SecretUseCase, SecretUseCaseImpl},
 };
 
@@ -31,17 +38,23 @@ pub(crate) mod authority;
 pub(crate) mod parameter;
 pub(crate) mod path;
 pub(crate) mod policy;
+pub(crate) mod role;
 pub(crate) mod secret;
 pub(crate) mod workspace;
 
+#[derive(Clone)]
 pub(crate) struct Application {
     database_connection: Arc<DatabaseConnection>,
     workspace_service: Arc<WorkspaceServiceImpl>,
     secret_service: Arc<dyn SecretService + Sync + Send>,
     parameter_service: Arc<dyn ParameterService + Sync + Send>,
     policy_service: Arc<dyn PolicyService + Sync + Send>,
+    role_service: Arc<dyn RoleService + Sync + Send>,
+    role_permission_cache: Arc<RolePermissionCache>,
     authority_service: Arc<dyn AuthorityService + Sync + Send>,
+    authority_client: Arc<dyn AuthorityClient + Sync + Send>,
     jwks_discovery: Arc<dyn JwksDiscovery + Send + Sync>,
+    default_quota: WorkspaceQuota,
 }
 
 impl Application {
@@ -51,6 +64,7 @@ impl Application {
             self.workspace_service.clone(),
             self.secret_service.clone(),
             self.parameter_service.clone(),
+            self.policy_service.clone(),
         )
     }
 
@@ -58,25 +72,70 @@ impl Application {
         ApplicationWithWorkspace {
             workspace_name: workspace_name.to_owned(),
             database_connection: self.database_connection.clone(),
+            workspace_service: self.workspace_service.clone(),
             secret_service: self.secret_service.clone(),
             parameter_service: self.parameter_service.clone(),
             policy_service: self.policy_service.clone(),
+            role_service: self.role_service.clone(),
+            role_permission_cache: self.role_permission_cache.clone(),
             authority_service: self.authority_service.clone(),
+            authority_client: self.authority_client.clone(),
+            default_quota: self.default_quota,
         }
     }
 
     pub fn jwks_discovery(&self) -> Arc<dyn JwksDiscovery + Sync + Send> {
         self.jwks_discovery.clone()
     }
+
+    pub async fn readiness(&self) -> Readiness {
+        Readiness::new()
+            .check("database", self.database_connection.ping().await.is_ok())
+            .check("jwks", self.jwks_discovery.jwks().await.is_ok())
+    }
+
+    pub async fn close(&self) {
+        database::close_database(&self.database_connection).await;
+    }
+}
+
+#[cfg(test)]
+impl Application {
+    /// Application on a mock database with the Postgres services, which verifies tokens with the JWKS discovery.
+    pub(crate) fn with_mock_database(jwks_discovery: Arc<dyn JwksDiscovery + Send + Sync>) -> Self {
+        let database_connection =
+            Arc::new(sea_orm::MockDatabase::new(sea_orm::DatabaseBackend::Postgres).into_connection());
+        Self {
+            workspace_service: Arc::new(WorkspaceServiceImpl::new(
+                database_connection.clone(),
+                DatabaseLocation::Sqlite { path: std::env::temp_dir() },
+            )),
+            database_connection,
+            secret_service: Arc::new(PostgresSecretService {}),
+            parameter_service: Arc::new(PostgresParameterService),
+            policy_service: Arc::new(PostgresPolicyService {}),
+            role_service: Arc::new(PostgresRoleService {}),
+            role_permission_cache: Arc::new(RolePermissionCache::new(ROLE_PERMISSION_CACHE_TTL)),
+            authority_service: Arc::new(PostgresAuthorityService {}),
+            authority_client: Arc::new(crate::domain::authority::client::MockAuthorityClient::new()),
+            jwks_discovery,
+            default_quota: WorkspaceQuota::default(),
+        }
+    }
 }
 
 pub(crate) struct ApplicationWithWorkspace {
     workspace_name: String,
     database_connection: Arc<DatabaseConnection>,
+    workspace_service: Arc<WorkspaceServiceImpl>,
     secret_service: Arc<dyn SecretService + Sync + Send>,
     parameter_service: Arc<dyn ParameterService + Sync + Send>,
     policy_service: Arc<dyn PolicyService + Sync + Send>,
+    role_service: Arc<dyn RoleService + Sync + Send>,
+    role_permission_cache: Arc<RolePermissionCache>,
     authority_service: Arc<dyn AuthorityService + Sync + Send>,
+    authority_client: Arc<dyn AuthorityClient + Sync + Send>,
+    default_quota: WorkspaceQuota,
 }
 
 impl ApplicationWithWorkspace {
@@ -84,8 +143,11 @@ impl ApplicationWithWorkspace {
         SecretUseCaseImpl::new(
             self.workspace_name.to_owned(),
             self.database_connection.clone(),
+            self.workspace_service.clone(),
             self.secret_service.clone(),
+            self.parameter_service.clone(),
             self.policy_service.clone(),
+            self.default_quota,
         )
     }
 
@@ -105,11 +167,22 @@ impl ApplicationWithWorkspace {
         )
     }
 
+    pub fn role(&self) -> impl RoleUseCase {
+        RoleUseCaseImpl::new(
+            self.workspace_name.to_owned(),
+            self.database_connection.clone(),
+            self.role_service.clone(),
+            self.role_permission_cache.clone(),
+        )
+    }
+
     pub fn path(&self) -> impl PathUseCase {
         PathUseCaseImpl::new(
             self.workspace_name.to_owned(),
             self.database_connection.clone(),
+            self.workspace_service.clone(),
             self.secret_service.clone(),
+            self.default_quota,
         )
     }
 
@@ -117,7 +190,10 @@ impl ApplicationWithWorkspace {
         AuthorityUseCaseImpl::new(
             self.workspace_name.to_owned(),
             self.database_connection.clone(),
+            self.workspace_service.clone(),
             self.authority_service.clone(),
+            self.parameter_service.clone(),
+            self.authority_client.clone(),
         )
     }
 }
@@ -125,27 +201,28 @@ impl ApplicationWithWorkspace {
 pub(super) async fn init(config: &ApplicationConfig) -> anyhow::Result<Application> {
     let database_connection = init_database_connection(config).await?;
 
-    let jwks_discovery: Arc<dyn JwksDiscovery + Send + Sync> =
-        if let Some(refresh_interval) = config.jwks_refresh_interval {
-            Arc::new(CachedRemoteJwksDiscovery::new(config.jwks_url.clone(), Duration::from_secs(refresh_interval)))
-        } else {
-            Arc::new(CachedRemoteJwksDiscovery::new(config.jwks_url.clone(), Duration::from_secs(10)))
-        };
-
-    let workspace_service = Arc::new(WorkspaceServiceImpl::new(
-        database_connection.clone(),
-        config.database.host.to_owned(),
-        config.database.port,
-        config.database.database_name.to_owned(),
-        create_database_auth_method(config),
-    ));
+    let mut jwks_discovery = if let Some(refresh_interval) = config.jwks_refresh_interval {
+        CachedRemoteJwksDiscovery::new(config.jwks_url.clone(), Duration::from_secs(refresh_interval))
+    } else {
+        CachedRemoteJwksDiscovery::new(config.jwks_url.clone(), Duration::from_secs(10))
+    };
+    if let Some(tls) = &config.jwks_tls {
+        jwks_discovery = jwks_discovery.client(tls.client()?);
+    }
+    let jwks_discovery: Arc<dyn JwksDiscovery + Send + Sync> = Arc::new(jwks_discovery);
+
+    let workspace_service =
+        Arc::new(WorkspaceServiceImpl::new(database_connection.clone(), create_database_location(config)));
     let secret_service = Arc::new(PostgresSecretService {});
     let parameter_service = Arc::new(PostgresParameterService);
     let policy_service = Arc::new(PostgresPolicyService {});
+    let role_service = Arc::new(PostgresRoleService {});
     let authority_service = Arc::new(PostgresAuthorityService {});
+    let authority_client =
+        Arc::new(HttpAuthorityClient::new(AUTHORITY_REQUEST_TIMEOUT, config.authority_tls.as_ref())?);
     database::migrate(database_connection.as_ref()).await?;
     match config.workspace {
-        WorkspaceConfig::Static { ref name } => {
+        WorkspaceConfig::Static { ref name, curve } => {
             let transaction = database_connection.begin_with_workspace_scope(name).await?;
             match workspace_service.create(&transaction, name).await {
                 Ok(_) | Err(crate::domain::workspace::Error::WorkspaceNameConflicted) => {}
@@ -155,7 +232,7 @@ pub(super) async fn init(config: &ApplicationConfig) -> anyhow::Result<Applicati
                 }
             }
 
-            match parameter_service.create(&transaction).await {
+            match parameter_service.create(&transaction, curve).await {
                 Ok(_) | Err(crate::domain::parameter::Error::ParameterAlreadyCreated(_)) => {
                     transaction.commit().await?;
                 }
@@ -166,46 +243,134 @@ pub(super) async fn init(config: &ApplicationConfig) -> anyhow::Result<Applicati
             }
         }
         WorkspaceConfig::Dynamic => {
-            database::migrate_all_workspaces(
-                &database_connection.begin().await?,
-                &config.database.host,
-                config.database.port,
-                &config.database.database_name,
-                &create_database_auth_method(config),
-            )
-            .await?;
+            database::migrate_all_workspaces(&database_connection.begin().await?, &create_database_location(config))
+                .await?;
         }
     }
 
-    Ok(Application {
+    let application = Application {
         database_connection,
         workspace_service,
         secret_service,
         parameter_service,
         policy_service,
+        role_service,
+        role_permission_cache: Arc::new(RolePermissionCache::new(ROLE_PERMISSION_CACHE_TTL)),
         authority_service,
+        authority_client,
         jwks_discovery,
-    })
+        default_quota: config.quota.clone().map(WorkspaceQuota::from).unwrap_or_default(),
+    };
+
+    let grace_period = Duration::from_secs(
+        config.workspace_deletion_grace_period.unwrap_or(DEFAULT_WORKSPACE_DELETION_GRACE_PERIOD_SECONDS),
+    );
+    purge_deleted_workspaces_periodically(application.workspace(), grace_period);
+
+    let refresh_interval = Duration::from_secs(
+        config.authority_public_key_refresh_interval.unwrap_or(DEFAULT_AUTHORITY_PUBLIC_KEY_REFRESH_INTERVAL_SECONDS),
+    );
+    run_for_each_workspace_periodically(
+        application.clone(),
+        refresh_interval,
+        "refresh authority public keys",
+        |application| async move { application.authority().refresh_public_keys().await },
+    );
+
+    let health_check_interval = Duration::from_secs(
+        config.authority_health_check_interval.unwrap_or(DEFAULT_AUTHORITY_HEALTH_CHECK_INTERVAL_SECONDS),
+    );
+    run_for_each_workspace_periodically(
+        application.clone(),
+        health_check_interval,
+        "check authority health",
+        |application| async move { application.authority().check_health().await },
+    );
+
+    Ok(application)
+}
+
+const DEFAULT_WORKSPACE_DELETION_GRACE_PERIOD_SECONDS: u64 = 7 * 24 * 60 * 60;
+const WORKSPACE_PURGE_INTERVAL: Duration = Duration::from_secs(600);
+const DEFAULT_AUTHORITY_PUBLIC_KEY_REFRESH_INTERVAL_SECONDS: u64 = 300;
+const DEFAULT_AUTHORITY_HEALTH_CHECK_INTERVAL_SECONDS: u64 = 30;
+const AUTHORITY_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+const ROLE_PERMISSION_CACHE_TTL: Duration = Duration::from_secs(10);
+
+fn purge_deleted_workspaces_periodically(
+    workspace_use_case: impl WorkspaceUseCase + Send + Sync + 'static,
+    grace_period: Duration,
+) {
+    tokio::spawn(async move {
+        loop {
+            match workspace_use_case.purge_deleted(grace_period).await {
+                Ok(purged_workspace_names) if !purged_workspace_names.is_empty() => {
+                    info!("deleted workspaces({}) are purged.", purged_workspace_names.join(", "));
+                }
+                Ok(_) => {}
+                Err(e) => error!(error = %e, "failed to purge deleted workspaces."),
+            }
+            tokio::time::sleep(WORKSPACE_PURGE_INTERVAL).await;
+        }
+    });
+}
+
+/// Runs the task for each workspace at the interval. A failure of the task in a workspace does not affect the others.
+fn run_for_each_workspace_periodically<F, Fut>(application: Application, interval: Duration, task: &'static str, run: F)
+where
+    F: Fn(ApplicationWithWorkspace) -> Fut + Send + 'static,
+    Fut: Future<Output = authority::Result<()>> + Send,
+{
+    tokio::spawn(async move {
+        loop {
+            match application.workspace().get_all().await {
+                Ok(workspaces) => {
+                    for workspace in workspaces {
+                        if let Err(e) = run(application.with_workspace(&workspace.name)).await {
+                            error!(error = %e, workspace = workspace.name, "failed to {task}.");
+                        }
+                    }
+                }
+                Err(e) => error!(error = %e, "failed to get workspaces to {task}."),
+            }
+            tokio::time::sleep(interval).await;
+        }
+    });
 }
 
 async fn init_database_connection(config: &ApplicationConfig) -> anyhow::Result<Arc<DatabaseConnection>> {
-    let database_host = &config.database.host;
-    let database_port = config.database.port;
-    let database_name = &config.database.database_name;
-    let auth_method = create_database_auth_method(config);
+    connect_to_database(&create_database_location(config)).await
+}
+
+impl From<QuotaConfig> for WorkspaceQuota {
+    fn from(value: QuotaConfig) -> Self {
+        Self {
+            max_secrets: value.max_secrets,
+            max_ciphertext_bytes: value.max_ciphertext_bytes,
+            max_paths: value.max_paths,
+        }
+    }
+}
 
-    connect_to_database(database_host, database_port, database_name, &auth_method).await
+fn create_database_location(config: &ApplicationConfig) -> DatabaseLocation {
+    match &config.database {
+        DatabaseConfig::Postgres(postgres) => DatabaseLocation::Postgres {
+            host: postgres.host.to_owned(),
+            port: postgres.port,
+            database_name: postgres.database_name.to_owned(),
+            auth: create_database_auth_method(postgres),
+        },
+        DatabaseConfig::Sqlite { path } => DatabaseLocation::Sqlite { path: path.to_owned() },
+    }
 }
 
-fn create_database_auth_method(config: &ApplicationConfig) -> AuthMethod {
-    match &config.database.auth {
+fn create_database_auth_method(config: &PostgresConfig) -> AuthMethod {
+    match &config.auth {
         crate::config::DatabaseAuthConfig::Credential { username, password } => {
             AuthMethod::Credential { username: username.to_owned(), password: password.to_owned() }
         }
-        crate::config::DatabaseAuthConfig::RdsIamAuth { username } => AuthMethod::RdsIamAuth {
-            host: config.database.host.to_owned(),
-            port: config.database.port,
-            username: username.to_owned(),
-        },
+        crate::config::DatabaseAuthConfig::RdsIamAuth { username } => {
+            AuthMethod::RdsIamAuth { host: config.host.to_owned(), port: config.port, username: username.to_owned() }
+        }
     }
 }
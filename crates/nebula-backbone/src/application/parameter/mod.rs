@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use nebula_abe::{curves::bn462::Bn462Curve, schemes::isabella24::GlobalParams};
+use nebula_abe::curves::Curve;
 use sea_orm::DatabaseConnection;
 
 use crate::{
@@ -14,8 +14,11 @@ use crate::{
 
 #[async_trait]
 pub(crate) trait ParameterUseCase {
-    async fn create(&self) -> Result<ParameterData>;
-    async fn get(&self) -> Result<ParameterData>;
+    async fn create(&self, curve: Curve) -> Result<ParameterData>;
+    /// Adds the next version of the parameter. The curve of the latest version is used unless it is given.
+    async fn add_version(&self, curve: Option<Curve>) -> Result<ParameterData>;
+    async fn get(&self, version: Option<i32>) -> Result<ParameterData>;
+    async fn get_all(&self) -> Result<Vec<ParameterData>>;
 }
 
 pub(crate) struct ParameterUseCaseImpl {
@@ -36,26 +39,52 @@ impl ParameterUseCaseImpl {
 
 #[async_trait]
 impl ParameterUseCase for ParameterUseCaseImpl {
-    async fn create(&self) -> Result<ParameterData> {
+    #[tracing::instrument(skip_all, fields(workspace_name = %self.workspace_name))]
+    async fn create(&self, curve: Curve) -> Result<ParameterData> {
         let transaction = self.database_connection.begin_with_workspace_scope(&self.workspace_name).await?;
-        let parameter = self.parameter_service.create(&transaction).await.map_err(Error::GetParameterFailed)?;
+        let parameter = self.parameter_service.create(&transaction, curve).await.map_err(Error::GetParameterFailed)?;
         transaction.commit().await?;
 
         Ok(parameter.into())
     }
 
-    async fn get(&self) -> Result<ParameterData> {
+    #[tracing::instrument(skip_all, fields(workspace_name = %self.workspace_name))]
+    async fn add_version(&self, curve: Option<Curve>) -> Result<ParameterData> {
         let transaction = self.database_connection.begin_with_workspace_scope(&self.workspace_name).await?;
-        let parameter = self.parameter_service.get(&transaction).await.map_err(Error::GetParameterFailed)?;
+        let curve = match curve {
+            Some(curve) => curve,
+            None => self.parameter_service.get(&transaction, None).await.map_err(Error::GetParameterFailed)?.curve,
+        };
+        let parameter =
+            self.parameter_service.add_version(&transaction, curve).await.map_err(Error::CreateParameterFailed)?;
         transaction.commit().await?;
 
         Ok(parameter.into())
     }
+
+    #[tracing::instrument(skip_all, fields(workspace_name = %self.workspace_name))]
+    async fn get(&self, version: Option<i32>) -> Result<ParameterData> {
+        let transaction = self.database_connection.begin_with_workspace_scope(&self.workspace_name).await?;
+        let parameter = self.parameter_service.get(&transaction, version).await.map_err(Error::GetParameterFailed)?;
+        transaction.commit().await?;
+
+        Ok(parameter.into())
+    }
+
+    #[tracing::instrument(skip_all, fields(workspace_name = %self.workspace_name))]
+    async fn get_all(&self) -> Result<Vec<ParameterData>> {
+        let transaction = self.database_connection.begin_with_workspace_scope(&self.workspace_name).await?;
+        let parameters = self.parameter_service.get_all(&transaction).await.map_err(Error::GetParameterFailed)?;
+        transaction.commit().await?;
+
+        Ok(parameters.into_iter().map(ParameterData::from).collect())
+    }
 }
 
 pub(crate) struct ParameterData {
     pub version: i32,
-    pub value: GlobalParams<Bn462Curve>,
+    pub curve: Curve,
+    pub value: Vec<u8>,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -66,9 +95,6 @@ pub(crate) enum Error {
     #[error("Failed to get parameter: {0}")]
     GetParameterFailed(#[source] domain::parameter::Error),
 
-    #[error("Failed to serialize parameter: {0}")]
-    SerializeParameterFailed(#[from] rmp_serde::encode::Error),
-
     #[error(transparent)]
     Anyhow(#[from] anyhow::Error),
 }
@@ -81,7 +107,7 @@ impl From<sea_orm::DbErr> for Error {
 
 impl From<Parameter> for ParameterData {
     fn from(value: Parameter) -> Self {
-        Self { version: value.version, value: value.value }
+        Self { version: value.version, curve: value.curve, value: value.value }
     }
 }
 
@@ -91,10 +117,7 @@ pub(crate) type Result<T> = std::result::Result<T, Error>;
 mod test {
     use std::sync::Arc;
 
-    use nebula_abe::{
-        curves::{bn462::Bn462Curve, PairingCurve},
-        schemes::isabella24::GlobalParams,
-    };
+    use nebula_abe::curves::Curve;
     use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
 
     use crate::domain::parameter::{MockParameterService, Parameter};
@@ -109,12 +132,11 @@ mod test {
 
         let mock_connection = Arc::new(mock_database.into_connection());
 
-        let mut rng = <Bn462Curve as PairingCurve>::Rng::new();
         let mut mock_parameter_service = MockParameterService::new();
         mock_parameter_service
             .expect_get()
             .times(1)
-            .returning(move |_| Ok(Parameter { version: 1, value: GlobalParams::<Bn462Curve>::new(&mut rng) }));
+            .returning(move |_, _| Ok(Parameter { version: 1, curve: Curve::Bn462, value: vec![1, 2, 3] }));
 
         let parameter_usecase = ParameterUseCaseImpl::new(
             workspace_name.clone(),
@@ -122,7 +144,7 @@ mod test {
             Arc::new(mock_parameter_service),
         );
 
-        let result = parameter_usecase.get().await;
+        let result = parameter_usecase.get(None).await;
 
         assert!(result.is_ok());
     }
@@ -139,7 +161,7 @@ mod test {
         mock_parameter_service
             .expect_get()
             .times(1)
-            .returning(move |_| Err(crate::domain::parameter::Error::Anyhow(anyhow::anyhow!(""))));
+            .returning(move |_, _| Err(crate::domain::parameter::Error::Anyhow(anyhow::anyhow!(""))));
 
         let parameter_usecase = ParameterUseCaseImpl::new(
             workspace_name.clone(),
@@ -147,8 +169,39 @@ mod test {
             Arc::new(mock_parameter_service),
         );
 
-        let result = parameter_usecase.get().await;
+        let result = parameter_usecase.get(None).await;
 
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn when_adding_parameter_version_without_curve_then_parameter_usecase_uses_curve_of_latest_version() {
+        let workspace_name = "workspace".to_string();
+        let mock_database = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 1 }]);
+
+        let mock_connection = Arc::new(mock_database.into_connection());
+
+        let mut mock_parameter_service = MockParameterService::new();
+        mock_parameter_service
+            .expect_get()
+            .times(1)
+            .returning(move |_, _| Ok(Parameter { version: 1, curve: Curve::Bls24479, value: vec![1, 2, 3] }));
+        mock_parameter_service
+            .expect_add_version()
+            .withf(|_, curve| *curve == Curve::Bls24479)
+            .times(1)
+            .returning(move |_, curve| Ok(Parameter { version: 2, curve, value: vec![4, 5, 6] }));
+
+        let parameter_usecase = ParameterUseCaseImpl::new(
+            workspace_name.clone(),
+            mock_connection.clone(),
+            Arc::new(mock_parameter_service),
+        );
+
+        let result = parameter_usecase.add_version(None).await.expect("adding parameter version should be successful");
+
+        assert_eq!(result.version, 2);
+        assert_eq!(result.curve, Curve::Bls24479);
+    }
 }
@@ -2,11 +2,14 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use nebula_token::claim::NebulaClaim;
-use sea_orm::DatabaseConnection;
+use sea_orm::{DatabaseConnection, DatabaseTransaction};
 
 use crate::{
     database::{Persistable, WorkspaceScopedTransaction},
-    domain::secret::{self, AppliedPolicy, Path, SecretService},
+    domain::{
+        secret::{self, AppliedPolicy, Path, SecretService},
+        workspace::{self, QuotaResource, WorkspaceQuota, WorkspaceService},
+    },
 };
 
 pub(crate) struct PathData {
@@ -32,21 +35,47 @@ pub(crate) trait PathUseCase {
 pub(crate) struct PathUseCaseImpl {
     workspace_name: String,
     database_connection: Arc<DatabaseConnection>,
+    workspace_service: Arc<dyn WorkspaceService + Sync + Send>,
     secret_service: Arc<dyn SecretService + Sync + Send>,
+    default_quota: WorkspaceQuota,
 }
 
 impl PathUseCaseImpl {
     pub fn new(
         workspace_name: String,
         database_connection: Arc<DatabaseConnection>,
+        workspace_service: Arc<dyn WorkspaceService + Sync + Send>,
         secret_service: Arc<dyn SecretService + Sync + Send>,
+        default_quota: WorkspaceQuota,
     ) -> Self {
-        Self { workspace_name, database_connection, secret_service }
+        Self { workspace_name, database_connection, workspace_service, secret_service, default_quota }
+    }
+
+    /// Checks the quota with the workspace locked until the transaction ends, so that concurrent requests can't exceed
+    /// it.
+    async fn ensure_path_quota_allowed(&self, transaction: &DatabaseTransaction) -> Result<()> {
+        let workspace = self
+            .workspace_service
+            .get_by_name_for_update(transaction, &self.workspace_name)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("workspace({}) is not exists", self.workspace_name))?;
+
+        let quota = workspace.settings.quota.or(&self.default_quota);
+        if quota.is_unlimited() {
+            return Ok(());
+        }
+
+        let usage = self.secret_service.get_usage(transaction).await?;
+        match quota.exceeded_limit(QuotaResource::Paths, usage.path_count + 1) {
+            Some(limit) => Err(Error::QuotaExceeded { resource: QuotaResource::Paths, limit }),
+            None => Ok(()),
+        }
     }
 }
 
 #[async_trait]
 impl PathUseCase for PathUseCaseImpl {
+    #[tracing::instrument(skip_all, fields(workspace_name = %self.workspace_name))]
     async fn get_all(&self) -> Result<Vec<PathData>> {
         let transaction = self.database_connection.begin_with_workspace_scope(&self.workspace_name).await?;
         let paths = self.secret_service.get_paths(&transaction).await?;
@@ -55,13 +84,16 @@ impl PathUseCase for PathUseCaseImpl {
         Ok(paths.into_iter().map(PathData::from).collect())
     }
 
+    #[tracing::instrument(skip_all, fields(workspace_name = %self.workspace_name))]
     async fn register(&self, path: &str, policies: &[AppliedPolicy], claim: &NebulaClaim) -> Result<()> {
         let transaction = self.database_connection.begin_with_workspace_scope(&self.workspace_name).await?;
+        self.ensure_path_quota_allowed(&transaction).await?;
         self.secret_service.register_path(&transaction, path, policies, claim).await?;
         transaction.commit().await?;
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, fields(workspace_name = %self.workspace_name))]
     async fn delete(&self, path: &str, claim: &NebulaClaim) -> Result<()> {
         let transaction = self.database_connection.begin_with_workspace_scope(&self.workspace_name).await?;
         let mut path = self
@@ -77,6 +109,7 @@ impl PathUseCase for PathUseCaseImpl {
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, fields(workspace_name = %self.workspace_name))]
     async fn update(
         &self,
         path: &str,
@@ -104,6 +137,7 @@ impl PathUseCase for PathUseCaseImpl {
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, fields(workspace_name = %self.workspace_name))]
     async fn get(&self, path: &str) -> Result<PathData> {
         let transaction = self.database_connection.begin_with_workspace_scope(&self.workspace_name).await?;
         let path = self
@@ -139,10 +173,21 @@ pub(crate) enum Error {
     InvalidPathPolicy,
     #[error("Access denied")]
     AccessDenied,
+    #[error("Workspace quota of {resource:?} is exceeded (limit: {limit})")]
+    QuotaExceeded { resource: QuotaResource, limit: u64 },
     #[error(transparent)]
     Anyhow(#[from] anyhow::Error),
 }
 
+impl From<workspace::Error> for Error {
+    fn from(value: workspace::Error) -> Self {
+        match value {
+            workspace::Error::Anyhow(e) => Self::Anyhow(e),
+            _ => Self::Anyhow(value.into()),
+        }
+    }
+}
+
 impl From<secret::Error> for Error {
     fn from(value: secret::Error) -> Self {
         match value {
@@ -180,7 +225,10 @@ mod test {
 
     use crate::{
         database::{applied_path_policy, path, secret_metadata, secret_value, UlidId},
-        domain::secret::{MockSecretService, Path},
+        domain::{
+            secret::{MockSecretService, Path, SecretUsage},
+            workspace::{MockWorkspaceService, QuotaResource, Workspace, WorkspaceQuota},
+        },
     };
 
     use super::{Error, PathUseCase, PathUseCaseImpl};
@@ -201,8 +249,14 @@ mod test {
             .times(1)
             .returning(move |_| Ok(vec![Path::new(path.to_owned(), vec![])]));
 
-        let path_usecase =
-            PathUseCaseImpl::new("testworkspace".to_owned(), mock_connection, Arc::new(mock_secret_service));
+        let mock_workspace_service = MockWorkspaceService::new();
+        let path_usecase = PathUseCaseImpl::new(
+            "testworkspace".to_owned(),
+            mock_connection,
+            Arc::new(mock_workspace_service),
+            Arc::new(mock_secret_service),
+            WorkspaceQuota::default(),
+        );
 
         let result = path_usecase.get_all().await.expect("creating workspace should be successful");
 
@@ -222,8 +276,14 @@ mod test {
             .withf(|_| true)
             .times(1)
             .returning(move |_| Err(crate::domain::secret::Error::Anyhow(anyhow::anyhow!("some error"))));
-        let path_usecase =
-            PathUseCaseImpl::new("testworkspace".to_owned(), mock_connection, Arc::new(mock_secret_service));
+        let mock_workspace_service = MockWorkspaceService::new();
+        let path_usecase = PathUseCaseImpl::new(
+            "testworkspace".to_owned(),
+            mock_connection,
+            Arc::new(mock_workspace_service),
+            Arc::new(mock_secret_service),
+            WorkspaceQuota::default(),
+        );
 
         let result = path_usecase.get_all().await;
 
@@ -250,8 +310,18 @@ mod test {
         let mut mock_secret_service = MockSecretService::new();
         mock_secret_service.expect_register_path().times(1).returning(move |_, _, _, _| Ok(()));
 
-        let path_usecase =
-            PathUseCaseImpl::new("testworkspace".to_owned(), mock_connection, Arc::new(mock_secret_service));
+        let mut mock_workspace_service = MockWorkspaceService::new();
+        mock_workspace_service
+            .expect_get_by_name_for_update()
+            .times(1)
+            .returning(|_, name| Ok(Some(Workspace::new(Ulid::new(), name.to_owned()))));
+        let path_usecase = PathUseCaseImpl::new(
+            "testworkspace".to_owned(),
+            mock_connection,
+            Arc::new(mock_workspace_service),
+            Arc::new(mock_secret_service),
+            WorkspaceQuota::default(),
+        );
 
         path_usecase.register(path, &[], &claim).await.expect("registering path should be successful");
     }
@@ -309,8 +379,14 @@ mod test {
             .times(1)
             .returning(move |_, _| Ok(Some(Path::new(path.to_owned(), vec![]))));
 
-        let path_usecase =
-            PathUseCaseImpl::new("testworkspace".to_owned(), mock_connection, Arc::new(mock_secret_service));
+        let mock_workspace_service = MockWorkspaceService::new();
+        let path_usecase = PathUseCaseImpl::new(
+            "testworkspace".to_owned(),
+            mock_connection,
+            Arc::new(mock_workspace_service),
+            Arc::new(mock_secret_service),
+            WorkspaceQuota::default(),
+        );
 
         path_usecase.delete(path, &claim).await.expect("registering path should be successful");
     }
@@ -361,8 +437,14 @@ mod test {
             .times(1)
             .returning(move |_, _| Ok(Some(Path::new(path.to_owned(), vec![]))));
 
-        let path_usecase =
-            PathUseCaseImpl::new("testworkspace".to_owned(), mock_connection, Arc::new(mock_secret_service));
+        let mock_workspace_service = MockWorkspaceService::new();
+        let path_usecase = PathUseCaseImpl::new(
+            "testworkspace".to_owned(),
+            mock_connection,
+            Arc::new(mock_workspace_service),
+            Arc::new(mock_secret_service),
+            WorkspaceQuota::default(),
+        );
 
         let result = path_usecase.delete(path, &claim).await;
 
@@ -415,8 +497,14 @@ mod test {
             .times(1)
             .returning(move |_, _| Ok(Some(Path::new(path.to_owned(), vec![]))));
 
-        let path_usecase =
-            PathUseCaseImpl::new("testworkspace".to_owned(), mock_connection, Arc::new(mock_secret_service));
+        let mock_workspace_service = MockWorkspaceService::new();
+        let path_usecase = PathUseCaseImpl::new(
+            "testworkspace".to_owned(),
+            mock_connection,
+            Arc::new(mock_workspace_service),
+            Arc::new(mock_secret_service),
+            WorkspaceQuota::default(),
+        );
 
         let result = path_usecase.delete(path, &claim).await;
 
@@ -442,8 +530,14 @@ mod test {
         let mut mock_secret_service = MockSecretService::new();
         mock_secret_service.expect_get_path().times(1).returning(move |_, _| Ok(None));
 
-        let path_usecase =
-            PathUseCaseImpl::new("testworkspace".to_owned(), mock_connection, Arc::new(mock_secret_service));
+        let mock_workspace_service = MockWorkspaceService::new();
+        let path_usecase = PathUseCaseImpl::new(
+            "testworkspace".to_owned(),
+            mock_connection,
+            Arc::new(mock_workspace_service),
+            Arc::new(mock_secret_service),
+            WorkspaceQuota::default(),
+        );
 
         let result = path_usecase.delete(path, &claim).await;
 
@@ -494,8 +588,14 @@ mod test {
             .times(1)
             .returning(move |_, _| Ok(Some(Path::new(path.to_owned(), vec![]))));
 
-        let path_usecase =
-            PathUseCaseImpl::new("testworkspace".to_owned(), mock_connection, Arc::new(mock_secret_service));
+        let mock_workspace_service = MockWorkspaceService::new();
+        let path_usecase = PathUseCaseImpl::new(
+            "testworkspace".to_owned(),
+            mock_connection,
+            Arc::new(mock_workspace_service),
+            Arc::new(mock_secret_service),
+            WorkspaceQuota::default(),
+        );
 
         path_usecase
             .update(path, Some("/new/test/path"), None, &claim)
@@ -544,11 +644,55 @@ mod test {
             .times(1)
             .returning(move |_, _| Ok(Some(Path::new(path.to_owned(), vec![]))));
 
-        let path_usecase =
-            PathUseCaseImpl::new("testworkspace".to_owned(), mock_connection, Arc::new(mock_secret_service));
+        let mock_workspace_service = MockWorkspaceService::new();
+        let path_usecase = PathUseCaseImpl::new(
+            "testworkspace".to_owned(),
+            mock_connection,
+            Arc::new(mock_workspace_service),
+            Arc::new(mock_secret_service),
+            WorkspaceQuota::default(),
+        );
 
         let result = path_usecase.update(path, Some("/new/test/path"), None, &claim).await;
 
         assert!(matches!(result, Err(Error::PathDuplicated { .. })))
     }
+
+    #[tokio::test]
+    async fn when_registering_path_over_workspace_quota_then_path_usecase_returns_quota_exceeded_err() {
+        let claim = NebulaClaim {
+            gid: "test@cremit.io".to_owned(),
+            workspace_name: "cremit".to_owned(),
+            attributes: HashMap::new(),
+            role: Role::Member,
+        };
+
+        let mock_database = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 1 }]);
+
+        let mock_connection = Arc::new(mock_database.into_connection());
+
+        let mut mock_secret_service = MockSecretService::new();
+        mock_secret_service
+            .expect_get_usage()
+            .times(1)
+            .returning(|_| Ok(SecretUsage { secret_count: 0, ciphertext_bytes: 0, path_count: 3 }));
+
+        let mut mock_workspace_service = MockWorkspaceService::new();
+        mock_workspace_service
+            .expect_get_by_name_for_update()
+            .times(1)
+            .returning(|_, name| Ok(Some(Workspace::new(Ulid::new(), name.to_owned()))));
+        let path_usecase = PathUseCaseImpl::new(
+            "testworkspace".to_owned(),
+            mock_connection,
+            Arc::new(mock_workspace_service),
+            Arc::new(mock_secret_service),
+            WorkspaceQuota { max_paths: Some(3), ..Default::default() },
+        );
+
+        let result = path_usecase.register("/test/path", &[], &claim).await;
+
+        assert!(matches!(result, Err(Error::QuotaExceeded { resource: QuotaResource::Paths, limit: 3 })))
+    }
 }
@@ -36,6 +36,7 @@ impl PolicyUseCaseImpl {
 
 #[async_trait]
 impl PolicyUseCase for PolicyUseCaseImpl {
+    #[tracing::instrument(skip_all, fields(workspace_name = %self.workspace_name))]
     async fn get_all(&self) -> Result<Vec<PolicyData>> {
         let transaction = self.database_connection.begin_with_workspace_scope(&self.workspace_name).await?;
 
@@ -46,6 +47,7 @@ impl PolicyUseCase for PolicyUseCaseImpl {
         Ok(policies.into_iter().map(PolicyData::from).collect())
     }
 
+    #[tracing::instrument(skip_all, fields(workspace_name = %self.workspace_name))]
     async fn get_policy(&self, policy_id: Ulid) -> Result<PolicyData> {
         let transaction = self.database_connection.begin_with_workspace_scope(&self.workspace_name).await?;
 
@@ -60,6 +62,7 @@ impl PolicyUseCase for PolicyUseCaseImpl {
         Ok(policy.into())
     }
 
+    #[tracing::instrument(skip_all, fields(workspace_name = %self.workspace_name))]
     async fn register(&self, name: &str, expression: &str) -> Result<()> {
         let transaction = self.database_connection.begin_with_workspace_scope(&self.workspace_name).await?;
 
@@ -70,6 +73,7 @@ impl PolicyUseCase for PolicyUseCaseImpl {
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, fields(workspace_name = %self.workspace_name))]
     async fn update(&self, policy_id: &Ulid, new_name: Option<&str>, new_expression: Option<&str>) -> Result<()> {
         let transaction = self.database_connection.begin_with_workspace_scope(&self.workspace_name).await?;
 
@@ -93,6 +97,7 @@ impl PolicyUseCase for PolicyUseCaseImpl {
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, fields(workspace_name = %self.workspace_name))]
     async fn delete(&self, policy_id: &Ulid) -> Result<()> {
         let transaction = self.database_connection.begin_with_workspace_scope(&self.workspace_name).await?;
 
@@ -0,0 +1,375 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use nebula_token::claim::{self, Permission};
+use sea_orm::DatabaseConnection;
+use ulid::Ulid;
+
+use crate::{
+    database::{Persistable, WorkspaceScopedTransaction},
+    domain::{self, role::RoleService},
+};
+
+#[async_trait]
+pub(crate) trait RoleUseCase {
+    async fn get_all(&self) -> Result<Vec<RoleData>>;
+    async fn get_role(&self, role_id: &Ulid) -> Result<RoleData>;
+    async fn register(&self, name: &str, permissions: &[Permission]) -> Result<Ulid>;
+    async fn update(
+        &self,
+        role_id: &Ulid,
+        new_name: Option<&str>,
+        new_permissions: Option<&[Permission]>,
+    ) -> Result<()>;
+    async fn delete(&self, role_id: &Ulid) -> Result<()>;
+    /// Resolves the permissions of the role in a claim. A custom role not defined in the workspace has no permission.
+    async fn resolve_permissions(&self, role: &claim::Role) -> Result<BTreeSet<Permission>>;
+}
+
+/// Permissions of the custom roles resolved recently, which spares the permission check of every request a database
+/// transaction. Entries expire after `ttl` so that roles changed through another server are eventually picked up.
+pub(crate) struct RolePermissionCache {
+    ttl: Duration,
+    entries: RwLock<HashMap<(String, String), CachedPermissions>>,
+}
+
+struct CachedPermissions {
+    cached_at: Instant,
+    permissions: BTreeSet<Permission>,
+}
+
+impl RolePermissionCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self { ttl, entries: RwLock::new(HashMap::new()) }
+    }
+
+    fn get(&self, workspace_name: &str, role_name: &str) -> Option<BTreeSet<Permission>> {
+        let entries = self.entries.read().unwrap_or_else(|e| e.into_inner());
+        entries
+            .get(&(workspace_name.to_owned(), role_name.to_owned()))
+            .filter(|entry| entry.cached_at.elapsed() < self.ttl)
+            .map(|entry| entry.permissions.clone())
+    }
+
+    fn insert(&self, workspace_name: &str, role_name: &str, permissions: BTreeSet<Permission>) {
+        let mut entries = self.entries.write().unwrap_or_else(|e| e.into_inner());
+        entries.retain(|_, entry| entry.cached_at.elapsed() < self.ttl);
+        entries.insert(
+            (workspace_name.to_owned(), role_name.to_owned()),
+            CachedPermissions { cached_at: Instant::now(), permissions },
+        );
+    }
+
+    fn invalidate(&self, workspace_name: &str) {
+        let mut entries = self.entries.write().unwrap_or_else(|e| e.into_inner());
+        entries.retain(|(cached_workspace_name, _), _| cached_workspace_name != workspace_name);
+    }
+}
+
+pub(crate) struct RoleUseCaseImpl {
+    workspace_name: String,
+    database_connection: Arc<DatabaseConnection>,
+    role_service: Arc<dyn RoleService + Sync + Send>,
+    permission_cache: Arc<RolePermissionCache>,
+}
+
+impl RoleUseCaseImpl {
+    pub fn new(
+        workspace_name: String,
+        database_connection: Arc<DatabaseConnection>,
+        role_service: Arc<dyn RoleService + Sync + Send>,
+        permission_cache: Arc<RolePermissionCache>,
+    ) -> Self {
+        Self { workspace_name, database_connection, role_service, permission_cache }
+    }
+}
+
+#[async_trait]
+impl RoleUseCase for RoleUseCaseImpl {
+    #[tracing::instrument(skip_all, fields(workspace_name = %self.workspace_name))]
+    async fn get_all(&self) -> Result<Vec<RoleData>> {
+        let transaction = self.database_connection.begin_with_workspace_scope(&self.workspace_name).await?;
+
+        let roles = self.role_service.list(&transaction).await?;
+
+        transaction.commit().await?;
+
+        Ok(roles.into_iter().map(RoleData::from).collect())
+    }
+
+    #[tracing::instrument(skip_all, fields(workspace_name = %self.workspace_name))]
+    async fn get_role(&self, role_id: &Ulid) -> Result<RoleData> {
+        let transaction = self.database_connection.begin_with_workspace_scope(&self.workspace_name).await?;
+
+        let role = self
+            .role_service
+            .get(&transaction, role_id)
+            .await?
+            .ok_or_else(|| Error::RoleNotExists { entered_role_id: role_id.to_owned() })?;
+
+        transaction.commit().await?;
+
+        Ok(role.into())
+    }
+
+    #[tracing::instrument(skip_all, fields(workspace_name = %self.workspace_name))]
+    async fn register(&self, name: &str, permissions: &[Permission]) -> Result<Ulid> {
+        let transaction = self.database_connection.begin_with_workspace_scope(&self.workspace_name).await?;
+
+        let role_id = self.role_service.register(&transaction, name, permissions).await?;
+
+        transaction.commit().await?;
+        self.permission_cache.invalidate(&self.workspace_name);
+
+        Ok(role_id)
+    }
+
+    #[tracing::instrument(skip_all, fields(workspace_name = %self.workspace_name))]
+    async fn update(
+        &self,
+        role_id: &Ulid,
+        new_name: Option<&str>,
+        new_permissions: Option<&[Permission]>,
+    ) -> Result<()> {
+        let transaction = self.database_connection.begin_with_workspace_scope(&self.workspace_name).await?;
+
+        let mut role = self
+            .role_service
+            .get(&transaction, role_id)
+            .await?
+            .ok_or_else(|| Error::RoleNotExists { entered_role_id: role_id.to_owned() })?;
+
+        if let Some(new_name) = new_name {
+            role.update_name(new_name)?;
+        }
+        if let Some(new_permissions) = new_permissions {
+            role.update_permissions(new_permissions);
+        }
+
+        role.persist(&transaction).await?;
+
+        transaction.commit().await?;
+        self.permission_cache.invalidate(&self.workspace_name);
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip_all, fields(workspace_name = %self.workspace_name))]
+    async fn delete(&self, role_id: &Ulid) -> Result<()> {
+        let transaction = self.database_connection.begin_with_workspace_scope(&self.workspace_name).await?;
+
+        let mut role = self
+            .role_service
+            .get(&transaction, role_id)
+            .await?
+            .ok_or_else(|| Error::RoleNotExists { entered_role_id: role_id.to_owned() })?;
+        role.delete();
+        role.persist(&transaction).await?;
+
+        transaction.commit().await?;
+        self.permission_cache.invalidate(&self.workspace_name);
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip_all, fields(workspace_name = %self.workspace_name))]
+    async fn resolve_permissions(&self, role: &claim::Role) -> Result<BTreeSet<Permission>> {
+        if let Some(permissions) = role.builtin_permissions() {
+            return Ok(permissions.iter().copied().collect());
+        }
+        let role_name = role.to_string();
+        if let Some(permissions) = self.permission_cache.get(&self.workspace_name, &role_name) {
+            return Ok(permissions);
+        }
+
+        let transaction = self.database_connection.begin_with_workspace_scope(&self.workspace_name).await?;
+
+        let role = self.role_service.get_by_name(&transaction, &role_name).await?;
+
+        transaction.commit().await?;
+
+        let permissions = role.map(|role| role.permissions).unwrap_or_default();
+        self.permission_cache.insert(&self.workspace_name, &role_name, permissions.clone());
+
+        Ok(permissions)
+    }
+}
+
+pub(crate) struct RoleData {
+    pub id: Ulid,
+    pub name: String,
+    pub permissions: Vec<Permission>,
+}
+
+impl From<domain::role::Role> for RoleData {
+    fn from(value: domain::role::Role) -> Self {
+        Self { id: value.id, name: value.name, permissions: value.permissions.into_iter().collect() }
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub(crate) enum Error {
+    #[error("Entered role name({entered_role_name}) is already registered.")]
+    RoleNameDuplicated { entered_role_name: String },
+    #[error("Entered role name({entered_role_name}) is reserved for a built-in role.")]
+    ReservedRoleName { entered_role_name: String },
+    #[error("Entered role name({entered_role_name}) is invalid.")]
+    InvalidRoleName { entered_role_name: String },
+    #[error("Role({entered_role_id}) is not exists")]
+    RoleNotExists { entered_role_id: Ulid },
+    #[error(transparent)]
+    Anyhow(#[from] anyhow::Error),
+}
+
+impl From<sea_orm::DbErr> for Error {
+    fn from(value: sea_orm::DbErr) -> Self {
+        Error::Anyhow(value.into())
+    }
+}
+
+impl From<domain::role::Error> for Error {
+    fn from(value: domain::role::Error) -> Self {
+        match value {
+            domain::role::Error::RoleNameDuplicated { entered_role_name } => {
+                Error::RoleNameDuplicated { entered_role_name }
+            }
+            domain::role::Error::ReservedRoleName { entered_role_name } => {
+                Error::ReservedRoleName { entered_role_name }
+            }
+            domain::role::Error::InvalidRoleName { entered_role_name } => Error::InvalidRoleName { entered_role_name },
+            domain::role::Error::Anyhow(e) => Error::Anyhow(e),
+        }
+    }
+}
+
+pub(crate) type Result<T> = std::result::Result<T, Error>;
+
+#[cfg(test)]
+mod test {
+    use std::{collections::BTreeSet, sync::Arc, time::Duration};
+
+    use nebula_token::claim::{self, Permission};
+    use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+    use ulid::Ulid;
+
+    use crate::domain::role::{MockRoleService, Role};
+
+    use super::{RolePermissionCache, RoleUseCase, RoleUseCaseImpl};
+
+    #[tokio::test]
+    async fn when_resolving_builtin_role_then_role_usecase_returns_builtin_permissions_without_lookup() {
+        let mock_connection = Arc::new(MockDatabase::new(DatabaseBackend::Postgres).into_connection());
+        let mut mock_role_service = MockRoleService::new();
+        mock_role_service.expect_get_by_name().never();
+
+        let role_usecase = RoleUseCaseImpl::new(
+            "testworkspace".to_owned(),
+            mock_connection,
+            Arc::new(mock_role_service),
+            Arc::new(RolePermissionCache::new(Duration::from_secs(60))),
+        );
+
+        let permissions =
+            role_usecase.resolve_permissions(&claim::Role::Auditor).await.expect("resolving should be successful");
+
+        assert!(permissions.contains(&Permission::AuditRead));
+        assert!(!permissions.contains(&Permission::SecretRead));
+        assert!(!permissions.contains(&Permission::SecretWrite));
+    }
+
+    #[tokio::test]
+    async fn when_resolving_custom_role_then_role_usecase_returns_permissions_of_workspace_role() {
+        let mock_database = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 1 }]);
+        let mock_connection = Arc::new(mock_database.into_connection());
+        let mut mock_role_service = MockRoleService::new();
+        mock_role_service.expect_get_by_name().withf(|_, name| name == "policy-manager").times(1).returning(
+            |_, name| Ok(Some(Role::new(Ulid::new(), name.to_owned(), BTreeSet::from([Permission::PolicyWrite])))),
+        );
+
+        let role_usecase = RoleUseCaseImpl::new(
+            "testworkspace".to_owned(),
+            mock_connection,
+            Arc::new(mock_role_service),
+            Arc::new(RolePermissionCache::new(Duration::from_secs(60))),
+        );
+
+        let permissions = role_usecase
+            .resolve_permissions(&claim::Role::Custom("policy-manager".to_owned()))
+            .await
+            .expect("resolving should be successful");
+
+        assert_eq!(permissions, BTreeSet::from([Permission::PolicyWrite]));
+    }
+
+    #[tokio::test]
+    async fn when_resolving_undefined_custom_role_then_role_usecase_returns_no_permission() {
+        let mock_database = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 1 }]);
+        let mock_connection = Arc::new(mock_database.into_connection());
+        let mut mock_role_service = MockRoleService::new();
+        mock_role_service.expect_get_by_name().times(1).returning(|_, _| Ok(None));
+
+        let role_usecase = RoleUseCaseImpl::new(
+            "testworkspace".to_owned(),
+            mock_connection,
+            Arc::new(mock_role_service),
+            Arc::new(RolePermissionCache::new(Duration::from_secs(60))),
+        );
+
+        let permissions = role_usecase
+            .resolve_permissions(&claim::Role::Custom("unknown".to_owned()))
+            .await
+            .expect("resolving should be successful");
+
+        assert!(permissions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn when_resolving_cached_custom_role_then_role_usecase_does_not_look_up_role_again() {
+        let mock_database = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 1 }]);
+        let mock_connection = Arc::new(mock_database.into_connection());
+        let mut mock_role_service = MockRoleService::new();
+        mock_role_service.expect_get_by_name().times(1).returning(|_, name| {
+            Ok(Some(Role::new(Ulid::new(), name.to_owned(), BTreeSet::from([Permission::PolicyWrite]))))
+        });
+
+        let role_usecase = RoleUseCaseImpl::new(
+            "testworkspace".to_owned(),
+            mock_connection,
+            Arc::new(mock_role_service),
+            Arc::new(RolePermissionCache::new(Duration::from_secs(60))),
+        );
+
+        let role = claim::Role::Custom("policy-manager".to_owned());
+        let first = role_usecase.resolve_permissions(&role).await.expect("resolving should be successful");
+        let second = role_usecase.resolve_permissions(&role).await.expect("resolving should be successful");
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn when_workspace_roles_are_invalidated_then_role_permission_cache_drops_only_that_workspace() {
+        let cache = RolePermissionCache::new(Duration::from_secs(60));
+        cache.insert("workspace-a", "policy-manager", BTreeSet::from([Permission::PolicyWrite]));
+        cache.insert("workspace-b", "policy-manager", BTreeSet::from([Permission::PolicyRead]));
+
+        cache.invalidate("workspace-a");
+
+        assert_eq!(cache.get("workspace-a", "policy-manager"), None);
+        assert_eq!(cache.get("workspace-b", "policy-manager"), Some(BTreeSet::from([Permission::PolicyRead])));
+    }
+
+    #[test]
+    fn when_entry_is_expired_then_role_permission_cache_misses() {
+        let cache = RolePermissionCache::new(Duration::ZERO);
+        cache.insert("workspace-a", "policy-manager", BTreeSet::from([Permission::PolicyWrite]));
+
+        assert_eq!(cache.get("workspace-a", "policy-manager"), None);
+    }
+}
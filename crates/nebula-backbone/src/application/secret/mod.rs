@@ -9,8 +9,10 @@ use crate::{
     database::{Persistable, WorkspaceScopedTransaction},
     domain::{
         self,
+        parameter::ParameterService,
         policy::{AccessCondition, PolicyService},
-        secret::{SecretEntry, SecretService},
+        secret::{NewSecret, SecretEntry, SecretService},
+        workspace::{QuotaResource, Workspace, WorkspaceQuota, WorkspaceService},
     },
 };
 
@@ -26,18 +28,68 @@ pub(crate) trait SecretUseCase {
 pub(crate) struct SecretUseCaseImpl {
     workspace_name: String,
     database_connection: Arc<DatabaseConnection>,
+    workspace_service: Arc<dyn WorkspaceService + Sync + Send>,
     secret_service: Arc<dyn SecretService + Sync + Send>,
+    parameter_service: Arc<dyn ParameterService + Sync + Send>,
     policy_service: Arc<dyn PolicyService + Sync + Send>,
+    default_quota: WorkspaceQuota,
 }
 
 impl SecretUseCaseImpl {
     pub fn new(
         workspace_name: String,
         database_connection: Arc<DatabaseConnection>,
+        workspace_service: Arc<dyn WorkspaceService + Sync + Send>,
         secret_service: Arc<dyn SecretService + Sync + Send>,
+        parameter_service: Arc<dyn ParameterService + Sync + Send>,
         policy_service: Arc<dyn PolicyService + Sync + Send>,
+        default_quota: WorkspaceQuota,
     ) -> Self {
-        Self { workspace_name, database_connection, secret_service, policy_service }
+        Self {
+            workspace_name,
+            database_connection,
+            workspace_service,
+            secret_service,
+            parameter_service,
+            policy_service,
+            default_quota,
+        }
+    }
+
+    /// Gets the workspace locked until the transaction ends, so that concurrent requests can't exceed its quota.
+    async fn get_workspace(&self, transaction: &DatabaseTransaction) -> Result<Workspace> {
+        self.workspace_service
+            .get_by_name_for_update(transaction, &self.workspace_name)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("workspace({}) is not exists", self.workspace_name).into())
+    }
+
+    fn ensure_secret_size_allowed(workspace: &Workspace, cipher: &[u8]) -> Result<()> {
+        if !workspace.settings.is_secret_size_allowed(cipher.len()) {
+            return Err(Error::SecretSizeExceeded {
+                max_secret_size: workspace.settings.max_secret_size.unwrap_or_default(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn ensure_quota_allowed(quota: &WorkspaceQuota, resource: QuotaResource, usage: u64) -> Result<()> {
+        match quota.exceeded_limit(resource, usage) {
+            Some(limit) => Err(Error::QuotaExceeded { resource, limit }),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the entered parameter version if it exists, or the latest version if it is not entered.
+    async fn resolve_parameter_version(&self, transaction: &DatabaseTransaction, version: Option<i32>) -> Result<i32> {
+        match self.parameter_service.get(transaction, version).await {
+            Ok(parameter) => Ok(parameter.version),
+            Err(domain::parameter::Error::ParameterNotFound) => {
+                Err(Error::ParameterNotExists { entered_parameter_version: version })
+            }
+            Err(e) => Err(anyhow::Error::from(e).into()),
+        }
     }
 
     async fn get_policies(&self, transaction: &DatabaseTransaction, ids: Vec<Ulid>) -> Result<Vec<AccessCondition>> {
@@ -58,39 +110,77 @@ impl SecretUseCaseImpl {
 
 #[async_trait]
 impl SecretUseCase for SecretUseCaseImpl {
+    #[tracing::instrument(skip_all, fields(workspace_name = %self.workspace_name))]
     async fn list(&self, path: &str, claim: &NebulaClaim) -> Result<Vec<SecretData>> {
         let transaction = self.database_connection.begin_with_workspace_scope(&self.workspace_name).await?;
-        let secrets = self.secret_service.list_secret(&transaction, path, claim).await?;
+        let secrets = self.secret_service.list_secret(&transaction, path, claim).await.inspect_err(record_denied)?;
         transaction.commit().await?;
 
+        metrics::counter!(SECRETS_READ_TOTAL, "workspace" => self.workspace_name.clone())
+            .increment(secrets.len() as u64);
+
         Ok(secrets.into_iter().map(SecretData::from).collect())
     }
 
+    #[tracing::instrument(skip_all, fields(workspace_name = %self.workspace_name))]
     async fn get(&self, secret_identifier: &str, claim: &NebulaClaim) -> Result<SecretData> {
         let transaction = self.database_connection.begin_with_workspace_scope(&self.workspace_name).await?;
-        let secret = self.secret_service.get_secret(&transaction, secret_identifier, claim).await?;
+        let secret =
+            self.secret_service.get_secret(&transaction, secret_identifier, claim).await.inspect_err(record_denied)?;
         transaction.commit().await?;
 
+        metrics::counter!(SECRETS_READ_TOTAL, "workspace" => self.workspace_name.clone()).increment(1);
+
         Ok(secret.into())
     }
 
+    #[tracing::instrument(skip_all, fields(workspace_name = %self.workspace_name))]
     async fn register(&self, cmd: SecretRegisterCommand, claim: &NebulaClaim) -> Result<()> {
         let transaction = self.database_connection.begin_with_workspace_scope(&self.workspace_name).await?;
 
-        let access_conditions = self.get_policies(&transaction, cmd.access_condition_ids).await?;
+        let workspace = self.get_workspace(&transaction).await?;
+        Self::ensure_secret_size_allowed(&workspace, &cmd.cipher)?;
+
+        let quota = workspace.settings.quota.or(&self.default_quota);
+        if !quota.is_unlimited() {
+            let usage = self.secret_service.get_usage(&transaction).await?;
+            Self::ensure_quota_allowed(&quota, QuotaResource::Secrets, usage.secret_count + 1)?;
+            Self::ensure_quota_allowed(
+                &quota,
+                QuotaResource::CiphertextBytes,
+                usage.ciphertext_bytes + cmd.cipher.len() as u64,
+            )?;
+        }
+
+        let access_condition_ids = if cmd.access_condition_ids.is_empty() {
+            workspace.settings.default_access_condition_ids
+        } else {
+            cmd.access_condition_ids
+        };
+        let access_conditions = self.get_policies(&transaction, access_condition_ids).await?;
+        let parameter_version = self.resolve_parameter_version(&transaction, cmd.parameter_version).await?;
 
         self.secret_service
-            .register_secret(&transaction, cmd.path, cmd.key, cmd.cipher, access_conditions, claim)
-            .await?;
+            .register_secret(
+                &transaction,
+                NewSecret { path: cmd.path, key: cmd.key, cipher: cmd.cipher, parameter_version, access_conditions },
+                claim,
+            )
+            .await
+            .inspect_err(record_denied)?;
 
         transaction.commit().await?;
 
+        metrics::counter!(SECRETS_CREATED_TOTAL, "workspace" => self.workspace_name.clone()).increment(1);
+
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, fields(workspace_name = %self.workspace_name))]
     async fn delete(&self, secret_identifier: &str, claim: &NebulaClaim) -> Result<()> {
         let transaction = self.database_connection.begin_with_workspace_scope(&self.workspace_name).await?;
-        let mut secret = self.secret_service.get_secret(&transaction, secret_identifier, claim).await?;
+        let mut secret =
+            self.secret_service.get_secret(&transaction, secret_identifier, claim).await.inspect_err(record_denied)?;
         secret.delete(&transaction, claim).await?;
         secret.persist(&transaction).await?;
         transaction.commit().await?;
@@ -98,10 +188,12 @@ impl SecretUseCase for SecretUseCaseImpl {
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, fields(workspace_name = %self.workspace_name))]
     async fn update(&self, secret_identifier: &str, update: SecretUpdate, claim: &NebulaClaim) -> Result<()> {
         let transaction = self.database_connection.begin_with_workspace_scope(&self.workspace_name).await?;
 
-        let mut secret = self.secret_service.get_secret(&transaction, secret_identifier, claim).await?;
+        let mut secret =
+            self.secret_service.get_secret(&transaction, secret_identifier, claim).await.inspect_err(record_denied)?;
 
         if let Some(updated_access_policy_ids) = update.access_condition_ids {
             let updated_access_policies = self.get_policies(&transaction, updated_access_policy_ids).await?;
@@ -111,7 +203,18 @@ impl SecretUseCase for SecretUseCaseImpl {
             secret.update_path(&transaction, updated_path, claim).await?;
         }
         if let Some(updated_cipher) = update.cipher {
-            secret.update_cipher(&transaction, updated_cipher, claim).await?;
+            let workspace = self.get_workspace(&transaction).await?;
+            Self::ensure_secret_size_allowed(&workspace, &updated_cipher)?;
+
+            let quota = workspace.settings.quota.or(&self.default_quota);
+            if !quota.is_unlimited() {
+                let usage = self.secret_service.get_usage(&transaction).await?;
+                let ciphertext_bytes =
+                    usage.ciphertext_bytes.saturating_sub(secret.cipher.len() as u64) + updated_cipher.len() as u64;
+                Self::ensure_quota_allowed(&quota, QuotaResource::CiphertextBytes, ciphertext_bytes)?;
+            }
+            let parameter_version = self.resolve_parameter_version(&transaction, update.parameter_version).await?;
+            secret.update_cipher(&transaction, updated_cipher, parameter_version, claim).await?;
         }
 
         secret.persist(&transaction).await?;
@@ -121,16 +224,29 @@ impl SecretUseCase for SecretUseCaseImpl {
     }
 }
 
+const SECRETS_CREATED_TOTAL: &str = "secrets_created_total";
+const SECRETS_READ_TOTAL: &str = "secrets_read_total";
+const SECRETS_DENIED_TOTAL: &str = "secrets_denied_total";
+
+fn record_denied(error: &domain::secret::Error) {
+    if let domain::secret::Error::AccessDenied = error {
+        metrics::counter!(SECRETS_DENIED_TOTAL).increment(1);
+    }
+}
+
 pub(crate) struct SecretData {
     pub key: String,
     pub path: String,
     pub cipher: Vec<u8>,
+    pub parameter_version: i32,
     pub access_condition_ids: Vec<Ulid>,
 }
 
 pub(crate) struct SecretUpdate {
     pub path: Option<String>,
     pub cipher: Option<Vec<u8>>,
+    /// Parameter version of the updated cipher. The latest version is used if it is not entered.
+    pub parameter_version: Option<i32>,
     pub access_condition_ids: Option<Vec<Ulid>>,
 }
 
@@ -146,8 +262,14 @@ pub(crate) enum Error {
     PolicyNotExists { entered_policy_id: Ulid },
     #[error("Path({entered_path}) is not registered")]
     PathNotExists { entered_path: String },
+    #[error("Parameter({entered_parameter_version:?}) is not exists")]
+    ParameterNotExists { entered_parameter_version: Option<i32> },
     #[error("Access denied")]
     AccessDenied,
+    #[error("Secret size exceeds the maximum size({max_secret_size}) of the workspace")]
+    SecretSizeExceeded { max_secret_size: u64 },
+    #[error("Workspace quota of {resource:?} is exceeded (limit: {limit})")]
+    QuotaExceeded { resource: QuotaResource, limit: u64 },
     #[error(transparent)]
     Anyhow(#[from] anyhow::Error),
 }
@@ -158,6 +280,15 @@ impl From<sea_orm::DbErr> for Error {
     }
 }
 
+impl From<domain::workspace::Error> for Error {
+    fn from(value: domain::workspace::Error) -> Self {
+        match value {
+            domain::workspace::Error::Anyhow(e) => Self::Anyhow(e),
+            _ => Self::Anyhow(value.into()),
+        }
+    }
+}
+
 impl From<domain::secret::Error> for Error {
     fn from(value: domain::secret::Error) -> Self {
         match value {
@@ -196,6 +327,7 @@ impl From<SecretEntry> for SecretData {
             key: value.key,
             path: value.path,
             cipher: value.cipher,
+            parameter_version: value.parameter_version,
             access_condition_ids: value.access_condition_ids,
         }
     }
@@ -207,6 +339,8 @@ pub(crate) struct SecretRegisterCommand {
     pub path: String,
     pub key: String,
     pub cipher: Vec<u8>,
+    /// Parameter version with which the cipher is encrypted. The latest version is used if it is not entered.
+    pub parameter_version: Option<i32>,
     pub access_condition_ids: Vec<Ulid>,
 }
 
@@ -214,6 +348,7 @@ pub(crate) struct SecretRegisterCommand {
 mod test {
     use std::{collections::HashMap, str::FromStr, sync::Arc};
 
+    use nebula_abe::curves::Curve;
     use nebula_token::claim::{NebulaClaim, Role};
     use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
     use ulid::Ulid;
@@ -221,8 +356,10 @@ mod test {
     use crate::{
         application::secret::SecretRegisterCommand,
         domain::{
+            parameter::{MockParameterService, Parameter},
             policy::{AccessCondition, MockPolicyService},
-            secret::{MockSecretService, SecretEntry},
+            secret::{MockSecretService, SecretEntry, SecretUsage},
+            workspace::{MockWorkspaceService, QuotaResource, Workspace, WorkspaceQuota, WorkspaceSettings},
         },
     };
 
@@ -258,13 +395,17 @@ mod test {
                 vec![applied_policy_ids[0].to_owned()],
             )])
         });
+        let mock_workspace_service = MockWorkspaceService::new();
         let mock_policy_service = MockPolicyService::new();
 
         let secret_usecase = SecretUseCaseImpl::new(
             "testworkspace".to_owned(),
             mock_connection,
+            Arc::new(mock_workspace_service),
             Arc::new(mock_secret_service),
+            Arc::new(MockParameterService::new()),
             Arc::new(mock_policy_service),
+            WorkspaceQuota::default(),
         );
 
         let result = secret_usecase.list("/", &claim).await.expect("creating workspace should be successful");
@@ -295,13 +436,17 @@ mod test {
             .withf(|_, path, _| path == "/")
             .times(1)
             .returning(move |_, _, _| Err(crate::domain::secret::Error::Anyhow(anyhow::anyhow!("some error"))));
+        let mock_workspace_service = MockWorkspaceService::new();
         let mock_policy_service = MockPolicyService::new();
 
         let secret_usecase = SecretUseCaseImpl::new(
             "testworkspace".to_owned(),
             mock_connection,
+            Arc::new(mock_workspace_service),
             Arc::new(mock_secret_service),
+            Arc::new(MockParameterService::new()),
             Arc::new(mock_policy_service),
+            WorkspaceQuota::default(),
         );
 
         let result = secret_usecase.list("/", &claim).await;
@@ -343,13 +488,17 @@ mod test {
                 ))
             },
         );
+        let mock_workspace_service = MockWorkspaceService::new();
         let mock_policy_service = MockPolicyService::new();
 
         let secret_usecase = SecretUseCaseImpl::new(
             "testworkspace".to_owned(),
             mock_connection,
+            Arc::new(mock_workspace_service),
             Arc::new(mock_secret_service),
+            Arc::new(MockParameterService::new()),
             Arc::new(mock_policy_service),
+            WorkspaceQuota::default(),
         );
 
         let result = secret_usecase.get(identifier, &claim).await.expect("creating workspace should be successful");
@@ -379,7 +528,12 @@ mod test {
         let mock_connection = Arc::new(mock_database.into_connection());
 
         let mut mock_secret_service = MockSecretService::new();
-        mock_secret_service.expect_register_secret().times(1).returning(move |_, _, _, _, _, _| Ok(()));
+        mock_secret_service.expect_register_secret().times(1).returning(move |_, _, _| Ok(()));
+        let mut mock_workspace_service = MockWorkspaceService::new();
+        mock_workspace_service
+            .expect_get_by_name_for_update()
+            .times(1)
+            .returning(|_, name| Ok(Some(Workspace::new(Ulid::new(), name.to_owned()))));
         let mut mock_policy_service = MockPolicyService::new();
         mock_policy_service.expect_get().times(1).returning(move |_, _| {
             Ok(Some(AccessCondition::new(
@@ -389,11 +543,21 @@ mod test {
             )))
         });
 
+        let mut mock_parameter_service = MockParameterService::new();
+        mock_parameter_service
+            .expect_get()
+            .withf(|_, version| *version == Some(1))
+            .times(1)
+            .returning(|_, _| Ok(Parameter { version: 1, curve: Curve::Bn462, value: vec![] }));
+
         let secret_usecase = SecretUseCaseImpl::new(
             "testworkspace".to_owned(),
             mock_connection,
+            Arc::new(mock_workspace_service),
             Arc::new(mock_secret_service),
+            Arc::new(mock_parameter_service),
             Arc::new(mock_policy_service),
+            WorkspaceQuota::default(),
         );
 
         secret_usecase
@@ -402,6 +566,7 @@ mod test {
                     path: path.to_owned(),
                     key: key.to_owned(),
                     cipher: vec![4, 5, 6],
+                    parameter_version: Some(1),
                     access_condition_ids,
                 },
                 &claim,
@@ -429,14 +594,22 @@ mod test {
         let mock_connection = Arc::new(mock_database.into_connection());
 
         let mock_secret_service = MockSecretService::new();
+        let mut mock_workspace_service = MockWorkspaceService::new();
+        mock_workspace_service
+            .expect_get_by_name_for_update()
+            .times(1)
+            .returning(|_, name| Ok(Some(Workspace::new(Ulid::new(), name.to_owned()))));
         let mut mock_policy_service = MockPolicyService::new();
         mock_policy_service.expect_get().times(1).returning(move |_, _| Ok(None));
 
         let secret_usecase = SecretUseCaseImpl::new(
             "testworkspace".to_owned(),
             mock_connection,
+            Arc::new(mock_workspace_service),
             Arc::new(mock_secret_service),
+            Arc::new(MockParameterService::new()),
             Arc::new(mock_policy_service),
+            WorkspaceQuota::default(),
         );
 
         let result = secret_usecase
@@ -445,6 +618,7 @@ mod test {
                     path: path.to_owned(),
                     key: key.to_owned(),
                     cipher: vec![],
+                    parameter_version: None,
                     access_condition_ids,
                 },
                 &claim,
@@ -453,4 +627,238 @@ mod test {
 
         assert!(matches!(result, Err(Error::PolicyNotExists { .. })))
     }
+
+    #[tokio::test]
+    async fn when_registering_secret_with_not_existing_parameter_version_then_secret_usecase_returns_parameter_not_exists_err(
+    ) {
+        let claim = NebulaClaim {
+            gid: "test@cremit.io".to_owned(),
+            workspace_name: "cremit".to_owned(),
+            attributes: HashMap::new(),
+            role: Role::Member,
+        };
+
+        let mock_database = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 1 }]);
+
+        let mock_connection = Arc::new(mock_database.into_connection());
+
+        let mut mock_secret_service = MockSecretService::new();
+        mock_secret_service.expect_register_secret().times(0);
+        let mut mock_workspace_service = MockWorkspaceService::new();
+        mock_workspace_service
+            .expect_get_by_name_for_update()
+            .times(1)
+            .returning(|_, name| Ok(Some(Workspace::new(Ulid::new(), name.to_owned()))));
+        let mut mock_parameter_service = MockParameterService::new();
+        mock_parameter_service
+            .expect_get()
+            .times(1)
+            .returning(|_, _| Err(crate::domain::parameter::Error::ParameterNotFound));
+
+        let secret_usecase = SecretUseCaseImpl::new(
+            "testworkspace".to_owned(),
+            mock_connection,
+            Arc::new(mock_workspace_service),
+            Arc::new(mock_secret_service),
+            Arc::new(mock_parameter_service),
+            Arc::new(MockPolicyService::new()),
+            WorkspaceQuota::default(),
+        );
+
+        let result = secret_usecase
+            .register(
+                SecretRegisterCommand {
+                    path: "/test/path".to_owned(),
+                    key: "TEST_KEY".to_owned(),
+                    cipher: vec![4, 5, 6],
+                    parameter_version: Some(3),
+                    access_condition_ids: vec![],
+                },
+                &claim,
+            )
+            .await;
+
+        assert!(matches!(result, Err(Error::ParameterNotExists { entered_parameter_version: Some(3) })))
+    }
+
+    #[tokio::test]
+    async fn when_registering_secret_without_access_conditions_then_secret_usecase_applies_default_access_conditions() {
+        let claim = NebulaClaim {
+            gid: "test@cremit.io".to_owned(),
+            workspace_name: "cremit".to_owned(),
+            attributes: HashMap::new(),
+            role: Role::Member,
+        };
+
+        let default_access_condition_id = Ulid::from_str("01JACZ1B5W5Z3D9R1CVYB7JJ8S").unwrap();
+
+        let mock_database = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 1 }]);
+
+        let mock_connection = Arc::new(mock_database.into_connection());
+
+        let mut mock_workspace_service = MockWorkspaceService::new();
+        mock_workspace_service.expect_get_by_name_for_update().times(1).returning(move |_, name| {
+            let mut workspace = Workspace::new(Ulid::new(), name.to_owned());
+            workspace.settings = WorkspaceSettings {
+                default_access_condition_ids: vec![default_access_condition_id],
+                ..Default::default()
+            };
+            Ok(Some(workspace))
+        });
+        let mut mock_secret_service = MockSecretService::new();
+        mock_secret_service
+            .expect_register_secret()
+            .withf(move |_, secret, _| {
+                secret.parameter_version == 2
+                    && secret.access_conditions.len() == 1
+                    && secret.access_conditions[0].id == default_access_condition_id
+            })
+            .times(1)
+            .returning(move |_, _, _| Ok(()));
+        let mut mock_policy_service = MockPolicyService::new();
+        mock_policy_service.expect_get().withf(move |_, id| id == &default_access_condition_id).times(1).returning(
+            move |_, id| {
+                Ok(Some(AccessCondition::new(id.to_owned(), "default".to_owned(), "(\"role=FRONTEND\")".to_owned())))
+            },
+        );
+
+        let mut mock_parameter_service = MockParameterService::new();
+        mock_parameter_service
+            .expect_get()
+            .withf(|_, version| version.is_none())
+            .times(1)
+            .returning(|_, _| Ok(Parameter { version: 2, curve: Curve::Bls48556, value: vec![] }));
+
+        let secret_usecase = SecretUseCaseImpl::new(
+            "testworkspace".to_owned(),
+            mock_connection,
+            Arc::new(mock_workspace_service),
+            Arc::new(mock_secret_service),
+            Arc::new(mock_parameter_service),
+            Arc::new(mock_policy_service),
+            WorkspaceQuota::default(),
+        );
+
+        secret_usecase
+            .register(
+                SecretRegisterCommand {
+                    path: "/test/path".to_owned(),
+                    key: "TEST_KEY".to_owned(),
+                    cipher: vec![4, 5, 6],
+                    parameter_version: None,
+                    access_condition_ids: vec![],
+                },
+                &claim,
+            )
+            .await
+            .expect("registering secret should be successful");
+    }
+
+    #[tokio::test]
+    async fn when_registering_secret_larger_than_max_secret_size_then_secret_usecase_returns_secret_size_exceeded_err()
+    {
+        let claim = NebulaClaim {
+            gid: "test@cremit.io".to_owned(),
+            workspace_name: "cremit".to_owned(),
+            attributes: HashMap::new(),
+            role: Role::Member,
+        };
+
+        let mock_database = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 1 }]);
+
+        let mock_connection = Arc::new(mock_database.into_connection());
+
+        let mut mock_workspace_service = MockWorkspaceService::new();
+        mock_workspace_service.expect_get_by_name_for_update().times(1).returning(|_, name| {
+            let mut workspace = Workspace::new(Ulid::new(), name.to_owned());
+            workspace.settings = WorkspaceSettings { max_secret_size: Some(2), ..Default::default() };
+            Ok(Some(workspace))
+        });
+        let mock_secret_service = MockSecretService::new();
+        let mock_policy_service = MockPolicyService::new();
+
+        let secret_usecase = SecretUseCaseImpl::new(
+            "testworkspace".to_owned(),
+            mock_connection,
+            Arc::new(mock_workspace_service),
+            Arc::new(mock_secret_service),
+            Arc::new(MockParameterService::new()),
+            Arc::new(mock_policy_service),
+            WorkspaceQuota::default(),
+        );
+
+        let result = secret_usecase
+            .register(
+                SecretRegisterCommand {
+                    path: "/test/path".to_owned(),
+                    key: "TEST_KEY".to_owned(),
+                    cipher: vec![4, 5, 6],
+                    parameter_version: None,
+                    access_condition_ids: vec![],
+                },
+                &claim,
+            )
+            .await;
+
+        assert!(matches!(result, Err(Error::SecretSizeExceeded { max_secret_size: 2 })))
+    }
+
+    #[tokio::test]
+    async fn when_registering_secret_over_workspace_quota_then_secret_usecase_returns_quota_exceeded_err() {
+        let claim = NebulaClaim {
+            gid: "test@cremit.io".to_owned(),
+            workspace_name: "cremit".to_owned(),
+            attributes: HashMap::new(),
+            role: Role::Member,
+        };
+
+        let mock_database = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 1 }]);
+
+        let mock_connection = Arc::new(mock_database.into_connection());
+
+        let mut mock_workspace_service = MockWorkspaceService::new();
+        mock_workspace_service.expect_get_by_name_for_update().times(1).returning(|_, name| {
+            let mut workspace = Workspace::new(Ulid::new(), name.to_owned());
+            workspace.settings = WorkspaceSettings {
+                quota: WorkspaceQuota { max_secrets: Some(2), ..Default::default() },
+                ..Default::default()
+            };
+            Ok(Some(workspace))
+        });
+        let mut mock_secret_service = MockSecretService::new();
+        mock_secret_service
+            .expect_get_usage()
+            .times(1)
+            .returning(|_| Ok(SecretUsage { secret_count: 2, ciphertext_bytes: 6, path_count: 1 }));
+        let mock_policy_service = MockPolicyService::new();
+
+        let secret_usecase = SecretUseCaseImpl::new(
+            "testworkspace".to_owned(),
+            mock_connection,
+            Arc::new(mock_workspace_service),
+            Arc::new(mock_secret_service),
+            Arc::new(MockParameterService::new()),
+            Arc::new(mock_policy_service),
+            WorkspaceQuota { max_ciphertext_bytes: Some(1024), ..Default::default() },
+        );
+
+        let result = secret_usecase
+            .register(
+                SecretRegisterCommand {
+                    path: "/test/path".to_owned(),
+                    key: "TEST_KEY".to_owned(),
+                    cipher: vec![4, 5, 6],
+                    parameter_version: None,
+                    access_condition_ids: vec![],
+                },
+                &claim,
+            )
+            .await;
+
+        assert!(matches!(result, Err(Error::QuotaExceeded { resource: QuotaResource::Secrets, limit: 2 })))
+    }
 }
@@ -1,3 +1,19 @@
+use nebula_abe::curves::Curve;
+
+use crate::domain::workspace::WorkspaceSettings;
+
 pub(crate) struct CreatingWorkspaceCommand {
     pub name: String,
+    pub curve: Curve,
+}
+
+pub(crate) struct CloningWorkspaceCommand {
+    pub source_name: String,
+    pub name: String,
+}
+
+pub(crate) struct UpdatingWorkspaceCommand {
+    pub name: Option<String>,
+    pub display_name: Option<String>,
+    pub settings: Option<WorkspaceSettings>,
 }
@@ -1,3 +1,10 @@
+use chrono::{DateTime, Utc};
+
+use crate::domain::workspace::WorkspaceSettings;
+
 pub(crate) struct WorkspaceData {
     pub name: String,
+    pub display_name: Option<String>,
+    pub settings: WorkspaceSettings,
+    pub deleted_at: Option<DateTime<Utc>>,
 }
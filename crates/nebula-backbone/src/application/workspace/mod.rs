@@ -1,20 +1,27 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use async_trait::async_trait;
-use sea_orm::{DatabaseConnection, TransactionTrait};
+use chrono::Utc;
+use sea_orm::{DatabaseConnection, DatabaseTransaction, TransactionTrait};
+use tracing::warn;
+use ulid::Ulid;
 
 use crate::{
-    database::Persistable,
+    database::{PendingWorkspaceFiles, Persistable},
     domain::{
         parameter::Error as ParameterError,
+        policy::Error as PolicyError,
         secret::Error as SecretError,
         workspace::{Error as WorkspaceServiceError, Workspace, WorkspaceService},
     },
 };
 
-use self::{command::CreatingWorkspaceCommand, data::WorkspaceData};
+use self::{
+    command::{CloningWorkspaceCommand, CreatingWorkspaceCommand, UpdatingWorkspaceCommand},
+    data::WorkspaceData,
+};
 
-use super::{database::WorkspaceScopedTransaction, ParameterService, SecretService};
+use super::{database::WorkspaceScopedTransaction, ParameterService, PolicyService, SecretService};
 
 pub mod command;
 pub mod data;
@@ -22,8 +29,13 @@ pub mod data;
 #[async_trait]
 pub(crate) trait WorkspaceUseCase {
     async fn get_all(&self) -> Result<Vec<WorkspaceData>>;
+    async fn get_by_name(&self, name: &str) -> Result<WorkspaceData>;
     async fn create(&self, cmd: CreatingWorkspaceCommand) -> Result<()>;
+    async fn update(&self, name: &str, cmd: UpdatingWorkspaceCommand) -> Result<()>;
     async fn delete_by_name(&self, name: &str) -> Result<()>;
+    async fn restore_by_name(&self, name: &str) -> Result<()>;
+    async fn purge_deleted(&self, grace_period: Duration) -> Result<Vec<String>>;
+    async fn clone_workspace(&self, cmd: CloningWorkspaceCommand) -> Result<()>;
 }
 
 pub(crate) struct WorkspaceUseCaseImpl {
@@ -31,6 +43,7 @@ pub(crate) struct WorkspaceUseCaseImpl {
     workspace_service: Arc<dyn WorkspaceService + Sync + Send>,
     secret_service: Arc<dyn SecretService + Sync + Send>,
     parameter_service: Arc<dyn ParameterService + Sync + Send>,
+    policy_service: Arc<dyn PolicyService + Sync + Send>,
 }
 
 impl WorkspaceUseCaseImpl {
@@ -39,13 +52,63 @@ impl WorkspaceUseCaseImpl {
         workspace_service: Arc<dyn WorkspaceService + Sync + Send>,
         secret_service: Arc<dyn SecretService + Sync + Send>,
         parameter_service: Arc<dyn ParameterService + Sync + Send>,
+        policy_service: Arc<dyn PolicyService + Sync + Send>,
     ) -> Self {
-        Self { database_connection, workspace_service, secret_service, parameter_service }
+        Self { database_connection, workspace_service, secret_service, parameter_service, policy_service }
+    }
+
+    async fn get_active_workspace(&self, transaction: &DatabaseTransaction, name: &str) -> Result<Workspace> {
+        self.workspace_service
+            .get_by_name(transaction, name)
+            .await?
+            .filter(|workspace| !workspace.is_deleted())
+            .ok_or_else(|| Error::WorkspaceNotExists)
+    }
+
+    /// Secrets are encrypted with the key pairs of the authorities, which are named after the workspace, so a workspace
+    /// is renamed only while it has no secret. `transaction` is in the scope of the workspace, which is locked by it so
+    /// that no secret is created until the rename is committed.
+    async fn ensure_renamable(&self, transaction: &DatabaseTransaction, new_name: &str) -> Result<()> {
+        if self.workspace_service.get_by_name(transaction, new_name).await?.is_some() {
+            return Err(Error::WorkspaceNameConflicted);
+        }
+
+        let usage = self.secret_service.get_usage(transaction).await?;
+        if usage.secret_count > 0 {
+            return Err(Error::WorkspaceHasSecrets);
+        }
+
+        Ok(())
+    }
+
+    async fn clone_into(&self, source_transaction: &DatabaseTransaction, name: &str) -> Result<()> {
+        let transaction =
+            self.database_connection.begin_with_workspace_scope(name).await.map_err(anyhow::Error::from)?;
+
+        self.workspace_service.create(&transaction, name).await?;
+        self.workspace_service.copy_contents(source_transaction, &transaction).await?;
+        let source_parameter = self.parameter_service.get(source_transaction, None).await?;
+        self.parameter_service.create(&transaction, source_parameter.curve).await?;
+
+        transaction.commit().await.map_err(anyhow::Error::from)?;
+
+        Ok(())
+    }
+
+    async fn ensure_access_conditions_exist(&self, transaction: &DatabaseTransaction, ids: &[Ulid]) -> Result<()> {
+        for id in ids {
+            if self.policy_service.get(transaction, id).await?.is_none() {
+                return Err(Error::AccessConditionNotExists { entered_access_condition_id: *id });
+            }
+        }
+
+        Ok(())
     }
 }
 
 #[async_trait]
 impl WorkspaceUseCase for WorkspaceUseCaseImpl {
+    #[tracing::instrument(skip_all)]
     async fn get_all(&self) -> Result<Vec<WorkspaceData>> {
         let transaction = self.database_connection.begin().await.map_err(anyhow::Error::from)?;
 
@@ -57,36 +120,143 @@ impl WorkspaceUseCase for WorkspaceUseCaseImpl {
         Ok(data)
     }
 
+    #[tracing::instrument(skip_all, fields(workspace_name = %name))]
+    async fn get_by_name(&self, name: &str) -> Result<WorkspaceData> {
+        let transaction = self.database_connection.begin().await.map_err(anyhow::Error::from)?;
+
+        let workspace =
+            self.workspace_service.get_by_name(&transaction, name).await?.ok_or_else(|| Error::WorkspaceNotExists)?;
+
+        transaction.commit().await.map_err(anyhow::Error::from)?;
+
+        Ok(workspace.into())
+    }
+
+    #[tracing::instrument(skip_all, fields(workspace_name = %cmd.name))]
     async fn create(&self, cmd: CreatingWorkspaceCommand) -> Result<()> {
         let transaction =
             self.database_connection.begin_with_workspace_scope(&cmd.name).await.map_err(anyhow::Error::from)?;
 
         self.workspace_service.create(&transaction, &cmd.name).await?;
         self.secret_service.initialize_root_path(&transaction).await?;
-        self.parameter_service.create(&transaction).await?;
+        self.parameter_service.create(&transaction, cmd.curve).await?;
+
+        transaction.commit().await.map_err(anyhow::Error::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip_all, fields(workspace_name = %name))]
+    async fn update(&self, name: &str, cmd: UpdatingWorkspaceCommand) -> Result<()> {
+        let transaction =
+            self.database_connection.begin_with_workspace_scope(name).await.map_err(anyhow::Error::from)?;
+
+        let mut workspace = self
+            .workspace_service
+            .get_by_name_for_update(&transaction, name)
+            .await?
+            .filter(|workspace| !workspace.is_deleted())
+            .ok_or_else(|| Error::WorkspaceNotExists)?;
+        let mut pending_files = PendingWorkspaceFiles::default();
+        if let Some(new_name) = cmd.name {
+            workspace.rename(&new_name)?;
+            if new_name != name {
+                self.ensure_renamable(&transaction, &new_name).await?;
+                pending_files = self.workspace_service.rename_scope(&transaction, name, &new_name).await?;
+            }
+        }
+        if let Some(new_display_name) = cmd.display_name {
+            workspace.update_display_name(&new_display_name);
+        }
+        if let Some(new_settings) = cmd.settings {
+            self.ensure_access_conditions_exist(&transaction, &new_settings.default_access_condition_ids).await?;
+            workspace.update_settings(new_settings);
+        }
+        workspace.persist(&transaction).await?;
 
         transaction.commit().await.map_err(anyhow::Error::from)?;
+        pending_files.apply().await.map_err(anyhow::Error::from)?;
 
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, fields(workspace_name = %name))]
     async fn delete_by_name(&self, name: &str) -> Result<()> {
         let transaction = self.database_connection.begin().await.map_err(anyhow::Error::from)?;
 
+        let mut workspace = self.get_active_workspace(&transaction, name).await?;
+        workspace.delete();
+        workspace.persist(&transaction).await?;
+
+        transaction.commit().await.map_err(anyhow::Error::from)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip_all, fields(workspace_name = %name))]
+    async fn restore_by_name(&self, name: &str) -> Result<()> {
+        let transaction = self.database_connection.begin().await.map_err(anyhow::Error::from)?;
+
         let mut workspace =
             self.workspace_service.get_by_name(&transaction, name).await?.ok_or_else(|| Error::WorkspaceNotExists)?;
-        workspace.delete();
+        workspace.restore();
         workspace.persist(&transaction).await?;
 
         transaction.commit().await.map_err(anyhow::Error::from)?;
 
         Ok(())
     }
+
+    #[tracing::instrument(skip_all)]
+    async fn purge_deleted(&self, grace_period: Duration) -> Result<Vec<String>> {
+        let transaction = self.database_connection.begin().await.map_err(anyhow::Error::from)?;
+
+        let deleted_before = Utc::now() - grace_period;
+        let (purged_workspace_names, pending_files) =
+            self.workspace_service.purge_deleted(&transaction, deleted_before).await?;
+
+        transaction.commit().await.map_err(anyhow::Error::from)?;
+        pending_files.apply().await.map_err(anyhow::Error::from)?;
+
+        Ok(purged_workspace_names)
+    }
+
+    #[tracing::instrument(skip_all, fields(workspace_name = %cmd.source_name, new_workspace_name = %cmd.name))]
+    async fn clone_workspace(&self, cmd: CloningWorkspaceCommand) -> Result<()> {
+        let source_transaction =
+            self.database_connection.begin_with_workspace_scope(&cmd.source_name).await.map_err(anyhow::Error::from)?;
+
+        self.get_active_workspace(&source_transaction, &cmd.source_name).await?;
+        if self.workspace_service.get_by_name(&source_transaction, &cmd.name).await?.is_some() {
+            return Err(Error::WorkspaceNameConflicted);
+        }
+
+        let result = self.clone_into(&source_transaction, &cmd.name).await;
+        // The schema of the workspace is created outside of the transaction, so it is dropped not to leave a half-created
+        // workspace. A conflicting name is of a workspace created meanwhile, whose schema is kept.
+        if let Err(ref e) = result {
+            if !matches!(e, Error::WorkspaceNameConflicted) {
+                if let Err(e) = self.workspace_service.drop_scope(&cmd.name).await {
+                    warn!(error = %e, workspace = cmd.name, "failed to drop the schema of the workspace failed to clone.");
+                }
+            }
+        }
+        result?;
+
+        source_transaction.commit().await.map_err(anyhow::Error::from)?;
+
+        Ok(())
+    }
 }
 
 impl From<Workspace> for WorkspaceData {
     fn from(value: Workspace) -> Self {
-        Self { name: value.name }
+        Self {
+            name: value.name,
+            display_name: value.display_name,
+            settings: value.settings,
+            deleted_at: value.deleted_at,
+        }
     }
 }
 
@@ -98,6 +268,10 @@ pub enum Error {
     WorkspaceNameConflicted,
     #[error("Workspace name is invalid")]
     InvalidWorkspaceName,
+    #[error("workspace having secrets cannot be renamed")]
+    WorkspaceHasSecrets,
+    #[error("access condition({entered_access_condition_id}) not exists")]
+    AccessConditionNotExists { entered_access_condition_id: Ulid },
     #[error(transparent)]
     Anyhow(#[from] anyhow::Error),
 }
@@ -120,6 +294,15 @@ impl From<ParameterError> for Error {
     }
 }
 
+impl From<PolicyError> for Error {
+    fn from(value: PolicyError) -> Self {
+        match value {
+            PolicyError::Anyhow(e) => Self::Anyhow(e),
+            _ => Self::Anyhow(value.into()),
+        }
+    }
+}
+
 impl From<WorkspaceServiceError> for Error {
     fn from(value: WorkspaceServiceError) -> Self {
         match value {
@@ -134,22 +317,23 @@ pub type Result<T> = std::result::Result<T, Error>;
 
 #[cfg(test)]
 mod test {
-    use std::sync::Arc;
+    use std::{sync::Arc, time::Duration};
 
     use anyhow::anyhow;
-    use nebula_abe::{
-        curves::{bn462::Bn462Curve, PairingCurve},
-        schemes::isabella24::GlobalParams,
-    };
+    use nebula_abe::curves::Curve;
     use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
     use ulid::Ulid;
 
-    use super::{command::CreatingWorkspaceCommand, Error, WorkspaceUseCase, WorkspaceUseCaseImpl};
+    use super::{
+        command::{CloningWorkspaceCommand, CreatingWorkspaceCommand, UpdatingWorkspaceCommand},
+        Error, WorkspaceUseCase, WorkspaceUseCaseImpl,
+    };
 
     use crate::domain::{
         parameter::{MockParameterService, Parameter},
-        secret::MockSecretService,
-        workspace::{Error as WorkspaceServiceError, MockWorkspaceService, Workspace},
+        policy::MockPolicyService,
+        secret::{MockSecretService, SecretUsage},
+        workspace::{Error as WorkspaceServiceError, MockWorkspaceService, Workspace, WorkspaceSettings},
     };
 
     #[tokio::test]
@@ -169,21 +353,22 @@ mod test {
         let mut secret_service_mock = MockSecretService::new();
         secret_service_mock.expect_initialize_root_path().times(1).returning(|_| Ok(()));
 
-        let mut rng = <Bn462Curve as PairingCurve>::Rng::new();
         let mut parameter_service_mock = MockParameterService::new();
         parameter_service_mock
             .expect_create()
+            .withf(|_, curve| *curve == Curve::Bn462)
             .times(1)
-            .returning(move |_| Ok(Parameter { version: 1, value: GlobalParams::<Bn462Curve>::new(&mut rng) }));
+            .returning(|_, curve| Ok(Parameter { version: 1, curve, value: vec![] }));
 
         let workspace_use_case = WorkspaceUseCaseImpl::new(
             mock_connection,
             Arc::new(workspace_service_mock),
             Arc::new(secret_service_mock),
             Arc::new(parameter_service_mock),
+            Arc::new(MockPolicyService::new()),
         );
         workspace_use_case
-            .create(CreatingWorkspaceCommand { name: WORKSPACE_NAME.to_owned() })
+            .create(CreatingWorkspaceCommand { name: WORKSPACE_NAME.to_owned(), curve: Curve::Bn462 })
             .await
             .expect("creating workspace should be successful");
     }
@@ -209,8 +394,11 @@ mod test {
             Arc::new(workspace_service_mock),
             Arc::new(secret_service_mock),
             Arc::new(parameter_service_mock),
+            Arc::new(MockPolicyService::new()),
         );
-        let result = workspace_use_case.create(CreatingWorkspaceCommand { name: WORKSPACE_NAME.to_owned() }).await;
+        let result = workspace_use_case
+            .create(CreatingWorkspaceCommand { name: WORKSPACE_NAME.to_owned(), curve: Curve::Bn462 })
+            .await;
 
         assert!(matches!(result, Err(Error::Anyhow(_))));
         assert_eq!(result.err().unwrap().to_string(), "some error");
@@ -238,8 +426,11 @@ mod test {
             Arc::new(workspace_service_mock),
             Arc::new(secret_service_mock),
             Arc::new(parameter_service_mock),
+            Arc::new(MockPolicyService::new()),
         );
-        let result = workspace_use_case.create(CreatingWorkspaceCommand { name: WORKSPACE_NAME.to_owned() }).await;
+        let result = workspace_use_case
+            .create(CreatingWorkspaceCommand { name: WORKSPACE_NAME.to_owned(), curve: Curve::Bn462 })
+            .await;
 
         assert!(matches!(result, Err(Error::WorkspaceNameConflicted)));
     }
@@ -261,6 +452,7 @@ mod test {
             Arc::new(workspace_service_mock),
             Arc::new(secret_service_mock),
             Arc::new(parameter_service_mock),
+            Arc::new(MockPolicyService::new()),
         );
         let result = workspace_use_case.get_all().await;
 
@@ -289,6 +481,7 @@ mod test {
             Arc::new(workspace_service_mock),
             Arc::new(secret_service_mock),
             Arc::new(parameter_service_mock),
+            Arc::new(MockPolicyService::new()),
         );
         let result = workspace_use_case.delete_by_name(WORKSPACE_NAME).await;
 
@@ -314,9 +507,464 @@ mod test {
             Arc::new(workspace_service_mock),
             Arc::new(secret_service_mock),
             Arc::new(parameter_service_mock),
+            Arc::new(MockPolicyService::new()),
         );
         let result = workspace_use_case.delete_by_name(WORKSPACE_NAME).await;
 
         assert!(matches!(result, Err(Error::WorkspaceNotExists)));
     }
+
+    fn clone_workspace_service_mock(source_name: &'static str) -> MockWorkspaceService {
+        let mut workspace_service_mock = MockWorkspaceService::new();
+        workspace_service_mock
+            .expect_get_by_name()
+            .withf(move |_, name| name == source_name)
+            .times(1)
+            .returning(move |_, _| Ok(Some(Workspace::new(Ulid::new(), source_name.to_owned()))));
+        workspace_service_mock
+    }
+
+    #[tokio::test]
+    async fn when_cloning_workspace_use_case_should_create_workspace_with_new_parameter() {
+        const SOURCE_WORKSPACE_NAME: &str = "prod";
+        const WORKSPACE_NAME: &str = "staging";
+        let mock_database = MockDatabase::new(DatabaseBackend::Postgres).append_exec_results([
+            MockExecResult { last_insert_id: 0, rows_affected: 1 },
+            MockExecResult { last_insert_id: 0, rows_affected: 1 },
+        ]);
+        let mock_connection = Arc::new(mock_database.into_connection());
+        let mut workspace_service_mock = clone_workspace_service_mock(SOURCE_WORKSPACE_NAME);
+
+        workspace_service_mock
+            .expect_get_by_name()
+            .withf(|_, name| name == WORKSPACE_NAME)
+            .times(1)
+            .returning(|_, _| Ok(None));
+        workspace_service_mock
+            .expect_create()
+            .withf(|_, name| name == WORKSPACE_NAME)
+            .times(1)
+            .returning(|_, _| Ok(()));
+        workspace_service_mock.expect_copy_contents().times(1).returning(|_, _| Ok(()));
+        workspace_service_mock.expect_drop_scope().times(0);
+
+        let secret_service_mock = MockSecretService::new();
+
+        let mut parameter_service_mock = MockParameterService::new();
+        parameter_service_mock
+            .expect_get()
+            .withf(|_, version| version.is_none())
+            .times(1)
+            .returning(|_, _| Ok(Parameter { version: 2, curve: Curve::Bls48556, value: vec![] }));
+        parameter_service_mock
+            .expect_create()
+            .withf(|_, curve| *curve == Curve::Bls48556)
+            .times(1)
+            .returning(|_, curve| Ok(Parameter { version: 1, curve, value: vec![] }));
+
+        let workspace_use_case = WorkspaceUseCaseImpl::new(
+            mock_connection,
+            Arc::new(workspace_service_mock),
+            Arc::new(secret_service_mock),
+            Arc::new(parameter_service_mock),
+            Arc::new(MockPolicyService::new()),
+        );
+        workspace_use_case
+            .clone_workspace(CloningWorkspaceCommand {
+                source_name: SOURCE_WORKSPACE_NAME.to_owned(),
+                name: WORKSPACE_NAME.to_owned(),
+            })
+            .await
+            .expect("cloning workspace should be successful");
+    }
+
+    #[tokio::test]
+    async fn when_copying_contents_fails_then_use_case_should_drop_scope_of_cloned_workspace() {
+        const SOURCE_WORKSPACE_NAME: &str = "prod";
+        const WORKSPACE_NAME: &str = "staging";
+        let mock_database = MockDatabase::new(DatabaseBackend::Postgres).append_exec_results([
+            MockExecResult { last_insert_id: 0, rows_affected: 1 },
+            MockExecResult { last_insert_id: 0, rows_affected: 1 },
+        ]);
+        let mock_connection = Arc::new(mock_database.into_connection());
+        let mut workspace_service_mock = clone_workspace_service_mock(SOURCE_WORKSPACE_NAME);
+
+        workspace_service_mock
+            .expect_get_by_name()
+            .withf(|_, name| name == WORKSPACE_NAME)
+            .times(1)
+            .returning(|_, _| Ok(None));
+        workspace_service_mock.expect_create().times(1).returning(|_, _| Ok(()));
+        workspace_service_mock
+            .expect_copy_contents()
+            .times(1)
+            .returning(|_, _| Err(WorkspaceServiceError::Anyhow(anyhow!("some error"))));
+        workspace_service_mock.expect_drop_scope().withf(|name| name == WORKSPACE_NAME).times(1).returning(|_| Ok(()));
+
+        let workspace_use_case = WorkspaceUseCaseImpl::new(
+            mock_connection,
+            Arc::new(workspace_service_mock),
+            Arc::new(MockSecretService::new()),
+            Arc::new(MockParameterService::new()),
+            Arc::new(MockPolicyService::new()),
+        );
+        let result = workspace_use_case
+            .clone_workspace(CloningWorkspaceCommand {
+                source_name: SOURCE_WORKSPACE_NAME.to_owned(),
+                name: WORKSPACE_NAME.to_owned(),
+            })
+            .await;
+
+        assert!(matches!(result, Err(Error::Anyhow(_))));
+    }
+
+    #[tokio::test]
+    async fn when_cloning_workspace_to_existing_name_use_case_should_not_drop_scope_of_existing_workspace() {
+        const SOURCE_WORKSPACE_NAME: &str = "prod";
+        const WORKSPACE_NAME: &str = "staging";
+        let mock_database = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 1 }]);
+        let mock_connection = Arc::new(mock_database.into_connection());
+        let mut workspace_service_mock = clone_workspace_service_mock(SOURCE_WORKSPACE_NAME);
+
+        workspace_service_mock
+            .expect_get_by_name()
+            .withf(|_, name| name == WORKSPACE_NAME)
+            .times(1)
+            .returning(|_, _| Ok(Some(Workspace::new(Ulid::new(), WORKSPACE_NAME.to_owned()))));
+        workspace_service_mock.expect_create().times(0);
+        workspace_service_mock.expect_drop_scope().times(0);
+
+        let workspace_use_case = WorkspaceUseCaseImpl::new(
+            mock_connection,
+            Arc::new(workspace_service_mock),
+            Arc::new(MockSecretService::new()),
+            Arc::new(MockParameterService::new()),
+            Arc::new(MockPolicyService::new()),
+        );
+        let result = workspace_use_case
+            .clone_workspace(CloningWorkspaceCommand {
+                source_name: SOURCE_WORKSPACE_NAME.to_owned(),
+                name: WORKSPACE_NAME.to_owned(),
+            })
+            .await;
+
+        assert!(matches!(result, Err(Error::WorkspaceNameConflicted)));
+    }
+
+    #[tokio::test]
+    async fn when_cloning_not_existing_workspace_use_case_should_returns_workspace_not_exists_error() {
+        const SOURCE_WORKSPACE_NAME: &str = "prod";
+        let mock_database = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 1 }]);
+        let mock_connection = Arc::new(mock_database.into_connection());
+        let mut workspace_service_mock = MockWorkspaceService::new();
+
+        workspace_service_mock
+            .expect_get_by_name()
+            .withf(|_, name| name == SOURCE_WORKSPACE_NAME)
+            .times(1)
+            .returning(|_, _| Ok(None));
+        workspace_service_mock.expect_create().times(0);
+
+        let secret_service_mock = MockSecretService::new();
+        let parameter_service_mock = MockParameterService::new();
+
+        let workspace_use_case = WorkspaceUseCaseImpl::new(
+            mock_connection,
+            Arc::new(workspace_service_mock),
+            Arc::new(secret_service_mock),
+            Arc::new(parameter_service_mock),
+            Arc::new(MockPolicyService::new()),
+        );
+        let result = workspace_use_case
+            .clone_workspace(CloningWorkspaceCommand {
+                source_name: SOURCE_WORKSPACE_NAME.to_owned(),
+                name: "staging".to_owned(),
+            })
+            .await;
+
+        assert!(matches!(result, Err(Error::WorkspaceNotExists)));
+    }
+
+    #[tokio::test]
+    async fn when_updating_workspace_with_invalid_name_use_case_should_returns_invalid_workspace_name_error() {
+        const WORKSPACE_NAME: &str = "testworkspace";
+        let mock_database = Arc::new(
+            MockDatabase::new(DatabaseBackend::Postgres)
+                .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 1 }])
+                .into_connection(),
+        );
+        let mut workspace_service_mock = MockWorkspaceService::new();
+        let secret_service_mock = MockSecretService::new();
+        let parameter_service_mock = MockParameterService::new();
+
+        workspace_service_mock
+            .expect_get_by_name_for_update()
+            .withf(|_, name| name == WORKSPACE_NAME)
+            .times(1)
+            .returning(|_, _| Ok(Some(Workspace::new(Ulid::new(), WORKSPACE_NAME.to_owned()))));
+
+        let workspace_use_case = WorkspaceUseCaseImpl::new(
+            mock_database,
+            Arc::new(workspace_service_mock),
+            Arc::new(secret_service_mock),
+            Arc::new(parameter_service_mock),
+            Arc::new(MockPolicyService::new()),
+        );
+        let result = workspace_use_case
+            .update(
+                WORKSPACE_NAME,
+                UpdatingWorkspaceCommand { name: Some("invalid name!".to_owned()), display_name: None, settings: None },
+            )
+            .await;
+
+        assert!(matches!(result, Err(Error::InvalidWorkspaceName)));
+    }
+
+    #[tokio::test]
+    async fn when_updating_deleted_workspace_use_case_should_returns_workspace_not_exists_error() {
+        const WORKSPACE_NAME: &str = "testworkspace";
+        let mock_database = Arc::new(
+            MockDatabase::new(DatabaseBackend::Postgres)
+                .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 1 }])
+                .into_connection(),
+        );
+        let mut workspace_service_mock = MockWorkspaceService::new();
+        let secret_service_mock = MockSecretService::new();
+        let parameter_service_mock = MockParameterService::new();
+
+        workspace_service_mock
+            .expect_get_by_name_for_update()
+            .withf(|_, name| name == WORKSPACE_NAME)
+            .times(1)
+            .returning(|_, _| {
+                let mut workspace = Workspace::new(Ulid::new(), WORKSPACE_NAME.to_owned());
+                workspace.delete();
+                Ok(Some(workspace))
+            });
+
+        let workspace_use_case = WorkspaceUseCaseImpl::new(
+            mock_database,
+            Arc::new(workspace_service_mock),
+            Arc::new(secret_service_mock),
+            Arc::new(parameter_service_mock),
+            Arc::new(MockPolicyService::new()),
+        );
+        let result = workspace_use_case
+            .update(
+                WORKSPACE_NAME,
+                UpdatingWorkspaceCommand { name: None, display_name: Some("Test".to_owned()), settings: None },
+            )
+            .await;
+
+        assert!(matches!(result, Err(Error::WorkspaceNotExists)));
+    }
+
+    #[tokio::test]
+    async fn when_renaming_workspace_to_existing_name_use_case_should_returns_workspace_name_conflicted_error() {
+        const WORKSPACE_NAME: &str = "testworkspace";
+        const NEW_WORKSPACE_NAME: &str = "otherworkspace";
+        let mock_database = Arc::new(
+            MockDatabase::new(DatabaseBackend::Postgres)
+                .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 1 }])
+                .into_connection(),
+        );
+        let mut workspace_service_mock = MockWorkspaceService::new();
+        let mut secret_service_mock = MockSecretService::new();
+        let parameter_service_mock = MockParameterService::new();
+
+        workspace_service_mock
+            .expect_get_by_name_for_update()
+            .withf(|_, name| name == WORKSPACE_NAME)
+            .times(1)
+            .returning(|_, name| Ok(Some(Workspace::new(Ulid::new(), name.to_owned()))));
+        workspace_service_mock
+            .expect_get_by_name()
+            .withf(|_, name| name == NEW_WORKSPACE_NAME)
+            .times(1)
+            .returning(|_, name| Ok(Some(Workspace::new(Ulid::new(), name.to_owned()))));
+        secret_service_mock.expect_get_usage().times(0);
+
+        let workspace_use_case = WorkspaceUseCaseImpl::new(
+            mock_database,
+            Arc::new(workspace_service_mock),
+            Arc::new(secret_service_mock),
+            Arc::new(parameter_service_mock),
+            Arc::new(MockPolicyService::new()),
+        );
+        let result = workspace_use_case
+            .update(
+                WORKSPACE_NAME,
+                UpdatingWorkspaceCommand {
+                    name: Some(NEW_WORKSPACE_NAME.to_owned()),
+                    display_name: None,
+                    settings: None,
+                },
+            )
+            .await;
+
+        assert!(matches!(result, Err(Error::WorkspaceNameConflicted)));
+    }
+
+    #[tokio::test]
+    async fn when_renaming_workspace_having_secrets_use_case_should_returns_workspace_has_secrets_error() {
+        const WORKSPACE_NAME: &str = "testworkspace";
+        let mock_database = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 1 }]);
+        let mock_connection = Arc::new(mock_database.into_connection());
+        let mut workspace_service_mock = MockWorkspaceService::new();
+        let mut secret_service_mock = MockSecretService::new();
+        let parameter_service_mock = MockParameterService::new();
+
+        workspace_service_mock
+            .expect_get_by_name_for_update()
+            .withf(|_, name| name == WORKSPACE_NAME)
+            .times(1)
+            .returning(|_, _| Ok(Some(Workspace::new(Ulid::new(), WORKSPACE_NAME.to_owned()))));
+        workspace_service_mock
+            .expect_get_by_name()
+            .withf(|_, name| name == "newworkspace")
+            .times(1)
+            .returning(|_, _| Ok(None));
+        secret_service_mock
+            .expect_get_usage()
+            .times(1)
+            .returning(|_| Ok(SecretUsage { secret_count: 1, ..Default::default() }));
+
+        let workspace_use_case = WorkspaceUseCaseImpl::new(
+            mock_connection,
+            Arc::new(workspace_service_mock),
+            Arc::new(secret_service_mock),
+            Arc::new(parameter_service_mock),
+            Arc::new(MockPolicyService::new()),
+        );
+        let result = workspace_use_case
+            .update(
+                WORKSPACE_NAME,
+                UpdatingWorkspaceCommand { name: Some("newworkspace".to_owned()), display_name: None, settings: None },
+            )
+            .await;
+
+        assert!(matches!(result, Err(Error::WorkspaceHasSecrets)));
+    }
+
+    #[tokio::test]
+    async fn when_renaming_workspace_without_secrets_use_case_should_rename_scope_in_transaction_locking_workspace() {
+        const WORKSPACE_NAME: &str = "testworkspace";
+        let mock_database = MockDatabase::new(DatabaseBackend::Postgres).append_exec_results([
+            MockExecResult { last_insert_id: 0, rows_affected: 1 },
+            MockExecResult { last_insert_id: 0, rows_affected: 1 },
+        ]);
+        let mock_connection = Arc::new(mock_database.into_connection());
+        let mut workspace_service_mock = MockWorkspaceService::new();
+        let mut secret_service_mock = MockSecretService::new();
+
+        workspace_service_mock
+            .expect_get_by_name_for_update()
+            .withf(|_, name| name == WORKSPACE_NAME)
+            .times(1)
+            .returning(|_, _| Ok(Some(Workspace::new(Ulid::new(), WORKSPACE_NAME.to_owned()))));
+        workspace_service_mock
+            .expect_get_by_name()
+            .withf(|_, name| name == "newworkspace")
+            .times(1)
+            .returning(|_, _| Ok(None));
+        secret_service_mock.expect_get_usage().times(1).returning(|_| Ok(SecretUsage::default()));
+        workspace_service_mock
+            .expect_rename_scope()
+            .withf(|_, name, new_name| name == WORKSPACE_NAME && new_name == "newworkspace")
+            .times(1)
+            .returning(|_, _, _| Ok(Default::default()));
+
+        let workspace_use_case = WorkspaceUseCaseImpl::new(
+            mock_connection,
+            Arc::new(workspace_service_mock),
+            Arc::new(secret_service_mock),
+            Arc::new(MockParameterService::new()),
+            Arc::new(MockPolicyService::new()),
+        );
+        let result = workspace_use_case
+            .update(
+                WORKSPACE_NAME,
+                UpdatingWorkspaceCommand { name: Some("newworkspace".to_owned()), display_name: None, settings: None },
+            )
+            .await;
+
+        result.expect("renaming workspace should be successful");
+    }
+
+    #[tokio::test]
+    async fn when_updating_settings_with_not_existing_access_condition_use_case_should_returns_access_condition_not_exists_error(
+    ) {
+        const WORKSPACE_NAME: &str = "testworkspace";
+        let access_condition_id = Ulid::new();
+        let mock_database = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 1 }]);
+        let mock_connection = Arc::new(mock_database.into_connection());
+        let mut workspace_service_mock = MockWorkspaceService::new();
+        let secret_service_mock = MockSecretService::new();
+        let parameter_service_mock = MockParameterService::new();
+        let mut policy_service_mock = MockPolicyService::new();
+
+        workspace_service_mock
+            .expect_get_by_name_for_update()
+            .withf(|_, name| name == WORKSPACE_NAME)
+            .times(1)
+            .returning(|_, _| Ok(Some(Workspace::new(Ulid::new(), WORKSPACE_NAME.to_owned()))));
+        policy_service_mock
+            .expect_get()
+            .withf(move |_, id| id == &access_condition_id)
+            .times(1)
+            .returning(|_, _| Ok(None));
+
+        let workspace_use_case = WorkspaceUseCaseImpl::new(
+            mock_connection,
+            Arc::new(workspace_service_mock),
+            Arc::new(secret_service_mock),
+            Arc::new(parameter_service_mock),
+            Arc::new(policy_service_mock),
+        );
+        let result = workspace_use_case
+            .update(
+                WORKSPACE_NAME,
+                UpdatingWorkspaceCommand {
+                    name: None,
+                    display_name: None,
+                    settings: Some(WorkspaceSettings {
+                        default_access_condition_ids: vec![access_condition_id],
+                        ..Default::default()
+                    }),
+                },
+            )
+            .await;
+
+        assert!(
+            matches!(result, Err(Error::AccessConditionNotExists { entered_access_condition_id }) if entered_access_condition_id == access_condition_id)
+        );
+    }
+
+    #[tokio::test]
+    async fn when_purging_deleted_workspaces_use_case_should_returns_purged_workspace_names() {
+        let mock_database = Arc::new(MockDatabase::new(DatabaseBackend::Postgres).into_connection());
+        let mut workspace_service_mock = MockWorkspaceService::new();
+        let secret_service_mock = MockSecretService::new();
+        let parameter_service_mock = MockParameterService::new();
+
+        workspace_service_mock
+            .expect_purge_deleted()
+            .times(1)
+            .returning(|_, _| Ok((vec!["testworkspace".to_owned()], Default::default())));
+
+        let workspace_use_case = WorkspaceUseCaseImpl::new(
+            mock_database,
+            Arc::new(workspace_service_mock),
+            Arc::new(secret_service_mock),
+            Arc::new(parameter_service_mock),
+            Arc::new(MockPolicyService::new()),
+        );
+        let result = workspace_use_case.purge_deleted(Duration::from_secs(60)).await;
+
+        assert_eq!(result.expect("purging workspaces should be successful"), vec!["testworkspace".to_owned()]);
+    }
 }
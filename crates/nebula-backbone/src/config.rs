@@ -1,22 +1,65 @@
-use std::path::Path;
+use std::{collections::HashMap, path::PathBuf};
 
 use crate::Args;
-use config::{Config, File, FileFormat};
+use config::Config;
+use nebula_abe::curves::Curve;
+use nebula_config::{config_file, Environment, ENV_PREFIX};
+use nebula_telemetry::trace::TracingConfig;
+use nebula_tls::{ClientTlsConfig, TlsConfig};
+use nebula_token::rate_limit::RateLimitQuota;
 use serde::Deserialize;
 use url::Url;
 
 #[derive(Deserialize, Debug)]
-pub(crate) struct ApplicationConfig {
+pub struct ApplicationConfig {
     pub port: u16,
     pub jwks_url: Url,
     pub jwks_refresh_interval: Option<u64>,
+    /// TLS settings of the client fetching `jwks_url`.
+    pub jwks_tls: Option<ClientTlsConfig>,
     pub database: DatabaseConfig,
     pub workspace: WorkspaceConfig,
+    pub workspace_deletion_grace_period: Option<u64>,
+    pub authority_public_key_refresh_interval: Option<u64>,
+    pub authority_health_check_interval: Option<u64>,
+    /// TLS settings of the client requesting the authorities, e.g. a client certificate for mutual TLS.
+    pub authority_tls: Option<ClientTlsConfig>,
     pub cors: Option<CorsConfig>,
+    pub quota: Option<QuotaConfig>,
+    pub rate_limit: Option<RateLimitConfig>,
+    pub tracing: Option<TracingConfig>,
+    /// Serves `GET /metrics` on this port, apart from the API. Metrics are not served if not given.
+    pub metrics_port: Option<u16>,
+    pub tls: Option<TlsConfig>,
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct QuotaConfig {
+    pub max_secrets: Option<u64>,
+    pub max_ciphertext_bytes: Option<u64>,
+    pub max_paths: Option<u64>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct RateLimitConfig {
+    #[serde(flatten)]
+    pub default: RateLimitQuota,
+    #[serde(default)]
+    pub workspaces: HashMap<String, RateLimitQuota>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DatabaseConfig {
+    Postgres(PostgresConfig),
+    /// Stores the public tables and each workspace in separate database files under the directory.
+    Sqlite {
+        path: PathBuf,
+    },
 }
 
 #[derive(Deserialize, Debug)]
-pub struct DatabaseConfig {
+pub struct PostgresConfig {
     pub host: String,
     pub port: u16,
     pub database_name: String,
@@ -32,7 +75,7 @@ pub enum DatabaseAuthConfig {
 
 #[derive(Deserialize, Debug, Clone)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE", tag = "type", content = "domains")]
-pub(crate) enum CorsConfig {
+pub enum CorsConfig {
     AllowAll,
     AllowList(Vec<String>),
 }
@@ -40,47 +83,37 @@ pub(crate) enum CorsConfig {
 #[derive(Deserialize, Debug)]
 #[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum WorkspaceConfig {
-    Static { name: String },
+    Static {
+        name: String,
+        /// Curve of the parameter created with the workspace.
+        #[serde(default)]
+        curve: Curve,
+    },
     Dynamic,
 }
 
-pub(super) fn load_config(args: Args) -> anyhow::Result<ApplicationConfig> {
-    let config_file_path = if let Some(path_override) = args.config {
-        path_override
-    } else {
-        let xdg_dirs = xdg::BaseDirectories::with_prefix("nebula").unwrap();
-
-        let user_config_dir = xdg_dirs.get_config_home();
-        if !user_config_dir.exists() {
-            std::fs::create_dir_all(&user_config_dir)?;
-        }
-
-        let mut config_file_path = user_config_dir.clone();
-        config_file_path.push("backbone_config.toml");
-
-        if !config_file_path.exists() {
-            write_default_config_file(&config_file_path)?;
-        }
+pub fn load_config(args: Args) -> anyhow::Result<ApplicationConfig> {
+    Ok(build_config(args)?.try_deserialize()?)
+}
 
-        config_file_path
-    };
+pub fn dump_config(args: Args) -> anyhow::Result<String> {
+    nebula_config::dump(&build_config(args)?, &[])
+}
 
-    let config: ApplicationConfig = Config::builder()
-        .add_source(File::new(config_file_path.to_str().unwrap(), FileFormat::Toml))
+/// Merges the configuration sources. Later sources take precedence: config file, `NEBULA_*` environment variables and
+/// command line arguments. The config file is optional unless its path is given.
+fn build_config(args: Args) -> anyhow::Result<Config> {
+    let config = Config::builder()
+        .add_source(config_file(args.config, "backbone_config.toml")?)
+        .add_source(Environment::new(ENV_PREFIX))
+        .set_default("database.type", "POSTGRES")?
         .set_override_option("port", args.port.map(|port| port.to_string()))?
         .set_override_option("database.host", args.database_host)?
         .set_override_option("database.port", args.database_port)?
         .set_override_option("database.database_name", args.database_name)?
         .set_override_option("database.auth.username", args.database_username)?
         .set_override_option("database.auth.password", args.database_password)?
-        .build()?
-        .try_deserialize()?;
+        .build()?;
 
     Ok(config)
 }
-
-fn write_default_config_file(path: &Path) -> anyhow::Result<()> {
-    let default_config_content = include_str!("../static/default_config.toml");
-    std::fs::write(path, default_config_content)?;
-    Ok(())
-}
@@ -11,10 +11,26 @@ pub struct Model {
     pub name: String,
     pub host: String,
     pub public_key: Option<String>,
+    pub status: Option<AuthorityStatus>,
+    pub latest_key_version: Option<i64>,
+    pub status_checked_at: Option<DateTime<Utc>>,
+    /// Hash of the credential with which the authority authenticates itself once registered.
+    pub credential_hash: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+#[derive(EnumIter, DeriveActiveEnum, Clone, Copy, Debug, PartialEq)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(32))")]
+pub enum AuthorityStatus {
+    #[sea_orm(string_value = "AVAILABLE")]
+    Available,
+    #[sea_orm(string_value = "ARMORED")]
+    Armored,
+    #[sea_orm(string_value = "UNAVAILABLE")]
+    Unavailable,
+}
+
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {}
 
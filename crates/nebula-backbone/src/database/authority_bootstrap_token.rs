@@ -0,0 +1,22 @@
+use chrono::{DateTime, Utc};
+use sea_orm::prelude::*;
+
+use super::UlidId;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "authority_bootstrap_token")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: UlidId,
+    pub token_hash: String,
+    /// Authority which registered itself with the token, which is `None` until the token is used.
+    pub authority_id: Option<UlidId>,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,36 @@
+use chrono::{DateTime, Utc};
+use sea_orm::prelude::*;
+
+use super::{parameter::ParameterCurve, UlidId};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "authority_public_key")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: UlidId,
+    pub authority_id: UlidId,
+    pub version: i64,
+    pub parameter_version: i32,
+    pub curve: ParameterCurve,
+    pub public_key: String,
+    pub status: PublicKeyStatus,
+    pub status_checked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(EnumIter, DeriveActiveEnum, Clone, Copy, Debug, PartialEq)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(32))")]
+pub enum PublicKeyStatus {
+    #[sea_orm(string_value = "ACTIVE")]
+    Active,
+    #[sea_orm(string_value = "RETIRED")]
+    Retired,
+    #[sea_orm(string_value = "REVOKED")]
+    Revoked,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,61 @@
+use async_trait::async_trait;
+use sea_orm_migration::{prelude::*, schema::*, sea_orm::DatabaseBackend};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[derive(DeriveIden)]
+pub enum Workspace {
+    Table,
+    DisplayName,
+    Settings,
+    DeletedAt,
+}
+
+#[async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() == DatabaseBackend::Sqlite {
+            // SQLite alters only one column per statement.
+            for column in [
+                string_len_null(Workspace::DisplayName, 255),
+                json_binary(Workspace::Settings).default("{}").take(),
+                timestamp_with_time_zone_null(Workspace::DeletedAt),
+            ] {
+                manager.alter_table(Table::alter().table(Workspace::Table).add_column(column).take()).await?;
+            }
+            return Ok(());
+        }
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Workspace::Table)
+                    .add_column_if_not_exists(string_len_null(Workspace::DisplayName, 255))
+                    .add_column_if_not_exists(json_binary(Workspace::Settings).default(Expr::cust("'{}'::jsonb")))
+                    .add_column_if_not_exists(timestamp_with_time_zone_null(Workspace::DeletedAt))
+                    .take(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() == DatabaseBackend::Sqlite {
+            for column in [Workspace::DisplayName, Workspace::Settings, Workspace::DeletedAt] {
+                manager.alter_table(Table::alter().table(Workspace::Table).drop_column(column).take()).await?;
+            }
+            return Ok(());
+        }
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Workspace::Table)
+                    .drop_column(Workspace::DisplayName)
+                    .drop_column(Workspace::Settings)
+                    .drop_column(Workspace::DeletedAt)
+                    .take(),
+            )
+            .await
+    }
+}
@@ -3,13 +3,17 @@ use sea_orm::DbErr;
 use sea_orm_migration::{IntoSchemaManagerConnection, MigrationTrait, MigratorTrait};
 
 mod m20241126_001_init_backbone;
+mod m20241210_001_add_workspace_settings;
 
 pub struct Migrator;
 
 #[async_trait]
 impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-        vec![Box::new(m20241126_001_init_backbone::Migration)]
+        vec![
+            Box::new(m20241126_001_init_backbone::Migration),
+            Box::new(m20241210_001_add_workspace_settings::Migration),
+        ]
     }
 }
 
@@ -1,7 +1,9 @@
 use std::{
     borrow::Borrow,
+    collections::HashMap,
     fmt::Display,
     ops::Deref,
+    path::{Path, PathBuf},
     sync::Arc,
     time::{Duration, SystemTime},
 };
@@ -14,12 +16,17 @@ use aws_sigv4::{
     http_request::{sign, SignableBody, SignableRequest, SigningSettings},
     sign::v4::SigningParams,
 };
+use lazy_static::lazy_static;
 use nebula_common::validate_workspace_name;
-use sea_orm::sqlx::postgres::PgConnectOptions;
+use sea_orm::sqlx::{
+    postgres::PgConnectOptions,
+    sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions},
+};
 use sea_orm::{
     ConnectOptions, ConnectionTrait, Database, DatabaseBackend, DatabaseConnection, DatabaseTransaction, DbErr,
-    Statement, TransactionTrait, TryFromU64, TryGetError,
+    SqlxSqliteConnector, Statement, TransactionTrait, TryFromU64, TryGetError,
 };
+use tokio::sync::Mutex;
 use ulid::Ulid;
 use url::Url;
 
@@ -30,15 +37,34 @@ pub(crate) mod applied_path_policy;
 pub(crate) mod applied_path_policy_allowed_action;
 pub(crate) mod applied_policy;
 pub(crate) mod authority;
+pub(crate) mod authority_bootstrap_token;
+pub(crate) mod authority_public_key;
 mod migration;
 pub(crate) mod parameter;
 pub(crate) mod path;
 pub(crate) mod policy;
+pub(crate) mod role;
+pub(crate) mod role_permission;
 pub(crate) mod secret_metadata;
 pub(crate) mod secret_value;
 pub(crate) mod workspace;
 mod workspace_migration;
 
+const SQLITE_PUBLIC_DATABASE_FILE: &str = "nebula.db";
+const SQLITE_WORKSPACE_DIRECTORY: &str = "workspaces";
+
+lazy_static! {
+    static ref SQLITE_WORKSPACE_CONNECTIONS: Mutex<HashMap<PathBuf, Arc<DatabaseConnection>>> = Default::default();
+}
+
+/// Location of the database. Workspaces are isolated by schemas on Postgres, and by database files on SQLite.
+#[derive(Clone)]
+pub enum DatabaseLocation {
+    Postgres { host: String, port: u16, database_name: String, auth: AuthMethod },
+    Sqlite { path: PathBuf },
+}
+
+#[derive(Clone)]
 pub enum AuthMethod {
     Credential { username: String, password: Option<String> },
     RdsIamAuth { host: String, port: u16, username: String },
@@ -90,13 +116,28 @@ async fn generate_rds_iam_token(db_hostname: &str, port: u16, db_username: &str)
     Ok(response)
 }
 
-pub async fn connect_to_database(
-    host: &str,
-    port: u16,
-    database_name: &str,
-    auth: &AuthMethod,
+pub async fn connect_to_database(location: &DatabaseLocation) -> anyhow::Result<Arc<DatabaseConnection>> {
+    let connection = match location {
+        DatabaseLocation::Postgres { host, port, database_name, auth } => {
+            connect_to_database_with_search_path(host, *port, database_name, auth, None).await?
+        }
+        DatabaseLocation::Sqlite { path } => connect_to_sqlite_database(path, None).await?,
+    };
+    nebula_telemetry::metrics::record_database_pool_periodically(connection.clone());
+
+    Ok(connection)
+}
+
+async fn connect_to_workspace_database(
+    location: &DatabaseLocation,
+    workspace_slug: &str,
 ) -> anyhow::Result<Arc<DatabaseConnection>> {
-    connect_to_database_with_search_path(host, port, database_name, auth, None).await
+    match location {
+        DatabaseLocation::Postgres { host, port, database_name, auth } => {
+            connect_to_database_with_search_path(host, *port, database_name, auth, Some(workspace_slug)).await
+        }
+        DatabaseLocation::Sqlite { path } => sqlite_workspace_connection(path, workspace_slug).await,
+    }
 }
 
 async fn connect_to_database_with_search_path(
@@ -140,6 +181,216 @@ async fn connect_to_database_with_search_path(
     Ok(connection)
 }
 
+/// Connects to the public database file, or to the database file of the workspace which attaches the public one as
+/// `public`, so that the public tables are accessible in the workspace scope like the `public` schema on Postgres.
+async fn connect_to_sqlite_database(
+    path: &Path,
+    workspace_slug: Option<&str>,
+) -> anyhow::Result<Arc<DatabaseConnection>> {
+    let public_database_file = path.join(SQLITE_PUBLIC_DATABASE_FILE);
+    let database_file = if let Some(workspace_slug) = workspace_slug {
+        if !validate_workspace_name(workspace_slug) {
+            bail!("workspace slug is invalid");
+        }
+        sqlite_workspace_file(path, workspace_slug)
+    } else {
+        public_database_file.clone()
+    };
+    if let Some(directory) = database_file.parent() {
+        std::fs::create_dir_all(directory)?;
+    }
+
+    let options = SqliteConnectOptions::new()
+        .filename(&database_file)
+        .create_if_missing(true)
+        // Readers do not block the writer, e.g. while copying a workspace into a new one.
+        .journal_mode(SqliteJournalMode::Wal)
+        .foreign_keys(true)
+        // `LIKE` is case-insensitive on SQLite by default, while paths are matched case-sensitively on Postgres.
+        .pragma("case_sensitive_like", "ON");
+    let options = sea_orm::sqlx::ConnectOptions::log_statements(options, tracing::log::LevelFilter::Debug);
+
+    let mut pool_options = SqlitePoolOptions::new();
+    if workspace_slug.is_some() {
+        let attach_statement =
+            format!("ATTACH DATABASE '{}' AS public;", public_database_file.to_string_lossy().replace('\'', "''"));
+        pool_options = pool_options.after_connect(move |connection, _| {
+            let attach_statement = attach_statement.clone();
+            Box::pin(async move {
+                sea_orm::sqlx::query(&attach_statement).execute(connection).await?;
+                Ok(())
+            })
+        });
+    }
+    let pool = pool_options.connect_with(options).await?;
+
+    Ok(Arc::new(SqlxSqliteConnector::from_sqlx_sqlite_pool(pool)))
+}
+
+fn sqlite_workspace_file(path: &Path, workspace_slug: &str) -> PathBuf {
+    path.join(SQLITE_WORKSPACE_DIRECTORY).join(format!("{workspace_slug}.db"))
+}
+
+/// Returns the connection to the database file of the workspace, which is shared until the workspace is dropped or
+/// renamed.
+async fn sqlite_workspace_connection(path: &Path, workspace_slug: &str) -> anyhow::Result<Arc<DatabaseConnection>> {
+    let mut connections = SQLITE_WORKSPACE_CONNECTIONS.lock().await;
+    let database_file = sqlite_workspace_file(path, workspace_slug);
+    if let Some(connection) = connections.get(&database_file) {
+        return Ok(connection.clone());
+    }
+
+    let connection = connect_to_sqlite_database(path, Some(workspace_slug)).await?;
+    connections.insert(database_file, connection.clone());
+    Ok(connection)
+}
+
+/// Finds the directory of the SQLite databases from the public database file, which is either the main database of
+/// the connection or attached as `public`.
+async fn sqlite_path<C: ConnectionTrait>(connection: &C) -> Result<PathBuf, DbErr> {
+    let public_database_file: String = connection
+        .query_one(Statement::from_string(
+            DatabaseBackend::Sqlite,
+            "SELECT file FROM pragma_database_list WHERE name IN ('main', 'public') ORDER BY name = 'public' DESC;",
+        ))
+        .await?
+        .ok_or_else(|| DbErr::Custom("sqlite database file is not found".to_owned()))?
+        .try_get("", "file")?;
+
+    Path::new(&public_database_file)
+        .parent()
+        .map(Path::to_path_buf)
+        .ok_or_else(|| DbErr::Custom("sqlite database directory is not found".to_owned()))
+}
+
+/// Closes the connection to the database file of the workspace and moves or removes the file with its journals.
+async fn release_sqlite_workspace_file(
+    path: &Path,
+    workspace_slug: &str,
+    rename_to: Option<&str>,
+) -> Result<(), DbErr> {
+    let database_file = sqlite_workspace_file(path, workspace_slug);
+    if let Some(connection) = SQLITE_WORKSPACE_CONNECTIONS.lock().await.remove(&database_file) {
+        connection.get_sqlite_connection_pool().close().await;
+    }
+
+    for suffix in ["", "-wal", "-shm"] {
+        let file = PathBuf::from(format!("{}{suffix}", database_file.display()));
+        if !file.exists() {
+            continue;
+        }
+        let result = match rename_to {
+            Some(rename_to) => {
+                std::fs::rename(&file, format!("{}{suffix}", sqlite_workspace_file(path, rename_to).display()))
+            }
+            None => std::fs::remove_file(&file),
+        };
+        result.map_err(|e| DbErr::Custom(format!("failed to release {}: {e}", file.display())))?;
+    }
+
+    Ok(())
+}
+
+/// Database files of workspaces to move or remove on SQLite. The files are not part of a transaction, so they are
+/// changed only once the transaction changing the rows of the workspaces is committed, and are left untouched if it is
+/// rolled back. On Postgres, the schemas are changed in the transaction and nothing is pending.
+#[derive(Debug, Default)]
+#[must_use = "the database files of the workspaces are changed only when the pending changes are applied"]
+pub struct PendingWorkspaceFiles {
+    releases: Vec<(PathBuf, String, Option<String>)>,
+}
+
+impl PendingWorkspaceFiles {
+    fn release(path: PathBuf, workspace_slug: &str, rename_to: Option<&str>) -> Self {
+        Self { releases: vec![(path, workspace_slug.to_owned(), rename_to.map(str::to_owned))] }
+    }
+
+    pub fn merge(&mut self, other: PendingWorkspaceFiles) {
+        self.releases.extend(other.releases);
+    }
+
+    /// Moves or removes the files, which is called after the transaction is committed.
+    pub async fn apply(self) -> Result<(), DbErr> {
+        for (path, workspace_slug, rename_to) in self.releases {
+            release_sqlite_workspace_file(&path, &workspace_slug, rename_to.as_deref()).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Creates the schema of the workspace. On SQLite, the database file of the workspace is created by its migration.
+pub(crate) async fn create_workspace_scope<C: ConnectionTrait>(
+    connection: &C,
+    workspace_slug: &str,
+) -> Result<(), DbErr> {
+    match connection.get_database_backend() {
+        DatabaseBackend::Sqlite => {}
+        backend => {
+            connection
+                .execute(Statement::from_string(backend, format!("CREATE SCHEMA IF NOT EXISTS \"{workspace_slug}\";")))
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Drops the schema of the workspace. On SQLite, the database file of the workspace is removed when the returned
+/// changes are applied.
+pub(crate) async fn drop_workspace_scope<C: ConnectionTrait>(
+    connection: &C,
+    workspace_slug: &str,
+) -> Result<PendingWorkspaceFiles, DbErr> {
+    match connection.get_database_backend() {
+        DatabaseBackend::Sqlite => {
+            Ok(PendingWorkspaceFiles::release(sqlite_path(connection).await?, workspace_slug, None))
+        }
+        backend => {
+            connection
+                .execute(Statement::from_string(
+                    backend,
+                    format!("DROP SCHEMA IF EXISTS \"{workspace_slug}\" CASCADE;"),
+                ))
+                .await?;
+            Ok(PendingWorkspaceFiles::default())
+        }
+    }
+}
+
+/// Renames the schema of the workspace. On SQLite, the database file of the workspace is renamed when the returned
+/// changes are applied.
+pub(crate) async fn rename_workspace_scope<C: ConnectionTrait>(
+    connection: &C,
+    workspace_slug: &str,
+    updated_slug: &str,
+) -> Result<PendingWorkspaceFiles, DbErr> {
+    match connection.get_database_backend() {
+        DatabaseBackend::Sqlite => {
+            Ok(PendingWorkspaceFiles::release(sqlite_path(connection).await?, workspace_slug, Some(updated_slug)))
+        }
+        backend => {
+            connection
+                .execute(Statement::from_string(
+                    backend,
+                    format!("ALTER SCHEMA \"{workspace_slug}\" RENAME TO \"{updated_slug}\";"),
+                ))
+                .await?;
+            Ok(PendingWorkspaceFiles::default())
+        }
+    }
+}
+
+pub async fn close_database(connection: &DatabaseConnection) {
+    match connection.get_database_backend() {
+        DatabaseBackend::Sqlite => {
+            for (_, workspace_connection) in SQLITE_WORKSPACE_CONNECTIONS.lock().await.drain() {
+                workspace_connection.get_sqlite_connection_pool().close().await;
+            }
+            connection.get_sqlite_connection_pool().close().await;
+        }
+        _ => connection.get_postgres_connection_pool().close().await,
+    }
+}
+
 fn reassign_token_periodically_to_database(
     database: Arc<DatabaseConnection>,
     database_host: String,
@@ -171,11 +422,19 @@ pub trait WorkspaceScopedTransaction {
 
 #[async_trait]
 impl WorkspaceScopedTransaction for DatabaseConnection {
+    #[tracing::instrument(name = "db.transaction.begin", skip(self))]
     async fn begin_with_workspace_scope(&self, workspace_slug: &str) -> Result<DatabaseTransaction, DbErr> {
-        let transaction = self.begin().await?;
         if !validate_workspace_name(workspace_slug) {
             return Err(DbErr::Custom("workspace slug is invalid".to_string()));
         }
+        if self.get_database_backend() == DatabaseBackend::Sqlite {
+            let connection = sqlite_workspace_connection(&sqlite_path(self).await?, workspace_slug)
+                .await
+                .map_err(|e| DbErr::Custom(e.to_string()))?;
+            return connection.begin().await;
+        }
+
+        let transaction = self.begin().await?;
         transaction
             .execute(Statement::from_string(
                 DatabaseBackend::Postgres,
@@ -292,6 +551,12 @@ impl sea_orm::sea_query::ValueType for UlidId {
     }
 }
 
+impl sea_orm::sea_query::Nullable for UlidId {
+    fn null() -> sea_orm::Value {
+        sea_orm::Value::String(None)
+    }
+}
+
 #[async_trait]
 pub(crate) trait Persistable {
     type Error;
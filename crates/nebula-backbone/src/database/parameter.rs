@@ -9,11 +9,25 @@ pub struct Model {
     #[sea_orm(primary_key)]
     pub id: UlidId,
     pub version: i32,
+    pub curve: ParameterCurve,
     pub value: Vec<u8>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+#[derive(EnumIter, DeriveActiveEnum, Clone, Copy, Debug, PartialEq)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(32))")]
+pub enum ParameterCurve {
+    #[sea_orm(string_value = "BN462")]
+    Bn462,
+    #[sea_orm(string_value = "BLS24479")]
+    Bls24479,
+    #[sea_orm(string_value = "BLS48556")]
+    Bls48556,
+    #[sea_orm(string_value = "BLS48581")]
+    Bls48581,
+}
+
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {}
 
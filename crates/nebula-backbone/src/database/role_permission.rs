@@ -0,0 +1,47 @@
+use chrono::{DateTime, Utc};
+use sea_orm::prelude::*;
+
+use super::UlidId;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "role_permission")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: UlidId,
+    pub role_id: UlidId,
+    pub permission: Permission,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(EnumIter, DeriveActiveEnum, Clone, Copy, Debug, PartialEq)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Permission {
+    WorkspaceRead,
+    WorkspaceManage,
+    SecretRead,
+    SecretWrite,
+    PathRead,
+    PathWrite,
+    PolicyRead,
+    PolicyWrite,
+    AuthorityRead,
+    AuthorityManage,
+    ParameterManage,
+    RoleManage,
+    AuditRead,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(belongs_to = "super::role::Entity", from = "Column::RoleId", to = "super::role::Column::Id")]
+    Role,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Related<super::role::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Role.def()
+    }
+}
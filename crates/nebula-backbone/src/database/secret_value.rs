@@ -10,6 +10,7 @@ pub struct Model {
     pub id: UlidId,
     pub identifier: String,
     pub cipher: Vec<u8>,
+    pub parameter_version: i32,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
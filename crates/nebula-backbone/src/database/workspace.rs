@@ -1,5 +1,7 @@
 use chrono::{DateTime, Utc};
-use sea_orm::prelude::*;
+use sea_orm::{prelude::*, FromJsonQueryResult};
+use serde::{Deserialize, Serialize};
+use ulid::Ulid;
 
 use super::UlidId;
 
@@ -9,10 +11,32 @@ pub struct Model {
     #[sea_orm(primary_key)]
     pub id: UlidId,
     pub name: String,
+    pub display_name: Option<String>,
+    pub settings: Settings,
+    pub deleted_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, FromJsonQueryResult)]
+#[serde(rename_all = "camelCase")]
+pub struct Settings {
+    #[serde(default)]
+    pub default_access_condition_ids: Vec<Ulid>,
+    pub max_secret_size: Option<u64>,
+    pub allowed_authority_hosts: Option<Vec<String>>,
+    #[serde(default)]
+    pub quota: Quota,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Quota {
+    pub max_secrets: Option<u64>,
+    pub max_ciphertext_bytes: Option<u64>,
+    pub max_paths: Option<u64>,
+}
+
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {}
 
@@ -0,0 +1,60 @@
+use async_trait::async_trait;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[derive(DeriveIden)]
+pub enum Parameter {
+    Table,
+    Version,
+    Curve,
+}
+
+#[derive(DeriveIden)]
+pub enum SecretValue {
+    Table,
+    ParameterVersion,
+}
+
+#[async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Parameters and secrets created before are on the BN462 curve with the first parameter version.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Parameter::Table)
+                    .add_column(string_len(Parameter::Curve, 32).default("BN462"))
+                    .take(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .table(Parameter::Table)
+                    .if_not_exists()
+                    .name("idx_parameter_version")
+                    .col(Parameter::Version)
+                    .unique()
+                    .take(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SecretValue::Table)
+                    .add_column(integer(SecretValue::ParameterVersion).default(1))
+                    .take(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(Table::alter().table(SecretValue::Table).drop_column(SecretValue::ParameterVersion).take())
+            .await?;
+        manager.drop_index(Index::drop().table(Parameter::Table).name("idx_parameter_version").to_owned()).await?;
+        manager.alter_table(Table::alter().table(Parameter::Table).drop_column(Parameter::Curve).take()).await
+    }
+}
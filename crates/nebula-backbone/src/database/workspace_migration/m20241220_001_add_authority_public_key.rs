@@ -0,0 +1,56 @@
+use async_trait::async_trait;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[derive(DeriveIden)]
+pub enum AuthorityPublicKey {
+    Table,
+    Id,
+    AuthorityId,
+    Version,
+    ParameterVersion,
+    Curve,
+    PublicKey,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AuthorityPublicKey::Table)
+                    .if_not_exists()
+                    .col(char_len(AuthorityPublicKey::Id, 26).primary_key())
+                    .col(char_len(AuthorityPublicKey::AuthorityId, 26))
+                    .col(big_integer(AuthorityPublicKey::Version))
+                    .col(integer(AuthorityPublicKey::ParameterVersion))
+                    .col(string_len(AuthorityPublicKey::Curve, 32))
+                    .col(text(AuthorityPublicKey::PublicKey))
+                    .col(timestamp_with_time_zone(AuthorityPublicKey::CreatedAt))
+                    .col(timestamp_with_time_zone(AuthorityPublicKey::UpdatedAt))
+                    .take(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .table(AuthorityPublicKey::Table)
+                    .if_not_exists()
+                    .name("idx_authority_public_key_version")
+                    .col(AuthorityPublicKey::AuthorityId)
+                    .col(AuthorityPublicKey::Version)
+                    .unique()
+                    .take(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.drop_table(Table::drop().table(AuthorityPublicKey::Table).if_exists().take()).await
+    }
+}
@@ -0,0 +1,48 @@
+use async_trait::async_trait;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[derive(DeriveIden)]
+pub enum Authority {
+    Table,
+    Status,
+    LatestKeyVersion,
+    StatusCheckedAt,
+}
+
+#[async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite cannot add multiple columns in a single statement.
+        manager
+            .alter_table(
+                Table::alter().table(Authority::Table).add_column(string_len_null(Authority::Status, 32)).take(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter().table(Authority::Table).add_column(big_integer_null(Authority::LatestKeyVersion)).take(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Authority::Table)
+                    .add_column(timestamp_with_time_zone_null(Authority::StatusCheckedAt))
+                    .take(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(Table::alter().table(Authority::Table).drop_column(Authority::StatusCheckedAt).take())
+            .await?;
+        manager
+            .alter_table(Table::alter().table(Authority::Table).drop_column(Authority::LatestKeyVersion).take())
+            .await?;
+        manager.alter_table(Table::alter().table(Authority::Table).drop_column(Authority::Status).take()).await
+    }
+}
@@ -0,0 +1,40 @@
+use async_trait::async_trait;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[derive(DeriveIden)]
+pub enum AuthorityBootstrapToken {
+    Table,
+    Id,
+    TokenHash,
+    AuthorityId,
+    ExpiresAt,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AuthorityBootstrapToken::Table)
+                    .if_not_exists()
+                    .col(char_len(AuthorityBootstrapToken::Id, 26).primary_key())
+                    .col(string_len_uniq(AuthorityBootstrapToken::TokenHash, 64))
+                    .col(char_len_null(AuthorityBootstrapToken::AuthorityId, 26))
+                    .col(timestamp_with_time_zone(AuthorityBootstrapToken::ExpiresAt))
+                    .col(timestamp_with_time_zone(AuthorityBootstrapToken::CreatedAt))
+                    .col(timestamp_with_time_zone(AuthorityBootstrapToken::UpdatedAt))
+                    .take(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.drop_table(Table::drop().table(AuthorityBootstrapToken::Table).if_exists().take()).await
+    }
+}
@@ -0,0 +1,70 @@
+use async_trait::async_trait;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[derive(DeriveIden)]
+pub enum Role {
+    Table,
+    Id,
+    Name,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum RolePermission {
+    Table,
+    Id,
+    RoleId,
+    Permission,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Role::Table)
+                    .if_not_exists()
+                    .col(char_len(Role::Id, 26).primary_key())
+                    .col(string_len(Role::Name, 255).unique_key())
+                    .col(timestamp_with_time_zone(Role::CreatedAt))
+                    .col(timestamp_with_time_zone(Role::UpdatedAt))
+                    .take(),
+            )
+            .await?;
+        manager
+            .create_table(
+                Table::create()
+                    .table(RolePermission::Table)
+                    .if_not_exists()
+                    .col(char_len(RolePermission::Id, 26).primary_key())
+                    .col(char_len(RolePermission::RoleId, 26))
+                    .col(string_len(RolePermission::Permission, 50))
+                    .col(timestamp_with_time_zone(RolePermission::CreatedAt))
+                    .col(timestamp_with_time_zone(RolePermission::UpdatedAt))
+                    .take(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .table(RolePermission::Table)
+                    .if_not_exists()
+                    .name("idx_role_permission_role_id")
+                    .col(RolePermission::RoleId)
+                    .take(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.drop_table(Table::drop().table(RolePermission::Table).if_exists().take()).await?;
+        manager.drop_table(Table::drop().table(Role::Table).if_exists().take()).await
+    }
+}
@@ -0,0 +1,46 @@
+use async_trait::async_trait;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[derive(DeriveIden)]
+pub enum AuthorityPublicKey {
+    Table,
+    Status,
+    StatusCheckedAt,
+}
+
+#[async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite cannot add multiple columns in a single statement.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AuthorityPublicKey::Table)
+                    .add_column(string_len(AuthorityPublicKey::Status, 32).default("ACTIVE"))
+                    .take(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AuthorityPublicKey::Table)
+                    .add_column(timestamp_with_time_zone_null(AuthorityPublicKey::StatusCheckedAt))
+                    .take(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter().table(AuthorityPublicKey::Table).drop_column(AuthorityPublicKey::StatusCheckedAt).take(),
+            )
+            .await?;
+        manager
+            .alter_table(Table::alter().table(AuthorityPublicKey::Table).drop_column(AuthorityPublicKey::Status).take())
+            .await
+    }
+}
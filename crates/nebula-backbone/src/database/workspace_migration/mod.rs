@@ -3,32 +3,42 @@ use futures_util::future::join_all;
 use sea_orm::{DatabaseTransaction, DbErr, EntityTrait};
 use sea_orm_migration::{IntoSchemaManagerConnection, MigrationTrait, MigratorTrait};
 
-use super::{workspace, AuthMethod};
+use super::{workspace, DatabaseLocation};
 
 mod m20241126_001_init_backbone;
+mod m20241218_001_add_parameter_curve;
+mod m20241220_001_add_authority_public_key;
+mod m20241222_001_add_authority_status;
+mod m20241224_001_add_authority_bootstrap_token;
+mod m20241226_001_add_role;
+mod m20241228_001_add_authority_credential;
+mod m20241230_001_add_authority_public_key_status;
 
 pub struct Migrator;
 
 #[async_trait]
 impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-        vec![Box::new(m20241126_001_init_backbone::Migration)]
+        vec![
+            Box::new(m20241126_001_init_backbone::Migration),
+            Box::new(m20241218_001_add_parameter_curve::Migration),
+            Box::new(m20241220_001_add_authority_public_key::Migration),
+            Box::new(m20241222_001_add_authority_status::Migration),
+            Box::new(m20241224_001_add_authority_bootstrap_token::Migration),
+            Box::new(m20241226_001_add_role::Migration),
+            Box::new(m20241228_001_add_authority_credential::Migration),
+            Box::new(m20241230_001_add_authority_public_key_status::Migration),
+        ]
     }
 }
 
 pub async fn migrate_all_workspaces(
     transaction: &DatabaseTransaction,
-    host: &str,
-    port: u16,
-    database_name: &str,
-    auth: &AuthMethod,
+    location: &DatabaseLocation,
 ) -> anyhow::Result<()> {
     let workspaces = workspace::Entity::find().all(transaction).await?;
 
-    let results = join_all(
-        workspaces.iter().map(|workspace| migrate_workspace(&workspace.name, host, port, database_name, auth)),
-    )
-    .await;
+    let results = join_all(workspaces.iter().map(|workspace| migrate_workspace(&workspace.name, location))).await;
 
     for result in results {
         result?;
@@ -37,31 +47,8 @@ pub async fn migrate_all_workspaces(
     Ok(())
 }
 
-#[cfg(test)]
-pub async fn migrate_workspace(
-    _workspace_slug: &str,
-    _host: &str,
-    _port: u16,
-    _database_name: &str,
-    _auth: &AuthMethod,
-) -> anyhow::Result<()> {
-    use tracing::debug;
-
-    debug!("workspace migration not supported in test environment");
-
-    Ok(())
-}
-
-#[cfg(not(test))]
-pub async fn migrate_workspace(
-    workspace_slug: &str,
-    host: &str,
-    port: u16,
-    database_name: &str,
-    auth: &AuthMethod,
-) -> anyhow::Result<()> {
-    let connection =
-        super::connect_to_database_with_search_path(host, port, database_name, auth, Some(workspace_slug)).await?;
+pub async fn migrate_workspace(workspace_slug: &str, location: &DatabaseLocation) -> anyhow::Result<()> {
+    let connection = super::connect_to_workspace_database(location, workspace_slug).await?;
 
     migrate(connection.as_ref()).await?;
 
@@ -74,3 +61,51 @@ where
 {
     Migrator::up(db, None).await
 }
+
+#[cfg(test)]
+mod test {
+    use sea_orm::{EntityTrait, PaginatorTrait};
+    use sea_orm_migration::MigratorTrait;
+    use ulid::Ulid;
+
+    use super::{migrate_workspace, Migrator};
+    use crate::database::{
+        close_database, connect_to_database, connect_to_workspace_database, migrate, migration, path, workspace,
+        DatabaseLocation, WorkspaceScopedTransaction,
+    };
+
+    #[tokio::test]
+    async fn when_migrating_sqlite_database_then_public_and_workspace_migrations_are_applied() {
+        const WORKSPACE_NAME: &str = "testworkspace";
+        let directory = std::env::temp_dir().join(format!("nebula-backbone-migration-test-{}", Ulid::new()));
+        let location = DatabaseLocation::Sqlite { path: directory.clone() };
+        let connection = connect_to_database(&location).await.expect("connecting to database should be successful");
+
+        migrate(connection.as_ref()).await.expect("public migrations should be applied");
+        migrate_workspace(WORKSPACE_NAME, &location).await.expect("workspace migrations should be applied");
+
+        let workspace_connection = connect_to_workspace_database(&location, WORKSPACE_NAME)
+            .await
+            .expect("connecting to workspace database should be successful");
+        let pending_public_migrations = migration::Migrator::get_pending_migrations(connection.as_ref())
+            .await
+            .expect("getting pending public migrations should be successful");
+        let pending_workspace_migrations = Migrator::get_pending_migrations(workspace_connection.as_ref())
+            .await
+            .expect("getting pending workspace migrations should be successful");
+        assert!(pending_public_migrations.is_empty());
+        assert!(pending_workspace_migrations.is_empty());
+
+        let transaction = connection
+            .begin_with_workspace_scope(WORKSPACE_NAME)
+            .await
+            .expect("begining transaction should be successful");
+        let path_count = path::Entity::find().count(&transaction).await.expect("workspace table should exist");
+        let workspace_count = workspace::Entity::find().count(&transaction).await.expect("public table should exist");
+        transaction.commit().await.expect("commiting transaction should be successful");
+        assert_eq!((path_count, workspace_count), (0, 0));
+
+        close_database(&connection).await;
+        std::fs::remove_dir_all(directory).expect("removing test directory should be successful");
+    }
+}
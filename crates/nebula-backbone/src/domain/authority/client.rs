@@ -0,0 +1,143 @@
+use std::{collections::HashMap, time::Duration};
+
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use nebula_abe::curves::Curve;
+use nebula_tls::ClientTlsConfig;
+use serde::Deserialize;
+use url::Url;
+
+use super::{AuthorityStatus, PublicKeyStatus};
+
+/// Public key of a key pair version as served by an authority.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FetchedPublicKey {
+    pub public_key: String,
+    pub version: u64,
+    /// Authorities of older releases do not serve the parameter version and the curve, whose key pairs are always
+    /// generated with the first parameter version on BN462.
+    #[serde(default = "initial_parameter_version")]
+    pub parameter_version: i32,
+    #[serde(default)]
+    pub curve: Curve,
+    /// Authorities of older releases do not serve the status, whose key pair versions are always active.
+    #[serde(default)]
+    pub status: PublicKeyStatus,
+}
+
+fn initial_parameter_version() -> i32 {
+    super::super::parameter::INITIAL_PARAMETER_VERSION
+}
+
+/// Readiness of an authority as served by `GET /ready`.
+#[derive(Deserialize)]
+struct ReadinessResponse {
+    status: String,
+    #[serde(default)]
+    checks: HashMap<String, String>,
+}
+
+impl From<ReadinessResponse> for AuthorityStatus {
+    /// An authority not ready only because its key pair storage is armored is told apart from one which is down.
+    fn from(readiness: ReadinessResponse) -> Self {
+        if readiness.status == "READY" {
+            AuthorityStatus::Available
+        } else if readiness.checks.get("shield").map(String::as_str) == Some("DOWN") {
+            AuthorityStatus::Armored
+        } else {
+            AuthorityStatus::Unavailable
+        }
+    }
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait AuthorityClient {
+    /// Fetches the public key of the key pair version from the authority, or of the latest key pair if the version is
+    /// not given.
+    async fn get_public_key(
+        &self,
+        host: &str,
+        workspace_name: &str,
+        version: Option<u64>,
+    ) -> anyhow::Result<FetchedPublicKey>;
+    /// Probes the readiness of the authority. Fails if the authority is unreachable.
+    async fn get_status(&self, host: &str) -> anyhow::Result<AuthorityStatus>;
+}
+
+pub struct HttpAuthorityClient {
+    client: reqwest::Client,
+}
+
+impl HttpAuthorityClient {
+    pub fn new(timeout: Duration, tls: Option<&ClientTlsConfig>) -> anyhow::Result<Self> {
+        let mut builder = reqwest::Client::builder().timeout(timeout);
+        if let Some(tls) = tls {
+            builder = tls.apply(builder)?;
+        }
+        Ok(Self { client: builder.build()? })
+    }
+}
+
+#[async_trait]
+impl AuthorityClient for HttpAuthorityClient {
+    async fn get_public_key(
+        &self,
+        host: &str,
+        workspace_name: &str,
+        version: Option<u64>,
+    ) -> anyhow::Result<FetchedPublicKey> {
+        let mut url = Url::parse(host)?.join(&format!("workspaces/{workspace_name}/public-key"))?;
+        if let Some(version) = version {
+            url.query_pairs_mut().append_pair("version", &version.to_string());
+        }
+
+        Ok(self.client.get(url).send().await?.error_for_status()?.json().await?)
+    }
+
+    async fn get_status(&self, host: &str) -> anyhow::Result<AuthorityStatus> {
+        // Health endpoints are served at the root even if the authority is served under a path prefix.
+        let url = Url::parse(host)?.join("/ready")?;
+        let readiness: ReadinessResponse = self.client.get(url).send().await?.json().await?;
+
+        Ok(readiness.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::domain::authority::AuthorityStatus;
+
+    use super::ReadinessResponse;
+
+    fn status(readiness: &str) -> AuthorityStatus {
+        serde_json::from_str::<ReadinessResponse>(readiness).unwrap().into()
+    }
+
+    #[test]
+    fn when_authority_is_ready_then_status_is_available() {
+        assert_eq!(
+            status(r#"{"status": "READY", "checks": {"database": "UP", "jwks": "UP", "shield": "UP"}}"#),
+            AuthorityStatus::Available
+        );
+    }
+
+    #[test]
+    fn when_only_shield_of_authority_is_down_then_status_is_armored() {
+        assert_eq!(
+            status(r#"{"status": "NOT_READY", "checks": {"database": "UP", "jwks": "UP", "shield": "DOWN"}}"#),
+            AuthorityStatus::Armored
+        );
+    }
+
+    #[test]
+    fn when_authority_is_not_ready_otherwise_then_status_is_unavailable() {
+        assert_eq!(
+            status(r#"{"status": "NOT_READY", "checks": {"database": "DOWN", "jwks": "UP", "shield": "UP"}}"#),
+            AuthorityStatus::Unavailable
+        );
+        assert_eq!(status(r#"{"status": "NOT_READY"}"#), AuthorityStatus::Unavailable);
+    }
+}
@@ -1,21 +1,77 @@
-use crate::database::{authority, Persistable, UlidId};
+use crate::database::{authority, authority_bootstrap_token, authority_public_key, Persistable, UlidId};
 use async_trait::async_trait;
-use chrono::Utc;
+use base64::{
+    engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD},
+    Engine as _,
+};
+use chrono::{DateTime, Utc};
+#[cfg(test)]
+use mockall::automock;
+use nebula_abe::{
+    curves::{Curve, GroupG1 as _, PairingCurve},
+    schemes::isabella24,
+    with_curve,
+};
+use rand::{rngs::OsRng, RngCore as _};
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseTransaction, DbErr, EntityTrait, PaginatorTrait, QueryFilter, Set,
+    ActiveModelTrait, ColumnTrait, DatabaseTransaction, DbErr, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set,
 };
+use serde::Deserialize;
 use ulid::Ulid;
 
+use sha3::{Digest as _, Sha3_256};
+
+use super::parameter::Parameter;
+
+pub mod client;
+
 pub struct Authority {
     pub id: Ulid,
     pub name: String,
     pub host: String,
     pub public_key: Option<String>,
+    /// Status observed by the last health check, which is `None` until the authority is checked first.
+    pub status: Option<AuthorityStatus>,
+    pub latest_key_version: Option<u64>,
+    pub status_checked_at: Option<DateTime<Utc>>,
     updated_name: Option<String>,
     updated_public_key: Option<String>,
+    updated_status: Option<(AuthorityStatus, Option<u64>)>,
+    updated_credential_hash: Option<String>,
     deleted: bool,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuthorityStatus {
+    /// The authority is reachable and issues user keys.
+    Available,
+    /// The authority is reachable, but its key pair storage is armored.
+    Armored,
+    /// The authority is unreachable or not ready for another reason.
+    Unavailable,
+}
+
+impl From<authority::AuthorityStatus> for AuthorityStatus {
+    fn from(value: authority::AuthorityStatus) -> Self {
+        match value {
+            authority::AuthorityStatus::Available => AuthorityStatus::Available,
+            authority::AuthorityStatus::Armored => AuthorityStatus::Armored,
+            authority::AuthorityStatus::Unavailable => AuthorityStatus::Unavailable,
+        }
+    }
+}
+
+impl From<AuthorityStatus> for authority::AuthorityStatus {
+    fn from(value: AuthorityStatus) -> Self {
+        match value {
+            AuthorityStatus::Available => authority::AuthorityStatus::Available,
+            AuthorityStatus::Armored => authority::AuthorityStatus::Armored,
+            AuthorityStatus::Unavailable => authority::AuthorityStatus::Unavailable,
+        }
+    }
+}
+
 impl From<authority::Model> for Authority {
     fn from(value: authority::Model) -> Self {
         Self {
@@ -23,8 +79,13 @@ impl From<authority::Model> for Authority {
             name: value.name,
             host: value.host,
             public_key: value.public_key,
+            status: value.status.map(Into::into),
+            latest_key_version: value.latest_key_version.map(|version| version as u64),
+            status_checked_at: value.status_checked_at,
             updated_name: None,
             updated_public_key: None,
+            updated_status: None,
+            updated_credential_hash: None,
             deleted: false,
         }
     }
@@ -50,6 +111,18 @@ impl Authority {
 
         self.updated_public_key = Some(new_public_key.to_owned())
     }
+
+    /// Issues a credential with which the authority authenticates itself, replacing the one issued before.
+    pub fn issue_credential(&mut self) -> String {
+        let credential = generate_token();
+        self.updated_credential_hash = Some(hash_token(&credential));
+        credential
+    }
+
+    /// Records the result of a health check. The latest key version is kept if it could not be checked.
+    pub fn update_status(&mut self, status: AuthorityStatus, latest_key_version: Option<u64>) {
+        self.updated_status = Some((status, latest_key_version.or(self.latest_key_version)));
+    }
 }
 
 #[async_trait]
@@ -58,6 +131,14 @@ impl Persistable for Authority {
 
     async fn persist(self, transaction: &DatabaseTransaction) -> std::result::Result<(), Self::Error> {
         if self.deleted {
+            authority_public_key::Entity::delete_many()
+                .filter(authority_public_key::Column::AuthorityId.eq(UlidId::new(self.id)))
+                .exec(transaction)
+                .await?;
+            authority_bootstrap_token::Entity::delete_many()
+                .filter(authority_bootstrap_token::Column::AuthorityId.eq(UlidId::new(self.id)))
+                .exec(transaction)
+                .await?;
             authority::Entity::delete_by_id(UlidId::new(self.id)).exec(transaction).await?;
             return Ok(());
         }
@@ -68,6 +149,14 @@ impl Persistable for Authority {
 
         let mut active_model =
             authority::ActiveModel { name: name_setter, public_key: public_key_setter, ..Default::default() };
+        if let Some(credential_hash) = self.updated_credential_hash {
+            active_model.credential_hash = Set(Some(credential_hash));
+        }
+        if let Some((status, latest_key_version)) = self.updated_status {
+            active_model.status = Set(Some(status.into()));
+            active_model.latest_key_version = Set(latest_key_version.map(|version| version as i64));
+            active_model.status_checked_at = Set(Some(Utc::now()));
+        }
 
         if active_model.is_changed() {
             active_model.updated_at = Set(Utc::now());
@@ -82,36 +171,243 @@ impl Persistable for Authority {
     }
 }
 
+/// One-time token with which an authority registers itself to the workspace. The token is bound to the authority which
+/// uses it, and can't be used again.
+pub struct BootstrapToken {
+    pub id: Ulid,
+    pub authority_id: Option<Ulid>,
+    pub expires_at: DateTime<Utc>,
+    updated_authority_id: Option<Ulid>,
+}
+
+impl From<authority_bootstrap_token::Model> for BootstrapToken {
+    fn from(value: authority_bootstrap_token::Model) -> Self {
+        Self {
+            id: value.id.inner(),
+            authority_id: value.authority_id.map(UlidId::inner),
+            expires_at: value.expires_at,
+            updated_authority_id: None,
+        }
+    }
+}
+
+impl BootstrapToken {
+    /// A token is valid until it expires or an authority registers itself with it.
+    pub fn is_valid(&self) -> bool {
+        self.authority_id.is_none() && self.expires_at > Utc::now()
+    }
+
+    pub fn bind(&mut self, authority_id: Ulid) {
+        if self.authority_id.is_none() {
+            self.updated_authority_id = Some(authority_id);
+        }
+    }
+}
+
+#[async_trait]
+impl Persistable for BootstrapToken {
+    type Error = Error;
+
+    async fn persist(self, transaction: &DatabaseTransaction) -> std::result::Result<(), Self::Error> {
+        if let Some(authority_id) = self.updated_authority_id {
+            // Binds the token only if no other registration has bound it meanwhile, so that it is used only once.
+            let result = authority_bootstrap_token::Entity::update_many()
+                .filter(authority_bootstrap_token::Column::Id.eq(UlidId::new(self.id)))
+                .filter(authority_bootstrap_token::Column::AuthorityId.is_null())
+                .set(authority_bootstrap_token::ActiveModel {
+                    authority_id: Set(Some(UlidId::new(authority_id))),
+                    updated_at: Set(Utc::now()),
+                    ..Default::default()
+                })
+                .exec(transaction)
+                .await?;
+            if result.rows_affected == 0 {
+                return Err(Error::BootstrapTokenAlreadyUsed);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn generate_token() -> String {
+    let mut token = [0u8; 32];
+    OsRng.fill_bytes(&mut token);
+    URL_SAFE_NO_PAD.encode(token)
+}
+
+/// Only the hashes of bootstrap tokens and authority credentials are stored, so that a leaked database does not leak
+/// usable tokens.
+fn hash_token(token: &str) -> String {
+    STANDARD.encode(Sha3_256::digest(token.as_bytes()))
+}
+
+/// Public key of a key pair version of an authority, cached in the backbone once it has been verified.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuthorityPublicKey {
+    pub authority_id: Ulid,
+    pub version: u64,
+    pub parameter_version: i32,
+    pub curve: Curve,
+    /// Public key serialized in MessagePack and encoded in base64.
+    pub public_key: String,
+    pub status: PublicKeyStatus,
+    /// When the status was last fetched from the authority, which is `None` for keys cached before it was recorded.
+    pub status_checked_at: Option<DateTime<Utc>>,
+}
+
+impl From<authority_public_key::Model> for AuthorityPublicKey {
+    fn from(value: authority_public_key::Model) -> Self {
+        Self {
+            authority_id: value.authority_id.inner(),
+            version: value.version as u64,
+            parameter_version: value.parameter_version,
+            curve: value.curve.into(),
+            public_key: value.public_key,
+            status: value.status.into(),
+            status_checked_at: value.status_checked_at,
+        }
+    }
+}
+
+/// Status of a key pair version as served by the authority. A retired version should not be used for new encryptions,
+/// and a revoked version issues no more user keys.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PublicKeyStatus {
+    #[default]
+    Active,
+    Retired,
+    Revoked,
+}
+
+impl From<authority_public_key::PublicKeyStatus> for PublicKeyStatus {
+    fn from(value: authority_public_key::PublicKeyStatus) -> Self {
+        match value {
+            authority_public_key::PublicKeyStatus::Active => PublicKeyStatus::Active,
+            authority_public_key::PublicKeyStatus::Retired => PublicKeyStatus::Retired,
+            authority_public_key::PublicKeyStatus::Revoked => PublicKeyStatus::Revoked,
+        }
+    }
+}
+
+impl From<PublicKeyStatus> for authority_public_key::PublicKeyStatus {
+    fn from(value: PublicKeyStatus) -> Self {
+        match value {
+            PublicKeyStatus::Active => authority_public_key::PublicKeyStatus::Active,
+            PublicKeyStatus::Retired => authority_public_key::PublicKeyStatus::Retired,
+            PublicKeyStatus::Revoked => authority_public_key::PublicKeyStatus::Revoked,
+        }
+    }
+}
+
+impl AuthorityPublicKey {
+    /// Name of the key pair the public key must belong to, which is also the attribute authority name used in
+    /// ciphertexts.
+    pub fn key_pair_name(authority_name: &str, workspace_name: &str, version: u64) -> String {
+        format!("{authority_name}-{workspace_name}#{version}")
+    }
+
+    /// Verifies that the public key is generated with one of `parameters` on the same curve, is a well-formed key
+    /// without the identity element in its canonical encoding, and belongs to the key pair named `key_pair_name`.
+    pub fn verify(&self, parameters: &[Parameter], key_pair_name: &str) -> Result<()> {
+        let parameter =
+            parameters.iter().find(|parameter| parameter.version == self.parameter_version).ok_or_else(|| {
+                Error::InvalidPublicKey(format!("parameter version {} not exists", self.parameter_version))
+            })?;
+        if parameter.curve != self.curve {
+            return Err(Error::InvalidPublicKey(format!(
+                "curve {} does not match the curve {} of parameter version {}",
+                self.curve, parameter.curve, parameter.version
+            )));
+        }
+
+        let public_key =
+            STANDARD.decode(&self.public_key).map_err(|_| Error::InvalidPublicKey("not base64 encoded".to_owned()))?;
+        let name = with_curve!(self.curve, C => {
+            let decoded = rmp_serde::from_slice::<isabella24::AuthorityPublicKey<C>>(&public_key)
+                .map_err(|_| Error::InvalidPublicKey(format!("not a public key on curve {}", self.curve)))?;
+            if rmp_serde::to_vec(&decoded).map_err(anyhow::Error::from)? != public_key {
+                return Err(Error::InvalidPublicKey("not canonically encoded".to_owned()));
+            }
+            let identity = rmp_serde::to_vec(&<C as PairingCurve>::G1::zero()).map_err(anyhow::Error::from)?;
+            for element in [&decoded.large_a, &decoded.large_b, &decoded.large_b_prime] {
+                if rmp_serde::to_vec(element).map_err(anyhow::Error::from)? == identity {
+                    return Err(Error::InvalidPublicKey("has the identity element".to_owned()));
+                }
+            }
+            decoded.name
+        });
+        if name != key_pair_name {
+            return Err(Error::InvalidPublicKey(format!("belongs to key pair {name}, not {key_pair_name}")));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg_attr(test, automock)]
 #[async_trait]
 pub trait AuthorityService {
-    async fn register_authority(&self, transaction: &DatabaseTransaction, name: &str, host: &str) -> Result<()>;
+    async fn register_authority(&self, transaction: &DatabaseTransaction, name: &str, host: &str) -> Result<Ulid>;
     async fn get_authorities(&self, transaction: &DatabaseTransaction) -> Result<Vec<Authority>>;
     async fn get_authority(&self, transaction: &DatabaseTransaction, authority_id: &Ulid) -> Result<Option<Authority>>;
+    /// Gets the authority which authenticates itself with the credential.
+    async fn get_authority_by_credential(
+        &self,
+        transaction: &DatabaseTransaction,
+        credential: &str,
+    ) -> Result<Option<Authority>>;
+    /// Gets the cached public keys of all authorities ordered by version.
+    async fn get_public_keys(&self, transaction: &DatabaseTransaction) -> Result<Vec<AuthorityPublicKey>>;
+    /// Caches the public keys, or updates the status of those already cached.
+    async fn save_public_keys(
+        &self,
+        transaction: &DatabaseTransaction,
+        public_keys: &[AuthorityPublicKey],
+    ) -> Result<()>;
+    /// Issues a bootstrap token which expires at `expires_at` unless an authority registers itself with it.
+    async fn issue_bootstrap_token(
+        &self,
+        transaction: &DatabaseTransaction,
+        expires_at: DateTime<Utc>,
+    ) -> Result<String>;
+    /// Gets the bootstrap token and locks it until the transaction ends, so that it is used by one registration.
+    async fn get_bootstrap_token(
+        &self,
+        transaction: &DatabaseTransaction,
+        token: &str,
+    ) -> Result<Option<BootstrapToken>>;
 }
 
 pub struct PostgresAuthorityService {}
 
 #[async_trait]
 impl AuthorityService for PostgresAuthorityService {
-    async fn register_authority(&self, transaction: &DatabaseTransaction, name: &str, host: &str) -> Result<()> {
+    async fn register_authority(&self, transaction: &DatabaseTransaction, name: &str, host: &str) -> Result<Ulid> {
         let now = Utc::now();
+        let id = Ulid::new();
 
         if authority::Entity::find().filter(authority::Column::Name.eq(name)).count(transaction).await? > 0 {
             return Err(Error::NameAlreadyInUse { entered_authority_name: name.to_owned() });
         }
 
         authority::ActiveModel {
-            id: Set(UlidId::new(Ulid::new())),
+            id: Set(UlidId::new(id)),
             name: Set(name.to_owned()),
             host: Set(host.to_owned()),
             public_key: Set(None),
+            status: Set(None),
+            latest_key_version: Set(None),
+            status_checked_at: Set(None),
+            credential_hash: Set(None),
             created_at: Set(now),
             updated_at: Set(now),
         }
         .insert(transaction)
         .await?;
 
-        Ok(())
+        Ok(id)
     }
 
     async fn get_authorities(&self, transaction: &DatabaseTransaction) -> Result<Vec<Authority>> {
@@ -121,12 +417,118 @@ impl AuthorityService for PostgresAuthorityService {
     async fn get_authority(&self, transaction: &DatabaseTransaction, authority_id: &Ulid) -> Result<Option<Authority>> {
         Ok(authority::Entity::find_by_id(UlidId::new(authority_id.to_owned())).one(transaction).await?.map(Into::into))
     }
+
+    async fn get_authority_by_credential(
+        &self,
+        transaction: &DatabaseTransaction,
+        credential: &str,
+    ) -> Result<Option<Authority>> {
+        Ok(authority::Entity::find()
+            .filter(authority::Column::CredentialHash.eq(hash_token(credential)))
+            .one(transaction)
+            .await?
+            .map(Into::into))
+    }
+
+    async fn get_public_keys(&self, transaction: &DatabaseTransaction) -> Result<Vec<AuthorityPublicKey>> {
+        Ok(authority_public_key::Entity::find()
+            .order_by_asc(authority_public_key::Column::Version)
+            .all(transaction)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect())
+    }
+
+    async fn save_public_keys(
+        &self,
+        transaction: &DatabaseTransaction,
+        public_keys: &[AuthorityPublicKey],
+    ) -> Result<()> {
+        let now = Utc::now();
+
+        for public_key in public_keys {
+            let cached = authority_public_key::Entity::find()
+                .filter(authority_public_key::Column::AuthorityId.eq(UlidId::new(public_key.authority_id)))
+                .filter(authority_public_key::Column::Version.eq(public_key.version as i64))
+                .one(transaction)
+                .await?;
+            if let Some(cached) = cached {
+                authority_public_key::ActiveModel {
+                    id: Set(cached.id),
+                    status: Set(public_key.status.into()),
+                    status_checked_at: Set(public_key.status_checked_at),
+                    updated_at: Set(now),
+                    ..Default::default()
+                }
+                .update(transaction)
+                .await?;
+                continue;
+            }
+
+            authority_public_key::ActiveModel {
+                id: Set(UlidId::new(Ulid::new())),
+                authority_id: Set(UlidId::new(public_key.authority_id)),
+                version: Set(public_key.version as i64),
+                parameter_version: Set(public_key.parameter_version),
+                curve: Set(public_key.curve.into()),
+                public_key: Set(public_key.public_key.clone()),
+                status: Set(public_key.status.into()),
+                status_checked_at: Set(public_key.status_checked_at),
+                created_at: Set(now),
+                updated_at: Set(now),
+            }
+            .insert(transaction)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn issue_bootstrap_token(
+        &self,
+        transaction: &DatabaseTransaction,
+        expires_at: DateTime<Utc>,
+    ) -> Result<String> {
+        let token = generate_token();
+
+        let now = Utc::now();
+        authority_bootstrap_token::ActiveModel {
+            id: Set(UlidId::new(Ulid::new())),
+            token_hash: Set(hash_token(&token)),
+            authority_id: Set(None),
+            expires_at: Set(expires_at),
+            created_at: Set(now),
+            updated_at: Set(now),
+        }
+        .insert(transaction)
+        .await?;
+
+        Ok(token)
+    }
+
+    async fn get_bootstrap_token(
+        &self,
+        transaction: &DatabaseTransaction,
+        token: &str,
+    ) -> Result<Option<BootstrapToken>> {
+        Ok(authority_bootstrap_token::Entity::find()
+            .filter(authority_bootstrap_token::Column::TokenHash.eq(hash_token(token)))
+            .lock_exclusive()
+            .one(transaction)
+            .await?
+            .map(Into::into))
+    }
 }
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error("Authority name is already in use")]
     NameAlreadyInUse { entered_authority_name: String },
+    #[error("Invalid public key: {0}")]
+    InvalidPublicKey(String),
+    #[error("Bootstrap token is already used")]
+    BootstrapTokenAlreadyUsed,
     #[error(transparent)]
     Anyhow(#[from] anyhow::Error),
 }
@@ -138,3 +540,173 @@ impl From<DbErr> for Error {
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
+
+#[cfg(test)]
+mod tests {
+    use base64::{engine::general_purpose::STANDARD, Engine as _};
+    use chrono::{Duration, Utc};
+    use nebula_abe::{
+        curves::{bn462::Bn462Curve, Curve, GroupG1 as _, PairingCurve},
+        schemes::isabella24::{self, AuthorityKeyPair, GlobalParams},
+    };
+    use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult, TransactionTrait};
+    use ulid::Ulid;
+
+    use crate::{
+        database::{authority, authority_bootstrap_token, Persistable, UlidId},
+        domain::parameter::Parameter,
+    };
+
+    use super::{
+        Authority, AuthorityPublicKey, AuthorityService, AuthorityStatus, Error, PostgresAuthorityService,
+        PublicKeyStatus,
+    };
+
+    fn public_key(key_pair_name: &str, parameter_version: i32) -> AuthorityPublicKey {
+        let mut rng = <Bn462Curve as PairingCurve>::Rng::new();
+        rng.seed(&[0u8; 64]);
+        let gp = GlobalParams::<Bn462Curve>::new(&mut rng);
+        let key_pair = AuthorityKeyPair::new(&mut rng, &gp, key_pair_name);
+
+        AuthorityPublicKey {
+            authority_id: Ulid::new(),
+            version: 1,
+            parameter_version,
+            curve: Curve::Bn462,
+            public_key: STANDARD.encode(rmp_serde::to_vec(&key_pair.pk).unwrap()),
+            status: PublicKeyStatus::Active,
+            status_checked_at: None,
+        }
+    }
+
+    fn authority(latest_key_version: Option<i64>) -> Authority {
+        let now = chrono::Utc::now();
+        authority::Model {
+            id: UlidId::new(Ulid::new()),
+            name: "authority".to_owned(),
+            host: "http://localhost:8090/".to_owned(),
+            public_key: None,
+            status: Some(authority::AuthorityStatus::Available),
+            latest_key_version,
+            status_checked_at: Some(now),
+            credential_hash: None,
+            created_at: now,
+            updated_at: now,
+        }
+        .into()
+    }
+
+    #[test]
+    fn when_updating_status_without_latest_key_version_then_checked_latest_key_version_is_kept() {
+        let mut authority = authority(Some(3));
+
+        authority.update_status(AuthorityStatus::Armored, None);
+
+        assert_eq!(authority.updated_status, Some((AuthorityStatus::Armored, Some(3))));
+    }
+
+    #[test]
+    fn when_updating_status_with_latest_key_version_then_latest_key_version_is_updated() {
+        let mut authority = authority(Some(3));
+
+        authority.update_status(AuthorityStatus::Available, Some(4));
+
+        assert_eq!(authority.updated_status, Some((AuthorityStatus::Available, Some(4))));
+    }
+
+    fn parameters(curve: Curve) -> Vec<Parameter> {
+        vec![Parameter { version: 1, curve, value: vec![] }]
+    }
+
+    #[test]
+    fn when_verifying_public_key_of_key_pair_then_verification_is_successful() {
+        let public_key = public_key("authority-workspace#1", 1);
+
+        let result = public_key.verify(&parameters(Curve::Bn462), "authority-workspace#1");
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn when_verifying_public_key_of_other_key_pair_then_verification_returns_invalid_public_key_err() {
+        let public_key = public_key("authority-other-workspace#1", 1);
+
+        let result = public_key.verify(&parameters(Curve::Bn462), "authority-workspace#1");
+
+        assert!(matches!(result, Err(Error::InvalidPublicKey(_))));
+    }
+
+    #[test]
+    fn when_verifying_public_key_with_unknown_parameter_version_then_verification_returns_invalid_public_key_err() {
+        let public_key = public_key("authority-workspace#1", 2);
+
+        let result = public_key.verify(&parameters(Curve::Bn462), "authority-workspace#1");
+
+        assert!(matches!(result, Err(Error::InvalidPublicKey(_))));
+    }
+
+    #[test]
+    fn when_verifying_public_key_on_other_curve_than_parameter_then_verification_returns_invalid_public_key_err() {
+        let public_key = public_key("authority-workspace#1", 1);
+
+        let result = public_key.verify(&parameters(Curve::Bls48556), "authority-workspace#1");
+
+        assert!(matches!(result, Err(Error::InvalidPublicKey(_))));
+    }
+
+    #[test]
+    fn when_verifying_public_key_not_canonically_encoded_then_verification_returns_invalid_public_key_err() {
+        let mut public_key = public_key("authority-workspace#1", 1);
+        let mut encoded = STANDARD.decode(&public_key.public_key).unwrap();
+        encoded.push(0);
+        public_key.public_key = STANDARD.encode(encoded);
+
+        let result = public_key.verify(&parameters(Curve::Bn462), "authority-workspace#1");
+
+        assert!(matches!(result, Err(Error::InvalidPublicKey(_))));
+    }
+
+    #[test]
+    fn when_verifying_public_key_with_identity_element_then_verification_returns_invalid_public_key_err() {
+        let mut public_key = public_key("authority-workspace#1", 1);
+        let mut decoded: isabella24::AuthorityPublicKey<Bn462Curve> =
+            rmp_serde::from_slice(&STANDARD.decode(&public_key.public_key).unwrap()).unwrap();
+        decoded.large_b = <Bn462Curve as PairingCurve>::G1::zero();
+        public_key.public_key = STANDARD.encode(rmp_serde::to_vec(&decoded).unwrap());
+
+        let result = public_key.verify(&parameters(Curve::Bn462), "authority-workspace#1");
+
+        assert!(matches!(result, Err(Error::InvalidPublicKey(_))));
+    }
+
+    #[tokio::test]
+    async fn when_bootstrap_token_is_bound_then_it_is_locked_and_updated_only_while_unbound() {
+        let now = Utc::now();
+        let mock_database = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([vec![authority_bootstrap_token::Model {
+                id: UlidId::new(Ulid::new()),
+                token_hash: "hash".to_owned(),
+                authority_id: None,
+                expires_at: now + Duration::days(1),
+                created_at: now,
+                updated_at: now,
+            }]])
+            .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 0 }]);
+        let mock_connection = mock_database.into_connection();
+
+        let transaction = mock_connection.begin().await.expect("begining transaction should be successful");
+        let mut bootstrap_token = PostgresAuthorityService {}
+            .get_bootstrap_token(&transaction, "token")
+            .await
+            .expect("getting bootstrap token should be successful")
+            .expect("bootstrap token should exist");
+        bootstrap_token.bind(Ulid::new());
+        let result = bootstrap_token.persist(&transaction).await;
+        transaction.commit().await.expect("commiting transaction should be successful");
+
+        assert!(matches!(result, Err(Error::BootstrapTokenAlreadyUsed)));
+        let transaction_log = format!("{:?}", mock_connection.into_transaction_log());
+        assert!(transaction_log.contains("FOR UPDATE"));
+        assert!(transaction_log.contains("IS NULL"));
+    }
+}
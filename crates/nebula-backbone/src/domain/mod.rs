@@ -1,5 +1,6 @@
 pub mod authority;
 pub mod parameter;
 pub mod policy;
+pub mod role;
 pub mod secret;
 pub mod workspace;
@@ -5,73 +5,130 @@ use mockall::automock;
 use rand::{rngs::OsRng, Rng};
 use sea_orm::{
     ActiveModelTrait as _, ActiveValue, ColumnTrait, DatabaseTransaction, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder,
 };
 use ulid::Ulid;
 
-use crate::database::parameter;
+use crate::database::parameter::{self, ParameterCurve};
 use nebula_abe::{
-    curves::{bn462::Bn462Curve, PairingCurve},
+    curves::{Curve, PairingCurve},
     schemes::isabella24::GlobalParams,
+    with_curve,
 };
 
 pub struct Parameter {
     pub version: i32,
-    pub value: GlobalParams<Bn462Curve>,
+    pub curve: Curve,
+    /// Global parameters on the curve serialized in MessagePack.
+    pub value: Vec<u8>,
 }
 
 #[cfg_attr(test, automock)]
 #[async_trait]
 pub trait ParameterService {
-    async fn create(&self, transaction: &DatabaseTransaction) -> Result<Parameter>;
-    async fn get(&self, transaction: &DatabaseTransaction) -> Result<Parameter>;
+    /// Creates the first version of the parameter on the curve.
+    async fn create(&self, transaction: &DatabaseTransaction, curve: Curve) -> Result<Parameter>;
+    /// Adds the next version of the parameter on the curve. The previous versions are kept to decrypt the secrets
+    /// encrypted with them.
+    async fn add_version(&self, transaction: &DatabaseTransaction, curve: Curve) -> Result<Parameter>;
+    /// Gets the parameter of the version, or the latest version if it is not given.
+    async fn get(&self, transaction: &DatabaseTransaction, version: Option<i32>) -> Result<Parameter>;
+    async fn get_all(&self, transaction: &DatabaseTransaction) -> Result<Vec<Parameter>>;
 }
 
 pub struct PostgresParameterService;
 
-pub const PARAMETER_VERSION: i32 = 1;
+pub const INITIAL_PARAMETER_VERSION: i32 = 1;
 
-#[async_trait]
-impl ParameterService for PostgresParameterService {
-    async fn create(&self, transaction: &DatabaseTransaction) -> Result<Parameter> {
-        let has_parameter = parameter::Entity::find()
-            .filter(parameter::Column::Version.eq(PARAMETER_VERSION))
-            .count(transaction)
-            .await?
-            > 0;
-        if has_parameter {
-            return Err(Error::ParameterAlreadyCreated(PARAMETER_VERSION));
-        }
-
-        let mut rng = <Bn462Curve as PairingCurve>::Rng::new();
+impl PostgresParameterService {
+    async fn insert(&self, transaction: &DatabaseTransaction, version: i32, curve: Curve) -> Result<Parameter> {
         let mut seed = [0u8; 64];
         OsRng.fill(&mut seed);
-        rng.seed(&seed);
+        let value = with_curve!(curve, C => {
+            let mut rng = <C as PairingCurve>::Rng::new();
+            rng.seed(&seed);
+            let gp = tracing::info_span!("abe.global_params_generation", %curve)
+                .in_scope(|| GlobalParams::<C>::new(&mut rng));
+            rmp_serde::to_vec(&gp)?
+        });
 
-        let gp = GlobalParams::<Bn462Curve>::new(&mut rng);
-        let value = rmp_serde::to_vec(&gp)?;
         let now = Utc::now();
         parameter::ActiveModel {
             id: ActiveValue::Set(Ulid::new().into()),
-            version: ActiveValue::Set(PARAMETER_VERSION),
-            value: ActiveValue::Set(value),
+            version: ActiveValue::Set(version),
+            curve: ActiveValue::Set(curve.into()),
+            value: ActiveValue::Set(value.clone()),
             created_at: ActiveValue::Set(now),
             updated_at: ActiveValue::Set(now),
         }
         .insert(transaction)
         .await?;
 
-        Ok(Parameter { version: PARAMETER_VERSION, value: gp })
+        Ok(Parameter { version, curve, value })
+    }
+}
+
+#[async_trait]
+impl ParameterService for PostgresParameterService {
+    async fn create(&self, transaction: &DatabaseTransaction, curve: Curve) -> Result<Parameter> {
+        let has_parameter = parameter::Entity::find().count(transaction).await? > 0;
+        if has_parameter {
+            return Err(Error::ParameterAlreadyCreated(INITIAL_PARAMETER_VERSION));
+        }
+
+        self.insert(transaction, INITIAL_PARAMETER_VERSION, curve).await
+    }
+
+    async fn add_version(&self, transaction: &DatabaseTransaction, curve: Curve) -> Result<Parameter> {
+        let latest_parameter =
+            parameter::Entity::find().order_by_desc(parameter::Column::Version).one(transaction).await?;
+        let version = latest_parameter.map(|parameter| parameter.version + 1).unwrap_or(INITIAL_PARAMETER_VERSION);
+
+        self.insert(transaction, version, curve).await
+    }
+
+    async fn get(&self, transaction: &DatabaseTransaction, version: Option<i32>) -> Result<Parameter> {
+        let query = match version {
+            Some(version) => parameter::Entity::find().filter(parameter::Column::Version.eq(version)),
+            None => parameter::Entity::find().order_by_desc(parameter::Column::Version),
+        };
+        let parameter = query.one(transaction).await?.ok_or(Error::ParameterNotFound)?;
+
+        Ok(parameter.into())
+    }
+
+    async fn get_all(&self, transaction: &DatabaseTransaction) -> Result<Vec<Parameter>> {
+        let parameters = parameter::Entity::find().order_by_asc(parameter::Column::Version).all(transaction).await?;
+
+        Ok(parameters.into_iter().map(Parameter::from).collect())
+    }
+}
+
+impl From<parameter::Model> for Parameter {
+    fn from(value: parameter::Model) -> Self {
+        Self { version: value.version, curve: value.curve.into(), value: value.value }
     }
+}
 
-    async fn get(&self, transaction: &DatabaseTransaction) -> Result<Parameter> {
-        let parameter = parameter::Entity::find()
-            .filter(parameter::Column::Version.eq(PARAMETER_VERSION))
-            .one(transaction)
-            .await?
-            .ok_or(Error::ParameterNotFound)?;
-        let value = parameter.value;
-        let gp: GlobalParams<Bn462Curve> = rmp_serde::from_slice(&value)?;
-        Ok(Parameter { version: PARAMETER_VERSION, value: gp })
+impl From<Curve> for ParameterCurve {
+    fn from(value: Curve) -> Self {
+        match value {
+            Curve::Bn462 => ParameterCurve::Bn462,
+            Curve::Bls24479 => ParameterCurve::Bls24479,
+            Curve::Bls48556 => ParameterCurve::Bls48556,
+            Curve::Bls48581 => ParameterCurve::Bls48581,
+        }
+    }
+}
+
+impl From<ParameterCurve> for Curve {
+    fn from(value: ParameterCurve) -> Self {
+        match value {
+            ParameterCurve::Bn462 => Curve::Bn462,
+            ParameterCurve::Bls24479 => Curve::Bls24479,
+            ParameterCurve::Bls48556 => Curve::Bls48556,
+            ParameterCurve::Bls48581 => Curve::Bls48581,
+        }
     }
 }
 
@@ -86,9 +143,6 @@ pub enum Error {
     #[error(transparent)]
     Serialization(#[from] rmp_serde::encode::Error),
 
-    #[error(transparent)]
-    Deserialization(#[from] rmp_serde::decode::Error),
-
     #[error(transparent)]
     Anyhow(#[from] anyhow::Error),
 }
@@ -106,47 +160,66 @@ mod test {
     use std::{str::FromStr as _, sync::Arc};
 
     use chrono::Utc;
-    use nebula_abe::{
-        curves::{bn462::Bn462Curve, PairingCurve},
-        schemes::isabella24::GlobalParams,
-    };
+    use nebula_abe::curves::Curve;
     use sea_orm::{DatabaseBackend, DbErr, MockDatabase, TransactionTrait};
     use ulid::Ulid;
 
-    use crate::database::UlidId;
+    use crate::database::{
+        parameter::{Model, ParameterCurve},
+        UlidId,
+    };
 
-    use super::{ParameterService, PostgresParameterService, PARAMETER_VERSION};
+    use super::{Error, ParameterService, PostgresParameterService, INITIAL_PARAMETER_VERSION};
+
+    fn parameter_model(version: i32, curve: ParameterCurve) -> Model {
+        let now = Utc::now();
+        Model {
+            id: UlidId::new(Ulid::from_str("01JACYVTYB4F2PEBFRG1BB7BFP").unwrap()),
+            version,
+            curve,
+            value: vec![1, 2, 3],
+            created_at: now,
+            updated_at: now,
+        }
+    }
 
     #[tokio::test]
     async fn when_creating_parameter_is_successful_then_parameter_service_returns_ok() {
-        use crate::database::parameter::Model;
-        let mut rng = <Bn462Curve as PairingCurve>::Rng::new();
-        let gp = GlobalParams::<Bn462Curve>::new(&mut rng);
-        let value = rmp_serde::to_vec(&gp).expect("serializing global params should be successful");
-
-        let now = Utc::now();
         let mock_database = MockDatabase::new(DatabaseBackend::Postgres)
             .append_query_results([[maplit::btreemap! {
                 "num_items" => sea_orm::Value::BigInt(Some(0))
             }]])
-            .append_query_results([vec![Model {
-                id: Ulid::new().into(),
-                version: PARAMETER_VERSION,
-                value,
-                created_at: now,
-                updated_at: now,
-            }]]);
+            .append_query_results([vec![parameter_model(INITIAL_PARAMETER_VERSION, ParameterCurve::Bn462)]]);
         let mock_connection = Arc::new(mock_database.into_connection());
 
         let parameter_service = PostgresParameterService;
 
         let transaction = mock_connection.begin().await.expect("begining transaction should be successful");
 
-        let result = parameter_service.create(&transaction).await;
+        let result = parameter_service.create(&transaction, Curve::Bn462).await;
 
         transaction.commit().await.expect("commiting transaction should be successful");
 
-        result.expect("creating workspace should be successful");
+        let result = result.expect("creating parameter should be successful");
+        assert_eq!(result.version, INITIAL_PARAMETER_VERSION);
+        assert_eq!(result.curve, Curve::Bn462);
+    }
+
+    #[tokio::test]
+    async fn when_creating_parameter_already_created_then_parameter_service_returns_already_created_err() {
+        let mock_database = MockDatabase::new(DatabaseBackend::Postgres).append_query_results([[maplit::btreemap! {
+            "num_items" => sea_orm::Value::BigInt(Some(1))
+        }]]);
+        let mock_connection = Arc::new(mock_database.into_connection());
+
+        let parameter_service = PostgresParameterService;
+
+        let transaction = mock_connection.begin().await.expect("begining transaction should be successful");
+
+        let result = parameter_service.create(&transaction, Curve::Bn462).await;
+        transaction.commit().await.expect("commiting transaction should be successful");
+
+        assert!(matches!(result, Err(Error::ParameterAlreadyCreated(INITIAL_PARAMETER_VERSION))));
     }
 
     #[tokio::test]
@@ -159,43 +232,64 @@ mod test {
 
         let transaction = mock_connection.begin().await.expect("begining transaction should be successful");
 
-        let result = parameter_service.create(&transaction).await;
+        let result = parameter_service.create(&transaction, Curve::Bn462).await;
         transaction.commit().await.expect("commiting transaction should be successful");
 
         assert!(result.is_err());
         assert_eq!(result.err().unwrap().to_string(), "Custom Error: some error");
     }
 
+    #[tokio::test]
+    async fn when_adding_parameter_version_then_parameter_service_returns_next_version_on_curve() {
+        let mock_database = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([vec![parameter_model(1, ParameterCurve::Bn462)]])
+            .append_query_results([vec![parameter_model(2, ParameterCurve::Bls24479)]]);
+        let mock_connection = Arc::new(mock_database.into_connection());
+
+        let parameter_service = PostgresParameterService;
+
+        let transaction = mock_connection.begin().await.expect("begining transaction should be successful");
+
+        let result = parameter_service.add_version(&transaction, Curve::Bls24479).await;
+        transaction.commit().await.expect("commiting transaction should be successful");
+
+        let result = result.expect("adding parameter version should be successful");
+        assert_eq!(result.version, 2);
+        assert_eq!(result.curve, Curve::Bls24479);
+    }
+
     #[tokio::test]
     async fn when_getting_parameter_is_successful_then_parameter_service_returns_ok() {
-        use crate::database::parameter::Model;
-        let mut rng = <Bn462Curve as PairingCurve>::Rng::new();
-        let gp = GlobalParams::<Bn462Curve>::new(&mut rng);
-        let value = rmp_serde::to_vec(&gp).expect("serializing global params should be successful");
+        let mock_database = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([vec![parameter_model(2, ParameterCurve::Bls48581)]]);
+        let mock_connection = Arc::new(mock_database.into_connection());
 
-        let now = Utc::now();
-        let mock_database = MockDatabase::new(DatabaseBackend::Postgres).append_query_results([vec![Model {
-            id: UlidId::new(Ulid::from_str("01JACYVTYB4F2PEBFRG1BB7BFP").unwrap()),
-            version: PARAMETER_VERSION,
-            value: value.clone(),
-            created_at: now,
-            updated_at: now,
-        }]]);
+        let parameter_service = PostgresParameterService;
+
+        let transaction = mock_connection.begin().await.expect("begining transaction should be successful");
+
+        let result = parameter_service.get(&transaction, Some(2)).await;
+        transaction.commit().await.expect("commiting transaction should be successful");
+
+        let result = result.expect("getting parameter should be successful");
+        assert_eq!(result.version, 2);
+        assert_eq!(result.curve, Curve::Bls48581);
+        assert_eq!(result.value, vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn when_getting_parameter_not_exists_then_parameter_service_returns_not_found_err() {
+        let mock_database = MockDatabase::new(DatabaseBackend::Postgres).append_query_results([Vec::<Model>::new()]);
         let mock_connection = Arc::new(mock_database.into_connection());
 
         let parameter_service = PostgresParameterService;
 
         let transaction = mock_connection.begin().await.expect("begining transaction should be successful");
 
-        let result = parameter_service.get(&transaction).await;
+        let result = parameter_service.get(&transaction, None).await;
         transaction.commit().await.expect("commiting transaction should be successful");
 
-        let result = result.expect("getting workspace should be successful");
-        assert_eq!(result.version, PARAMETER_VERSION);
-        assert_eq!(
-            rmp_serde::to_vec(&result.value).expect("serializing global params should be successful"),
-            rmp_serde::to_vec(&gp).expect("serializing global params should be successful")
-        );
+        assert!(matches!(result, Err(Error::ParameterNotFound)));
     }
 
     #[tokio::test]
@@ -208,7 +302,7 @@ mod test {
 
         let transaction = mock_connection.begin().await.expect("begining transaction should be successful");
 
-        let result = parameter_service.get(&transaction).await;
+        let result = parameter_service.get(&transaction, None).await;
         transaction.commit().await.expect("commiting transaction should be successful");
 
         assert!(result.is_err());
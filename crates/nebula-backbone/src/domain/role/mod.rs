@@ -0,0 +1,386 @@
+use std::collections::{BTreeSet, HashMap};
+
+use async_trait::async_trait;
+use chrono::Utc;
+#[cfg(test)]
+use mockall::automock;
+use nebula_token::claim::{self, Permission};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseTransaction, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, Set,
+};
+use ulid::Ulid;
+
+use crate::database::{role, role_permission, Persistable, UlidId};
+
+/// Role defined by a workspace on top of the built-in admin, member and auditor roles.
+pub struct Role {
+    pub id: Ulid,
+    pub name: String,
+    pub permissions: BTreeSet<Permission>,
+    updated_name: Option<String>,
+    updated_permissions: Option<BTreeSet<Permission>>,
+    deleted: bool,
+}
+
+impl Role {
+    pub fn new(id: Ulid, name: String, permissions: BTreeSet<Permission>) -> Self {
+        Self { id, name, permissions, updated_name: None, updated_permissions: None, deleted: false }
+    }
+
+    pub fn update_name(&mut self, new_name: &str) -> Result<()> {
+        validate_role_name(new_name)?;
+        if self.name == new_name || self.updated_name.as_deref() == Some(new_name) {
+            return Ok(());
+        }
+
+        self.updated_name = Some(new_name.to_owned());
+
+        Ok(())
+    }
+
+    pub fn update_permissions(&mut self, new_permissions: &[Permission]) {
+        let new_permissions = new_permissions.iter().copied().collect::<BTreeSet<_>>();
+        if self.permissions == new_permissions {
+            return;
+        }
+
+        self.updated_permissions = Some(new_permissions);
+    }
+
+    pub fn delete(&mut self) {
+        self.deleted = true
+    }
+}
+
+impl From<(role::Model, Vec<role_permission::Model>)> for Role {
+    fn from((role, permissions): (role::Model, Vec<role_permission::Model>)) -> Self {
+        Self::new(
+            role.id.inner(),
+            role.name,
+            permissions.into_iter().map(|permission| permission.permission.into()).collect(),
+        )
+    }
+}
+
+impl From<role_permission::Permission> for Permission {
+    fn from(value: role_permission::Permission) -> Self {
+        match value {
+            role_permission::Permission::WorkspaceRead => Permission::WorkspaceRead,
+            role_permission::Permission::WorkspaceManage => Permission::WorkspaceManage,
+            role_permission::Permission::SecretRead => Permission::SecretRead,
+            role_permission::Permission::SecretWrite => Permission::SecretWrite,
+            role_permission::Permission::PathRead => Permission::PathRead,
+            role_permission::Permission::PathWrite => Permission::PathWrite,
+            role_permission::Permission::PolicyRead => Permission::PolicyRead,
+            role_permission::Permission::PolicyWrite => Permission::PolicyWrite,
+            role_permission::Permission::AuthorityRead => Permission::AuthorityRead,
+            role_permission::Permission::AuthorityManage => Permission::AuthorityManage,
+            role_permission::Permission::ParameterManage => Permission::ParameterManage,
+            role_permission::Permission::RoleManage => Permission::RoleManage,
+            role_permission::Permission::AuditRead => Permission::AuditRead,
+        }
+    }
+}
+
+impl From<Permission> for role_permission::Permission {
+    fn from(value: Permission) -> Self {
+        match value {
+            Permission::WorkspaceRead => role_permission::Permission::WorkspaceRead,
+            Permission::WorkspaceManage => role_permission::Permission::WorkspaceManage,
+            Permission::SecretRead => role_permission::Permission::SecretRead,
+            Permission::SecretWrite => role_permission::Permission::SecretWrite,
+            Permission::PathRead => role_permission::Permission::PathRead,
+            Permission::PathWrite => role_permission::Permission::PathWrite,
+            Permission::PolicyRead => role_permission::Permission::PolicyRead,
+            Permission::PolicyWrite => role_permission::Permission::PolicyWrite,
+            Permission::AuthorityRead => role_permission::Permission::AuthorityRead,
+            Permission::AuthorityManage => role_permission::Permission::AuthorityManage,
+            Permission::ParameterManage => role_permission::Permission::ParameterManage,
+            Permission::RoleManage => role_permission::Permission::RoleManage,
+            Permission::AuditRead => role_permission::Permission::AuditRead,
+        }
+    }
+}
+
+#[async_trait]
+impl Persistable for Role {
+    type Error = Error;
+
+    async fn persist(self, transaction: &DatabaseTransaction) -> std::result::Result<(), Self::Error> {
+        if self.deleted || self.updated_permissions.is_some() {
+            role_permission::Entity::delete_many()
+                .filter(role_permission::Column::RoleId.eq(UlidId::new(self.id)))
+                .exec(transaction)
+                .await?;
+        }
+
+        if self.deleted {
+            role::Entity::delete_by_id(UlidId::new(self.id)).exec(transaction).await?;
+            return Ok(());
+        }
+
+        let now = Utc::now();
+        if let Some(updated_name) = self.updated_name {
+            ensure_role_name_not_duplicated(transaction, &updated_name).await?;
+            role::Entity::update_many()
+                .set(role::ActiveModel { name: Set(updated_name), updated_at: Set(now), ..Default::default() })
+                .filter(role::Column::Id.eq(UlidId::new(self.id)))
+                .exec(transaction)
+                .await?;
+        }
+
+        if let Some(updated_permissions) = self.updated_permissions {
+            insert_permissions(transaction, &self.id, &updated_permissions).await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait RoleService {
+    async fn list(&self, transaction: &DatabaseTransaction) -> Result<Vec<Role>>;
+    async fn get(&self, transaction: &DatabaseTransaction, id: &Ulid) -> Result<Option<Role>>;
+    async fn get_by_name(&self, transaction: &DatabaseTransaction, name: &str) -> Result<Option<Role>>;
+    async fn register(&self, transaction: &DatabaseTransaction, name: &str, permissions: &[Permission])
+        -> Result<Ulid>;
+}
+
+pub struct PostgresRoleService {}
+
+#[async_trait]
+impl RoleService for PostgresRoleService {
+    async fn list(&self, transaction: &DatabaseTransaction) -> Result<Vec<Role>> {
+        let roles = role::Entity::find().order_by_asc(role::Column::Name).all(transaction).await?;
+        let mut permissions_map = HashMap::<UlidId, Vec<role_permission::Model>>::new();
+        for permission in role_permission::Entity::find().all(transaction).await? {
+            permissions_map.entry(permission.role_id.clone()).or_default().push(permission);
+        }
+
+        Ok(roles
+            .into_iter()
+            .map(|role| {
+                let permissions = permissions_map.remove(&role.id).unwrap_or_default();
+                Role::from((role, permissions))
+            })
+            .collect())
+    }
+
+    async fn get(&self, transaction: &DatabaseTransaction, id: &Ulid) -> Result<Option<Role>> {
+        let Some(role) = role::Entity::find_by_id(UlidId::new(*id)).one(transaction).await? else {
+            return Ok(None);
+        };
+
+        Ok(Some(with_permissions(transaction, role).await?))
+    }
+
+    async fn get_by_name(&self, transaction: &DatabaseTransaction, name: &str) -> Result<Option<Role>> {
+        let Some(role) = role::Entity::find().filter(role::Column::Name.eq(name)).one(transaction).await? else {
+            return Ok(None);
+        };
+
+        Ok(Some(with_permissions(transaction, role).await?))
+    }
+
+    async fn register(
+        &self,
+        transaction: &DatabaseTransaction,
+        name: &str,
+        permissions: &[Permission],
+    ) -> Result<Ulid> {
+        validate_role_name(name)?;
+        ensure_role_name_not_duplicated(transaction, name).await?;
+
+        let id = Ulid::new();
+        let now = Utc::now();
+        role::ActiveModel {
+            id: Set(UlidId::new(id)),
+            name: Set(name.to_owned()),
+            created_at: Set(now),
+            updated_at: Set(now),
+        }
+        .insert(transaction)
+        .await?;
+        insert_permissions(transaction, &id, &permissions.iter().copied().collect()).await?;
+
+        Ok(id)
+    }
+}
+
+async fn with_permissions(transaction: &DatabaseTransaction, role: role::Model) -> Result<Role> {
+    let permissions = role_permission::Entity::find()
+        .filter(role_permission::Column::RoleId.eq(role.id.clone()))
+        .all(transaction)
+        .await?;
+
+    Ok(Role::from((role, permissions)))
+}
+
+async fn insert_permissions(
+    transaction: &DatabaseTransaction,
+    role_id: &Ulid,
+    permissions: &BTreeSet<Permission>,
+) -> Result<()> {
+    if permissions.is_empty() {
+        return Ok(());
+    }
+
+    let now = Utc::now();
+    let active_models = permissions.iter().map(|permission| role_permission::ActiveModel {
+        id: Set(UlidId::new(Ulid::new())),
+        role_id: Set(UlidId::new(*role_id)),
+        permission: Set((*permission).into()),
+        created_at: Set(now),
+        updated_at: Set(now),
+    });
+    role_permission::Entity::insert_many(active_models).exec(transaction).await?;
+
+    Ok(())
+}
+
+async fn ensure_role_name_not_duplicated(transaction: &DatabaseTransaction, role_name: &str) -> Result<()> {
+    if role::Entity::find().filter(role::Column::Name.eq(role_name)).count(transaction).await? > 0 {
+        return Err(Error::RoleNameDuplicated { entered_role_name: role_name.to_owned() });
+    }
+
+    Ok(())
+}
+
+fn validate_role_name(name: &str) -> Result<()> {
+    if name.is_empty() {
+        return Err(Error::InvalidRoleName { entered_role_name: name.to_owned() });
+    }
+    if claim::Role::from(name.to_owned()).is_builtin() {
+        return Err(Error::ReservedRoleName { entered_role_name: name.to_owned() });
+    }
+
+    Ok(())
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Entered role name({entered_role_name}) is already registered.")]
+    RoleNameDuplicated { entered_role_name: String },
+    #[error("Entered role name({entered_role_name}) is reserved for a built-in role.")]
+    ReservedRoleName { entered_role_name: String },
+    #[error("Entered role name({entered_role_name}) is invalid.")]
+    InvalidRoleName { entered_role_name: String },
+    #[error(transparent)]
+    Anyhow(#[from] anyhow::Error),
+}
+
+impl From<sea_orm::DbErr> for Error {
+    fn from(value: sea_orm::DbErr) -> Self {
+        Error::Anyhow(value.into())
+    }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[cfg(test)]
+mod test {
+    use std::{collections::BTreeSet, sync::Arc};
+
+    use chrono::Utc;
+    use nebula_token::claim::Permission;
+    use sea_orm::{DatabaseBackend, MockDatabase, TransactionTrait};
+    use ulid::Ulid;
+
+    use super::{Error, PostgresRoleService, Role, RoleService};
+    use crate::database::{role, role_permission, UlidId};
+
+    #[tokio::test]
+    async fn when_registering_role_with_builtin_name_then_role_service_returns_reserved_role_name_err() {
+        let mock_connection = Arc::new(MockDatabase::new(DatabaseBackend::Postgres).into_connection());
+        let role_service = PostgresRoleService {};
+
+        for name in ["admin", "member", "auditor"] {
+            let transaction = mock_connection.begin().await.expect("begining transaction should be successful");
+            let result = role_service.register(&transaction, name, &[Permission::PolicyWrite]).await;
+            transaction.commit().await.expect("commiting transaction should be successful");
+
+            assert!(matches!(result, Err(Error::ReservedRoleName { .. })));
+        }
+    }
+
+    #[tokio::test]
+    async fn when_registering_role_with_already_registered_name_then_role_service_returns_role_name_duplicated_err() {
+        let mock_database = MockDatabase::new(DatabaseBackend::Postgres).append_query_results([[maplit::btreemap! {
+            "num_items" => sea_orm::Value::BigInt(Some(1))
+        }]]);
+        let mock_connection = Arc::new(mock_database.into_connection());
+        let role_service = PostgresRoleService {};
+
+        let transaction = mock_connection.begin().await.expect("begining transaction should be successful");
+        let result = role_service.register(&transaction, "policy-manager", &[Permission::PolicyWrite]).await;
+        transaction.commit().await.expect("commiting transaction should be successful");
+
+        assert!(matches!(result, Err(Error::RoleNameDuplicated { .. })));
+    }
+
+    #[tokio::test]
+    async fn when_getting_role_by_name_then_role_service_returns_role_with_permissions() {
+        let now = Utc::now();
+        let role_id = UlidId::new(Ulid::new());
+        let mock_database = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([[role::Model {
+                id: role_id.clone(),
+                name: "policy-manager".to_owned(),
+                created_at: now,
+                updated_at: now,
+            }]])
+            .append_query_results([[
+                role_permission::Model {
+                    id: UlidId::new(Ulid::new()),
+                    role_id: role_id.clone(),
+                    permission: role_permission::Permission::PolicyWrite,
+                    created_at: now,
+                    updated_at: now,
+                },
+                role_permission::Model {
+                    id: UlidId::new(Ulid::new()),
+                    role_id: role_id.clone(),
+                    permission: role_permission::Permission::PolicyRead,
+                    created_at: now,
+                    updated_at: now,
+                },
+            ]]);
+        let mock_connection = Arc::new(mock_database.into_connection());
+        let role_service = PostgresRoleService {};
+
+        let transaction = mock_connection.begin().await.expect("begining transaction should be successful");
+        let role = role_service
+            .get_by_name(&transaction, "policy-manager")
+            .await
+            .expect("getting role should be successful")
+            .expect("role should exist");
+        transaction.commit().await.expect("commiting transaction should be successful");
+
+        assert_eq!(role.id, role_id.inner());
+        assert_eq!(role.permissions, BTreeSet::from([Permission::PolicyRead, Permission::PolicyWrite]));
+    }
+
+    #[tokio::test]
+    async fn when_updating_permissions_with_same_permissions_then_updated_permissions_not_changed() {
+        let mut role = Role::new(
+            Ulid::new(),
+            "policy-manager".to_owned(),
+            BTreeSet::from([Permission::PolicyRead, Permission::PolicyWrite]),
+        );
+
+        role.update_permissions(&[Permission::PolicyWrite, Permission::PolicyRead]);
+
+        assert_eq!(role.updated_permissions, None);
+    }
+
+    #[tokio::test]
+    async fn when_updating_name_to_builtin_name_then_role_returns_reserved_role_name_err() {
+        let mut role = Role::new(Ulid::new(), "policy-manager".to_owned(), BTreeSet::new());
+
+        let result = role.update_name("admin");
+
+        assert!(matches!(result, Err(Error::ReservedRoleName { .. })));
+        assert_eq!(role.updated_name, None);
+    }
+}
@@ -8,8 +8,8 @@ use mockall::automock;
 use nebula_token::claim::NebulaClaim;
 use regex::Regex;
 use sea_orm::{
-    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseTransaction, EntityTrait, IntoActiveModel, LoaderTrait,
-    PaginatorTrait, QueryFilter, QuerySelect, QueryTrait, Set,
+    sea_query::Expr, ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, DatabaseBackend, DatabaseTransaction,
+    EntityTrait, IntoActiveModel, LoaderTrait, PaginatorTrait, QueryFilter, QuerySelect, QueryTrait, Set,
 };
 use tracing::warn;
 use ulid::Ulid;
@@ -27,10 +27,12 @@ pub struct SecretEntry {
     pub key: String,
     pub path: String,
     pub cipher: Vec<u8>,
+    /// Version of the parameter with which the cipher is encrypted.
+    pub parameter_version: i32,
     pub access_condition_ids: Vec<Ulid>,
     deleted: bool,
     updated_path: Option<String>,
-    updated_cipher: Option<Vec<u8>>,
+    updated_cipher: Option<(Vec<u8>, i32)>,
     updated_access_condition_ids: Option<Vec<Ulid>>,
 }
 
@@ -41,6 +43,7 @@ impl SecretEntry {
             key,
             path,
             cipher,
+            parameter_version: 1,
             access_condition_ids,
             deleted: false,
             updated_path: None,
@@ -78,13 +81,14 @@ impl SecretEntry {
         &mut self,
         transaction: &DatabaseTransaction,
         new_cipher: Vec<u8>,
+        new_parameter_version: i32,
         claim: &NebulaClaim,
     ) -> Result<()> {
-        if self.cipher == new_cipher {
+        if self.cipher == new_cipher && self.parameter_version == new_parameter_version {
             return Ok(());
         }
         self.ensure_path_accessible(transaction, AllowedAction::Update, claim).await?;
-        self.updated_cipher = Some(new_cipher);
+        self.updated_cipher = Some((new_cipher, new_parameter_version));
 
         Ok(())
     }
@@ -289,10 +293,17 @@ impl Persistable for SecretEntry {
         } else {
             ActiveValue::default()
         };
-        let cipher_setter = self.updated_cipher.map(Set).unwrap_or_default();
+        let (cipher_setter, parameter_version_setter) = match self.updated_cipher {
+            Some((cipher, parameter_version)) => (Set(cipher), Set(parameter_version)),
+            None => (ActiveValue::default(), ActiveValue::default()),
+        };
 
-        let active_model =
-            secret_value::ActiveModel { identifier: identifier_setter, cipher: cipher_setter, ..Default::default() };
+        let active_model = secret_value::ActiveModel {
+            identifier: identifier_setter,
+            cipher: cipher_setter,
+            parameter_version: parameter_version_setter,
+            ..Default::default()
+        };
 
         if active_model.is_changed() {
             secret_value::Entity::update_many()
@@ -306,15 +317,20 @@ impl Persistable for SecretEntry {
     }
 }
 
-impl From<(secret_metadata::Model, Vec<applied_policy::Model>, Vec<u8>)> for SecretEntry {
+impl From<(secret_metadata::Model, Vec<applied_policy::Model>, (Vec<u8>, i32))> for SecretEntry {
     fn from(
-        (metadata, applied_policies, cipher): (secret_metadata::Model, Vec<applied_policy::Model>, Vec<u8>),
+        (metadata, applied_policies, (cipher, parameter_version)): (
+            secret_metadata::Model,
+            Vec<applied_policy::Model>,
+            (Vec<u8>, i32),
+        ),
     ) -> Self {
         let access_condition_ids = applied_policies.into_iter().map(|ap| ap.policy_id.inner()).collect();
 
         SecretEntry {
             key: metadata.key,
             cipher,
+            parameter_version,
             path: metadata.path,
             access_condition_ids,
             deleted: false,
@@ -598,10 +614,7 @@ pub trait SecretService {
     async fn register_secret(
         &self,
         transaction: &DatabaseTransaction,
-        path: String,
-        key: String,
-        cipher: Vec<u8>,
-        access_conditions: Vec<AccessCondition>,
+        secret: NewSecret,
         claim: &NebulaClaim,
     ) -> Result<()>;
 
@@ -616,6 +629,25 @@ pub trait SecretService {
     async fn get_path(&self, transaction: &DatabaseTransaction, path: &str) -> Result<Option<Path>>;
 
     async fn initialize_root_path(&self, transaction: &DatabaseTransaction) -> Result<()>;
+
+    async fn get_usage(&self, transaction: &DatabaseTransaction) -> Result<SecretUsage>;
+}
+
+/// Secret to be registered under `path` with `key`.
+pub struct NewSecret {
+    pub path: String,
+    pub key: String,
+    pub cipher: Vec<u8>,
+    /// Parameter version with which the cipher is encrypted.
+    pub parameter_version: i32,
+    pub access_conditions: Vec<AccessCondition>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SecretUsage {
+    pub secret_count: u64,
+    pub ciphertext_bytes: u64,
+    pub path_count: u64,
 }
 
 lazy_static! {
@@ -698,7 +730,7 @@ impl SecretService for PostgresSecretService {
         let metadata =
             secret_metadata::Entity::find().filter(secret_metadata::Column::Path.eq(path)).all(transaction).await?;
         let applied_policies = metadata.load_many(applied_policy::Entity, transaction).await?;
-        let mut ciphers: HashMap<String, Vec<u8>> = secret_value::Entity::find()
+        let mut ciphers: HashMap<String, (Vec<u8>, i32)> = secret_value::Entity::find()
             .filter(
                 secret_value::Column::Identifier
                     .is_in(metadata.iter().map(|metadata| create_identifier(&metadata.path, &metadata.key))),
@@ -706,7 +738,7 @@ impl SecretService for PostgresSecretService {
             .all(transaction)
             .await?
             .into_iter()
-            .map(|secret_value| (secret_value.identifier, secret_value.cipher))
+            .map(|secret_value| (secret_value.identifier, (secret_value.cipher, secret_value.parameter_version)))
             .collect();
 
         let policies_by_id: HashMap<_, _> = policy::Entity::find()
@@ -785,7 +817,7 @@ impl SecretService for PostgresSecretService {
             .filter(secret_value::Column::Identifier.eq(create_identifier(&metadata.path, &metadata.key)))
             .one(transaction)
             .await?
-            .map(|secret_value| secret_value.cipher)
+            .map(|secret_value| (secret_value.cipher, secret_value.parameter_version))
             .unwrap_or_default();
 
         let policies = policy::Entity::find()
@@ -867,12 +899,10 @@ impl SecretService for PostgresSecretService {
     async fn register_secret(
         &self,
         transaction: &DatabaseTransaction,
-        path: String,
-        key: String,
-        cipher: Vec<u8>,
-        access_conditions: Vec<AccessCondition>,
+        secret: NewSecret,
         claim: &NebulaClaim,
     ) -> Result<()> {
+        let NewSecret { path, key, cipher, parameter_version, access_conditions } = secret;
         let parent_path = get_path(transaction, &path)
             .await?
             .ok_or_else(|| Error::ParentPathNotExists { entered_path: path.to_owned() })?;
@@ -920,6 +950,7 @@ impl SecretService for PostgresSecretService {
             id: Set(UlidId::new(Ulid::new())),
             identifier: Set(identifier),
             cipher: Set(cipher),
+            parameter_version: Set(parameter_version),
             created_at: Set(now),
             updated_at: Set(now),
         }
@@ -1006,6 +1037,24 @@ impl SecretService for PostgresSecretService {
     async fn get_path(&self, transaction: &DatabaseTransaction, path: &str) -> Result<Option<Path>> {
         get_path(transaction, path).await
     }
+
+    async fn get_usage(&self, transaction: &DatabaseTransaction) -> Result<SecretUsage> {
+        let secret_count = secret_metadata::Entity::find().count(transaction).await?;
+        let path_count = path::Entity::find().filter(path::Column::Path.ne("/")).count(transaction).await?;
+        let ciphertext_bytes = match transaction.get_database_backend() {
+            DatabaseBackend::Sqlite => "COALESCE(SUM(LENGTH(cipher)), 0)",
+            _ => "COALESCE(SUM(OCTET_LENGTH(cipher)), 0)::BIGINT",
+        };
+        let ciphertext_bytes = secret_value::Entity::find()
+            .select_only()
+            .column_as(Expr::cust(ciphertext_bytes), "ciphertext_bytes")
+            .into_tuple::<i64>()
+            .one(transaction)
+            .await?
+            .unwrap_or_default();
+
+        Ok(SecretUsage { secret_count, ciphertext_bytes: ciphertext_bytes as u64, path_count })
+    }
 }
 
 impl PostgresSecretService {
@@ -1147,7 +1196,7 @@ mod test {
     use sea_orm::{DatabaseBackend, DbErr, MockDatabase, TransactionTrait};
     use ulid::Ulid;
 
-    use super::{Error, PostgresSecretService, SecretService};
+    use super::{Error, NewSecret, PostgresSecretService, SecretService};
     use crate::{
         database::{
             applied_path_policy, applied_path_policy_allowed_action, applied_policy, path, policy, secret_metadata,
@@ -1199,6 +1248,7 @@ mod test {
                 id: UlidId::new(Ulid::new()),
                 identifier: "/test/path/TEST_KEY".to_owned(),
                 cipher: vec![1, 2, 3],
+                parameter_version: 1,
                 created_at: now,
                 updated_at: now,
             }]])
@@ -1285,6 +1335,7 @@ mod test {
                 id: UlidId::new(Ulid::new()),
                 identifier: "/test/path/TEST_KEY".to_owned(),
                 cipher: vec![1, 2, 3],
+                parameter_version: 1,
                 created_at: now,
                 updated_at: now,
             }]])
@@ -1536,6 +1587,7 @@ mod test {
                 id: UlidId::new(Ulid::new()),
                 identifier: "/test/path/TEST_KEY".to_owned(),
                 cipher: vec![1, 2, 3],
+                parameter_version: 1,
                 created_at: now,
                 updated_at: now,
             }]]);
@@ -1547,7 +1599,17 @@ mod test {
         let transaction = mock_connection.begin().await.expect("begining transaction should be successful");
 
         secret_service
-            .register_secret(&transaction, path.to_owned(), key.to_owned(), vec![1, 2, 3], access_conditions, &claim)
+            .register_secret(
+                &transaction,
+                NewSecret {
+                    path: path.to_owned(),
+                    key: key.to_owned(),
+                    cipher: vec![1, 2, 3],
+                    parameter_version: 1,
+                    access_conditions,
+                },
+                &claim,
+            )
             .await
             .expect("creating workspace should be successful");
         transaction.commit().await.expect("commiting transaction should be successful");
@@ -1599,7 +1661,17 @@ mod test {
         let transaction = mock_connection.begin().await.expect("begining transaction should be successful");
 
         let result = secret_service
-            .register_secret(&transaction, path.to_owned(), key.to_owned(), vec![], access_conditions, &claim)
+            .register_secret(
+                &transaction,
+                NewSecret {
+                    path: path.to_owned(),
+                    key: key.to_owned(),
+                    cipher: vec![],
+                    parameter_version: 1,
+                    access_conditions,
+                },
+                &claim,
+            )
             .await;
         transaction.commit().await.expect("commiting transaction should be successful");
 
@@ -1657,7 +1729,17 @@ mod test {
         let transaction = mock_connection.begin().await.expect("begining transaction should be successful");
 
         let result = secret_service
-            .register_secret(&transaction, path.to_owned(), key.to_owned(), vec![], access_conditions, &claim)
+            .register_secret(
+                &transaction,
+                NewSecret {
+                    path: path.to_owned(),
+                    key: key.to_owned(),
+                    cipher: vec![],
+                    parameter_version: 1,
+                    access_conditions,
+                },
+                &claim,
+            )
             .await;
         transaction.commit().await.expect("commiting transaction should be successful");
 
@@ -1704,6 +1786,7 @@ mod test {
             key: "TEST_KEY".to_owned(),
             path: "/test/path".to_owned(),
             cipher: vec![1, 2, 3],
+            parameter_version: 1,
             access_condition_ids: vec![Ulid::from_string("01JACZ44MJDY5GD21X2W910CFV").unwrap()],
             deleted: false,
             updated_path: None,
@@ -1758,6 +1841,7 @@ mod test {
             key: "TEST_KEY".to_owned(),
             path: "/test/path".to_owned(),
             cipher: vec![1, 2, 3],
+            parameter_version: 1,
             access_condition_ids: vec![Ulid::from_string("01JACZ44MJDY5GD21X2W910CFV").unwrap()],
             deleted: false,
             updated_path: None,
@@ -1815,6 +1899,7 @@ mod test {
             key: "TEST_KEY".to_owned(),
             path: "/test/path".to_owned(),
             cipher: vec![1, 2, 3],
+            parameter_version: 1,
             access_condition_ids: vec![Ulid::from_string("01JACZ44MJDY5GD21X2W910CFV").unwrap()],
             deleted: false,
             updated_path: None,
@@ -1823,13 +1908,13 @@ mod test {
         };
 
         secret_entry
-            .update_cipher(&transaction, vec![4, 5, 6], &claim)
+            .update_cipher(&transaction, vec![4, 5, 6], 2, &claim)
             .await
             .expect("updating secre tshould be successful");
 
         transaction.commit().await.expect("commiting transaction should be successful");
 
-        assert_eq!(secret_entry.updated_cipher, Some(vec![4, 5, 6]));
+        assert_eq!(secret_entry.updated_cipher, Some((vec![4, 5, 6], 2)));
     }
 
     #[tokio::test]
@@ -1846,6 +1931,7 @@ mod test {
             key: "TEST_KEY".to_owned(),
             path: "/test/path".to_owned(),
             cipher: vec![1, 2, 3],
+            parameter_version: 1,
             access_condition_ids: vec![Ulid::from_string("01JACZ44MJDY5GD21X2W910CFV").unwrap()],
             deleted: false,
             updated_path: None,
@@ -2,29 +2,163 @@ use async_trait::async_trait;
 
 mod workspace_service;
 
-use sea_orm::{DatabaseTransaction, EntityTrait};
+use chrono::{DateTime, Utc};
+use nebula_common::validate_workspace_name;
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseTransaction, EntityTrait, QueryFilter, Set};
 use tracing::info;
 use ulid::Ulid;
 #[cfg(test)]
 pub use workspace_service::MockWorkspaceService;
 pub use workspace_service::{WorkspaceService, WorkspaceServiceImpl};
 
-use crate::database::{Persistable, UlidId};
+use crate::database::{workspace, Persistable, UlidId};
 
 #[derive(Debug, PartialEq)]
 pub struct Workspace {
     id: Ulid,
     pub name: String,
-    deleted: bool,
+    pub display_name: Option<String>,
+    pub settings: WorkspaceSettings,
+    pub deleted_at: Option<DateTime<Utc>>,
+    updated_name: Option<String>,
+    updated_display_name: Option<String>,
+    updated_settings: Option<WorkspaceSettings>,
+    deletion_changed: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorkspaceSettings {
+    pub default_access_condition_ids: Vec<Ulid>,
+    pub max_secret_size: Option<u64>,
+    pub allowed_authority_hosts: Option<Vec<String>>,
+    pub quota: WorkspaceQuota,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WorkspaceQuota {
+    pub max_secrets: Option<u64>,
+    pub max_ciphertext_bytes: Option<u64>,
+    pub max_paths: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuotaResource {
+    Secrets,
+    CiphertextBytes,
+    Paths,
+}
+
+impl WorkspaceQuota {
+    /// Fills the limits which are not overridden by the workspace with the server-wide defaults.
+    pub fn or(self, default: &WorkspaceQuota) -> Self {
+        Self {
+            max_secrets: self.max_secrets.or(default.max_secrets),
+            max_ciphertext_bytes: self.max_ciphertext_bytes.or(default.max_ciphertext_bytes),
+            max_paths: self.max_paths.or(default.max_paths),
+        }
+    }
+
+    pub fn is_unlimited(&self) -> bool {
+        self.max_secrets.is_none() && self.max_ciphertext_bytes.is_none() && self.max_paths.is_none()
+    }
+
+    pub fn limit(&self, resource: QuotaResource) -> Option<u64> {
+        match resource {
+            QuotaResource::Secrets => self.max_secrets,
+            QuotaResource::CiphertextBytes => self.max_ciphertext_bytes,
+            QuotaResource::Paths => self.max_paths,
+        }
+    }
+
+    /// Returns the exceeded limit when `usage` would go over the quota of `resource`.
+    pub fn exceeded_limit(&self, resource: QuotaResource, usage: u64) -> Option<u64> {
+        self.limit(resource).filter(|limit| usage > *limit)
+    }
+}
+
+impl WorkspaceSettings {
+    pub fn is_authority_host_allowed(&self, host: &str) -> bool {
+        let Some(allowed_hosts) = &self.allowed_authority_hosts else {
+            return true;
+        };
+
+        let host_name = url::Url::parse(host).ok().and_then(|url| url.host_str().map(str::to_owned));
+
+        allowed_hosts
+            .iter()
+            .any(|allowed_host| allowed_host == host || host_name.as_deref() == Some(allowed_host.as_str()))
+    }
+
+    pub fn is_secret_size_allowed(&self, size: usize) -> bool {
+        self.max_secret_size.map(|max_secret_size| size as u64 <= max_secret_size).unwrap_or(true)
+    }
 }
 
 impl Workspace {
     pub fn new(id: Ulid, name: String) -> Self {
-        Self { id, name, deleted: false }
+        Self {
+            id,
+            name,
+            display_name: None,
+            settings: WorkspaceSettings::default(),
+            deleted_at: None,
+            updated_name: None,
+            updated_display_name: None,
+            updated_settings: None,
+            deletion_changed: false,
+        }
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
     }
 
     pub fn delete(&mut self) {
-        self.deleted = true
+        if self.is_deleted() {
+            return;
+        }
+
+        self.deleted_at = Some(Utc::now());
+        self.deletion_changed = true;
+    }
+
+    pub fn restore(&mut self) {
+        if !self.is_deleted() {
+            return;
+        }
+
+        self.deleted_at = None;
+        self.deletion_changed = true;
+    }
+
+    /// Renames the workspace. Its schema is renamed separately by `WorkspaceService::rename_scope`.
+    pub fn rename(&mut self, new_name: &str) -> Result<()> {
+        if !validate_workspace_name(new_name) {
+            return Err(Error::InvalidWorkspaceName);
+        }
+        if self.name == new_name {
+            self.updated_name = None;
+            return Ok(());
+        }
+
+        self.updated_name = Some(new_name.to_owned());
+        Ok(())
+    }
+
+    pub fn update_display_name(&mut self, new_display_name: &str) {
+        if self.display_name.as_deref() == Some(new_display_name) {
+            return;
+        }
+
+        self.updated_display_name = Some(new_display_name.to_owned());
+    }
+
+    pub fn update_settings(&mut self, new_settings: WorkspaceSettings) {
+        if self.settings == new_settings {
+            return;
+        }
+
+        self.updated_settings = Some(new_settings);
     }
 }
 
@@ -47,16 +181,130 @@ impl Persistable for Workspace {
     type Error = crate::domain::workspace::Error;
 
     async fn persist(self, transaction: &DatabaseTransaction) -> crate::domain::workspace::Result<()> {
-        if self.deleted {
-            use crate::database::workspace::Entity;
-
-            Entity::delete_by_id(UlidId::from(self.id)).exec(transaction).await?;
+        let workspace_name = self.name;
 
-            let workspace_name = self.name;
-            info!("workspace(name: {workspace_name}) is deleted.");
-            return Ok(());
+        let mut active_model = workspace::ActiveModel {
+            name: self.updated_name.clone().map(Set).unwrap_or_default(),
+            display_name: self.updated_display_name.map(|display_name| Set(Some(display_name))).unwrap_or_default(),
+            settings: self.updated_settings.map(|settings| Set(settings.into())).unwrap_or_default(),
+            ..Default::default()
         };
+        if self.deletion_changed {
+            active_model.deleted_at = Set(self.deleted_at);
+        }
+
+        if active_model.is_changed() {
+            active_model.updated_at = Set(Utc::now());
+            workspace::Entity::update_many()
+                .filter(workspace::Column::Id.eq(UlidId::new(self.id)))
+                .set(active_model)
+                .exec(transaction)
+                .await?;
+        }
+
+        if let Some(updated_name) = self.updated_name {
+            info!("workspace(name: {workspace_name}) is renamed to {updated_name}.");
+        }
+        if self.deletion_changed {
+            if self.deleted_at.is_some() {
+                info!("workspace(name: {workspace_name}) is marked as deleted.");
+            } else {
+                info!("workspace(name: {workspace_name}) is restored.");
+            }
+        }
 
         Ok(())
     }
 }
+
+impl From<workspace::Settings> for WorkspaceSettings {
+    fn from(value: workspace::Settings) -> Self {
+        Self {
+            default_access_condition_ids: value.default_access_condition_ids,
+            max_secret_size: value.max_secret_size,
+            allowed_authority_hosts: value.allowed_authority_hosts,
+            quota: value.quota.into(),
+        }
+    }
+}
+
+impl From<WorkspaceSettings> for workspace::Settings {
+    fn from(value: WorkspaceSettings) -> Self {
+        Self {
+            default_access_condition_ids: value.default_access_condition_ids,
+            max_secret_size: value.max_secret_size,
+            allowed_authority_hosts: value.allowed_authority_hosts,
+            quota: value.quota.into(),
+        }
+    }
+}
+
+impl From<workspace::Quota> for WorkspaceQuota {
+    fn from(value: workspace::Quota) -> Self {
+        Self {
+            max_secrets: value.max_secrets,
+            max_ciphertext_bytes: value.max_ciphertext_bytes,
+            max_paths: value.max_paths,
+        }
+    }
+}
+
+impl From<WorkspaceQuota> for workspace::Quota {
+    fn from(value: WorkspaceQuota) -> Self {
+        Self {
+            max_secrets: value.max_secrets,
+            max_ciphertext_bytes: value.max_ciphertext_bytes,
+            max_paths: value.max_paths,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ulid::Ulid;
+
+    use super::{Error, QuotaResource, Workspace, WorkspaceQuota, WorkspaceSettings};
+
+    #[test]
+    fn when_deleting_workspace_then_deleted_at_is_set_and_restore_clears_it() {
+        let mut workspace = Workspace::new(Ulid::new(), "testworkspace".to_owned());
+
+        workspace.delete();
+        assert!(workspace.is_deleted());
+
+        workspace.restore();
+        assert!(!workspace.is_deleted());
+    }
+
+    #[test]
+    fn when_renaming_workspace_with_invalid_name_then_workspace_returns_invalid_workspace_name_err() {
+        let mut workspace = Workspace::new(Ulid::new(), "testworkspace".to_owned());
+
+        let result = workspace.rename("invalid name!");
+
+        assert!(matches!(result, Err(Error::InvalidWorkspaceName)));
+    }
+
+    #[test]
+    fn when_allowed_authority_hosts_are_configured_then_only_matching_hosts_are_allowed() {
+        let settings = WorkspaceSettings {
+            allowed_authority_hosts: Some(vec!["authority.example.com".to_owned()]),
+            ..Default::default()
+        };
+
+        assert!(settings.is_authority_host_allowed("https://authority.example.com:8090"));
+        assert!(!settings.is_authority_host_allowed("https://evil.example.com"));
+        assert!(WorkspaceSettings::default().is_authority_host_allowed("https://evil.example.com"));
+    }
+
+    #[test]
+    fn when_workspace_quota_is_not_overridden_then_default_quota_is_applied() {
+        let default_quota = WorkspaceQuota { max_secrets: Some(10), max_ciphertext_bytes: Some(1024), max_paths: None };
+        let quota = WorkspaceQuota { max_secrets: Some(100), ..Default::default() }.or(&default_quota);
+
+        assert_eq!(quota.exceeded_limit(QuotaResource::Secrets, 100), None);
+        assert_eq!(quota.exceeded_limit(QuotaResource::Secrets, 101), Some(100));
+        assert_eq!(quota.exceeded_limit(QuotaResource::CiphertextBytes, 1025), Some(1024));
+        assert_eq!(quota.exceeded_limit(QuotaResource::Paths, u64::MAX), None);
+    }
+}
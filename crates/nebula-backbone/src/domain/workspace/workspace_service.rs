@@ -2,17 +2,20 @@ use std::sync::Arc;
 
 use super::Error;
 use crate::{
-    database::{migrate_workspace, AuthMethod},
+    database::{
+        create_workspace_scope, drop_workspace_scope, migrate_workspace, rename_workspace_scope, DatabaseLocation,
+        PendingWorkspaceFiles,
+    },
     domain::workspace::Workspace,
 };
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 #[cfg(test)]
 use mockall::automock;
 use nebula_common::validate_workspace_name;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseBackend, DatabaseConnection, DatabaseTransaction, DbErr,
-    EntityTrait, PaginatorTrait, QueryFilter, RuntimeErr, SqlxError, Statement,
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DatabaseTransaction, DbErr, EntityTrait, IntoActiveModel,
+    PaginatorTrait, QueryFilter, QuerySelect, RuntimeErr, SqlxError,
 };
 use tracing::info;
 use ulid::Ulid;
@@ -22,26 +25,43 @@ use ulid::Ulid;
 pub trait WorkspaceService {
     async fn get_all(&self, transaction: &DatabaseTransaction) -> Result<Vec<Workspace>>;
     async fn get_by_name(&self, transaction: &DatabaseTransaction, name: &str) -> Result<Option<Workspace>>;
+    /// Gets the workspace and locks it until the transaction ends, so that the quota of the workspace is checked and
+    /// used by one transaction at a time. SQLite has no row lock, but serializes the writing transactions anyway.
+    async fn get_by_name_for_update(&self, transaction: &DatabaseTransaction, name: &str) -> Result<Option<Workspace>>;
     async fn create(&self, transaction: &DatabaseTransaction, name: &str) -> Result<()>;
+    /// Copies the paths, access conditions and authority registrations of a workspace. Secrets are not copied, because
+    /// their ciphertexts are bound to the key pairs of the authorities named after the source workspace.
+    async fn copy_contents(
+        &self,
+        source_transaction: &DatabaseTransaction,
+        target_transaction: &DatabaseTransaction,
+    ) -> Result<()>;
+    /// Drops the schema of a workspace whose creation has failed, which is created outside of the transaction.
+    async fn drop_scope(&self, name: &str) -> Result<()>;
+    /// Renames the schema of a workspace. The returned files are applied after the transaction is committed.
+    async fn rename_scope(
+        &self,
+        transaction: &DatabaseTransaction,
+        name: &str,
+        new_name: &str,
+    ) -> Result<PendingWorkspaceFiles>;
+    /// Purges the workspaces deleted before `deleted_before`. The returned files are applied after the transaction is
+    /// committed.
+    async fn purge_deleted(
+        &self,
+        transaction: &DatabaseTransaction,
+        deleted_before: DateTime<Utc>,
+    ) -> Result<(Vec<String>, PendingWorkspaceFiles)>;
 }
 
 pub struct WorkspaceServiceImpl {
     connection: Arc<DatabaseConnection>,
-    database_host: String,
-    database_port: u16,
-    database_name: String,
-    database_auth: AuthMethod,
+    database_location: DatabaseLocation,
 }
 
 impl WorkspaceServiceImpl {
-    pub fn new(
-        connection: Arc<DatabaseConnection>,
-        database_host: String,
-        database_port: u16,
-        database_name: String,
-        database_auth: AuthMethod,
-    ) -> Self {
-        Self { connection, database_host, database_port, database_name, database_auth }
+    pub fn new(connection: Arc<DatabaseConnection>, database_location: DatabaseLocation) -> Self {
+        Self { connection, database_location }
     }
 
     async fn exists_by_name(&self, transaction: &DatabaseTransaction, name: &str) -> Result<bool> {
@@ -54,9 +74,9 @@ impl WorkspaceServiceImpl {
 #[async_trait]
 impl WorkspaceService for WorkspaceServiceImpl {
     async fn get_all(&self, transaction: &DatabaseTransaction) -> Result<Vec<Workspace>> {
-        use crate::database::workspace::Entity;
+        use crate::database::workspace::{Column, Entity};
 
-        let workspace_models = Entity::find().all(transaction).await?;
+        let workspace_models = Entity::find().filter(Column::DeletedAt.is_null()).all(transaction).await?;
 
         return Ok(workspace_models.into_iter().map(Workspace::from).collect());
     }
@@ -69,6 +89,14 @@ impl WorkspaceService for WorkspaceServiceImpl {
         Ok(workspace_model.map(Workspace::from))
     }
 
+    async fn get_by_name_for_update(&self, transaction: &DatabaseTransaction, name: &str) -> Result<Option<Workspace>> {
+        use crate::database::workspace::{Column, Entity};
+
+        let workspace_model = Entity::find().filter(Column::Name.eq(name)).lock_exclusive().one(transaction).await?;
+
+        Ok(workspace_model.map(Workspace::from))
+    }
+
     async fn create(&self, transaction: &DatabaseTransaction, name: &str) -> Result<()> {
         use crate::database::workspace::ActiveModel;
         use sea_orm::ActiveValue;
@@ -77,15 +105,8 @@ impl WorkspaceService for WorkspaceServiceImpl {
             return Err(Error::InvalidWorkspaceName);
         }
 
-        self.connection
-            .execute(Statement::from_string(
-                DatabaseBackend::Postgres,
-                format!("CREATE SCHEMA IF NOT EXISTS \"{name}\";"),
-            ))
-            .await?;
-
-        migrate_workspace(name, &self.database_host, self.database_port, &self.database_name, &self.database_auth)
-            .await?;
+        create_workspace_scope(self.connection.as_ref(), name).await?;
+        migrate_workspace(name, &self.database_location).await?;
 
         if self.exists_by_name(transaction, name).await? {
             return Err(Error::WorkspaceNameConflicted);
@@ -96,6 +117,9 @@ impl WorkspaceService for WorkspaceServiceImpl {
         ActiveModel {
             id: ActiveValue::Set(Ulid::new().into()),
             name: ActiveValue::Set(name.to_owned()),
+            display_name: ActiveValue::Set(None),
+            settings: ActiveValue::Set(Default::default()),
+            deleted_at: ActiveValue::Set(None),
             created_at: ActiveValue::Set(now),
             updated_at: ActiveValue::Set(now),
         }
@@ -106,18 +130,106 @@ impl WorkspaceService for WorkspaceServiceImpl {
 
         Ok(())
     }
+
+    async fn copy_contents(
+        &self,
+        source_transaction: &DatabaseTransaction,
+        target_transaction: &DatabaseTransaction,
+    ) -> Result<()> {
+        use crate::database::{applied_path_policy, applied_path_policy_allowed_action, authority, path, policy};
+
+        copy_all::<path::Entity>(source_transaction, target_transaction).await?;
+        copy_all::<applied_path_policy::Entity>(source_transaction, target_transaction).await?;
+        copy_all::<applied_path_policy_allowed_action::Entity>(source_transaction, target_transaction).await?;
+        copy_all::<policy::Entity>(source_transaction, target_transaction).await?;
+        copy_all::<authority::Entity>(source_transaction, target_transaction).await?;
+
+        Ok(())
+    }
+
+    async fn drop_scope(&self, name: &str) -> Result<()> {
+        if !validate_workspace_name(name) {
+            return Err(Error::InvalidWorkspaceName);
+        }
+
+        // Nothing is left to roll back, since the workspace has not been created.
+        drop_workspace_scope(self.connection.as_ref(), name).await?.apply().await?;
+        Ok(())
+    }
+
+    async fn rename_scope(
+        &self,
+        transaction: &DatabaseTransaction,
+        name: &str,
+        new_name: &str,
+    ) -> Result<PendingWorkspaceFiles> {
+        if !validate_workspace_name(new_name) {
+            return Err(Error::InvalidWorkspaceName);
+        }
+
+        Ok(rename_workspace_scope(transaction, name, new_name).await?)
+    }
+
+    async fn purge_deleted(
+        &self,
+        transaction: &DatabaseTransaction,
+        deleted_before: DateTime<Utc>,
+    ) -> Result<(Vec<String>, PendingWorkspaceFiles)> {
+        use crate::database::workspace::{Column, Entity};
+
+        let workspace_models = Entity::find().filter(Column::DeletedAt.lt(deleted_before)).all(transaction).await?;
+
+        let mut purged_workspace_names = vec![];
+        let mut pending_files = PendingWorkspaceFiles::default();
+        for workspace_model in workspace_models {
+            let name = workspace_model.name;
+            if !validate_workspace_name(&name) {
+                return Err(Error::InvalidWorkspaceName);
+            }
+
+            pending_files.merge(drop_workspace_scope(transaction, &name).await?);
+            Entity::delete_by_id(workspace_model.id).exec(transaction).await?;
+
+            info!("workspace(name: {name}) is purged.");
+            purged_workspace_names.push(name);
+        }
+
+        Ok((purged_workspace_names, pending_files))
+    }
+}
+
+async fn copy_all<E>(source_transaction: &DatabaseTransaction, target_transaction: &DatabaseTransaction) -> Result<()>
+where
+    E: EntityTrait,
+    E::Model: IntoActiveModel<E::ActiveModel>,
+    E::ActiveModel: ActiveModelTrait<Entity = E> + Send,
+{
+    let models = E::find().all(source_transaction).await?;
+    if models.is_empty() {
+        return Ok(());
+    }
+
+    E::insert_many(models.into_iter().map(|model| model.into_active_model().reset_all()))
+        .exec(target_transaction)
+        .await?;
+
+    Ok(())
 }
 
 impl From<crate::database::workspace::Model> for Workspace {
     fn from(value: crate::database::workspace::Model) -> Self {
-        Workspace::new(value.id.inner(), value.name)
+        let mut workspace = Workspace::new(value.id.inner(), value.name);
+        workspace.display_name = value.display_name;
+        workspace.settings = value.settings.into();
+        workspace.deleted_at = value.deleted_at;
+        workspace
     }
 }
 
 impl From<DbErr> for Error {
     fn from(value: DbErr) -> Self {
         if let DbErr::Query(RuntimeErr::SqlxError(SqlxError::Database(e))) = value {
-            if e.code().as_deref() == Some("23505") {
+            if e.is_unique_violation() {
                 Self::WorkspaceNameConflicted
             } else {
                 Self::Anyhow(e.into())
@@ -132,16 +244,89 @@ pub type Result<T> = std::result::Result<T, Error>;
 
 #[cfg(test)]
 mod test {
-    use std::sync::Arc;
+    use std::{
+        path::{Path, PathBuf},
+        sync::Arc,
+    };
 
     use chrono::Utc;
-    use sea_orm::{DatabaseBackend, DbErr, MockDatabase, MockExecResult, TransactionTrait};
+    use sea_orm::{
+        DatabaseBackend, DatabaseConnection, DbErr, EntityTrait, MockDatabase, MockExecResult, TransactionTrait,
+    };
     use ulid::Ulid;
 
-    use crate::database::AuthMethod;
+    use crate::{
+        database::{
+            close_database, connect_to_database, migrate, path, AuthMethod, DatabaseLocation, Persistable,
+            WorkspaceScopedTransaction,
+        },
+        domain::secret::{PostgresSecretService, SecretService},
+    };
 
     use super::{Error, WorkspaceService, WorkspaceServiceImpl};
 
+    /// Directory of the SQLite databases, where the workspace created by the test is migrated.
+    fn sqlite_directory() -> PathBuf {
+        std::env::temp_dir().join(format!("nebula-backbone-workspace-test-{}", Ulid::new()))
+    }
+
+    /// Creates a workspace having the root path on a SQLite database in `directory`.
+    async fn sqlite_workspace(directory: &Path, name: &str) -> (Arc<DatabaseConnection>, WorkspaceServiceImpl) {
+        let location = DatabaseLocation::Sqlite { path: directory.to_path_buf() };
+        let connection = connect_to_database(&location).await.expect("connecting to database should be successful");
+        migrate(connection.as_ref()).await.expect("migrating database should be successful");
+        let workspace_service = WorkspaceServiceImpl::new(connection.clone(), location);
+
+        let transaction =
+            connection.begin_with_workspace_scope(name).await.expect("begining transaction should be successful");
+        workspace_service.create(&transaction, name).await.expect("creating workspace should be successful");
+        PostgresSecretService {}
+            .initialize_root_path(&transaction)
+            .await
+            .expect("initializing root path should be successful");
+        transaction.commit().await.expect("commiting transaction should be successful");
+
+        (connection, workspace_service)
+    }
+
+    async fn rename_workspace(
+        connection: &DatabaseConnection,
+        workspace_service: &WorkspaceServiceImpl,
+        name: &str,
+        new_name: &str,
+        commit: bool,
+    ) {
+        let transaction = connection.begin().await.expect("begining transaction should be successful");
+        let mut workspace = workspace_service
+            .get_by_name(&transaction, name)
+            .await
+            .expect("getting workspace should be successful")
+            .expect("workspace should exist");
+        workspace.rename(new_name).expect("renaming workspace should be successful");
+        let pending_files = workspace_service
+            .rename_scope(&transaction, name, new_name)
+            .await
+            .expect("renaming scope should be successful");
+        workspace.persist(&transaction).await.expect("persisting workspace should be successful");
+
+        if commit {
+            transaction.commit().await.expect("commiting transaction should be successful");
+            pending_files.apply().await.expect("applying pending files should be successful");
+        } else {
+            transaction.rollback().await.expect("rolling back transaction should be successful");
+            drop(pending_files);
+        }
+    }
+
+    async fn root_paths(connection: &DatabaseConnection, name: &str) -> Vec<String> {
+        let transaction =
+            connection.begin_with_workspace_scope(name).await.expect("begining transaction should be successful");
+        let paths = path::Entity::find().all(&transaction).await.expect("getting paths should be successful");
+        transaction.commit().await.expect("commiting transaction should be successful");
+
+        paths.into_iter().map(|path| path.path).collect()
+    }
+
     #[tokio::test]
     async fn when_insert_is_successful_then_workspace_service_returns_ok() {
         use crate::database::workspace::Model;
@@ -155,19 +340,18 @@ mod test {
             .append_query_results([vec![Model {
                 id: Ulid::new().into(),
                 name: WORKSPACE_NAME.to_owned(),
+                display_name: None,
+                settings: Default::default(),
+                deleted_at: None,
                 created_at: now,
                 updated_at: now,
             }]])
             .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 0 }]);
         let mock_connection = Arc::new(mock_database.into_connection());
 
-        let workspace_service = WorkspaceServiceImpl::new(
-            mock_connection.clone(),
-            "mock.database.host".to_owned(),
-            5432,
-            "postgres".to_owned(),
-            AuthMethod::Credential { username: "postgres".to_owned(), password: None },
-        );
+        let directory = sqlite_directory();
+        let workspace_service =
+            WorkspaceServiceImpl::new(mock_connection.clone(), DatabaseLocation::Sqlite { path: directory.clone() });
 
         let transaction = mock_connection.begin().await.expect("begining transaction should be successful");
 
@@ -176,6 +360,7 @@ mod test {
         transaction.commit().await.expect("commiting transaction should be successful");
 
         result.expect("creating workspace should be successful");
+        std::fs::remove_dir_all(directory).expect("removing test directory should be successful");
     }
 
     #[tokio::test]
@@ -191,13 +376,9 @@ mod test {
             }]]);
         let mock_connection = Arc::new(mock_database.into_connection());
 
-        let workspace_service = WorkspaceServiceImpl::new(
-            mock_connection.clone(),
-            "mock.database.host".to_owned(),
-            5432,
-            "postgres".to_owned(),
-            AuthMethod::Credential { username: "postgres".to_owned(), password: None },
-        );
+        let directory = sqlite_directory();
+        let workspace_service =
+            WorkspaceServiceImpl::new(mock_connection.clone(), DatabaseLocation::Sqlite { path: directory.clone() });
 
         let transaction = mock_connection.begin().await.expect("begining transaction should be successful");
 
@@ -206,6 +387,7 @@ mod test {
         transaction.commit().await.expect("commiting transaction should be successful");
 
         assert!(matches!(result, Err(Error::WorkspaceNameConflicted)));
+        std::fs::remove_dir_all(directory).expect("removing test directory should be successful");
     }
 
     #[tokio::test]
@@ -219,13 +401,9 @@ mod test {
             .append_query_errors(vec![DbErr::Custom("some error".to_owned())]);
         let mock_connection = Arc::new(mock_database.into_connection());
 
-        let workspace_service = WorkspaceServiceImpl::new(
-            mock_connection.clone(),
-            "mock.database.host".to_owned(),
-            5432,
-            "postgres".to_owned(),
-            AuthMethod::Credential { username: "postgres".to_owned(), password: None },
-        );
+        let directory = sqlite_directory();
+        let workspace_service =
+            WorkspaceServiceImpl::new(mock_connection.clone(), DatabaseLocation::Sqlite { path: directory.clone() });
 
         let transaction = mock_connection.begin().await.expect("begining transaction should be successful");
 
@@ -235,6 +413,7 @@ mod test {
 
         assert!(matches!(result, Err(Error::Anyhow(_))));
         assert_eq!(result.err().unwrap().to_string(), "Custom Error: some error");
+        std::fs::remove_dir_all(directory).expect("removing test directory should be successful");
     }
 
     #[tokio::test]
@@ -246,6 +425,9 @@ mod test {
         let mock_database = MockDatabase::new(DatabaseBackend::Postgres).append_query_results([vec![Model {
             id: Ulid::new().into(),
             name: WORKSPACE_NAME.to_owned(),
+            display_name: None,
+            settings: Default::default(),
+            deleted_at: None,
             created_at: now,
             updated_at: now,
         }]]);
@@ -253,10 +435,12 @@ mod test {
 
         let workspace_service = WorkspaceServiceImpl::new(
             mock_connection.clone(),
-            "mock.database.host".to_owned(),
-            5432,
-            "postgres".to_owned(),
-            AuthMethod::Credential { username: "postgres".to_owned(), password: None },
+            DatabaseLocation::Postgres {
+                host: "mock.database.host".to_owned(),
+                port: 5432,
+                database_name: "postgres".to_owned(),
+                auth: AuthMethod::Credential { username: "postgres".to_owned(), password: None },
+            },
         );
 
         let transaction = mock_connection.begin().await.expect("begining transaction should be successful");
@@ -275,10 +459,12 @@ mod test {
 
         let workspace_service = WorkspaceServiceImpl::new(
             mock_connection.clone(),
-            "mock.database.host".to_owned(),
-            5432,
-            "postgres".to_owned(),
-            AuthMethod::Credential { username: "postgres".to_owned(), password: None },
+            DatabaseLocation::Postgres {
+                host: "mock.database.host".to_owned(),
+                port: 5432,
+                database_name: "postgres".to_owned(),
+                auth: AuthMethod::Credential { username: "postgres".to_owned(), password: None },
+            },
         );
 
         let transaction = mock_connection.begin().await.expect("begining transaction should be successful");
@@ -299,10 +485,12 @@ mod test {
 
         let workspace_service = WorkspaceServiceImpl::new(
             mock_connection.clone(),
-            "mock.database.host".to_owned(),
-            5432,
-            "postgres".to_owned(),
-            AuthMethod::Credential { username: "postgres".to_owned(), password: None },
+            DatabaseLocation::Postgres {
+                host: "mock.database.host".to_owned(),
+                port: 5432,
+                database_name: "postgres".to_owned(),
+                auth: AuthMethod::Credential { username: "postgres".to_owned(), password: None },
+            },
         );
 
         let transaction = mock_connection.begin().await.expect("begining transaction should be successful");
@@ -322,10 +510,12 @@ mod test {
 
         let workspace_service = WorkspaceServiceImpl::new(
             mock_connection.clone(),
-            "mock.database.host".to_owned(),
-            5432,
-            "postgres".to_owned(),
-            AuthMethod::Credential { username: "postgres".to_owned(), password: None },
+            DatabaseLocation::Postgres {
+                host: "mock.database.host".to_owned(),
+                port: 5432,
+                database_name: "postgres".to_owned(),
+                auth: AuthMethod::Credential { username: "postgres".to_owned(), password: None },
+            },
         );
 
         let transaction = mock_connection.begin().await.expect("begining transaction should be successful");
@@ -346,6 +536,9 @@ mod test {
         let mock_database = MockDatabase::new(DatabaseBackend::Postgres).append_query_results([vec![Model {
             id: Ulid::new().into(),
             name: WORKSPACE_NAME.to_owned(),
+            display_name: None,
+            settings: Default::default(),
+            deleted_at: None,
             created_at: now,
             updated_at: now,
         }]]);
@@ -353,10 +546,12 @@ mod test {
 
         let workspace_service = WorkspaceServiceImpl::new(
             mock_connection.clone(),
-            "mock.database.host".to_owned(),
-            5432,
-            "postgres".to_owned(),
-            AuthMethod::Credential { username: "postgres".to_owned(), password: None },
+            DatabaseLocation::Postgres {
+                host: "mock.database.host".to_owned(),
+                port: 5432,
+                database_name: "postgres".to_owned(),
+                auth: AuthMethod::Credential { username: "postgres".to_owned(), password: None },
+            },
         );
 
         let transaction = mock_connection.begin().await.expect("begining transaction should be successful");
@@ -369,4 +564,107 @@ mod test {
             WORKSPACE_NAME
         )
     }
+
+    #[tokio::test]
+    async fn when_copying_empty_workspace_then_workspace_service_returns_ok_without_insertion() {
+        use crate::database::{applied_path_policy, applied_path_policy_allowed_action, authority, path, policy};
+
+        let mock_database = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([Vec::<path::Model>::new()])
+            .append_query_results([Vec::<applied_path_policy::Model>::new()])
+            .append_query_results([Vec::<applied_path_policy_allowed_action::Model>::new()])
+            .append_query_results([Vec::<policy::Model>::new()])
+            .append_query_results([Vec::<authority::Model>::new()]);
+        let mock_connection = Arc::new(mock_database.into_connection());
+
+        let workspace_service = WorkspaceServiceImpl::new(
+            mock_connection.clone(),
+            DatabaseLocation::Postgres {
+                host: "mock.database.host".to_owned(),
+                port: 5432,
+                database_name: "postgres".to_owned(),
+                auth: AuthMethod::Credential { username: "postgres".to_owned(), password: None },
+            },
+        );
+
+        let source_transaction = mock_connection.begin().await.expect("begining transaction should be successful");
+        let target_transaction = mock_connection.begin().await.expect("begining transaction should be successful");
+
+        let result = workspace_service.copy_contents(&source_transaction, &target_transaction).await;
+
+        target_transaction.commit().await.expect("commiting transaction should be successful");
+        source_transaction.commit().await.expect("commiting transaction should be successful");
+
+        result.expect("copying workspace contents should be successful");
+    }
+
+    #[tokio::test]
+    async fn when_workspace_is_got_for_update_then_its_row_is_locked() {
+        use crate::database::workspace::Model;
+
+        const WORKSPACE_NAME: &str = "testworkspace";
+        let now = Utc::now();
+        let mock_database = MockDatabase::new(DatabaseBackend::Postgres).append_query_results([vec![Model {
+            id: Ulid::new().into(),
+            name: WORKSPACE_NAME.to_owned(),
+            display_name: None,
+            settings: Default::default(),
+            deleted_at: None,
+            created_at: now,
+            updated_at: now,
+        }]]);
+        let mock_connection = Arc::new(mock_database.into_connection());
+        let workspace_service =
+            WorkspaceServiceImpl::new(mock_connection.clone(), DatabaseLocation::Sqlite { path: std::env::temp_dir() });
+
+        let transaction = mock_connection.begin().await.expect("begining transaction should be successful");
+        let workspace = workspace_service
+            .get_by_name_for_update(&transaction, WORKSPACE_NAME)
+            .await
+            .expect("getting workspace should be successful");
+        transaction.commit().await.expect("commiting transaction should be successful");
+
+        assert_eq!(workspace.expect("workspace should exist").name, WORKSPACE_NAME);
+        drop(workspace_service);
+        let mock_connection = Arc::try_unwrap(mock_connection).expect("connection should not be shared");
+        let transaction_log = mock_connection.into_transaction_log();
+        assert!(format!("{transaction_log:?}").contains("FOR UPDATE"));
+    }
+
+    #[tokio::test]
+    async fn when_renaming_workspace_is_rolled_back_on_sqlite_then_its_data_stays_readable() {
+        const WORKSPACE_NAME: &str = "testworkspace";
+        let directory = sqlite_directory();
+        let (connection, workspace_service) = sqlite_workspace(&directory, WORKSPACE_NAME).await;
+
+        rename_workspace(&connection, &workspace_service, WORKSPACE_NAME, "renamedworkspace", false).await;
+
+        let transaction = connection.begin().await.expect("begining transaction should be successful");
+        let workspace = workspace_service
+            .get_by_name(&transaction, WORKSPACE_NAME)
+            .await
+            .expect("getting workspace should be successful");
+        transaction.commit().await.expect("commiting transaction should be successful");
+        assert!(workspace.is_some());
+        assert_eq!(root_paths(&connection, WORKSPACE_NAME).await, vec!["/".to_owned()]);
+        assert!(!directory.join("workspaces").join("renamedworkspace.db").exists());
+
+        close_database(&connection).await;
+        std::fs::remove_dir_all(directory).expect("removing test directory should be successful");
+    }
+
+    #[tokio::test]
+    async fn when_renaming_workspace_is_committed_on_sqlite_then_its_data_moves_to_new_name() {
+        const WORKSPACE_NAME: &str = "testworkspace";
+        let directory = sqlite_directory();
+        let (connection, workspace_service) = sqlite_workspace(&directory, WORKSPACE_NAME).await;
+
+        rename_workspace(&connection, &workspace_service, WORKSPACE_NAME, "renamedworkspace", true).await;
+
+        assert_eq!(root_paths(&connection, "renamedworkspace").await, vec!["/".to_owned()]);
+        assert!(!directory.join("workspaces").join(format!("{WORKSPACE_NAME}.db")).exists());
+
+        close_database(&connection).await;
+        std::fs::remove_dir_all(directory).expect("removing test directory should be successful");
+    }
 }
@@ -0,0 +1,53 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+
+mod application;
+pub mod config;
+mod database;
+mod domain;
+mod server;
+
+pub use server::Server;
+
+#[derive(Parser, Debug)]
+#[command(version, about)]
+pub struct Args {
+    /// Sets a custom config file
+    #[arg(short, long, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+    /// Sets a port to start a backbone server
+    #[arg(short, long, value_name = "FILE")]
+    pub port: Option<u16>,
+    /// Sets a database host
+    #[arg(long)]
+    pub database_host: Option<String>,
+    /// Sets a database port
+    #[arg(long)]
+    pub database_port: Option<String>,
+    /// Sets a database name
+    #[arg(long)]
+    pub database_name: Option<String>,
+    /// Sets a database username
+    #[arg(long)]
+    pub database_username: Option<String>,
+    /// Sets a database password
+    #[arg(long)]
+    pub database_password: Option<String>,
+    /// Prints the resolved configuration with secrets redacted, then exits
+    #[arg(long)]
+    pub dump_config: bool,
+}
+
+pub trait IntoAnyhow<T> {
+    fn anyhow(self) -> anyhow::Result<T>;
+}
+
+impl<T, E> IntoAnyhow<T> for std::result::Result<T, E>
+where
+    E: Into<anyhow::Error>,
+{
+    fn anyhow(self) -> anyhow::Result<T> {
+        self.map_err(|e| e.into())
+    }
+}
@@ -1,65 +1,27 @@
-use std::path::PathBuf;
-
 use clap::Parser;
+use nebula_backbone::{config, Args, Server};
 
 use crate::logger::LoggerConfig;
 
-pub mod application;
-pub mod config;
-pub mod database;
-pub mod domain;
-pub mod logger;
-pub mod server;
-
-#[derive(Parser, Debug)]
-#[command(version, about)]
-struct Args {
-    /// Sets a custom config file
-    #[arg(short, long, value_name = "FILE")]
-    pub config: Option<PathBuf>,
-    /// Sets a port to start a backbone server
-    #[arg(short, long, value_name = "FILE")]
-    pub port: Option<u16>,
-    /// Sets a database host
-    #[arg(long)]
-    pub database_host: Option<String>,
-    /// Sets a database port
-    #[arg(long)]
-    pub database_port: Option<String>,
-    /// Sets a database name
-    #[arg(long)]
-    pub database_name: Option<String>,
-    /// Sets a database username
-    #[arg(long)]
-    pub database_username: Option<String>,
-    /// Sets a database password
-    #[arg(long)]
-    pub database_password: Option<String>,
-}
+mod logger;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let args = Args::parse();
+    if args.dump_config {
+        println!("{}", config::dump_config(args)?);
+        return Ok(());
+    }
 
     let app_config = config::load_config(args)?;
 
-    logger::init_logger(LoggerConfig::default());
-
-    let application = application::init(&app_config).await?;
+    logger::init_logger(LoggerConfig { tracing: app_config.tracing.clone(), ..Default::default() })?;
+    nebula_telemetry::metrics::init()?;
+    if let Some(metrics_port) = app_config.metrics_port {
+        nebula_telemetry::metrics::spawn_server(metrics_port).await?;
+    }
 
-    server::run(application, (&app_config).into()).await?;
+    Server::init(&app_config).await?.run().await?;
+    nebula_telemetry::trace::shutdown();
     Ok(())
 }
-
-pub trait IntoAnyhow<T> {
-    fn anyhow(self) -> anyhow::Result<T>;
-}
-
-impl<T, E> IntoAnyhow<T> for std::result::Result<T, E>
-where
-    E: Into<anyhow::Error>,
-{
-    fn anyhow(self) -> anyhow::Result<T> {
-        self.map_err(|e| e.into())
-    }
-}
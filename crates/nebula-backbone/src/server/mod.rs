@@ -1,15 +1,18 @@
-use std::sync::Arc;
+use std::{future::Future, sync::Arc};
 
 use axum::{
-    extract::{Path, Request},
-    middleware::Next,
-    response::Response,
+    extract::{Path, Request, State},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
     routing::get,
     Extension, Router,
 };
+use nebula_telemetry::health::Readiness;
+use nebula_tls::TlsConfig;
 use nebula_token::{
     auth::layer::NebulaAuthLayer,
-    claim::{NebulaClaim, Role},
+    claim::{NebulaClaim, Permission},
+    rate_limit::NebulaRateLimitLayer,
 };
 use reqwest::{
     header::{AUTHORIZATION, CONTENT_TYPE, LINK},
@@ -19,11 +22,16 @@ use serde::Deserialize;
 use tower_http::cors::AllowOrigin;
 use tower_http::cors::Any;
 use tower_http::cors::CorsLayer;
-use tracing::debug;
+use tracing::{debug, info};
 
 use crate::{
-    application::Application,
-    config::{ApplicationConfig, CorsConfig},
+    application::{
+        authority::AuthorityUseCase,
+        role::RoleUseCase,
+        workspace::{self, WorkspaceUseCase},
+        Application,
+    },
+    config::{ApplicationConfig, CorsConfig, RateLimitConfig},
 };
 
 mod response;
@@ -32,32 +40,100 @@ mod router;
 pub(super) struct ServerConfig {
     pub port: u16,
     pub cors: Option<CorsConfig>,
+    pub rate_limit: Option<RateLimitConfig>,
+    pub tls: Option<TlsConfig>,
 }
 
 impl From<&ApplicationConfig> for ServerConfig {
     fn from(value: &ApplicationConfig) -> Self {
-        Self { port: value.port, cors: value.cors.clone() }
+        Self {
+            port: value.port,
+            cors: value.cors.clone(),
+            rate_limit: value.rate_limit.clone(),
+            tls: value.tls.clone(),
+        }
     }
 }
 
-pub(super) async fn run(application: Application, config: ServerConfig) -> anyhow::Result<()> {
-    let application = Arc::new(application);
+/// Backbone server, which can be served on its own port or mounted into another router by [`Server::router`].
+pub struct Server {
+    application: Arc<Application>,
+    config: ServerConfig,
+}
+
+impl Server {
+    pub async fn init(config: &ApplicationConfig) -> anyhow::Result<Self> {
+        let application = crate::application::init(config).await?;
+        Ok(Self { application: Arc::new(application), config: config.into() })
+    }
+
+    pub fn router(&self) -> Router {
+        router(self.application.clone(), &self.config)
+    }
+
+    /// Serves on the configured port until the shutdown future resolves.
+    pub async fn serve(&self, shutdown: impl Future<Output = ()> + Send + 'static) -> anyhow::Result<()> {
+        debug!("starting backbone server on {}", self.config.port);
+        nebula_tls::serve(self.config.port, self.router(), self.config.tls.as_ref(), shutdown).await
+    }
+
+    pub async fn run(self) -> anyhow::Result<()> {
+        self.serve(nebula_tls::shutdown_signal()).await?;
+        self.close().await;
+        info!("backbone server is stopped");
+        Ok(())
+    }
+
+    pub async fn close(&self) {
+        self.application.close().await;
+    }
+
+    /// Registers the authority to the workspace unless an authority with the same name is registered.
+    pub async fn register_authority_if_absent(
+        &self,
+        workspace_name: &str,
+        name: &str,
+        host: &str,
+    ) -> anyhow::Result<()> {
+        let authority = self.application.with_workspace(workspace_name).authority();
+        if authority.get_authorities().await?.iter().any(|authority| authority.name == name) {
+            return Ok(());
+        }
+        authority.register_authority(name, host).await?;
+        Ok(())
+    }
+}
+
+fn router(application: Arc<Application>, config: &ServerConfig) -> Router {
     let public_router = Router::new()
         .route("/health", get(|| async { "" }))
+        .route("/ready", get(handle_ready).with_state(application.clone()))
         .merge(router::workspace::public_router(application.clone()))
-        .merge(router::parameter::public_router(application.clone()));
+        .merge(router::parameter::public_router(application.clone()))
+        .merge(router::authority::public_router(application.clone()));
 
     let protected_router = Router::new()
         .merge(router::workspace::router(application.clone()))
         .merge(router::secret::router(application.clone()))
         .merge(router::parameter::router(application.clone()))
         .merge(router::policy::router(application.clone()))
+        .merge(router::role::router(application.clone()))
         .merge(router::path::router(application.clone()))
-        .merge(router::authority::router(application.clone()))
-        .layer(NebulaAuthLayer::builder().jwk_discovery(application.jwks_discovery().clone()).build());
+        .merge(router::authority::router(application.clone()));
+    let protected_router = if let Some(rate_limit) = config.rate_limit.clone() {
+        protected_router.layer(NebulaRateLimitLayer::new(rate_limit.default, rate_limit.workspaces))
+    } else {
+        protected_router
+    };
+    let protected_router =
+        protected_router.layer(NebulaAuthLayer::builder().jwk_discovery(application.jwks_discovery().clone()).build());
 
-    let app = Router::new().merge(public_router).merge(protected_router);
-    let app = if let Some(cors) = config.cors {
+    let app = Router::new()
+        .merge(public_router)
+        .merge(protected_router)
+        .layer(middleware::from_fn(nebula_telemetry::metrics::track_http_requests))
+        .layer(middleware::from_fn(nebula_telemetry::trace::propagate_trace_context));
+    if let Some(cors) = config.cors.clone() {
         let cors = CorsLayer::new()
             .allow_methods(Any)
             .allow_origin(match cors {
@@ -80,35 +156,31 @@ pub(super) async fn run(application: Application, config: ServerConfig) -> anyho
         app.layer(cors)
     } else {
         app
-    };
-
-    let listener = tokio::net::TcpListener::bind(("0.0.0.0", config.port)).await?;
-    debug!("starting backbone server on {}", config.port);
-    axum::serve(listener, app).await?;
-    Ok(())
+    }
 }
 
-pub(crate) async fn check_admin_role(
-    Extension(claim): Extension<NebulaClaim>,
-    req: Request,
-    next: Next,
-) -> Result<Response, StatusCode> {
-    if claim.role == Role::Admin {
-        Ok(next.run(req).await)
-    } else {
-        Err(StatusCode::FORBIDDEN)
-    }
+async fn handle_ready(State(application): State<Arc<Application>>) -> Readiness {
+    application.readiness().await
 }
 
-pub(crate) async fn check_member_role(
+/// Allows the request only when the role in the claim has the permission in the workspace of the claim.
+/// Custom roles are resolved from the roles defined in the workspace.
+pub(crate) async fn check_permission(
+    State((application, permission)): State<(Arc<Application>, Permission)>,
     Extension(claim): Extension<NebulaClaim>,
     req: Request,
     next: Next,
-) -> Result<Response, StatusCode> {
-    if claim.role == Role::Member || claim.role == Role::Admin {
+) -> Result<Response, Response> {
+    let permissions = application
+        .with_workspace(&claim.workspace_name)
+        .role()
+        .resolve_permissions(&claim.role)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    if permissions.contains(&permission) {
         Ok(next.run(req).await)
     } else {
-        Err(StatusCode::FORBIDDEN)
+        Err(StatusCode::FORBIDDEN.into_response())
     }
 }
 
@@ -129,3 +201,80 @@ pub(crate) async fn check_workspace_name(
         Err(StatusCode::FORBIDDEN)
     }
 }
+
+pub(crate) async fn check_workspace_available(
+    State(application): State<Arc<Application>>,
+    Path(WorkspaceParams { workspace_name }): Path<WorkspaceParams>,
+    req: Request,
+    next: Next,
+) -> Result<Response, Response> {
+    let workspace = application.workspace().get_by_name(&workspace_name).await.map_err(IntoResponse::into_response)?;
+    if workspace.deleted_at.is_some() {
+        return Err(workspace::Error::WorkspaceNotExists.into_response());
+    }
+
+    Ok(next.run(req).await)
+}
+
+#[cfg(test)]
+mod test {
+    use std::{sync::Arc, time::Duration};
+
+    use axum::{
+        body::{to_bytes, Body},
+        extract::Request,
+        http::StatusCode,
+    };
+    use nebula_token::{
+        auth::jwks_discovery::{CachedRemoteJwksDiscovery, JwksDiscovery, StaticJwksDiscovery},
+        jwk::jwk_set::JwkSet,
+    };
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    use crate::application::Application;
+
+    use super::{router, ServerConfig};
+
+    async fn request(jwks_discovery: Arc<dyn JwksDiscovery + Send + Sync>, uri: &str) -> (StatusCode, Vec<u8>) {
+        let config = ServerConfig { port: 0, cors: None, rate_limit: None, tls: None };
+        let router = router(Arc::new(Application::with_mock_database(jwks_discovery)), &config);
+
+        let request = Request::builder().uri(uri).body(Body::empty()).expect("request should be built");
+        let response = router.oneshot(request).await.expect("request should be handled");
+        let status = response.status();
+        (status, to_bytes(response.into_body(), usize::MAX).await.expect("body should be read").to_vec())
+    }
+
+    #[tokio::test]
+    async fn when_health_is_requested_then_ok_is_returned() {
+        let (status, _) = request(Arc::new(StaticJwksDiscovery::new(JwkSet::default())), "/health").await;
+
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn when_database_and_jwks_are_up_then_server_is_ready() {
+        let (status, body) = request(Arc::new(StaticJwksDiscovery::new(JwkSet::default())), "/ready").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            serde_json::from_slice::<Value>(&body).unwrap(),
+            json!({ "status": "READY", "checks": { "database": "UP", "jwks": "UP" } })
+        );
+    }
+
+    #[tokio::test]
+    async fn when_jwks_is_unreachable_then_server_is_not_ready() {
+        let jwks_discovery =
+            CachedRemoteJwksDiscovery::new("http://127.0.0.1:1/jwks".parse().unwrap(), Duration::from_secs(10));
+
+        let (status, body) = request(Arc::new(jwks_discovery), "/ready").await;
+
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(
+            serde_json::from_slice::<Value>(&body).unwrap(),
+            json!({ "status": "NOT_READY", "checks": { "database": "UP", "jwks": "DOWN" } })
+        );
+    }
+}
@@ -2,6 +2,8 @@ use axum::{http::StatusCode, response::IntoResponse, Json};
 use serde::Serialize;
 use tracing::error;
 
+use crate::domain::workspace::QuotaResource;
+
 pub(crate) fn handle_internal_server_error<E: std::error::Error>(e: E) -> impl IntoResponse {
     error!(error = %e, "unhandled error occurred.");
     StatusCode::INTERNAL_SERVER_ERROR
@@ -30,3 +32,34 @@ pub fn error_payload_with_data<'a, D: Serialize>(
 ) -> Json<ErrorPayload<'a, D>> {
     Json(ErrorPayload { code, message, data })
 }
+
+pub(crate) struct WorkspaceQuotaExceededErrorResponse {
+    pub resource: QuotaResource,
+    pub limit: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WorkspaceQuotaExceededErrorData {
+    resource: &'static str,
+    limit: u64,
+}
+
+impl IntoResponse for WorkspaceQuotaExceededErrorResponse {
+    fn into_response(self) -> axum::response::Response {
+        let resource = match self.resource {
+            QuotaResource::Secrets => "SECRETS",
+            QuotaResource::CiphertextBytes => "CIPHERTEXT_BYTES",
+            QuotaResource::Paths => "PATHS",
+        };
+        (
+            StatusCode::FORBIDDEN,
+            error_payload_with_data(
+                "WORKSPACE_QUOTA_EXCEEDED",
+                "workspace quota is exceeded",
+                WorkspaceQuotaExceededErrorData { resource, limit: self.limit },
+            ),
+        )
+            .into_response()
+    }
+}
@@ -1,49 +1,79 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use axum::{
     debug_handler,
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     middleware,
     response::IntoResponse,
     routing::{get, patch, post},
     Json, Router,
 };
+use nebula_token::claim::Permission;
+use serde::Deserialize;
 use ulid::Ulid;
 
 use crate::{
     application::{
         self,
-        authority::{AuthorityData, AuthorityUseCase},
+        authority::{AuthorityData, AuthorityPublicKeyData, AuthorityUseCase},
         Application,
     },
-    server::{check_admin_role, check_member_role, check_workspace_name, response::handle_internal_server_error},
+    server::{
+        check_permission, check_workspace_available, check_workspace_name, response::handle_internal_server_error,
+    },
 };
 
 use self::{
-    request::{PatchAuthorityRequest, PostAuthorityRequest},
-    response::AuthorityResponse,
+    request::{
+        PatchAuthorityRequest, PostAuthorityRequest, PostBootstrapTokenRequest, PostSelfRefreshRequest,
+        PostSelfRegistrationRequest,
+    },
+    response::{AuthorityPublicKeyResponse, AuthorityResponse, BootstrapTokenResponse, SelfRegistrationResponse},
 };
 
 mod request;
 mod response;
 
+const DEFAULT_BOOTSTRAP_TOKEN_EXPIRES_IN_SECONDS: u64 = 24 * 60 * 60;
+
+/// Routes called by authorities, which authenticate themselves with a bootstrap token or the credential issued at their
+/// registration instead of a Nebula token.
+pub(crate) fn public_router(application: Arc<Application>) -> axum::Router {
+    Router::new()
+        .route("/workspaces/:workspace_name/authorities/self-registration", post(handle_post_self_registration))
+        .route(
+            "/workspaces/:workspace_name/authorities/self-registration/public-keys/refresh",
+            post(handle_post_self_public_keys_refresh),
+        )
+        .route_layer(middleware::from_fn_with_state(application.clone(), check_workspace_available))
+        .with_state(application)
+}
+
 pub(crate) fn router(application: Arc<Application>) -> axum::Router {
-    let member_router = Router::new()
+    let read_router = Router::new()
         .route("/workspaces/:workspace_name/authorities", get(handle_get_authorities))
+        .route("/workspaces/:workspace_name/authorities/public-keys", get(handle_get_public_keys))
         .route("/workspaces/:workspace_name/authorities/:authority_id", get(handle_get_authority))
-        .route_layer(middleware::from_fn(check_member_role))
+        .route_layer(middleware::from_fn_with_state(application.clone(), check_workspace_available))
+        .route_layer(middleware::from_fn_with_state((application.clone(), Permission::AuthorityRead), check_permission))
         .route_layer(middleware::from_fn(check_workspace_name));
-    let admin_router = Router::new()
+    let manage_router = Router::new()
         .route("/workspaces/:workspace_name/authorities", post(handle_post_authority))
+        .route("/workspaces/:workspace_name/authorities/public-keys/refresh", post(handle_post_public_keys_refresh))
+        .route("/workspaces/:workspace_name/authorities/bootstrap-tokens", post(handle_post_bootstrap_token))
         .route(
             "/workspaces/:workspace_name/authorities/:authority_id",
             patch(handle_patch_authority).delete(handle_delete_authority),
         )
-        .route_layer(middleware::from_fn(check_admin_role))
+        .route_layer(middleware::from_fn_with_state(application.clone(), check_workspace_available))
+        .route_layer(middleware::from_fn_with_state(
+            (application.clone(), Permission::AuthorityManage),
+            check_permission,
+        ))
         .route_layer(middleware::from_fn(check_workspace_name));
 
-    Router::new().merge(member_router).merge(admin_router).with_state(application)
+    Router::new().merge(read_router).merge(manage_router).with_state(application)
 }
 
 impl IntoResponse for application::authority::Error {
@@ -56,16 +86,77 @@ impl IntoResponse for application::authority::Error {
             application::authority::Error::AuthorityNotExists { entered_authority_id } => {
                 response::AuthorityNotFoundResponse { entered_authority_id }.into_response()
             }
+            application::authority::Error::HostNotAllowed { entered_host } => {
+                response::AuthorityHostNotAllowedErrorResponse { entered_host }.into_response()
+            }
+            application::authority::Error::InvalidBootstrapToken => {
+                response::InvalidBootstrapTokenErrorResponse.into_response()
+            }
+            application::authority::Error::InvalidAuthorityCredential => {
+                response::InvalidAuthorityCredentialErrorResponse.into_response()
+            }
+            application::authority::Error::NameMismatch { registered_authority_name, entered_authority_name } => {
+                response::AuthorityNameMismatchErrorResponse { registered_authority_name, entered_authority_name }
+                    .into_response()
+            }
         }
     }
 }
 
 impl From<AuthorityData> for response::AuthorityResponse {
     fn from(value: AuthorityData) -> Self {
-        Self { id: value.id, name: value.name, host: value.host, public_key: value.public_key }
+        Self {
+            id: value.id,
+            name: value.name,
+            host: value.host,
+            public_key: value.public_key,
+            status: value.status.map(Into::into),
+            latest_key_version: value.latest_key_version,
+            status_checked_at: value.status_checked_at,
+        }
+    }
+}
+
+impl From<crate::domain::authority::PublicKeyStatus> for response::PublicKeyStatus {
+    fn from(value: crate::domain::authority::PublicKeyStatus) -> Self {
+        match value {
+            crate::domain::authority::PublicKeyStatus::Active => response::PublicKeyStatus::Active,
+            crate::domain::authority::PublicKeyStatus::Retired => response::PublicKeyStatus::Retired,
+            crate::domain::authority::PublicKeyStatus::Revoked => response::PublicKeyStatus::Revoked,
+        }
+    }
+}
+
+impl From<crate::domain::authority::AuthorityStatus> for response::AuthorityStatus {
+    fn from(value: crate::domain::authority::AuthorityStatus) -> Self {
+        match value {
+            crate::domain::authority::AuthorityStatus::Available => response::AuthorityStatus::Available,
+            crate::domain::authority::AuthorityStatus::Armored => response::AuthorityStatus::Armored,
+            crate::domain::authority::AuthorityStatus::Unavailable => response::AuthorityStatus::Unavailable,
+        }
     }
 }
 
+impl From<AuthorityPublicKeyData> for response::AuthorityPublicKeyResponse {
+    fn from(value: AuthorityPublicKeyData) -> Self {
+        Self {
+            authority_id: value.authority_id,
+            authority_name: value.authority_name,
+            version: value.version,
+            parameter_version: value.parameter_version,
+            curve: value.curve,
+            public_key: value.public_key,
+            status: value.status.into(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct GetPublicKeysQueryParam {
+    latest: Option<bool>,
+}
+
 #[debug_handler]
 async fn handle_post_authority(
     Path(workspace_name): Path<String>,
@@ -124,3 +215,75 @@ async fn handle_delete_authority(
 
     Ok(StatusCode::NO_CONTENT)
 }
+
+#[debug_handler]
+async fn handle_get_public_keys(
+    Path(workspace_name): Path<String>,
+    Query(query_params): Query<GetPublicKeysQueryParam>,
+    State(application): State<Arc<Application>>,
+) -> application::authority::Result<impl IntoResponse> {
+    let public_keys = application
+        .with_workspace(&workspace_name)
+        .authority()
+        .get_public_keys(query_params.latest.unwrap_or(false))
+        .await?;
+
+    let payload: Vec<_> = public_keys.into_iter().map(AuthorityPublicKeyResponse::from).collect();
+
+    Ok(Json(payload))
+}
+
+#[debug_handler]
+async fn handle_post_public_keys_refresh(
+    Path(workspace_name): Path<String>,
+    State(application): State<Arc<Application>>,
+) -> application::authority::Result<impl IntoResponse> {
+    application.with_workspace(&workspace_name).authority().refresh_public_keys().await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[debug_handler]
+async fn handle_post_bootstrap_token(
+    Path(workspace_name): Path<String>,
+    State(application): State<Arc<Application>>,
+    payload: Option<Json<PostBootstrapTokenRequest>>,
+) -> application::authority::Result<impl IntoResponse> {
+    let Json(payload) = payload.unwrap_or_default();
+    let expires_in = Duration::from_secs(payload.expires_in.unwrap_or(DEFAULT_BOOTSTRAP_TOKEN_EXPIRES_IN_SECONDS));
+    let bootstrap_token =
+        application.with_workspace(&workspace_name).authority().issue_bootstrap_token(expires_in).await?;
+
+    let payload = BootstrapTokenResponse { token: bootstrap_token.token, expires_at: bootstrap_token.expires_at };
+
+    Ok((StatusCode::CREATED, Json(payload)))
+}
+
+#[debug_handler]
+async fn handle_post_self_registration(
+    Path(workspace_name): Path<String>,
+    State(application): State<Arc<Application>>,
+    Json(payload): Json<PostSelfRegistrationRequest>,
+) -> application::authority::Result<impl IntoResponse> {
+    let registration = application
+        .with_workspace(&workspace_name)
+        .authority()
+        .register_self(&payload.bootstrap_token, &payload.name, &payload.host)
+        .await?;
+
+    Ok(Json(SelfRegistrationResponse {
+        authority: AuthorityResponse::from(registration.authority),
+        credential: registration.credential,
+    }))
+}
+
+#[debug_handler]
+async fn handle_post_self_public_keys_refresh(
+    Path(workspace_name): Path<String>,
+    State(application): State<Arc<Application>>,
+    Json(payload): Json<PostSelfRefreshRequest>,
+) -> application::authority::Result<impl IntoResponse> {
+    application.with_workspace(&workspace_name).authority().refresh_own_public_keys(&payload.credential).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
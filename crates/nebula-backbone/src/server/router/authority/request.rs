@@ -13,3 +13,25 @@ pub(super) struct PatchAuthorityRequest {
     pub name: Option<String>,
     pub public_key: Option<String>,
 }
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct PostBootstrapTokenRequest {
+    /// Seconds until the token expires unless an authority registers itself with it.
+    pub expires_in: Option<u64>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct PostSelfRegistrationRequest {
+    pub bootstrap_token: String,
+    pub name: String,
+    pub host: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct PostSelfRefreshRequest {
+    /// Credential issued to the authority at its registration.
+    pub credential: String,
+}
@@ -1,8 +1,10 @@
 use axum::{http::StatusCode, response::IntoResponse};
+use chrono::{DateTime, Utc};
+use nebula_abe::curves::Curve;
 use serde::Serialize;
 use ulid::Ulid;
 
-use crate::server::response::error_payload_with_data;
+use crate::server::response::{error_payload, error_payload_with_data};
 
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -11,6 +13,53 @@ pub struct AuthorityResponse {
     pub name: String,
     pub host: String,
     pub public_key: Option<String>,
+    pub status: Option<AuthorityStatus>,
+    pub latest_key_version: Option<u64>,
+    pub status_checked_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelfRegistrationResponse {
+    #[serde(flatten)]
+    pub authority: AuthorityResponse,
+    /// Credential with which the authority authenticates itself afterward, which is only shown once.
+    pub credential: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AuthorityStatus {
+    Available,
+    Armored,
+    Unavailable,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthorityPublicKeyResponse {
+    pub authority_id: Ulid,
+    pub authority_name: String,
+    pub version: u64,
+    pub parameter_version: i32,
+    pub curve: Curve,
+    pub public_key: String,
+    pub status: PublicKeyStatus,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PublicKeyStatus {
+    Active,
+    Retired,
+    Revoked,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BootstrapTokenResponse {
+    pub token: String,
+    pub expires_at: DateTime<Utc>,
 }
 
 pub struct AuthorityNameAlreadyInUseErrorResponse {
@@ -60,3 +109,77 @@ impl IntoResponse for AuthorityNotFoundResponse {
             .into_response()
     }
 }
+
+pub struct AuthorityHostNotAllowedErrorResponse {
+    pub entered_host: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct EnteredAuthorityHostData {
+    entered_host: String,
+}
+
+impl IntoResponse for AuthorityHostNotAllowedErrorResponse {
+    fn into_response(self) -> axum::response::Response {
+        (
+            StatusCode::BAD_REQUEST,
+            error_payload_with_data(
+                "AUTHORITY_HOST_NOT_ALLOWED",
+                "entered authority host is not allowed in this workspace",
+                EnteredAuthorityHostData { entered_host: self.entered_host },
+            ),
+        )
+            .into_response()
+    }
+}
+
+pub struct InvalidBootstrapTokenErrorResponse;
+
+impl IntoResponse for InvalidBootstrapTokenErrorResponse {
+    fn into_response(self) -> axum::response::Response {
+        (
+            StatusCode::UNAUTHORIZED,
+            error_payload("INVALID_BOOTSTRAP_TOKEN", "bootstrap token is invalid, expired or already used"),
+        )
+            .into_response()
+    }
+}
+
+pub struct InvalidAuthorityCredentialErrorResponse;
+
+impl IntoResponse for InvalidAuthorityCredentialErrorResponse {
+    fn into_response(self) -> axum::response::Response {
+        (StatusCode::UNAUTHORIZED, error_payload("INVALID_AUTHORITY_CREDENTIAL", "authority credential is invalid"))
+            .into_response()
+    }
+}
+
+pub struct AuthorityNameMismatchErrorResponse {
+    pub registered_authority_name: String,
+    pub entered_authority_name: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AuthorityNameMismatchData {
+    registered_authority_name: String,
+    entered_authority_name: String,
+}
+
+impl IntoResponse for AuthorityNameMismatchErrorResponse {
+    fn into_response(self) -> axum::response::Response {
+        (
+            StatusCode::CONFLICT,
+            error_payload_with_data(
+                "AUTHORITY_NAME_MISMATCH",
+                "entered authority name differs from the registered authority name",
+                AuthorityNameMismatchData {
+                    registered_authority_name: self.registered_authority_name,
+                    entered_authority_name: self.entered_authority_name,
+                },
+            ),
+        )
+            .into_response()
+    }
+}
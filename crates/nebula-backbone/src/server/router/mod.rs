@@ -2,5 +2,6 @@ pub(crate) mod authority;
 pub(crate) mod parameter;
 pub(crate) mod path;
 pub(crate) mod policy;
+pub(crate) mod role;
 pub(crate) mod secret;
 pub(crate) mod workspace;
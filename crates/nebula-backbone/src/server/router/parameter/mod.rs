@@ -1,13 +1,15 @@
 use std::sync::Arc;
 
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     middleware,
     response::IntoResponse,
     routing::{get, post},
     Json, Router,
 };
 use base64::{engine::general_purpose::STANDARD, Engine as _};
+use nebula_token::claim::Permission;
+use serde::Deserialize;
 
 use crate::{
     application::{
@@ -15,51 +17,96 @@ use crate::{
         parameter::{ParameterData, ParameterUseCase},
         Application,
     },
-    server::{check_admin_role, check_workspace_name},
+    server::{check_permission, check_workspace_available, check_workspace_name},
 };
 
-use self::response::ParameterResponse;
+use self::{
+    request::PostParameterRequest,
+    response::{ParameterResponse, ParameterVersionResponse},
+};
 
+mod request;
 mod response;
 
 pub(crate) fn public_router(application: Arc<Application>) -> axum::Router {
-    Router::new().route("/workspaces/:workspace_name/parameter", get(handle_get_parameter)).with_state(application)
+    Router::new()
+        .route("/workspaces/:workspace_name/parameter", get(handle_get_parameter))
+        .route("/workspaces/:workspace_name/parameter/versions", get(handle_get_parameter_versions))
+        .with_state(application)
 }
 
 pub(crate) fn router(application: Arc<Application>) -> axum::Router {
     let admin_router = Router::new()
         .route("/workspaces/:workspace_name/parameter", post(handle_post_parameter))
-        .route_layer(middleware::from_fn(check_admin_role))
+        .route("/workspaces/:workspace_name/parameter/versions", post(handle_post_parameter_version))
+        .route_layer(middleware::from_fn_with_state(application.clone(), check_workspace_available))
+        .route_layer(middleware::from_fn_with_state(
+            (application.clone(), Permission::ParameterManage),
+            check_permission,
+        ))
         .route_layer(middleware::from_fn(check_workspace_name));
     Router::new().merge(admin_router).with_state(application)
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct GetParameterQueryParam {
+    version: Option<i32>,
+}
+
 async fn handle_post_parameter(
     Path(workspace_name): Path<String>,
     State(application): State<Arc<Application>>,
+    payload: Option<Json<PostParameterRequest>>,
+) -> Result<impl IntoResponse, application::parameter::Error> {
+    let curve = payload.and_then(|Json(payload)| payload.curve).unwrap_or_default();
+    let parameter = application.with_workspace(&workspace_name).parameter().create(curve).await?;
+    let response: ParameterResponse = parameter.into();
+
+    Ok(Json(response))
+}
+
+async fn handle_post_parameter_version(
+    Path(workspace_name): Path<String>,
+    State(application): State<Arc<Application>>,
+    payload: Option<Json<PostParameterRequest>>,
 ) -> Result<impl IntoResponse, application::parameter::Error> {
-    let parameter = application.with_workspace(&workspace_name).parameter().create().await?;
-    let response: ParameterResponse = parameter.try_into()?;
+    let curve = payload.and_then(|Json(payload)| payload.curve);
+    let parameter = application.with_workspace(&workspace_name).parameter().add_version(curve).await?;
+    let response: ParameterResponse = parameter.into();
 
     Ok(Json(response))
 }
 
 async fn handle_get_parameter(
     Path(workspace_name): Path<String>,
+    Query(query_params): Query<GetParameterQueryParam>,
     State(application): State<Arc<Application>>,
 ) -> Result<impl IntoResponse, application::parameter::Error> {
-    let parameter = application.with_workspace(&workspace_name).parameter().get().await?;
-    let response: ParameterResponse = parameter.try_into()?;
+    let parameter = application.with_workspace(&workspace_name).parameter().get(query_params.version).await?;
+    let response: ParameterResponse = parameter.into();
 
     Ok(Json(response))
 }
 
-impl TryFrom<ParameterData> for ParameterResponse {
-    type Error = application::parameter::Error;
+async fn handle_get_parameter_versions(
+    Path(workspace_name): Path<String>,
+    State(application): State<Arc<Application>>,
+) -> Result<impl IntoResponse, application::parameter::Error> {
+    let parameters = application.with_workspace(&workspace_name).parameter().get_all().await?;
+    let response: Vec<ParameterVersionResponse> = parameters.into_iter().map(ParameterVersionResponse::from).collect();
+
+    Ok(Json(response))
+}
+
+impl From<ParameterData> for ParameterResponse {
+    fn from(value: ParameterData) -> Self {
+        Self { version: value.version, curve: value.curve, parameter: STANDARD.encode(&value.value) }
+    }
+}
 
-    fn try_from(value: ParameterData) -> Result<Self, Self::Error> {
-        let parameter = rmp_serde::to_vec(&value.value)?;
-        let parameter = STANDARD.encode(&parameter);
-        Ok(Self { version: value.version, parameter })
+impl From<ParameterData> for ParameterVersionResponse {
+    fn from(value: ParameterData) -> Self {
+        Self { version: value.version, curve: value.curve }
     }
 }
@@ -0,0 +1,8 @@
+use nebula_abe::curves::Curve;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PostParameterRequest {
+    pub curve: Option<Curve>,
+}
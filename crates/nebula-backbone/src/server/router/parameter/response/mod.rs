@@ -3,6 +3,7 @@ use crate::{
     server::response::{error_payload, handle_internal_server_error},
 };
 use axum::{http::StatusCode, response::IntoResponse};
+use nebula_abe::curves::Curve;
 use serde::Serialize;
 use tracing::error;
 
@@ -14,14 +15,6 @@ impl IntoResponse for application::parameter::Error {
                 (StatusCode::NOT_FOUND, error_payload("GET_PARAMETER_FAILED", "Failed to get parameter"))
                     .into_response()
             }
-            application::parameter::Error::SerializeParameterFailed(error) => {
-                error!("Failed to serialize parameter: {}", error);
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    error_payload("SERIALIZE_PARAMETER_FAILED", "Failed to serialize parameter"),
-                )
-                    .into_response()
-            }
             application::parameter::Error::CreateParameterFailed(error) => {
                 error!("Failed to create parameter: {}", error);
                 (
@@ -39,5 +32,13 @@ impl IntoResponse for application::parameter::Error {
 #[serde(rename_all = "camelCase")]
 pub(super) struct ParameterResponse {
     pub version: i32,
+    pub curve: Curve,
     pub parameter: String,
 }
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct ParameterVersionResponse {
+    pub version: i32,
+    pub curve: Curve,
+}
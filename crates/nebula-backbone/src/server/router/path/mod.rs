@@ -6,14 +6,16 @@ use axum::{
     http::StatusCode,
     middleware,
     response::IntoResponse,
-    routing::get,
+    routing::{delete, get, post},
     Extension, Json, Router,
 };
-use nebula_token::claim::NebulaClaim;
+use nebula_token::claim::{NebulaClaim, Permission};
 
 use crate::{
     application::{self, path::PathUseCase, Application},
-    server::{check_member_role, check_workspace_name, router::path::request::PatchPathRequest},
+    server::{
+        check_permission, check_workspace_available, check_workspace_name, router::path::request::PatchPathRequest,
+    },
 };
 
 use self::request::PostPathRequest;
@@ -23,15 +25,20 @@ mod request;
 mod response;
 
 pub(crate) fn router(application: Arc<Application>) -> axum::Router {
-    Router::new()
-        .route("/workspaces/:workspace_name/paths", get(handle_get_paths).post(handle_post_path))
-        .route(
-            "/workspaces/:workspace_name/paths/*path",
-            get(handle_get_path).delete(handle_delete_path).patch(handle_patch_path),
-        )
-        .route_layer(middleware::from_fn(check_member_role))
-        .route_layer(middleware::from_fn(check_workspace_name))
-        .with_state(application)
+    let read_router = Router::new()
+        .route("/workspaces/:workspace_name/paths", get(handle_get_paths))
+        .route("/workspaces/:workspace_name/paths/*path", get(handle_get_path))
+        .route_layer(middleware::from_fn_with_state(application.clone(), check_workspace_available))
+        .route_layer(middleware::from_fn_with_state((application.clone(), Permission::PathRead), check_permission))
+        .route_layer(middleware::from_fn(check_workspace_name));
+    let write_router = Router::new()
+        .route("/workspaces/:workspace_name/paths", post(handle_post_path))
+        .route("/workspaces/:workspace_name/paths/*path", delete(handle_delete_path).patch(handle_patch_path))
+        .route_layer(middleware::from_fn_with_state(application.clone(), check_workspace_available))
+        .route_layer(middleware::from_fn_with_state((application.clone(), Permission::PathWrite), check_permission))
+        .route_layer(middleware::from_fn(check_workspace_name));
+
+    Router::new().merge(read_router).merge(write_router).with_state(application)
 }
 
 #[debug_handler]
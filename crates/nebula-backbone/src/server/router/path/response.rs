@@ -1,6 +1,8 @@
 use crate::{
     application::path,
-    server::response::{error_payload, error_payload_with_data, handle_internal_server_error},
+    server::response::{
+        error_payload, error_payload_with_data, handle_internal_server_error, WorkspaceQuotaExceededErrorResponse,
+    },
 };
 use axum::{http::StatusCode, response::IntoResponse};
 use serde::Serialize;
@@ -107,6 +109,9 @@ impl IntoResponse for path::Error {
             path::Error::PathIsInUse { .. } => PathIsInUseErrorResponse {}.into_response(),
             path::Error::InvalidPathPolicy => InvalidPathPolicyEnteredErrorResponse {}.into_response(),
             path::Error::AccessDenied => StatusCode::FORBIDDEN.into_response(),
+            path::Error::QuotaExceeded { resource, limit } => {
+                WorkspaceQuotaExceededErrorResponse { resource, limit }.into_response()
+            }
         }
     }
 }
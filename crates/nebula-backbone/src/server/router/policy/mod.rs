@@ -9,11 +9,12 @@ use axum::{
     routing::{get, patch, post},
     Json, Router,
 };
+use nebula_token::claim::Permission;
 use ulid::Ulid;
 
 use crate::{
     application::{self, policy::PolicyUseCase, Application},
-    server::{check_admin_role, check_member_role, check_workspace_name},
+    server::{check_permission, check_workspace_available, check_workspace_name},
 };
 
 use self::response::PolicyResponse;
@@ -22,21 +23,23 @@ mod request;
 mod response;
 
 pub(crate) fn router(application: Arc<Application>) -> axum::Router {
-    let member_router = Router::new()
+    let read_router = Router::new()
         .route("/workspaces/:workspace_name/policies", get(handle_get_policies))
         .route("/workspaces/:workspace_name/policies/:policy_id", get(handle_get_policy))
-        .route_layer(middleware::from_fn(check_member_role))
+        .route_layer(middleware::from_fn_with_state(application.clone(), check_workspace_available))
+        .route_layer(middleware::from_fn_with_state((application.clone(), Permission::PolicyRead), check_permission))
         .route_layer(middleware::from_fn(check_workspace_name));
-    let admin_router = Router::new()
+    let write_router = Router::new()
         .route("/workspaces/:workspace_name/policies", post(handle_post_policy))
         .route(
             "/workspaces/:workspace_name/policies/:policy_id",
             patch(handle_patch_policy).delete(handle_delete_policy),
         )
-        .route_layer(middleware::from_fn(check_admin_role))
+        .route_layer(middleware::from_fn_with_state(application.clone(), check_workspace_available))
+        .route_layer(middleware::from_fn_with_state((application.clone(), Permission::PolicyWrite), check_permission))
         .route_layer(middleware::from_fn(check_workspace_name));
 
-    Router::new().merge(member_router).merge(admin_router).with_state(application)
+    Router::new().merge(read_router).merge(write_router).with_state(application)
 }
 
 #[debug_handler]
@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use axum::{
+    debug_handler,
+    extract::{Path, State},
+    http::StatusCode,
+    middleware,
+    response::IntoResponse,
+    routing::{get, patch, post},
+    Json, Router,
+};
+use nebula_token::claim::Permission;
+use ulid::Ulid;
+
+use crate::{
+    application::{self, role::RoleUseCase, Application},
+    server::{check_permission, check_workspace_available, check_workspace_name},
+};
+
+use self::response::RoleResponse;
+
+mod request;
+mod response;
+
+pub(crate) fn router(application: Arc<Application>) -> axum::Router {
+    let read_router = Router::new()
+        .route("/workspaces/:workspace_name/roles", get(handle_get_roles))
+        .route("/workspaces/:workspace_name/roles/:role_id", get(handle_get_role))
+        .route_layer(middleware::from_fn_with_state(application.clone(), check_workspace_available))
+        .route_layer(middleware::from_fn_with_state((application.clone(), Permission::WorkspaceRead), check_permission))
+        .route_layer(middleware::from_fn(check_workspace_name));
+    let manage_router = Router::new()
+        .route("/workspaces/:workspace_name/roles", post(handle_post_role))
+        .route("/workspaces/:workspace_name/roles/:role_id", patch(handle_patch_role).delete(handle_delete_role))
+        .route_layer(middleware::from_fn_with_state(application.clone(), check_workspace_available))
+        .route_layer(middleware::from_fn_with_state((application.clone(), Permission::RoleManage), check_permission))
+        .route_layer(middleware::from_fn(check_workspace_name));
+
+    Router::new().merge(read_router).merge(manage_router).with_state(application)
+}
+
+#[debug_handler]
+async fn handle_get_roles(
+    Path(workspace_name): Path<String>,
+    State(application): State<Arc<Application>>,
+) -> Result<impl IntoResponse, application::role::Error> {
+    let roles = application.with_workspace(&workspace_name).role().get_all().await?;
+
+    Ok(Json(roles.into_iter().map(RoleResponse::from).collect::<Vec<_>>()))
+}
+
+#[debug_handler]
+async fn handle_post_role(
+    Path(workspace_name): Path<String>,
+    State(application): State<Arc<Application>>,
+    Json(payload): Json<request::PostRoleRequest>,
+) -> Result<impl IntoResponse, application::role::Error> {
+    application.with_workspace(&workspace_name).role().register(&payload.name, &payload.permissions).await?;
+
+    Ok(StatusCode::CREATED)
+}
+
+#[debug_handler]
+async fn handle_get_role(
+    Path((workspace_name, role_id)): Path<(String, Ulid)>,
+    State(application): State<Arc<Application>>,
+) -> Result<impl IntoResponse, application::role::Error> {
+    let role = application.with_workspace(&workspace_name).role().get_role(&role_id).await?;
+
+    Ok(Json(RoleResponse::from(role)))
+}
+
+#[debug_handler]
+async fn handle_patch_role(
+    Path((workspace_name, role_id)): Path<(String, Ulid)>,
+    State(application): State<Arc<Application>>,
+    Json(payload): Json<request::PatchRoleRequest>,
+) -> Result<impl IntoResponse, application::role::Error> {
+    application
+        .with_workspace(&workspace_name)
+        .role()
+        .update(&role_id, payload.name.as_deref(), payload.permissions.as_deref())
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[debug_handler]
+async fn handle_delete_role(
+    Path((workspace_name, role_id)): Path<(String, Ulid)>,
+    State(application): State<Arc<Application>>,
+) -> Result<impl IntoResponse, application::role::Error> {
+    application.with_workspace(&workspace_name).role().delete(&role_id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+impl From<application::role::RoleData> for RoleResponse {
+    fn from(value: application::role::RoleData) -> Self {
+        Self { id: value.id, name: value.name, permissions: value.permissions }
+    }
+}
@@ -0,0 +1,16 @@
+use nebula_token::claim::Permission;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct PostRoleRequest {
+    pub name: String,
+    pub permissions: Vec<Permission>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct PatchRoleRequest {
+    pub name: Option<String>,
+    pub permissions: Option<Vec<Permission>>,
+}
@@ -0,0 +1,97 @@
+use crate::{
+    application::role,
+    server::response::{error_payload_with_data, handle_internal_server_error},
+};
+use axum::{http::StatusCode, response::IntoResponse};
+use nebula_token::claim::Permission;
+use serde::Serialize;
+use ulid::Ulid;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct RoleResponse {
+    pub id: Ulid,
+    pub name: String,
+    pub permissions: Vec<Permission>,
+}
+
+struct RoleNotExistsResponse {
+    entered_role_id: Ulid,
+}
+
+impl IntoResponse for RoleNotExistsResponse {
+    fn into_response(self) -> axum::response::Response {
+        (
+            StatusCode::NOT_FOUND,
+            error_payload_with_data(
+                "ROLE_NOT_EXISTS",
+                "role is not exists.",
+                EnteredRoleIdData { entered_role_id: self.entered_role_id },
+            ),
+        )
+            .into_response()
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct EnteredRoleIdData {
+    entered_role_id: Ulid,
+}
+
+struct RoleNameResponse {
+    status: StatusCode,
+    code: &'static str,
+    message: &'static str,
+    entered_role_name: String,
+}
+
+impl IntoResponse for RoleNameResponse {
+    fn into_response(self) -> axum::response::Response {
+        (
+            self.status,
+            error_payload_with_data(
+                self.code,
+                self.message,
+                EnteredRoleNameData { entered_role_name: self.entered_role_name },
+            ),
+        )
+            .into_response()
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct EnteredRoleNameData {
+    entered_role_name: String,
+}
+
+impl IntoResponse for role::Error {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            role::Error::Anyhow(e) => handle_internal_server_error(&*e).into_response(),
+            role::Error::RoleNotExists { entered_role_id } => RoleNotExistsResponse { entered_role_id }.into_response(),
+            role::Error::RoleNameDuplicated { entered_role_name } => RoleNameResponse {
+                status: StatusCode::CONFLICT,
+                code: "ROLE_NAME_DUPLICATED",
+                message: "entered role name is already in used.",
+                entered_role_name,
+            }
+            .into_response(),
+            role::Error::ReservedRoleName { entered_role_name } => RoleNameResponse {
+                status: StatusCode::BAD_REQUEST,
+                code: "RESERVED_ROLE_NAME",
+                message: "entered role name is reserved for a built-in role.",
+                entered_role_name,
+            }
+            .into_response(),
+            role::Error::InvalidRoleName { entered_role_name } => RoleNameResponse {
+                status: StatusCode::BAD_REQUEST,
+                code: "INVALID_ROLE_NAME",
+                message: "entered role name is invalid.",
+                entered_role_name,
+            }
+            .into_response(),
+        }
+    }
+}
@@ -6,11 +6,11 @@ use axum::{
     http::StatusCode,
     middleware,
     response::IntoResponse,
-    routing::get,
+    routing::{delete, get, post},
     Extension, Json, Router,
 };
 use base64::{prelude::BASE64_STANDARD, Engine};
-use nebula_token::claim::NebulaClaim;
+use nebula_token::claim::{NebulaClaim, Permission};
 use serde::Deserialize;
 
 use crate::{
@@ -19,7 +19,7 @@ use crate::{
         secret::{SecretData, SecretRegisterCommand, SecretUpdate, SecretUseCase},
         Application,
     },
-    server::{check_member_role, check_workspace_name},
+    server::{check_permission, check_workspace_available, check_workspace_name},
 };
 
 use self::{
@@ -31,15 +31,23 @@ mod request;
 mod response;
 
 pub(crate) fn router(application: Arc<Application>) -> axum::Router {
-    Router::new()
-        .route("/workspaces/:workspace_name/secrets", get(handle_get_secrets).post(handle_post_secret))
+    let read_router = Router::new()
+        .route("/workspaces/:workspace_name/secrets", get(handle_get_secrets))
+        .route("/workspaces/:workspace_name/secrets/*secret_identifier", get(handle_get_secret))
+        .route_layer(middleware::from_fn_with_state(application.clone(), check_workspace_available))
+        .route_layer(middleware::from_fn_with_state((application.clone(), Permission::SecretRead), check_permission))
+        .route_layer(middleware::from_fn(check_workspace_name));
+    let write_router = Router::new()
+        .route("/workspaces/:workspace_name/secrets", post(handle_post_secret))
         .route(
             "/workspaces/:workspace_name/secrets/*secret_identifier",
-            get(handle_get_secret).delete(handle_delete_secret).patch(handle_patch_secret),
+            delete(handle_delete_secret).patch(handle_patch_secret),
         )
-        .route_layer(middleware::from_fn(check_member_role))
-        .route_layer(middleware::from_fn(check_workspace_name))
-        .with_state(application)
+        .route_layer(middleware::from_fn_with_state(application.clone(), check_workspace_available))
+        .route_layer(middleware::from_fn_with_state((application.clone(), Permission::SecretWrite), check_permission))
+        .route_layer(middleware::from_fn(check_workspace_name));
+
+    Router::new().merge(read_router).merge(write_router).with_state(application)
 }
 
 #[derive(Deserialize)]
@@ -86,6 +94,7 @@ async fn handle_post_secret(
                 path: payload.path,
                 key: payload.key,
                 cipher,
+                parameter_version: payload.parameter_version,
                 access_condition_ids: payload.access_condition_ids,
             },
             &claim,
@@ -136,7 +145,12 @@ async fn handle_patch_secret(
         .secret()
         .update(
             &format!("/{secret_identifier}"),
-            SecretUpdate { path: payload.path, cipher, access_condition_ids: payload.access_condition_ids },
+            SecretUpdate {
+                path: payload.path,
+                cipher,
+                parameter_version: payload.parameter_version,
+                access_condition_ids: payload.access_condition_ids,
+            },
             &claim,
         )
         .await?;
@@ -150,6 +164,7 @@ impl From<SecretData> for SecretResponse {
             key: value.key,
             path: value.path,
             cipher: BASE64_STANDARD.encode(value.cipher),
+            parameter_version: value.parameter_version,
             access_condition_ids: value.access_condition_ids,
         }
     }
@@ -7,6 +7,7 @@ pub struct PostSecretRequest {
     pub path: String,
     pub key: String,
     pub cipher: String,
+    pub parameter_version: Option<i32>,
     pub access_condition_ids: Vec<Ulid>,
 }
 
@@ -15,5 +16,6 @@ pub struct PostSecretRequest {
 pub struct PatchSecretRequest {
     pub path: Option<String>,
     pub cipher: Option<String>,
+    pub parameter_version: Option<i32>,
     pub access_condition_ids: Option<Vec<Ulid>>,
 }
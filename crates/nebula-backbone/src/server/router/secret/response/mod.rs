@@ -1,6 +1,8 @@
 use crate::{
     application,
-    server::response::{error_payload, error_payload_with_data, handle_internal_server_error},
+    server::response::{
+        error_payload, error_payload_with_data, handle_internal_server_error, WorkspaceQuotaExceededErrorResponse,
+    },
 };
 use axum::{http::StatusCode, response::IntoResponse};
 use serde::Serialize;
@@ -20,10 +22,19 @@ impl IntoResponse for application::secret::Error {
             application::secret::Error::PathNotExists { entered_path } => {
                 PathNotExistsErrorResponse { entered_path }.into_response()
             }
+            application::secret::Error::ParameterNotExists { entered_parameter_version } => {
+                ParameterNotExistsErrorResponse { entered_parameter_version }.into_response()
+            }
             application::secret::Error::IdentifierConflicted { entered_identifier } => {
                 SecretIdentifierConlictedErrorResponse { entered_secret_identifier: entered_identifier }.into_response()
             }
             application::secret::Error::AccessDenied => StatusCode::FORBIDDEN.into_response(),
+            application::secret::Error::SecretSizeExceeded { max_secret_size } => {
+                SecretSizeExceededErrorResponse { max_secret_size }.into_response()
+            }
+            application::secret::Error::QuotaExceeded { resource, limit } => {
+                WorkspaceQuotaExceededErrorResponse { resource, limit }.into_response()
+            }
         }
     }
 }
@@ -66,6 +77,7 @@ pub(super) struct SecretResponse {
     pub key: String,
     pub path: String,
     pub cipher: String,
+    pub parameter_version: i32,
     pub access_condition_ids: Vec<Ulid>,
 }
 
@@ -117,6 +129,30 @@ struct EnteredPathErrorData {
     entered_path: String,
 }
 
+struct ParameterNotExistsErrorResponse {
+    entered_parameter_version: Option<i32>,
+}
+
+impl IntoResponse for ParameterNotExistsErrorResponse {
+    fn into_response(self) -> axum::response::Response {
+        (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            error_payload_with_data(
+                "ENTERED_PARAMETER_NOT_EXISTS",
+                "entered parameter version is not exists",
+                EnteredParameterVersionErrorData { entered_parameter_version: self.entered_parameter_version },
+            ),
+        )
+            .into_response()
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct EnteredParameterVersionErrorData {
+    entered_parameter_version: Option<i32>,
+}
+
 struct SecretIdentifierConlictedErrorResponse {
     entered_secret_identifier: String,
 }
@@ -149,3 +185,27 @@ impl IntoResponse for InvalidSecretCipherResponse {
             .into_response()
     }
 }
+
+struct SecretSizeExceededErrorResponse {
+    max_secret_size: u64,
+}
+
+impl IntoResponse for SecretSizeExceededErrorResponse {
+    fn into_response(self) -> axum::response::Response {
+        (
+            StatusCode::PAYLOAD_TOO_LARGE,
+            error_payload_with_data(
+                "SECRET_SIZE_EXCEEDED",
+                "secret cipher exceeds the maximum secret size of the workspace",
+                MaxSecretSizeErrorData { max_secret_size: self.max_secret_size },
+            ),
+        )
+            .into_response()
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct MaxSecretSizeErrorData {
+    max_secret_size: u64,
+}
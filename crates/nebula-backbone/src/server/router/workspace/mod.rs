@@ -6,19 +6,32 @@ use axum::{
     http::StatusCode,
     middleware,
     response::IntoResponse,
-    routing::{delete, get},
+    routing::{get, patch, post},
     Json, Router,
 };
+use nebula_token::claim::Permission;
 
 use crate::{
     application::{
-        workspace::{self, command::CreatingWorkspaceCommand, data::WorkspaceData, WorkspaceUseCase},
+        workspace::{
+            self,
+            command::{CloningWorkspaceCommand, CreatingWorkspaceCommand, UpdatingWorkspaceCommand},
+            data::WorkspaceData,
+            WorkspaceUseCase,
+        },
         Application,
     },
-    server::{check_admin_role, check_workspace_name, response::handle_internal_server_error},
+    domain::workspace::{WorkspaceQuota, WorkspaceSettings},
+    server::{check_permission, check_workspace_name, response::handle_internal_server_error},
 };
 
-use self::{request::PostWorkspaceRequest, response::GetWorkspacesResponse};
+use self::{
+    request::{
+        PatchWorkspaceRequest, PostWorkspaceCloneRequest, PostWorkspaceRequest, WorkspaceQuotaRequest,
+        WorkspaceSettingsRequest,
+    },
+    response::{GetWorkspaceResponse, GetWorkspacesResponse, WorkspaceQuotaResponse, WorkspaceSettingsResponse},
+};
 
 mod request;
 mod response;
@@ -28,11 +41,20 @@ pub(crate) fn public_router(application: Arc<Application>) -> axum::Router {
 }
 
 pub(crate) fn router(application: Arc<Application>) -> axum::Router {
-    let admin_routers = Router::new()
-        .route("/workspaces/:workspace_name", delete(handle_delete_workspace))
-        .route_layer(middleware::from_fn(check_admin_role))
+    let read_routers = Router::new()
+        .route("/workspaces/:workspace_name", get(handle_get_workspace))
+        .route_layer(middleware::from_fn_with_state((application.clone(), Permission::WorkspaceRead), check_permission))
         .route_layer(middleware::from_fn(check_workspace_name));
-    Router::new().merge(admin_routers).with_state(application)
+    let manage_routers = Router::new()
+        .route("/workspaces/:workspace_name", patch(handle_patch_workspace).delete(handle_delete_workspace))
+        .route("/workspaces/:workspace_name/clone", post(handle_post_workspace_clone))
+        .route("/workspaces/:workspace_name/restore", post(handle_post_workspace_restore))
+        .route_layer(middleware::from_fn_with_state(
+            (application.clone(), Permission::WorkspaceManage),
+            check_permission,
+        ))
+        .route_layer(middleware::from_fn(check_workspace_name));
+    Router::new().merge(read_routers).merge(manage_routers).with_state(application)
 }
 
 #[debug_handler]
@@ -47,7 +69,7 @@ async fn handle_post_workspace(
 
 impl From<PostWorkspaceRequest> for CreatingWorkspaceCommand {
     fn from(value: PostWorkspaceRequest) -> Self {
-        Self { name: value.name }
+        Self { name: value.name, curve: value.curve }
     }
 }
 
@@ -58,6 +80,10 @@ impl IntoResponse for workspace::Error {
             workspace::Error::WorkspaceNameConflicted => response::WorkspaceNameConflictedErrorResponse.into_response(),
             workspace::Error::WorkspaceNotExists => response::WorkspaceNotExistsErrorResponse.into_response(),
             workspace::Error::InvalidWorkspaceName => response::InvalidWorkspaceNameErrorResponse.into_response(),
+            workspace::Error::WorkspaceHasSecrets => response::WorkspaceHasSecretsErrorResponse.into_response(),
+            workspace::Error::AccessConditionNotExists { entered_access_condition_id } => {
+                response::AccessConditionNotExistsErrorResponse { entered_access_condition_id }.into_response()
+            }
         }
     }
 }
@@ -75,10 +101,104 @@ async fn handle_get_workspaces(
 
 impl From<WorkspaceData> for GetWorkspacesResponse {
     fn from(value: WorkspaceData) -> Self {
-        Self { name: value.name }
+        Self { name: value.name, display_name: value.display_name }
+    }
+}
+
+#[debug_handler]
+async fn handle_get_workspace(
+    Path(workspace_name): Path<String>,
+    State(application): State<Arc<Application>>,
+) -> Result<impl IntoResponse, workspace::Error> {
+    let workspace = application.workspace().get_by_name(&workspace_name).await?;
+
+    Ok((StatusCode::OK, Json(GetWorkspaceResponse::from(workspace))))
+}
+
+impl From<WorkspaceData> for GetWorkspaceResponse {
+    fn from(value: WorkspaceData) -> Self {
+        Self {
+            name: value.name,
+            display_name: value.display_name,
+            settings: value.settings.into(),
+            deleted_at: value.deleted_at,
+        }
+    }
+}
+
+impl From<WorkspaceSettings> for WorkspaceSettingsResponse {
+    fn from(value: WorkspaceSettings) -> Self {
+        Self {
+            default_access_condition_ids: value.default_access_condition_ids,
+            max_secret_size: value.max_secret_size,
+            allowed_authority_hosts: value.allowed_authority_hosts,
+            quota: value.quota.into(),
+        }
+    }
+}
+
+impl From<WorkspaceQuota> for WorkspaceQuotaResponse {
+    fn from(value: WorkspaceQuota) -> Self {
+        Self {
+            max_secrets: value.max_secrets,
+            max_ciphertext_bytes: value.max_ciphertext_bytes,
+            max_paths: value.max_paths,
+        }
+    }
+}
+
+#[debug_handler]
+async fn handle_patch_workspace(
+    Path(workspace_name): Path<String>,
+    State(application): State<Arc<Application>>,
+    Json(payload): Json<PatchWorkspaceRequest>,
+) -> Result<impl IntoResponse, workspace::Error> {
+    application
+        .workspace()
+        .update(
+            &workspace_name,
+            UpdatingWorkspaceCommand {
+                name: payload.name,
+                display_name: payload.display_name,
+                settings: payload.settings.map(WorkspaceSettings::from),
+            },
+        )
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+impl From<WorkspaceSettingsRequest> for WorkspaceSettings {
+    fn from(value: WorkspaceSettingsRequest) -> Self {
+        Self {
+            default_access_condition_ids: value.default_access_condition_ids,
+            max_secret_size: value.max_secret_size,
+            allowed_authority_hosts: value.allowed_authority_hosts,
+            quota: value.quota.into(),
+        }
+    }
+}
+
+impl From<WorkspaceQuotaRequest> for WorkspaceQuota {
+    fn from(value: WorkspaceQuotaRequest) -> Self {
+        Self {
+            max_secrets: value.max_secrets,
+            max_ciphertext_bytes: value.max_ciphertext_bytes,
+            max_paths: value.max_paths,
+        }
     }
 }
 
+#[debug_handler]
+async fn handle_post_workspace_restore(
+    Path(workspace_name): Path<String>,
+    State(application): State<Arc<Application>>,
+) -> Result<impl IntoResponse, workspace::Error> {
+    application.workspace().restore_by_name(&workspace_name).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
 #[debug_handler]
 async fn handle_delete_workspace(
     Path(workspace_name): Path<String>,
@@ -88,3 +208,17 @@ async fn handle_delete_workspace(
 
     Ok(StatusCode::NO_CONTENT)
 }
+
+#[debug_handler]
+async fn handle_post_workspace_clone(
+    Path(workspace_name): Path<String>,
+    State(application): State<Arc<Application>>,
+    Json(payload): Json<PostWorkspaceCloneRequest>,
+) -> Result<impl IntoResponse, workspace::Error> {
+    application
+        .workspace()
+        .clone_workspace(CloningWorkspaceCommand { source_name: workspace_name, name: payload.name })
+        .await?;
+
+    Ok(StatusCode::CREATED)
+}
@@ -1,7 +1,46 @@
+use nebula_abe::curves::Curve;
 use serde::Deserialize;
+use ulid::Ulid;
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct PostWorkspaceRequest {
     pub name: String,
+    #[serde(default)]
+    pub curve: Curve,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PostWorkspaceCloneRequest {
+    pub name: String,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PatchWorkspaceRequest {
+    /// New name of the workspace. Its schema and data are kept, but the rename is refused while the workspace has
+    /// secrets because the key pairs of its authorities are named after the workspace.
+    pub name: Option<String>,
+    pub display_name: Option<String>,
+    pub settings: Option<WorkspaceSettingsRequest>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceSettingsRequest {
+    #[serde(default)]
+    pub default_access_condition_ids: Vec<Ulid>,
+    pub max_secret_size: Option<u64>,
+    pub allowed_authority_hosts: Option<Vec<String>>,
+    #[serde(default)]
+    pub quota: WorkspaceQuotaRequest,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceQuotaRequest {
+    pub max_secrets: Option<u64>,
+    pub max_ciphertext_bytes: Option<u64>,
+    pub max_paths: Option<u64>,
 }
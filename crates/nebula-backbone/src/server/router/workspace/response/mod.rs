@@ -1,7 +1,9 @@
 use axum::{http::StatusCode, response::IntoResponse};
+use chrono::{DateTime, Utc};
 use serde::Serialize;
+use ulid::Ulid;
 
-use crate::server::response::error_payload;
+use crate::server::response::{error_payload, error_payload_with_data};
 
 pub(super) struct WorkspaceNameConflictedErrorResponse;
 
@@ -16,6 +18,33 @@ impl IntoResponse for WorkspaceNameConflictedErrorResponse {
 #[serde(rename_all = "camelCase")]
 pub(super) struct GetWorkspacesResponse {
     pub name: String,
+    pub display_name: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct GetWorkspaceResponse {
+    pub name: String,
+    pub display_name: Option<String>,
+    pub settings: WorkspaceSettingsResponse,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct WorkspaceSettingsResponse {
+    pub default_access_condition_ids: Vec<Ulid>,
+    pub max_secret_size: Option<u64>,
+    pub allowed_authority_hosts: Option<Vec<String>>,
+    pub quota: WorkspaceQuotaResponse,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct WorkspaceQuotaResponse {
+    pub max_secrets: Option<u64>,
+    pub max_ciphertext_bytes: Option<u64>,
+    pub max_paths: Option<u64>,
 }
 
 pub(super) struct WorkspaceNotExistsErrorResponse;
@@ -33,3 +62,36 @@ impl IntoResponse for InvalidWorkspaceNameErrorResponse {
         (StatusCode::BAD_REQUEST, error_payload("INVALID_WORKSPACE_NAME", "workspace name is invalid.")).into_response()
     }
 }
+
+pub(super) struct WorkspaceHasSecretsErrorResponse;
+
+impl IntoResponse for WorkspaceHasSecretsErrorResponse {
+    fn into_response(self) -> axum::response::Response {
+        (StatusCode::CONFLICT, error_payload("WORKSPACE_HAS_SECRETS", "workspace having secrets cannot be renamed."))
+            .into_response()
+    }
+}
+
+pub(super) struct AccessConditionNotExistsErrorResponse {
+    pub entered_access_condition_id: Ulid,
+}
+
+impl IntoResponse for AccessConditionNotExistsErrorResponse {
+    fn into_response(self) -> axum::response::Response {
+        (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            error_payload_with_data(
+                "ENTERED_ACCESS_CONDITION_NOT_EXISTS",
+                "entered access condition is not exists",
+                EnteredAccessConditionIdErrorData { entered_access_condition_id: self.entered_access_condition_id },
+            ),
+        )
+            .into_response()
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct EnteredAccessConditionIdErrorData {
+    entered_access_condition_id: Ulid,
+}
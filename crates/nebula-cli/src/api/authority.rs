@@ -1,30 +1,6 @@
-use cached::proc_macro::io_cached;
-use reqwest::IntoUrl;
+use reqwest::{IntoUrl, StatusCode};
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct GetPublicKeyResponse {
-    pub public_key: String,
-    pub version: u64,
-}
-
-#[io_cached(
-    map_error = "|e| anyhow::anyhow!(e)",
-    disk = true,
-    time = 60,
-    key = "String",
-    convert = r#"{ format!("pk:{}/{}", authority_url.as_str(), workspace_name) }"#
-)]
-pub async fn get_public_key(authority_url: impl IntoUrl, workspace_name: &str) -> anyhow::Result<GetPublicKeyResponse> {
-    let client = reqwest::Client::new();
-
-    let url = authority_url.into_url()?.join(&format!("workspaces/{workspace_name}/public-key"))?;
-    let response = client.get(url).send().await?.json::<GetPublicKeyResponse>().await?;
-
-    Ok(response)
-}
-
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GetUserKeyResponse {
@@ -32,19 +8,49 @@ pub struct GetUserKeyResponse {
     pub version: u64,
 }
 
+/// Gets the user key issued by the key pair of the version, or by the latest key pair if the version is not given. An
+/// authority binding user keys to epochs binds it to the epoch, or to the current epoch if not given.
 pub async fn get_user_key(
     authority_url: impl IntoUrl,
     workspace_name: &str,
+    version: Option<u64>,
+    epoch: Option<&str>,
     token: &str,
 ) -> anyhow::Result<GetUserKeyResponse> {
     let client = reqwest::Client::new();
 
-    let url = authority_url.into_url()?.join(&format!("workspaces/{workspace_name}/user-key"))?;
+    let mut url = authority_url.into_url()?.join(&format!("workspaces/{workspace_name}/user-key"))?;
+    if let Some(version) = version {
+        url.query_pairs_mut().append_pair("version", &version.to_string());
+    }
+    if let Some(epoch) = epoch {
+        url.query_pairs_mut().append_pair("epoch", epoch);
+    }
     let response = client.get(url).bearer_auth(token).send().await?.json::<GetUserKeyResponse>().await?;
 
     Ok(response)
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetEpochResponse {
+    pub attribute: String,
+    pub epoch: String,
+}
+
+/// Gets the current epoch of the authority, or `None` if it does not bind user keys to epochs.
+pub async fn get_epoch(authority_url: impl IntoUrl, workspace_name: &str) -> anyhow::Result<Option<GetEpochResponse>> {
+    let client = reqwest::Client::new();
+
+    let url = authority_url.into_url()?.join(&format!("workspaces/{workspace_name}/epoch"))?;
+    let response = client.get(url).send().await?;
+    if response.status() == StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+
+    Ok(Some(response.error_for_status()?.json().await?))
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct InitRequest {
@@ -77,3 +83,120 @@ pub async fn disarm(authority_url: impl IntoUrl, shares: Vec<String>) -> anyhow:
 
     Ok(())
 }
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisarmProgress {
+    pub progress: usize,
+    pub threshold: Option<usize>,
+    pub disarmed: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenDisarmSessionResponse {
+    pub nonce: String,
+    #[serde(flatten)]
+    pub progress: DisarmProgress,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenDisarmSessionRequest {
+    share: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisarmShareRequest<'a> {
+    share: String,
+    nonce: &'a str,
+}
+
+pub async fn get_disarm_session(authority_url: impl IntoUrl) -> anyhow::Result<DisarmProgress> {
+    let client = reqwest::Client::new();
+
+    let url = authority_url.into_url()?.join("disarm/session")?;
+    Ok(client.get(url).send().await?.error_for_status()?.json().await?)
+}
+
+/// Opens a disarm session with the share, and returns the nonce with which the other shares are submitted.
+pub async fn open_disarm_session(
+    authority_url: impl IntoUrl,
+    share: String,
+) -> anyhow::Result<OpenDisarmSessionResponse> {
+    let client = reqwest::Client::new();
+
+    let url = authority_url.into_url()?.join("disarm/session")?;
+    let response = client.post(url).json(&OpenDisarmSessionRequest { share }).send().await?;
+    if response.status().is_client_error() {
+        anyhow::bail!(response.text().await?);
+    }
+
+    Ok(response.error_for_status()?.json().await?)
+}
+
+pub async fn submit_disarm_share(
+    authority_url: impl IntoUrl,
+    share: String,
+    nonce: &str,
+) -> anyhow::Result<DisarmProgress> {
+    let client = reqwest::Client::new();
+
+    let url = authority_url.into_url()?.join("disarm/session/shares")?;
+    let response = client.post(url).json(&DisarmShareRequest { share, nonce }).send().await?;
+    if response.status().is_client_error() {
+        anyhow::bail!(response.text().await?);
+    }
+
+    Ok(response.error_for_status()?.json().await?)
+}
+
+/// Cancels the disarm session with the nonce, which a share holder does with their share.
+pub async fn cancel_disarm_session(authority_url: impl IntoUrl, share: String, nonce: &str) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+
+    let url = authority_url.into_url()?.join("disarm/session")?;
+    let response = client.delete(url).json(&DisarmShareRequest { share, nonce }).send().await?;
+    if response.status().is_client_error() {
+        anyhow::bail!(response.text().await?);
+    }
+    response.error_for_status()?;
+
+    Ok(())
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetStatusResponse {
+    pub initialized: bool,
+    pub armored: bool,
+    pub threshold: Option<usize>,
+}
+
+pub async fn get_status(authority_url: impl IntoUrl) -> anyhow::Result<GetStatusResponse> {
+    let client = reqwest::Client::new();
+
+    let url = authority_url.into_url()?.join("status")?;
+    Ok(client.get(url).send().await?.error_for_status()?.json().await?)
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyPairVersion {
+    pub version: u64,
+    pub created_at: Option<String>,
+    pub status: String,
+    pub status_reason: Option<String>,
+}
+
+pub async fn get_key_pairs(
+    authority_url: impl IntoUrl,
+    workspace_name: &str,
+    token: &str,
+) -> anyhow::Result<Vec<KeyPairVersion>> {
+    let client = reqwest::Client::new();
+
+    let url = authority_url.into_url()?.join(&format!("workspaces/{workspace_name}/key-pairs"))?;
+    Ok(client.get(url).bearer_auth(token).send().await?.error_for_status()?.json().await?)
+}
@@ -1,5 +1,6 @@
 use anyhow::Result;
 use cached::proc_macro::{cached, io_cached};
+use nebula_abe::curves::Curve;
 use reqwest::IntoUrl;
 use serde::{Deserialize, Serialize};
 use ulid::Ulid;
@@ -10,9 +11,16 @@ pub(crate) struct SecretResponse {
     pub key: String,
     pub path: String,
     pub cipher: String,
+    #[serde(default = "initial_parameter_version")]
+    pub parameter_version: i32,
     pub access_condition_ids: Vec<Ulid>,
 }
 
+/// Parameter version assumed for the responses of the servers which do not support parameter versions.
+pub(crate) fn initial_parameter_version() -> i32 {
+    1
+}
+
 pub async fn get_secrets(
     backbone_url: impl IntoUrl,
     workspace_name: &str,
@@ -51,6 +59,7 @@ pub struct PostSecretRequest {
     pub path: String,
     pub key: String,
     pub cipher: String,
+    pub parameter_version: i32,
     pub access_condition_ids: Vec<Ulid>,
 }
 
@@ -131,6 +140,45 @@ pub async fn create_access_condition(
     Ok(())
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoleResponse {
+    pub id: Ulid,
+    pub name: String,
+    pub permissions: Vec<String>,
+}
+
+pub async fn get_roles(backbone_url: impl IntoUrl, workspace_name: &str, token: &str) -> Result<Vec<RoleResponse>> {
+    let client = reqwest::Client::new();
+
+    let url = backbone_url.into_url()?.join(&format!("workspaces/{workspace_name}/roles"))?;
+    let response = client.get(url).bearer_auth(token).send().await?.json::<Vec<RoleResponse>>().await?;
+
+    Ok(response)
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PostRoleRequest {
+    pub name: String,
+    pub permissions: Vec<String>,
+}
+
+pub async fn create_role(
+    backbone_url: impl IntoUrl,
+    workspace_name: &str,
+    request: PostRoleRequest,
+    token: &str,
+) -> Result<()> {
+    let client = reqwest::Client::new();
+
+    let url = backbone_url.into_url()?.join(&format!("workspaces/{workspace_name}/roles"))?;
+    let response = client.post(url).bearer_auth(token).json(&request).send().await?;
+    response.error_for_status()?;
+
+    Ok(())
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PathResponse {
@@ -203,24 +251,42 @@ pub async fn create_path(
 #[serde(rename_all = "camelCase")]
 pub struct ParameterResponse {
     pub version: i32,
+    #[serde(default)]
+    pub curve: Curve,
     pub parameter: String,
 }
 
+/// Gets the latest parameter of the workspace. It is not cached since a version can be added to the workspace.
+pub async fn get_latest_parameter(
+    backbone_url: impl IntoUrl,
+    workspace_name: &str,
+    token: &str,
+) -> Result<ParameterResponse> {
+    let client = reqwest::Client::new();
+
+    let url = backbone_url.into_url()?.join(&format!("workspaces/{workspace_name}/parameter"))?;
+    let response = client.get(url).bearer_auth(token).send().await?.json::<ParameterResponse>().await?;
+
+    Ok(response)
+}
+
 #[io_cached(
     map_error = "|e| anyhow::anyhow!(e)",
     disk = true,
     time = 600,
     key = "String",
-    convert = r#"{ format!("gp:{}/{}", backbone_url.as_str(), workspace_name) }"#
+    convert = r#"{ format!("gp:{}/{}#{}", backbone_url.as_str(), workspace_name, version) }"#
 )]
-pub async fn get_global_params(
+pub async fn get_parameter(
     backbone_url: impl IntoUrl,
     workspace_name: &str,
+    version: i32,
     token: &str,
 ) -> Result<ParameterResponse> {
     let client = reqwest::Client::new();
 
-    let url = backbone_url.into_url()?.join(&format!("workspaces/{workspace_name}/parameter"))?;
+    let mut url = backbone_url.into_url()?.join(&format!("workspaces/{workspace_name}/parameter"))?;
+    url.query_pairs_mut().append_pair("version", &version.to_string());
     let response = client.get(url).bearer_auth(token).send().await?.json::<ParameterResponse>().await?;
 
     Ok(response)
@@ -233,6 +299,11 @@ pub struct AuthorityResponse {
     pub name: String,
     pub host: String,
     pub public_key: Option<String>,
+    /// `None` until the backbone checks the health of the authority.
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub latest_key_version: Option<u64>,
 }
 
 pub async fn get_authorities(
@@ -248,6 +319,31 @@ pub async fn get_authorities(
     Ok(response)
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthorityPublicKeyResponse {
+    pub authority_name: String,
+    pub version: u64,
+    pub parameter_version: i32,
+    pub public_key: String,
+}
+
+/// Gets the public keys of the authorities cached in the backbone, or only the latest one of each authority if `latest`.
+pub async fn get_authority_public_keys(
+    backbone_url: impl IntoUrl,
+    workspace_name: &str,
+    latest: bool,
+    token: &str,
+) -> Result<Vec<AuthorityPublicKeyResponse>> {
+    let client = reqwest::Client::new();
+
+    let mut url = backbone_url.into_url()?.join(&format!("workspaces/{workspace_name}/authorities/public-keys"))?;
+    url.query_pairs_mut().append_pair("latest", &latest.to_string());
+    let response = client.get(url).bearer_auth(token).send().await?.error_for_status()?;
+
+    Ok(response.json().await?)
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PostAuthorityRequest {
@@ -255,6 +351,39 @@ pub struct PostAuthorityRequest {
     pub host: String,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PostBootstrapTokenRequest {
+    expires_in: Option<u64>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BootstrapTokenResponse {
+    pub token: String,
+    pub expires_at: String,
+}
+
+pub async fn issue_bootstrap_token(
+    backbone_url: impl IntoUrl,
+    workspace_name: &str,
+    expires_in: Option<u64>,
+    token: &str,
+) -> Result<BootstrapTokenResponse> {
+    let client = reqwest::Client::new();
+
+    let url = backbone_url.into_url()?.join(&format!("workspaces/{workspace_name}/authorities/bootstrap-tokens"))?;
+    let response = client
+        .post(url)
+        .bearer_auth(token)
+        .json(&PostBootstrapTokenRequest { expires_in })
+        .send()
+        .await?
+        .error_for_status()?;
+
+    Ok(response.json().await?)
+}
+
 pub async fn add_authority(
     backbone_url: impl IntoUrl,
     workspace_name: &str,
@@ -10,8 +10,11 @@ use crossterm::style::{Color, Print, ResetColor, SetForegroundColor};
 use inquire::validator::Validation;
 use inquire::{CustomType, Password, Text};
 
-use crate::api::authority::{disarm, init};
-use crate::api::backbone::{add_authority, get_authorities, PostAuthorityRequest};
+use crate::api::authority::{
+    cancel_disarm_session, disarm, get_disarm_session, get_key_pairs, get_status, init, open_disarm_session,
+    submit_disarm_share, DisarmProgress,
+};
+use crate::api::backbone::{add_authority, get_authorities, issue_bootstrap_token, PostAuthorityRequest};
 use crate::config::{load_token, NebulaConfig};
 use crate::utils::validation::validate_url;
 
@@ -23,6 +26,9 @@ pub enum AuthorityCommand {
     Init(AuthorityInitCommand),
     Add(AuthorityAddCommand),
     Disarm(AuthorityDisarmCommand),
+    Status(AuthorityStatusCommand),
+    KeyPairs(AuthorityKeyPairsCommand),
+    BootstrapToken(AuthorityBootstrapTokenCommand),
 }
 
 #[async_trait]
@@ -33,6 +39,9 @@ impl RunCommand for AuthorityCommand {
             AuthorityCommand::Init(cmd) => cmd.run(args).await,
             AuthorityCommand::Add(cmd) => cmd.run(args).await,
             AuthorityCommand::Disarm(cmd) => cmd.run(args).await,
+            AuthorityCommand::Status(cmd) => cmd.run(args).await,
+            AuthorityCommand::KeyPairs(cmd) => cmd.run(args).await,
+            AuthorityCommand::BootstrapToken(cmd) => cmd.run(args).await,
         }
     }
 }
@@ -52,10 +61,15 @@ impl RunCommand for AuthorityListCommand {
 
         let mut table = Table::new();
         table.load_preset(UTF8_FULL).apply_modifier(UTF8_ROUND_CORNERS);
-        table.set_header(vec!["Name", "Host"]);
+        table.set_header(vec!["Name", "Host", "Status", "Latest Key Version"]);
 
         for authority in authorities {
-            table.add_row(vec![Cell::new(authority.name), Cell::new(authority.host)]);
+            table.add_row(vec![
+                Cell::new(authority.name),
+                Cell::new(authority.host),
+                Cell::new(authority.status.unwrap_or_else(|| "UNKNOWN".to_owned())),
+                Cell::new(authority.latest_key_version.map(|version| version.to_string()).unwrap_or_default()),
+            ]);
         }
         println!("{table}");
         Ok(())
@@ -205,10 +219,130 @@ impl RunCommand for AuthorityAddCommand {
     }
 }
 
+/// Shows whether the authority is initialized and armored, and the number of shares required to disarm it.
+#[derive(Args, Debug)]
+pub struct AuthorityStatusCommand {
+    #[clap(short = 'n', long = "name")]
+    name: String,
+}
+
+#[async_trait]
+impl RunCommand for AuthorityStatusCommand {
+    async fn run(&self, args: &GlobalArgs) -> anyhow::Result<()> {
+        let config = NebulaConfig::load(args.profile.as_str(), args.config.clone().map(Into::into))?;
+        let token = load_token(&args.profile)?;
+        let backbone_url = config.backbone.host;
+        let workspace_name = config.workspace;
+
+        let authorities = get_authorities(backbone_url.clone(), &workspace_name, &token).await?;
+
+        let authority = authorities
+            .into_iter()
+            .find(|a| a.name == self.name)
+            .ok_or_else(|| anyhow::anyhow!(format!("Authority `{}` not found", self.name)))?;
+
+        let status = get_status(authority.host).await?;
+
+        let mut table = Table::new();
+        table.load_preset(UTF8_FULL).apply_modifier(UTF8_ROUND_CORNERS);
+        table.set_header(vec!["Initialized", "Armored", "Threshold"]);
+        table.add_row(vec![
+            Cell::new(status.initialized),
+            Cell::new(status.armored),
+            Cell::new(status.threshold.map(|threshold| threshold.to_string()).unwrap_or_else(|| "UNKNOWN".to_owned())),
+        ]);
+        println!("{table}");
+
+        Ok(())
+    }
+}
+
+/// Lists the key pair versions of the workspace on the authority. Requires the admin role.
+#[derive(Args, Debug)]
+pub struct AuthorityKeyPairsCommand {
+    #[clap(short = 'n', long = "name")]
+    name: String,
+}
+
+#[async_trait]
+impl RunCommand for AuthorityKeyPairsCommand {
+    async fn run(&self, args: &GlobalArgs) -> anyhow::Result<()> {
+        let config = NebulaConfig::load(args.profile.as_str(), args.config.clone().map(Into::into))?;
+        let token = load_token(&args.profile)?;
+        let backbone_url = config.backbone.host;
+        let workspace_name = config.workspace;
+
+        let authorities = get_authorities(backbone_url.clone(), &workspace_name, &token).await?;
+
+        let authority = authorities
+            .into_iter()
+            .find(|a| a.name == self.name)
+            .ok_or_else(|| anyhow::anyhow!(format!("Authority `{}` not found", self.name)))?;
+
+        let key_pairs = get_key_pairs(authority.host, &workspace_name, &token).await?;
+
+        let mut table = Table::new();
+        table.load_preset(UTF8_FULL).apply_modifier(UTF8_ROUND_CORNERS);
+        table.set_header(vec!["Version", "Created At", "Status", "Reason"]);
+        for key_pair in key_pairs {
+            table.add_row(vec![
+                Cell::new(key_pair.version),
+                Cell::new(key_pair.created_at.unwrap_or_default()),
+                Cell::new(key_pair.status),
+                Cell::new(key_pair.status_reason.unwrap_or_default()),
+            ]);
+        }
+        println!("{table}");
+
+        Ok(())
+    }
+}
+
+/// Issues a bootstrap token with which an authority registers itself on startup.
+#[derive(Args, Debug)]
+pub struct AuthorityBootstrapTokenCommand {
+    /// Seconds until the token expires unless an authority registers itself with it.
+    #[clap(long = "expires-in")]
+    expires_in: Option<u64>,
+}
+
+#[async_trait]
+impl RunCommand for AuthorityBootstrapTokenCommand {
+    async fn run(&self, args: &GlobalArgs) -> anyhow::Result<()> {
+        let config = NebulaConfig::load(args.profile.as_str(), args.config.clone().map(Into::into))?;
+        let token = load_token(&args.profile)?;
+        let backbone_url = config.backbone.host;
+        let workspace_name = config.workspace;
+
+        let bootstrap_token = issue_bootstrap_token(backbone_url, &workspace_name, self.expires_in, &token).await?;
+
+        let mut table = Table::new();
+        table.load_preset(UTF8_FULL).apply_modifier(UTF8_ROUND_CORNERS);
+        table.set_header(vec!["Bootstrap Token", "Expires At"]);
+        table.add_row(vec![Cell::new(bootstrap_token.token), Cell::new(bootstrap_token.expires_at)]);
+        println!("{table}");
+
+        Ok(())
+    }
+}
+
 #[derive(Args, Debug)]
 pub struct AuthorityDisarmCommand {
     #[clap(short = 'n', long = "name")]
     name: String,
+
+    /// Submits only your share to the disarm session, so that each custodian submits their own share. A session is
+    /// opened without `--nonce`, and its nonce is printed to be passed to the other custodians.
+    #[clap(long = "single-share", conflicts_with = "reset")]
+    single_share: bool,
+
+    /// Nonce of the disarm session opened by another custodian.
+    #[clap(long = "nonce")]
+    nonce: Option<String>,
+
+    /// Discards the shares submitted to the disarm session with the nonce, which requires your share.
+    #[clap(long = "reset", requires = "nonce")]
+    reset: bool,
 }
 
 #[async_trait]
@@ -226,6 +360,12 @@ impl RunCommand for AuthorityDisarmCommand {
             .find(|a| a.name == self.name)
             .ok_or_else(|| anyhow::anyhow!(format!("Authority `{}` not found", self.name)))?;
 
+        match (self.reset, self.single_share, self.nonce.as_deref()) {
+            (true, _, Some(nonce)) => return AuthorityDisarmCommand::reset(authority.host, nonce).await,
+            (false, true, nonce) => return AuthorityDisarmCommand::submit_single_share(authority.host, nonce).await,
+            _ => {}
+        }
+
         let shared_count = CustomType::<u8>::new("Enter the number of shared secrets to disarm: ")
             .with_validator(|s: &u8| {
                 if *s == 0 {
@@ -257,3 +397,55 @@ impl RunCommand for AuthorityDisarmCommand {
         Ok(())
     }
 }
+
+impl AuthorityDisarmCommand {
+    async fn submit_single_share(authority_url: String, nonce: Option<&str>) -> anyhow::Result<()> {
+        let session = get_disarm_session(&authority_url).await?;
+        if session.disarmed {
+            println!("Authority is already disarmed");
+            return Ok(());
+        }
+
+        let share = AuthorityDisarmCommand::prompt_share()?;
+        let DisarmProgress { progress, threshold, disarmed } = match nonce {
+            Some(nonce) => submit_disarm_share(&authority_url, share, nonce).await?,
+            None => {
+                let opened = open_disarm_session(&authority_url, share).await?;
+                if !opened.progress.disarmed {
+                    println!("Opened a disarm session. Pass the nonce to the other custodians: {}", opened.nonce);
+                }
+                opened.progress
+            }
+        };
+
+        if disarmed {
+            execute!(
+                stdout(),
+                SetForegroundColor(Color::Green),
+                Print("✅ Successfully disarmed authority\n"),
+                ResetColor
+            )?;
+        } else {
+            let threshold = threshold.map(|threshold| threshold.to_string()).unwrap_or_else(|| "?".to_owned());
+            println!("Submitted the share ({progress} of {threshold})");
+        }
+
+        Ok(())
+    }
+
+    async fn reset(authority_url: String, nonce: &str) -> anyhow::Result<()> {
+        let share = AuthorityDisarmCommand::prompt_share()?;
+        cancel_disarm_session(&authority_url, share, nonce).await?;
+        println!("Discarded the submitted shares");
+
+        Ok(())
+    }
+
+    fn prompt_share() -> anyhow::Result<String> {
+        Ok(Password::new("Enter your shared secret")
+            .with_display_mode(inquire::PasswordDisplayMode::Masked)
+            .with_display_toggle_enabled()
+            .without_confirmation()
+            .prompt()?)
+    }
+}
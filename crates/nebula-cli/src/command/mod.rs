@@ -5,6 +5,7 @@ use clap::{command, Args, Parser};
 use config::ConfigCommand;
 use login::LoginCommand;
 use path::PathCommand;
+use role::RoleCommand;
 use secret::SecretCommand;
 
 pub mod access_condition;
@@ -12,6 +13,7 @@ pub mod authority;
 pub mod config;
 pub mod login;
 pub mod path;
+pub mod role;
 pub mod secret;
 
 #[derive(Args, Debug)]
@@ -56,6 +58,8 @@ pub enum CliCommand {
     Authority(AuthorityCommand),
     #[clap(subcommand)]
     Path(PathCommand),
+    #[clap(subcommand)]
+    Role(RoleCommand),
 }
 
 #[async_trait]
@@ -80,6 +84,9 @@ impl RunCommand for CliCommand {
             CliCommand::Path(ref path) => {
                 path.run(args).await?;
             }
+            CliCommand::Role(ref role) => {
+                role.run(args).await?;
+            }
         }
         Ok(())
     }
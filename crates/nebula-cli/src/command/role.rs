@@ -0,0 +1,82 @@
+use std::io::stdout;
+
+use async_trait::async_trait;
+use clap::{Args, Subcommand};
+use comfy_table::modifiers::UTF8_ROUND_CORNERS;
+use comfy_table::presets::UTF8_FULL;
+use comfy_table::{Cell, Table};
+use crossterm::execute;
+use crossterm::style::{Color, Print, ResetColor, SetForegroundColor};
+
+use crate::api::backbone::{create_role, get_roles, PostRoleRequest};
+use crate::config::{load_token, NebulaConfig};
+
+use super::{GlobalArgs, RunCommand};
+
+#[derive(Subcommand, Debug)]
+pub enum RoleCommand {
+    List(RoleListCommand),
+    Create(RoleCreateCommand),
+}
+
+#[async_trait]
+impl RunCommand for RoleCommand {
+    async fn run(&self, args: &GlobalArgs) -> anyhow::Result<()> {
+        match self {
+            RoleCommand::List(cmd) => cmd.run(args).await,
+            RoleCommand::Create(cmd) => cmd.run(args).await,
+        }
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct RoleListCommand {}
+
+#[async_trait]
+impl RunCommand for RoleListCommand {
+    async fn run(&self, args: &GlobalArgs) -> anyhow::Result<()> {
+        let config = NebulaConfig::load(args.profile.as_str(), args.config.clone().map(Into::into))?;
+        let token = load_token(&args.profile)?;
+        let backbone_url = config.backbone.host;
+        let workspace_name = config.workspace;
+
+        let roles = get_roles(backbone_url.clone(), &workspace_name, &token).await?;
+
+        let mut table = Table::new();
+        table.load_preset(UTF8_FULL).apply_modifier(UTF8_ROUND_CORNERS);
+        table.set_header(vec!["ID", "Name", "Permissions"]);
+
+        for role in roles {
+            table.add_row(vec![Cell::new(role.id), Cell::new(role.name), Cell::new(role.permissions.join(", "))]);
+        }
+        println!("{table}");
+        Ok(())
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct RoleCreateCommand {
+    #[clap(short = 'n', long)]
+    pub name: String,
+    /// Permission granted to the role, such as `policy:write`. Can be repeated.
+    #[clap(short = 'P', long = "permission", required = true)]
+    pub permissions: Vec<String>,
+}
+
+#[async_trait]
+impl RunCommand for RoleCreateCommand {
+    async fn run(&self, args: &GlobalArgs) -> anyhow::Result<()> {
+        let config = NebulaConfig::load(args.profile.as_str(), args.config.clone().map(Into::into))?;
+        let token = load_token(&args.profile)?;
+        let backbone_url = config.backbone.host;
+        let workspace_name = config.workspace;
+
+        let request = PostRoleRequest { name: self.name.clone(), permissions: self.permissions.clone() };
+
+        create_role(backbone_url.clone(), &workspace_name, request, &token).await?;
+
+        execute!(stdout(), SetForegroundColor(Color::Green), Print("✅ Successfully created role\n"), ResetColor)?;
+
+        Ok(())
+    }
+}
@@ -11,18 +11,19 @@ use comfy_table::{Cell, Table};
 use crossterm::execute;
 use crossterm::style::{Color, Print, ResetColor, SetForegroundColor};
 use futures_util::future::join_all;
-use nebula_abe::curves::bn462::Bn462Curve;
 use nebula_abe::random::miracl::MiraclRng;
 use nebula_abe::schemes::isabella24::{decrypt, encrypt, AuthorityPublicKey, Ciphertext, GlobalParams, UserSecretKey};
-use nebula_abe::PolicyLanguage;
+use nebula_abe::utils::attribute::unpack_attribute;
+use nebula_abe::utils::epoch::with_epoch_clauses;
+use nebula_abe::{with_curve, PolicyLanguage};
 use rand::rngs::OsRng;
 use rand::Rng as _;
 use ulid::Ulid;
 
-use crate::api::authority::{get_public_key, get_user_key};
+use crate::api::authority::{get_epoch, get_user_key};
 use crate::api::backbone::{
-    create_secret, get_access_condition, get_authorities, get_global_params, get_paths, get_secret_with_identifier,
-    get_secrets, PostSecretRequest,
+    create_secret, get_access_condition, get_authorities, get_authority_public_keys, get_latest_parameter,
+    get_parameter, get_paths, get_secret_with_identifier, get_secrets, PostSecretRequest,
 };
 use crate::config::{load_token, NebulaConfig};
 
@@ -124,27 +125,51 @@ impl RunCommand for SecretGetCommand {
         let workspace_name = config.workspace;
         let identifier = &self.path;
 
-        let gp = get_global_params(backbone_url.clone(), &workspace_name, &token).await?;
-        let gp = STANDARD.decode(gp.parameter)?;
-        let gp: GlobalParams<Bn462Curve> = rmp_serde::from_slice(&gp)?;
-
         let authorities = get_authorities(backbone_url.clone(), &workspace_name, &token).await?;
-        let secret = get_secret_with_identifier(backbone_url, &workspace_name, identifier, &token).await?;
+        let secret = get_secret_with_identifier(backbone_url.clone(), &workspace_name, identifier, &token).await?;
         let ct = STANDARD.decode(secret.cipher)?;
-        let ct: Ciphertext<Bn462Curve> = rmp_serde::from_slice(&ct)?;
+
+        // The secret is decrypted with the parameter and the key pair versions with which it is encrypted.
+        let gp = get_parameter(backbone_url, &workspace_name, secret.parameter_version, &token).await?;
+        let curve = gp.curve;
+        let gp = STANDARD.decode(gp.parameter)?;
+        let ciphertext_attributes = with_curve!(curve, C => {
+            let ct: Ciphertext<C> = rmp_serde::from_slice(&ct)?;
+            ct.cj
+                .keys()
+                .filter_map(|attribute| unpack_attribute(attribute).ok())
+                .map(|a| (a.name, a.authority))
+                .collect::<Vec<_>>()
+        });
+        let key_pair_names = ciphertext_attributes.iter().map(|(_, authority)| authority).collect::<Vec<_>>();
 
         let mut usks = vec![];
         for authority in authorities {
-            let usk = get_user_key(&authority.host, &workspace_name, &token).await?;
-
-            let usk = STANDARD.decode(&usk.user_key)?;
-            let usk: UserSecretKey<Bn462Curve> = rmp_serde::from_slice(&usk)?;
-            usks.push(usk);
+            let prefix = format!("{}-{}#", authority.name, workspace_name);
+            let Some((key_pair_name, version)) =
+                key_pair_names.iter().find_map(|name| Some((name, name.strip_prefix(&prefix)?)))
+            else {
+                continue;
+            };
+            // A secret encrypted in an epoch is decrypted with a user key bound to the same epoch.
+            let epoch = match get_epoch(&authority.host, &workspace_name).await? {
+                Some(epoch) => ciphertext_attributes
+                    .iter()
+                    .filter(|(_, authority)| authority == *key_pair_name)
+                    .find_map(|(name, _)| name.strip_prefix(&format!("{}=", epoch.attribute))),
+                None => None,
+            };
+            let usk = get_user_key(&authority.host, &workspace_name, Some(version.parse()?), epoch, &token).await?;
+            usks.push(STANDARD.decode(&usk.user_key)?);
         }
 
-        let sk = UserSecretKey::<Bn462Curve>::sum(usks.into_iter())?;
-
-        let plaintext = decrypt(&gp, &sk, &ct)?;
+        let plaintext = with_curve!(curve, C => {
+            let gp: GlobalParams<C> = rmp_serde::from_slice(&gp)?;
+            let ct: Ciphertext<C> = rmp_serde::from_slice(&ct)?;
+            let usks = usks.iter().map(|usk| rmp_serde::from_slice(usk)).collect::<Result<Vec<UserSecretKey<C>>, _>>()?;
+            let sk = UserSecretKey::<C>::sum(usks.into_iter())?;
+            decrypt(&gp, &sk, &ct)?
+        });
 
         let mut table = Table::new();
         table.load_preset(UTF8_FULL).apply_modifier(UTF8_ROUND_CORNERS);
@@ -178,18 +203,38 @@ impl RunCommand for SecretCreateCommand {
         let backbone_url = config.backbone.host;
         let workspace_name = config.workspace;
 
-        let gp = get_global_params(backbone_url.clone(), &workspace_name, &token).await?;
+        let gp = get_latest_parameter(backbone_url.clone(), &workspace_name, &token).await?;
+        let (parameter_version, curve) = (gp.version, gp.curve);
         let gp = STANDARD.decode(gp.parameter)?;
-        let gp: GlobalParams<Bn462Curve> = rmp_serde::from_slice(&gp)?;
 
+        // The backbone serves the public keys it has verified, so authorities need not be reachable to encrypt.
+        let public_keys = get_authority_public_keys(backbone_url.clone(), &workspace_name, true, &token).await?;
         let authorities = get_authorities(backbone_url.clone(), &workspace_name, &token).await?;
         let mut pks = HashMap::new();
-        for authority in authorities {
-            let pk_response = get_public_key(&authority.host, &workspace_name).await?;
+        // The secret is bound to the current epoch of the authorities binding user keys to epochs, so that user keys
+        // cached before the epoch rolls over can't decrypt it.
+        let mut epoch_attributes = vec![];
+        for public_key in public_keys {
+            if public_key.parameter_version != parameter_version {
+                return Err(anyhow::anyhow!(
+                    "The key pair of authority({}) is generated with parameter version {}, but the latest version is {}. \
+                     Roll the key pair of the authority first",
+                    public_key.authority_name,
+                    public_key.parameter_version,
+                    parameter_version
+                ));
+            }
 
-            let pk = STANDARD.decode(&pk_response.public_key)?;
-            let pk: AuthorityPublicKey<Bn462Curve> = rmp_serde::from_slice(&pk)?;
-            pks.insert(format!("{}-{}#{}", authority.name, workspace_name, pk_response.version), pk);
+            let key_pair_name = format!("{}-{}#{}", public_key.authority_name, workspace_name, public_key.version);
+            let authority = authorities.iter().find(|authority| authority.name == public_key.authority_name);
+            if let Some(authority) = authority {
+                if let Some(epoch) = get_epoch(&authority.host, &workspace_name).await? {
+                    epoch_attributes.push(format!("{}={}@{}", epoch.attribute, epoch.epoch, key_pair_name));
+                }
+            }
+
+            let pk = STANDARD.decode(&public_key.public_key)?;
+            pks.insert(key_pair_name, pk);
         }
 
         let mut rng = MiraclRng::new();
@@ -203,7 +248,8 @@ impl RunCommand for SecretCreateCommand {
                 get_access_condition(backbone_url.clone(), &workspace_name, &Ulid::from_str(id)?, &token).await?;
             policy.push(access_condition.expression);
         }
-        let policy = (policy.join(" OR "), PolicyLanguage::HumanPolicy);
+
+        let policy = (with_epoch_clauses(&policy.join(" OR "), &epoch_attributes)?, PolicyLanguage::HumanPolicy);
 
         let trimmed_path = self.path.trim_matches('/');
         if trimmed_path.is_empty() {
@@ -213,14 +259,21 @@ impl RunCommand for SecretCreateCommand {
         let key = path.split('/').last().unwrap().to_string();
         let path = path.trim_end_matches(&key).to_string();
 
-        let ct = encrypt(&mut rng, &gp, &pks, policy, self.value.as_bytes())?;
-        let ct = rmp_serde::to_vec(&ct)?;
+        let ct = with_curve!(curve, C => {
+            let gp: GlobalParams<C> = rmp_serde::from_slice(&gp)?;
+            let pks = pks
+                .into_iter()
+                .map(|(name, pk)| Ok((name, rmp_serde::from_slice::<AuthorityPublicKey<C>>(&pk)?)))
+                .collect::<anyhow::Result<HashMap<_, _>>>()?;
+            let ct = encrypt(&mut rng, &gp, &pks, policy, self.value.as_bytes())?;
+            rmp_serde::to_vec(&ct)?
+        });
         let ct = STANDARD.encode(&ct);
 
         let access_condition_ids =
             self.access_condition_ids.iter().map(|id| Ulid::from_str(id)).collect::<Result<Vec<_>, _>>()?;
 
-        let request = PostSecretRequest { path, key, cipher: ct, access_condition_ids };
+        let request = PostSecretRequest { path, key, cipher: ct, parameter_version, access_condition_ids };
         create_secret(backbone_url.clone(), &workspace_name, request, &token).await?;
 
         execute!(stdout(), SetForegroundColor(Color::Green), Print("✅ Successfully created secret\n"), ResetColor)?;
@@ -0,0 +1,248 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use anyhow::Context as _;
+use config::{Config, ConfigError, File, FileFormat, FileSourceFile, Map, Source, Value, ValueKind};
+use serde::Deserialize as _;
+
+pub const ENV_PREFIX: &str = "NEBULA_";
+
+const ENV_SEPARATOR: &str = "__";
+const ENV_FILE_SUFFIX: &str = "_FILE";
+const REDACTED: &str = "<redacted>";
+
+/// Configuration source which reads the environment variables starting with the prefix.
+///
+/// `__` separates nested keys, e.g. `NEBULA_DATABASE__AUTH__PASSWORD` sets `database.auth.password`.
+/// A variable suffixed with `_FILE` sets the key to the content of the file, which takes precedence over the plain one.
+/// A value starting with `[` or `{` is parsed as JSON or TOML inline value, e.g. `NEBULA_CORS__DOMAINS=["a", "b"]`.
+#[derive(Debug, Clone)]
+pub struct Environment {
+    prefix: String,
+    vars: BTreeMap<String, String>,
+}
+
+impl Environment {
+    pub fn new(prefix: &str) -> Self {
+        Self::from_vars(prefix, std::env::vars())
+    }
+
+    fn from_vars(prefix: &str, vars: impl IntoIterator<Item = (String, String)>) -> Self {
+        Self {
+            prefix: prefix.to_owned(),
+            vars: vars.into_iter().filter(|(name, _)| name.starts_with(prefix)).collect(),
+        }
+    }
+}
+
+impl Source for Environment {
+    fn clone_into_box(&self) -> Box<dyn Source + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn collect(&self) -> Result<Map<String, Value>, ConfigError> {
+        let mut properties = Map::new();
+        // Variables are sorted by name, so `<NAME>_FILE` always overrides `<NAME>`.
+        for (name, value) in &self.vars {
+            let name = &name[self.prefix.len()..];
+            let origin = format!("{}{}", self.prefix, name);
+            let (name, value) = match name.strip_suffix(ENV_FILE_SUFFIX) {
+                Some(name) => {
+                    let content = std::fs::read_to_string(value).map_err(|e| {
+                        ConfigError::Message(format!("failed to read {value} for environment variable {origin}: {e}"))
+                    })?;
+                    (name, content.trim_end_matches(['\r', '\n']).to_owned())
+                }
+                None => (name, value.to_owned()),
+            };
+            if name.is_empty() {
+                continue;
+            }
+
+            let key = name.to_lowercase().replace(ENV_SEPARATOR, ".");
+            properties.insert(key, parse_value(&origin, value));
+        }
+
+        Ok(properties)
+    }
+}
+
+fn parse_value(origin: &str, value: String) -> Value {
+    if value.starts_with(['[', '{']) {
+        if let Ok(value) = serde_json::from_str::<serde_json::Value>(&value) {
+            if let Ok(value) = Value::deserialize(value) {
+                return value;
+            }
+        }
+        if let Ok(mut table) = format!("value = {value}").parse::<toml::Table>() {
+            if let Some(value) = table.remove("value").and_then(|value| Value::deserialize(value).ok()) {
+                return value;
+            }
+        }
+    }
+
+    Value::new(Some(&origin.to_owned()), ValueKind::String(value))
+}
+
+/// Source of the TOML config file. The file at the path given by `--config` must exist, while `file_name` in the
+/// `nebula` directory of the user config directory is optional, so that a server can be configured by environment
+/// variables only.
+pub fn config_file(
+    path_override: Option<PathBuf>,
+    file_name: &str,
+) -> anyhow::Result<File<FileSourceFile, FileFormat>> {
+    let (path, is_required) = match path_override {
+        Some(path) => (path, true),
+        None => {
+            let config_dir = nebula_config_path::config_dir().context("failed to get the user config directory")?;
+            (config_dir.join("nebula").join(file_name), false)
+        }
+    };
+    Ok(File::from(path).format(FileFormat::Toml).required(is_required))
+}
+
+/// Renders the configuration as TOML. Values of `password` keys and of the secret keys (dotted paths) are redacted.
+pub fn dump(config: &Config, secret_keys: &[&str]) -> anyhow::Result<String> {
+    let mut value: toml::Value = config.clone().try_deserialize()?;
+    redact(&mut value, "", secret_keys);
+    Ok(toml::to_string_pretty(&value)?)
+}
+
+fn redact(value: &mut toml::Value, path: &str, secret_keys: &[&str]) {
+    match value {
+        toml::Value::Table(table) => {
+            for (key, value) in table.iter_mut() {
+                let path = if path.is_empty() { key.to_owned() } else { format!("{path}.{key}") };
+                if key == "password" || secret_keys.contains(&path.as_str()) {
+                    *value = toml::Value::String(REDACTED.to_owned());
+                } else {
+                    redact(value, &path, secret_keys);
+                }
+            }
+        }
+        toml::Value::Array(values) => values.iter_mut().for_each(|value| redact(value, path, secret_keys)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use config::{Config, File, FileFormat};
+    use serde::Deserialize;
+
+    use super::{config_file, dump, Environment, ENV_PREFIX};
+
+    #[derive(Deserialize, Debug)]
+    struct TestConfig {
+        port: u16,
+        database: DatabaseConfig,
+        domains: Vec<String>,
+    }
+
+    #[derive(Deserialize, Debug)]
+    struct DatabaseConfig {
+        database_name: String,
+        password: String,
+    }
+
+    fn vars(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+        vars.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect()
+    }
+
+    #[test]
+    fn when_environment_variables_are_set_then_nested_keys_are_overridden() {
+        let config: TestConfig = Config::builder()
+            .add_source(File::from_str(
+                "port = 8080\ndomains = []\n[database]\ndatabase_name = \"file\"\npassword = \"file\"",
+                FileFormat::Toml,
+            ))
+            .add_source(Environment::from_vars(
+                ENV_PREFIX,
+                vars(&[
+                    ("NEBULA_PORT", "9090"),
+                    ("NEBULA_DATABASE__DATABASE_NAME", "env"),
+                    ("NEBULA_DOMAINS", r#"["https://a.example.com", "https://b.example.com"]"#),
+                    ("OTHER_PORT", "1"),
+                ]),
+            ))
+            .build()
+            .unwrap()
+            .try_deserialize()
+            .unwrap();
+
+        assert_eq!(config.port, 9090);
+        assert_eq!(config.database.database_name, "env");
+        assert_eq!(config.database.password, "file");
+        assert_eq!(config.domains, vec!["https://a.example.com", "https://b.example.com"]);
+    }
+
+    #[test]
+    fn when_file_environment_variable_is_set_then_value_is_read_from_file() {
+        let path = std::env::temp_dir().join(format!("nebula-config-test-{}", std::process::id()));
+        std::fs::write(&path, "secret\n").unwrap();
+
+        let config: DatabaseConfig = Config::builder()
+            .add_source(Environment::from_vars(
+                ENV_PREFIX,
+                vars(&[
+                    ("NEBULA_DATABASE_NAME", "db"),
+                    ("NEBULA_PASSWORD", "plain"),
+                    ("NEBULA_PASSWORD_FILE", path.to_str().unwrap()),
+                ]),
+            ))
+            .build()
+            .unwrap()
+            .try_deserialize()
+            .unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(config.password, "secret");
+    }
+
+    #[test]
+    fn when_dump_config_then_secrets_are_redacted() {
+        let config = Config::builder()
+            .add_source(File::from_str(
+                "port = 8080\ndisarm_key_shares = [\"share\"]\n[database]\npassword = \"password\"",
+                FileFormat::Toml,
+            ))
+            .build()
+            .unwrap();
+
+        let dumped = dump(&config, &["disarm_key_shares"]).unwrap();
+
+        assert!(dumped.contains("port = 8080"));
+        assert!(!dumped.contains("\"password\""));
+        assert!(!dumped.contains("share\""));
+    }
+
+    #[test]
+    fn when_config_file_given_by_path_is_missing_then_config_fails_to_build() {
+        let path = std::env::temp_dir().join("nebula-config-test-missing").join("config.toml");
+
+        let result = Config::builder().add_source(config_file(Some(path), "unused.toml").unwrap()).build();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn when_config_file_is_given_by_path_then_it_is_read() {
+        let path = std::env::temp_dir().join(format!("nebula-config-test-{}.toml", std::process::id()));
+        std::fs::write(&path, "port = 8080").unwrap();
+
+        let config = Config::builder().add_source(config_file(Some(path.clone()), "unused.toml").unwrap()).build();
+
+        assert_eq!(config.unwrap().get_int("port").unwrap(), 8080);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn when_default_config_file_is_missing_then_config_is_built_from_other_sources() {
+        let config = Config::builder()
+            .add_source(config_file(None, "nebula-config-test-missing.toml").unwrap())
+            .set_default("port", 8080)
+            .unwrap()
+            .build();
+
+        assert_eq!(config.unwrap().get_int("port").unwrap(), 8080);
+    }
+}
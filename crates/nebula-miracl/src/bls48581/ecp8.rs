@@ -16,6 +16,10 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
 use crate::bls48581::big;
 use crate::bls48581::big::BIG;
@@ -28,6 +32,9 @@ use crate::bls48581::fp4::FP4;
 use crate::bls48581::fp8::FP8;
 use crate::bls48581::rom;
 
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "zeroize", derive(Zeroize, ZeroizeOnDrop))]
 pub struct ECP8 {
     x: FP8,
     y: FP8,
@@ -16,6 +16,10 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
 use crate::bls48581::big;
 use crate::bls48581::big::BIG;
@@ -23,7 +27,9 @@ use crate::bls48581::fp::FP;
 use crate::bls48581::fp2::FP2;
 use crate::bls48581::fp8::FP8;
 
-#[derive(Copy, Clone)]
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "zeroize", derive(Zeroize, ZeroizeOnDrop))]
 pub struct FP16 {
     a: FP8,
     b: FP8,
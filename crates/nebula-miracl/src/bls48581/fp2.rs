@@ -16,6 +16,10 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
 use crate::bls48581::big;
 use crate::bls48581::big::BIG;
@@ -26,7 +30,9 @@ use crate::bls48581::rom;
 
 use crate::rand::RAND;
 
-#[derive(Copy, Clone)]
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "zeroize", derive(Zeroize, ZeroizeOnDrop))]
 pub struct FP2 {
     a: FP,
     b: FP,
@@ -178,13 +184,13 @@ impl FP2 {
     /* extract a */
     #[allow(non_snake_case)]
     pub fn getA(&mut self) -> FP {
-        self.a
+        self.a.clone()
     }
 
     /* extract b */
     #[allow(non_snake_case)]
     pub fn getB(&mut self) -> FP {
-        self.b
+        self.b.clone()
     }
 
     /* extract a */
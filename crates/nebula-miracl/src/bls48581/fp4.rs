@@ -16,6 +16,10 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
 use crate::bls48581::big;
 use crate::bls48581::big::BIG;
@@ -26,7 +30,9 @@ use crate::bls48581::fp2::FP2;
 use crate::bls48581::rom;
 use crate::rand::RAND;
 
-#[derive(Copy, Clone)]
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "zeroize", derive(Zeroize, ZeroizeOnDrop))]
 pub struct FP4 {
     a: FP2,
     b: FP2,
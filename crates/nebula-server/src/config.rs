@@ -0,0 +1,126 @@
+use std::path::PathBuf;
+
+use config::Config;
+use nebula_config::{config_file, Environment, ENV_PREFIX};
+use nebula_telemetry::trace::TracingConfig;
+use nebula_tls::TlsConfig;
+use serde::Deserialize;
+
+use crate::Args;
+
+const BACKBONE: &str = "backbone";
+const AUTHORITY: &str = "authority";
+const AUTHORIZATION: &str = "authorization";
+
+#[derive(Deserialize, Debug)]
+pub(super) struct ApplicationConfig {
+    /// Serves all services on this port under `/backbone`, `/authority` and `/authorization` if set. Otherwise, each
+    /// service is served on the port of its own section.
+    pub port: Option<u16>,
+    #[serde(default)]
+    pub dev: bool,
+    /// File where the key shares of the authority are saved when it is initialized in dev mode.
+    pub key_shares_path: Option<PathBuf>,
+    /// Host of the authority which is registered to the static workspace in dev mode.
+    pub authority_host: String,
+    pub tracing: Option<TracingConfig>,
+    /// Serves `GET /metrics` of all services on this port, apart from the API. The `metrics_port` of each section is
+    /// ignored.
+    pub metrics_port: Option<u16>,
+    pub tls: Option<TlsConfig>,
+    pub backbone: nebula_backbone::config::ApplicationConfig,
+    pub authority: nebula_authority::config::ApplicationConfig,
+    pub authorization: nebula_authorization::config::ApplicationConfig,
+}
+
+pub(super) fn load_config(args: Args) -> anyhow::Result<ApplicationConfig> {
+    Ok(build_config(args)?.try_deserialize()?)
+}
+
+/// Keys of the secrets in the configuration, which are redacted by `dump_config`.
+const SECRET_KEYS: &[&str] =
+    &["authority.disarm_key_shares", "authority.authority.registration.bootstrap_tokens", "authorization.token.jwks"];
+
+pub(super) fn dump_config(args: Args) -> anyhow::Result<String> {
+    nebula_config::dump(&build_config(args)?, SECRET_KEYS)
+}
+
+/// Merges the configuration sources. Later sources take precedence: config file, `NEBULA_*` environment variables and
+/// command line arguments. The URLs by which the services reach each other default to the local addresses.
+fn build_config(args: Args) -> anyhow::Result<Config> {
+    let builder = Config::builder()
+        .add_source(config_file(args.config, "server_config.toml")?)
+        .add_source(Environment::new(ENV_PREFIX))
+        .set_default("backbone.database.type", "POSTGRES")?
+        .set_default("authorization.database.type", "POSTGRES")?
+        .set_default("backbone.port", 8080)?
+        .set_default("authority.port", 8090)?
+        .set_default("authorization.port", 9000)?
+        .set_default("backbone.workspace.type", "STATIC")?
+        .set_default("backbone.workspace.name", "default")?
+        .set_default("authorization.workspace.type", "STATIC")?
+        .set_default("authorization.workspace.name", "default")?
+        .set_default("authorization.token.lifetime", 6 * 3600)?
+        .set_override_option("port", args.port)?
+        .set_override_option("dev", args.dev.then_some(true))?;
+
+    let resolved = builder.build_cloned()?;
+    let scheme = if resolved.get_table("tls").is_ok() { "https" } else { "http" };
+    let base_url = |service: &str| -> anyhow::Result<String> {
+        Ok(match resolved.get::<u16>("port").ok() {
+            Some(port) => format!("{scheme}://localhost:{port}/{service}/"),
+            None => {
+                let scheme = if resolved.get_table(&format!("{service}.tls")).is_ok() { "https" } else { "http" };
+                format!("{scheme}://localhost:{}/", resolved.get::<u16>(&format!("{service}.port"))?)
+            }
+        })
+    };
+    let (backbone_url, authority_url, authorization_url) =
+        (base_url(BACKBONE)?, base_url(AUTHORITY)?, base_url(AUTHORIZATION)?);
+    let jwks_url = format!("{authorization_url}jwks");
+
+    let config = builder
+        .set_default("backbone.jwks_url", jwks_url.clone())?
+        .set_default("authority.jwks_url", jwks_url)?
+        .set_default("authority.backbone.host", backbone_url)?
+        .set_default("authorization.base_url", authorization_url)?
+        .set_default("authority_host", authority_url)?
+        .build()?;
+
+    Ok(config)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Args;
+
+    use super::dump_config;
+
+    #[test]
+    fn when_dumping_config_then_secrets_of_services_are_redacted() {
+        let path = std::env::temp_dir().join(format!("nebula-server-test-{}.toml", std::process::id()));
+        std::fs::write(
+            &path,
+            r#"
+            [authority]
+            disarm_key_shares = ["key-share"]
+            authority.name = "authority"
+            authority.registration.host = "http://localhost:8090"
+            authority.registration.bootstrap_tokens = { default = "bootstrap-token" }
+
+            [authorization.token]
+            jwks = "jwks-secret"
+            "#,
+        )
+        .unwrap();
+
+        let dump = dump_config(Args { config: Some(path.clone()), port: None, dev: false, dump_config: true });
+        std::fs::remove_file(path).unwrap();
+
+        let dump = dump.unwrap();
+        assert!(!dump.contains("key-share"));
+        assert!(!dump.contains("bootstrap-token"));
+        assert!(!dump.contains("jwks-secret"));
+        assert!(dump.contains("name = \"authority\""));
+    }
+}
@@ -0,0 +1,73 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context as _;
+use nebula_backbone::config::WorkspaceConfig;
+use nebula_config_path::config_dir;
+use tracing::info;
+
+use crate::config::ApplicationConfig;
+
+/// Number of key shares of the key pair storage initialized in dev mode. A single share disarms the storage.
+const KEY_SHARES: usize = 1;
+
+fn key_shares_path(config: &ApplicationConfig) -> PathBuf {
+    config.key_shares_path.clone().unwrap_or_else(|| {
+        let user_config_dir = config_dir().expect("Failed to get user config directory");
+        user_config_dir.join("nebula").join("server_key_shares")
+    })
+}
+
+/// Sets `disarm_key_shares` of the authority to the key shares saved by [`bootstrap`] unless they are configured.
+pub(super) fn load_key_shares(config: &mut ApplicationConfig) -> anyhow::Result<()> {
+    let path = key_shares_path(config);
+    if config.authority.disarm_key_shares.is_some() || !path.exists() {
+        return Ok(());
+    }
+
+    let key_shares = std::fs::read_to_string(&path).with_context(|| format!("failed to read {}", path.display()))?;
+    let key_shares = key_shares.lines().filter(|share| !share.is_empty()).map(str::to_owned).collect();
+    config.authority.disarm_key_shares = Some(key_shares);
+    Ok(())
+}
+
+/// Initializes the key pair storage of the authority on the first run and registers the authority to the static
+/// workspace of the backbone, which creates the workspace and its parameter on initialization.
+pub(super) async fn bootstrap(
+    config: &ApplicationConfig,
+    backbone: &nebula_backbone::Server,
+    authority: &nebula_authority::Server,
+) -> anyhow::Result<()> {
+    if config.authority.disarm_key_shares.is_none() {
+        let key_shares = authority.initialize_key_pair_storage(KEY_SHARES, KEY_SHARES).await.context(
+            "failed to initialize the key pair storage. set `authority.disarm_key_shares` if it is already initialized",
+        )?;
+        let path = key_shares_path(config);
+        write_key_shares(&path, &key_shares)?;
+        info!("key pair storage is initialized. key shares are saved to {}", path.display());
+    }
+
+    match &config.backbone.workspace {
+        WorkspaceConfig::Static { name, .. } => {
+            backbone
+                .register_authority_if_absent(name, &config.authority.authority.name, &config.authority_host)
+                .await?;
+            info!("authority({}) is registered to workspace({})", config.authority.authority.name, name);
+        }
+        WorkspaceConfig::Dynamic => info!("authority registration is skipped for dynamic workspaces"),
+    }
+    Ok(())
+}
+
+fn write_key_shares(path: &Path, key_shares: &[String]) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    let mut file = options.open(path).with_context(|| format!("failed to write {}", path.display()))?;
+    std::io::Write::write_all(&mut file, key_shares.join("\n").as_bytes())?;
+    Ok(())
+}
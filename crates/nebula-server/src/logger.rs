@@ -0,0 +1,28 @@
+use nebula_telemetry::trace::TracingConfig;
+use tracing_subscriber::{filter::LevelFilter, layer::SubscriberExt, util::SubscriberInitExt};
+
+#[derive(Default)]
+pub(super) struct LoggerConfig {
+    pub format: LoggerFormat,
+    pub tracing: Option<TracingConfig>,
+}
+
+#[derive(Default)]
+pub(super) enum LoggerFormat {
+    #[default]
+    Json,
+}
+
+pub(super) fn init_logger(config: LoggerConfig) -> anyhow::Result<()> {
+    let fmt_layer = match config.format {
+        LoggerFormat::Json => tracing_subscriber::fmt::layer().json(),
+    };
+    let otel_layer = config
+        .tracing
+        .as_ref()
+        .map(|tracing| nebula_telemetry::trace::layer(env!("CARGO_PKG_NAME"), tracing))
+        .transpose()?;
+
+    tracing_subscriber::registry().with(LevelFilter::INFO).with(fmt_layer).with(otel_layer).try_init()?;
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use std::path::PathBuf;
+
+use axum::Router;
+use clap::Parser;
+use tokio::sync::watch;
+use tracing::info;
+
+use crate::logger::LoggerConfig;
+
+mod config;
+mod dev;
+mod logger;
+
+#[derive(Parser, Debug)]
+#[command(version, about)]
+struct Args {
+    /// Sets a custom config file
+    #[arg(short, long, value_name = "FILE")]
+    config: Option<PathBuf>,
+    /// Serves all services on a single port under `/backbone`, `/authority` and `/authorization`
+    #[arg(short, long, value_name = "PORT")]
+    port: Option<u16>,
+    /// Initializes and disarms the authority and registers it to the static workspace automatically
+    #[arg(long)]
+    dev: bool,
+    /// Prints the resolved configuration with secrets redacted, then exits
+    #[arg(long)]
+    dump_config: bool,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    if args.dump_config {
+        println!("{}", config::dump_config(args)?);
+        return Ok(());
+    }
+
+    let mut app_config = config::load_config(args)?;
+    logger::init_logger(LoggerConfig { tracing: app_config.tracing.clone(), ..Default::default() })?;
+    nebula_telemetry::metrics::init()?;
+    if let Some(metrics_port) = app_config.metrics_port {
+        nebula_telemetry::metrics::spawn_server(metrics_port).await?;
+    }
+
+    if app_config.dev {
+        dev::load_key_shares(&mut app_config)?;
+    }
+    let backbone = nebula_backbone::Server::init(&app_config.backbone).await?;
+    let authorization = nebula_authorization::Server::init(&app_config.authorization).await?;
+    let authority = nebula_authority::Server::init(&app_config.authority).await?;
+    if app_config.dev {
+        dev::bootstrap(&app_config, &backbone, &authority).await?;
+    }
+
+    if let Some(port) = app_config.port {
+        info!("starting nebula server on {}", port);
+        let app = Router::new()
+            .nest("/backbone", backbone.router())
+            .nest("/authority", authority.router())
+            .nest("/authorization", authorization.router());
+        nebula_tls::serve(port, app, app_config.tls.as_ref(), nebula_tls::shutdown_signal()).await?;
+    } else {
+        let (shutdown_sender, shutdown_receiver) = watch::channel(());
+        tokio::spawn(async move {
+            nebula_tls::shutdown_signal().await;
+            let _ = shutdown_sender.send(());
+        });
+        let shutdown = || {
+            let mut shutdown_receiver = shutdown_receiver.clone();
+            async move {
+                let _ = shutdown_receiver.changed().await;
+            }
+        };
+        tokio::try_join!(backbone.serve(shutdown()), authority.serve(shutdown()), authorization.serve(shutdown()))?;
+    }
+
+    backbone.close().await;
+    authority.close().await;
+    authorization.close().await;
+    info!("nebula server is stopped");
+    nebula_telemetry::trace::shutdown();
+    Ok(())
+}
@@ -46,6 +46,17 @@ impl<'a> Storage for FileStorage<'a> {
         }
     }
 
+    async fn get_many(
+        &self,
+        keys: &[&Self::Key],
+    ) -> Result<Vec<Option<<Self::Value as ToOwned>::Owned>>, Self::StorageError> {
+        let mut values = Vec::with_capacity(keys.len());
+        for key in keys {
+            values.push(self.get(key).await?);
+        }
+        Ok(values)
+    }
+
     async fn set(&self, key: &Self::Key, value: &Self::Value) -> Result<(), Self::StorageError> {
         validate_key(key)?;
         let path = self.path.clone().into_owned().join(key.trim_start_matches('/'));
@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use sqlx::{Executor, Row};
 use thiserror::Error;
@@ -26,6 +26,29 @@ impl PostgresStorage {
 
         Ok(PostgresStorage { pool, table_name })
     }
+
+    /// Returns the entries whose key starts with `prefix` from the greatest key, up to `limit`. Only the keys less than
+    /// `before` are returned if it is given, so that the last key pages to the next entries.
+    pub async fn page(
+        &self,
+        prefix: &str,
+        before: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<(String, Vec<u8>)>, PostgresStorageError> {
+        let table_name = &self.table_name;
+        let entries = sqlx::query_as(&format!(
+            r#"SELECT key, value FROM {table_name}
+               WHERE key LIKE $1 ESCAPE '\' AND ($2::TEXT IS NULL OR key < $2)
+               ORDER BY key DESC LIMIT $3"#
+        ))
+        .bind(format!("{}%", escape_like(prefix)))
+        .bind(before)
+        .bind(i64::try_from(limit).unwrap_or(i64::MAX))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entries)
+    }
 }
 
 #[derive(Error, Debug)]
@@ -54,6 +77,23 @@ impl Storage for PostgresStorage {
         }
     }
 
+    async fn get_many(
+        &self,
+        keys: &[&Self::Key],
+    ) -> Result<Vec<Option<<Self::Value as ToOwned>::Owned>>, Self::StorageError> {
+        let table_name = &self.table_name;
+
+        let mut values: HashMap<String, Vec<u8>> =
+            sqlx::query_as(&format!("SELECT key, value FROM {table_name} WHERE key = ANY($1)"))
+                .bind(keys)
+                .fetch_all(&self.pool)
+                .await?
+                .into_iter()
+                .collect();
+
+        Ok(keys.iter().map(|key| values.remove(*key)).collect())
+    }
+
     async fn set(&self, key: &Self::Key, value: &Self::Value) -> Result<(), Self::StorageError> {
         let table_name = &self.table_name;
 
@@ -80,28 +120,63 @@ impl Storage for PostgresStorage {
         prefix: &Self::Key,
     ) -> Result<impl IntoIterator<Item = <Self::Key as ToOwned>::Owned>, Self::StorageError> {
         let table_name = &self.table_name;
-        let full_paths = sqlx::query(&format!(r#"SELECT key as full_path FROM {table_name} WHERE key LIKE $1"#))
-            .bind(format!("{}%", prefix))
-            .fetch_all(&self.pool)
-            .await?
-            .into_iter()
-            .map(|row| row.get("key"))
-            .fold(HashSet::new(), |mut set, path: String| {
-                let trimmed_path = path.trim_start_matches(prefix);
-                let index = trimmed_path.find('/');
-                if let Some(index) = index {
-                    set.insert(trimmed_path[..index + 1].to_string());
-                } else {
-                    set.insert(trimmed_path.to_string());
-                }
-
-                set
-            });
-
-        Ok(full_paths)
+        let full_paths: Vec<String> =
+            sqlx::query_scalar(&format!(r#"SELECT key FROM {table_name} WHERE key LIKE $1 ESCAPE '\'"#))
+                .bind(format!("{}%", escape_like(prefix)))
+                .fetch_all(&self.pool)
+                .await?;
+
+        Ok(child_names(prefix, full_paths))
     }
 }
 
+/// Escapes the wildcards of `LIKE`, so that a key matches only the literal prefix.
+fn escape_like(prefix: &str) -> String {
+    prefix.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
+
+/// Names of the direct children of `prefix` among the full paths, where a child having children ends with `/`.
+fn child_names(prefix: &str, full_paths: impl IntoIterator<Item = String>) -> HashSet<String> {
+    full_paths.into_iter().fold(HashSet::new(), |mut set, path| {
+        let trimmed_path = path.strip_prefix(prefix).unwrap_or(&path);
+        match trimmed_path.find('/') {
+            Some(index) => set.insert(trimmed_path[..index + 1].to_string()),
+            None => set.insert(trimmed_path.to_string()),
+        };
+        set
+    })
+}
+
 fn quote_ident(ident: &str) -> String {
     format!(r#""{}""#, ident.replace('"', r#""""#).replace(';', ""))
 }
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use super::{child_names, escape_like};
+
+    #[test]
+    fn when_listing_full_paths_then_only_direct_children_of_prefix_are_returned() {
+        let full_paths = ["/a/b", "/a/c/d", "/a/c/e", "/a/f/g/h"].map(str::to_owned);
+
+        let names = child_names("/a/", full_paths);
+
+        assert_eq!(names, HashSet::from(["b", "c/", "f/"].map(str::to_owned)));
+    }
+
+    #[test]
+    fn when_prefix_repeats_then_only_leading_prefix_is_trimmed() {
+        let names = child_names("/a/", ["/a//a/b".to_owned()]);
+
+        assert_eq!(names, HashSet::from(["/".to_owned()]));
+    }
+
+    #[test]
+    fn when_prefix_has_like_wildcards_then_they_are_escaped() {
+        assert_eq!(escape_like("/work_space%/"), r"/work\_space\%/");
+        assert_eq!(escape_like(r"/a\b/"), r"/a\\b/");
+        assert_eq!(escape_like("/plain/"), "/plain/");
+    }
+}
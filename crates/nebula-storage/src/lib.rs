@@ -9,6 +9,11 @@ pub trait LocalStorage {
     type Value: ToOwned + ?Sized;
     type StorageError: std::error::Error;
     async fn get(&self, key: &Self::Key) -> Result<Option<<Self::Value as ToOwned>::Owned>, Self::StorageError>;
+    /// Returns the values of the keys in the order of the keys, reading them at once where the backend allows it.
+    async fn get_many(
+        &self,
+        keys: &[&Self::Key],
+    ) -> Result<Vec<Option<<Self::Value as ToOwned>::Owned>>, Self::StorageError>;
     async fn set(&self, key: &Self::Key, value: &Self::Value) -> Result<(), Self::StorageError>;
     async fn delete(&self, key: &Self::Key) -> Result<(), Self::StorageError>;
     async fn list(
@@ -8,7 +8,7 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tokio::sync::RwLock;
 #[cfg(feature = "zeroize")]
-use zeroize::ZeroizeOnDrop;
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
 const AES_GCM_VERSION: u8 = 1;
 const AES_BLOCK_SIZE: usize = 32;
@@ -20,14 +20,14 @@ pub struct AESShieldStorage<S: Storage> {
 }
 
 #[derive(Serialize, Deserialize)]
-#[cfg_attr(feature = "zeroize", derive(ZeroizeOnDrop))]
+#[cfg_attr(feature = "zeroize", derive(Zeroize, ZeroizeOnDrop))]
 pub struct AESShieldKey {
     version: u8,
     key: ShieldKey,
 }
 
 #[derive(Serialize, Deserialize)]
-#[cfg_attr(feature = "zeroize", derive(ZeroizeOnDrop))]
+#[cfg_attr(feature = "zeroize", derive(Zeroize, ZeroizeOnDrop))]
 pub struct ShieldKey(Vec<u8>);
 
 impl ShieldKey {
@@ -117,6 +117,12 @@ impl<S: Storage> AESShieldStorage<S> {
     pub fn new(inner: S) -> Self {
         Self { inner, shield_key: RwLock::new(None) }
     }
+
+    /// Returns the storage which the shield encrypts into. Values set through it are not encrypted, and can be read
+    /// while the shield is armored.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
 }
 
 impl<S: Storage<Key = str, Value = [u8]> + Sync> Shield for AESShieldStorage<S> {
@@ -153,9 +159,12 @@ impl<S: Storage<Key = str, Value = [u8]> + Sync> Shield for AESShieldStorage<S>
     }
 
     async fn armor(&self) -> Result<(), Self::ShieldError> {
-        let mut shield_key = self.shield_key.write().await;
-        let shield_key = shield_key.deref_mut();
-        *shield_key = None;
+        #[allow(unused_mut)]
+        if let Some(mut shield_key) = self.shield_key.write().await.take() {
+            // We manually zeroize `shield_key` rather than relying on it being dropped.
+            #[cfg(feature = "zeroize")]
+            shield_key.zeroize();
+        }
         Ok(())
     }
 
@@ -190,7 +199,7 @@ impl<S: Storage<Key = str, Value = [u8]> + Sync> Shield for AESShieldStorage<S>
 }
 
 impl<S: Storage> AESShieldStorage<S> {
-    async fn is_armored(&self) -> bool {
+    pub async fn is_armored(&self) -> bool {
         self.shield_key.read().await.is_none()
     }
 
@@ -242,6 +251,23 @@ impl<S: Storage<Key = str, Value = [u8]> + Sync> Storage for AESShieldStorage<S>
         }
     }
 
+    async fn get_many(&self, keys: &[&str]) -> Result<Vec<Option<Vec<u8>>>, Self::StorageError> {
+        if self.is_armored().await {
+            return Err(AESShieldStorageError::ShieldArmored);
+        }
+        let shield_key = self.shield_key.read().await;
+        let shield_key = shield_key.as_ref().unwrap(); // Since we've already validated this earlier with is_armored(), it's safe to use unwrap() here.
+        let ciphertexts =
+            self.inner.get_many(keys).await.map_err(|e| AESShieldStorageError::StorageError(e.to_string()))?;
+
+        ciphertexts
+            .into_iter()
+            .map(|ciphertext| {
+                Ok(ciphertext.map(|ciphertext| self.decrypt(&shield_key.key, &ciphertext)).transpose()?)
+            })
+            .collect()
+    }
+
     async fn set(&self, key: &str, value: &[u8]) -> Result<(), Self::StorageError> {
         if self.is_armored().await {
             return Err(AESShieldStorageError::ShieldArmored);
@@ -0,0 +1,102 @@
+use std::collections::BTreeMap;
+
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CheckStatus {
+    Up,
+    Down,
+}
+
+impl From<bool> for CheckStatus {
+    fn from(value: bool) -> Self {
+        if value {
+            Self::Up
+        } else {
+            Self::Down
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ReadinessStatus {
+    Ready,
+    NotReady,
+}
+
+/// Result of the readiness checks of a server, which responds with `503 Service Unavailable` if any check is down.
+#[derive(Serialize, Default, Debug)]
+pub struct Readiness {
+    checks: BTreeMap<&'static str, CheckStatus>,
+}
+
+impl Readiness {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn check(mut self, name: &'static str, is_up: bool) -> Self {
+        self.checks.insert(name, is_up.into());
+        self
+    }
+
+    pub fn status(&self) -> ReadinessStatus {
+        if self.checks.values().all(|status| *status == CheckStatus::Up) {
+            ReadinessStatus::Ready
+        } else {
+            ReadinessStatus::NotReady
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ReadinessResponse<'a> {
+    status: ReadinessStatus,
+    checks: &'a BTreeMap<&'static str, CheckStatus>,
+}
+
+impl IntoResponse for Readiness {
+    fn into_response(self) -> Response {
+        let status = self.status();
+        let status_code = match status {
+            ReadinessStatus::Ready => StatusCode::OK,
+            ReadinessStatus::NotReady => StatusCode::SERVICE_UNAVAILABLE,
+        };
+        (status_code, Json(ReadinessResponse { status, checks: &self.checks })).into_response()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use axum::{http::StatusCode, response::IntoResponse};
+
+    use super::{Readiness, ReadinessStatus};
+
+    #[test]
+    fn when_every_check_is_up_then_readiness_is_ready_with_ok() {
+        let readiness = Readiness::new().check("database", true).check("jwks", true);
+
+        assert_eq!(readiness.status(), ReadinessStatus::Ready);
+        assert_eq!(readiness.into_response().status(), StatusCode::OK);
+    }
+
+    #[test]
+    fn when_any_check_is_down_then_readiness_is_not_ready_with_service_unavailable() {
+        let readiness = Readiness::new().check("database", true).check("jwks", false);
+
+        assert_eq!(readiness.status(), ReadinessStatus::NotReady);
+        assert_eq!(readiness.into_response().status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[test]
+    fn when_nothing_is_checked_then_readiness_is_ready() {
+        assert_eq!(Readiness::new().status(), ReadinessStatus::Ready);
+    }
+}
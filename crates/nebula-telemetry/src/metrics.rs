@@ -0,0 +1,179 @@
+use std::{
+    net::SocketAddr,
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
+
+use axum::{
+    extract::{MatchedPath, Request},
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection};
+use tokio::time::Instant;
+use tracing::{error, info};
+
+const HTTP_REQUESTS_TOTAL: &str = "http_requests_total";
+const HTTP_REQUEST_DURATION_SECONDS: &str = "http_request_duration_seconds";
+const HTTP_REQUEST_DURATION_BUCKETS: &[f64] = &[0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+const UPKEEP_INTERVAL: Duration = Duration::from_secs(5);
+const DATABASE_POOL_RECORD_INTERVAL: Duration = Duration::from_secs(5);
+
+static PROMETHEUS_HANDLE: OnceLock<PrometheusHandle> = OnceLock::new();
+
+/// Installs the global Prometheus recorder.
+/// Metrics recorded before this is called are discarded, so it should be called at the start of `main`.
+pub fn init() -> anyhow::Result<()> {
+    let handle = PrometheusBuilder::new()
+        .set_buckets_for_metric(Matcher::Full(HTTP_REQUEST_DURATION_SECONDS.to_owned()), HTTP_REQUEST_DURATION_BUCKETS)?
+        .install_recorder()?;
+
+    let upkeep_handle = handle.clone();
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(UPKEEP_INTERVAL).await;
+            upkeep_handle.run_upkeep();
+        }
+    });
+
+    PROMETHEUS_HANDLE.set(handle).map_err(|_| anyhow::anyhow!("metrics recorder is already initialized"))?;
+    Ok(())
+}
+
+/// Router serving `GET /metrics` in the Prometheus text format.
+pub fn router() -> Router {
+    Router::new().route("/metrics", get(handle_get_metrics))
+}
+
+/// Serves [`router`] on its own port in the background and returns the bound address. The metrics carry per-workspace
+/// labels, so they are kept off the API ports and exposed only where the metrics port is reachable.
+pub async fn spawn_server(port: u16) -> anyhow::Result<SocketAddr> {
+    let listener = tokio::net::TcpListener::bind(("0.0.0.0", port)).await?;
+    let address = listener.local_addr()?;
+    info!("serving metrics on {address}");
+    tokio::spawn(async move {
+        if let Err(e) = axum::serve(listener, router()).await {
+            error!(error = %e, "metrics server failed.");
+        }
+    });
+    Ok(address)
+}
+
+async fn handle_get_metrics() -> Response {
+    match PROMETHEUS_HANDLE.get() {
+        Some(handle) => handle.render().into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+/// Middleware recording the request count and latency per route, method and status.
+pub async fn track_http_requests(request: Request, next: Next) -> Response {
+    let started_at = Instant::now();
+    let method = request.method().to_string();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|matched_path| matched_path.as_str().to_owned())
+        .unwrap_or_else(|| "unmatched".to_owned());
+
+    let response = next.run(request).await;
+
+    let labels = [("method", method), ("route", route), ("status", response.status().as_u16().to_string())];
+    metrics::counter!(HTTP_REQUESTS_TOTAL, &labels).increment(1);
+    metrics::histogram!(HTTP_REQUEST_DURATION_SECONDS, &labels).record(started_at.elapsed().as_secs_f64());
+
+    response
+}
+
+/// Records the utilisation of a database connection pool.
+pub fn record_database_pool(size: u32, idle: usize) {
+    let idle = idle as f64;
+    metrics::gauge!("db_pool_connections", "state" => "idle").set(idle);
+    metrics::gauge!("db_pool_connections", "state" => "active").set((size as f64 - idle).max(0.0));
+}
+
+/// Records the utilisation of the connection pool of the database every few seconds in the background.
+pub fn record_database_pool_periodically(database: Arc<DatabaseConnection>) {
+    tokio::spawn(async move {
+        loop {
+            let (size, num_idle) = match database.get_database_backend() {
+                DatabaseBackend::Sqlite => {
+                    let pool = database.get_sqlite_connection_pool();
+                    (pool.size(), pool.num_idle())
+                }
+                _ => {
+                    let pool = database.get_postgres_connection_pool();
+                    (pool.size(), pool.num_idle())
+                }
+            };
+            record_database_pool(size, num_idle);
+            tokio::time::sleep(DATABASE_POOL_RECORD_INTERVAL).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        sync::{Arc, Once},
+        time::Duration,
+    };
+
+    use axum::{body::Body, extract::Request, middleware, routing::get, Router};
+    use tower::ServiceExt;
+
+    use super::{init, record_database_pool_periodically, spawn_server, track_http_requests, PROMETHEUS_HANDLE};
+
+    static INIT: Once = Once::new();
+
+    /// Installs the global recorder once for the tests sharing it, and renders the metrics recorded so far.
+    async fn rendered_metrics() -> String {
+        INIT.call_once(|| init().unwrap());
+        PROMETHEUS_HANDLE.get().unwrap().render()
+    }
+
+    #[tokio::test]
+    async fn when_request_is_tracked_then_matched_route_is_recorded_instead_of_path() {
+        rendered_metrics().await;
+        let app = Router::new()
+            .route("/tracked/:workspace_name", get(|| async { "" }))
+            .layer(middleware::from_fn(track_http_requests));
+
+        app.oneshot(Request::get("/tracked/finance").body(Body::empty()).unwrap()).await.unwrap();
+
+        let metrics = rendered_metrics().await;
+        assert!(
+            metrics.contains(r#"http_requests_total{method="GET",route="/tracked/:workspace_name",status="200"} 1"#)
+        );
+        assert!(!metrics.contains("/tracked/finance"));
+    }
+
+    #[tokio::test]
+    async fn when_metrics_server_is_spawned_then_metrics_are_served_on_its_own_port() {
+        rendered_metrics().await;
+        metrics::counter!("metrics_server_test_total").increment(1);
+
+        let address = spawn_server(0).await.unwrap();
+        let response = reqwest::get(format!("http://127.0.0.1:{}/metrics", address.port())).await.unwrap();
+
+        assert!(response.status().is_success());
+        assert!(response.text().await.unwrap().contains("metrics_server_test_total 1"));
+    }
+
+    #[tokio::test]
+    async fn when_database_pool_is_recorded_periodically_then_pool_connections_are_reported() {
+        rendered_metrics().await;
+        let database = sea_orm::Database::connect("sqlite::memory:").await.unwrap();
+
+        record_database_pool_periodically(Arc::new(database));
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let metrics = rendered_metrics().await;
+        assert!(metrics.contains(r#"db_pool_connections{state="idle"}"#));
+        assert!(metrics.contains(r#"db_pool_connections{state="active"}"#));
+    }
+}
@@ -0,0 +1,92 @@
+use axum::{
+    extract::{MatchedPath, Request},
+    http::HeaderMap,
+    middleware::Next,
+    response::Response,
+};
+use opentelemetry::{global, trace::TracerProvider as _, KeyValue};
+use opentelemetry_http::{HeaderExtractor, HeaderInjector};
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{
+    propagation::TraceContextPropagator,
+    runtime,
+    trace::{Sampler, TracerProvider},
+    Resource,
+};
+use serde::Deserialize;
+use tracing::{info_span, Instrument, Subscriber};
+use tracing_opentelemetry::{OpenTelemetryLayer, OpenTelemetrySpanExt};
+use tracing_subscriber::registry::LookupSpan;
+use url::Url;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TracingConfig {
+    /// OTLP/HTTP endpoint of the collector. (e.g. `http://localhost:4318/v1/traces`)
+    pub otlp_endpoint: Url,
+    pub service_name: Option<String>,
+    /// Ratio of the root traces to be sampled, between `0.0` and `1.0`.
+    pub sample_ratio: Option<f64>,
+}
+
+/// Builds a tracing layer exporting spans to the OTLP collector and registers the W3C trace context propagator.
+pub fn layer<S>(
+    default_service_name: &str,
+    config: &TracingConfig,
+) -> anyhow::Result<OpenTelemetryLayer<S, opentelemetry_sdk::trace::Tracer>>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    let exporter =
+        opentelemetry_otlp::SpanExporter::builder().with_http().with_endpoint(config.otlp_endpoint.as_str()).build()?;
+
+    let service_name = config.service_name.clone().unwrap_or_else(|| default_service_name.to_owned());
+    let provider = TracerProvider::builder()
+        .with_batch_exporter(exporter, runtime::Tokio)
+        .with_sampler(Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(config.sample_ratio.unwrap_or(1.0)))))
+        .with_resource(Resource::new([KeyValue::new("service.name", service_name.clone())]))
+        .build();
+    let tracer = provider.tracer(service_name);
+
+    global::set_text_map_propagator(TraceContextPropagator::new());
+    global::set_tracer_provider(provider);
+
+    Ok(tracing_opentelemetry::layer().with_tracer(tracer))
+}
+
+/// Flushes the pending spans. It should be called before the process exits.
+pub fn shutdown() {
+    global::shutdown_tracer_provider();
+}
+
+/// Middleware wrapping each request in a span continued from the incoming `traceparent` header.
+pub async fn propagate_trace_context(request: Request, next: Next) -> Response {
+    let parent_context =
+        global::get_text_map_propagator(|propagator| propagator.extract(&HeaderExtractor(request.headers())));
+    let route = request.extensions().get::<MatchedPath>().map(|matched_path| matched_path.as_str().to_owned());
+
+    let span = info_span!(
+        "http.request",
+        http.request.method = %request.method(),
+        http.route = route.as_deref().unwrap_or_default(),
+        http.response.status_code = tracing::field::Empty,
+    );
+    span.set_parent(parent_context);
+
+    let response = next.run(request).instrument(span.clone()).await;
+    span.record("http.response.status_code", response.status().as_u16());
+
+    response
+}
+
+/// Injects the context of the current span into the headers of an outgoing request.
+pub fn inject_trace_context(headers: &mut HeaderMap) {
+    let context = tracing::Span::current().context();
+    global::get_text_map_propagator(|propagator| propagator.inject_context(&context, &mut HeaderInjector(headers)));
+}
+
+/// Builds an outgoing request with the context of the current span injected.
+pub fn traced(request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+    let mut headers = HeaderMap::new();
+    inject_trace_context(&mut headers);
+    request.headers(headers)
+}
@@ -45,4 +45,16 @@ pub enum AuthError {
     #[error("The token has expired")]
     #[status(StatusCode::UNAUTHORIZED)]
     ExpiredJwt,
+
+    #[error("The audience of the token is not accepted")]
+    #[status(StatusCode::UNAUTHORIZED)]
+    InvalidAudience,
+
+    #[error("The issuer of the token is not trusted")]
+    #[status(StatusCode::UNAUTHORIZED)]
+    UntrustedIssuer,
+
+    #[error("The issuer of the token is not trusted for the workspace")]
+    #[status(StatusCode::FORBIDDEN)]
+    WorkspaceNotAllowed,
 }
@@ -0,0 +1,120 @@
+use std::{collections::HashSet, sync::Arc};
+
+use bon::Builder;
+
+use crate::{claim::ClaimMapping, jwk::jwk_set::JWK_SET_DEFAULT_KEY_ID, jwt::Jwt};
+
+use super::{error::AuthError, jwks_discovery::JwksDiscovery};
+
+/// Issuer whose tokens are accepted, verified with its own JWKS.
+#[derive(Builder, Clone)]
+pub struct TrustedIssuer {
+    /// `iss` claim of the tokens. Tokens of any issuer are accepted if not given, which is tried only after the issuers
+    /// matching the claim exactly.
+    pub issuer: Option<String>,
+    /// Audience which the `aud` claim of the tokens must contain. The audience isn't checked if not given.
+    pub audience: Option<String>,
+    pub jwks_discovery: Arc<dyn JwksDiscovery + Send + Sync>,
+    /// Workspaces the tokens are accepted for. Every workspace is allowed if not given.
+    pub workspaces: Option<HashSet<String>>,
+    #[builder(default)]
+    pub claim_mapping: ClaimMapping,
+}
+
+impl TrustedIssuer {
+    pub fn is_issuer_of(&self, jwt: &Jwt) -> bool {
+        self.issuer.as_deref().is_none_or(|issuer| jwt.payload().issuer() == Some(issuer))
+    }
+
+    /// Whether tokens of any issuer are accepted.
+    pub fn is_wildcard(&self) -> bool {
+        self.issuer.is_none()
+    }
+
+    pub fn is_audience_of(&self, jwt: &Jwt) -> bool {
+        self.audience
+            .as_deref()
+            .is_none_or(|audience| jwt.payload().audience().is_some_and(|audiences| audiences.contains(&audience)))
+    }
+
+    pub fn is_workspace_allowed(&self, workspace_name: &str) -> bool {
+        self.workspaces.as_ref().is_none_or(|workspaces| workspaces.contains(workspace_name))
+    }
+
+    pub async fn verify(&self, jwt: &Jwt) -> Result<(), AuthError> {
+        let jwks = self.jwks_discovery.jwks().await?;
+        let jwk = jwks.get(jwt.kid().unwrap_or(JWK_SET_DEFAULT_KEY_ID)).ok_or(AuthError::NoJwk)?;
+        jwt.verify(jwk).map_err(AuthError::VerifyJwt)?;
+        if !self.is_audience_of(jwt) {
+            return Err(AuthError::InvalidAudience);
+        }
+        match jwt.is_expired() {
+            true => Err(AuthError::ExpiredJwt),
+            false => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use josekit::{jws::JwsHeader, jwt::JwtPayload};
+
+    use crate::{
+        auth::jwks_discovery::StaticJwksDiscovery,
+        jwk::jwk_set::{JwkSet, JWK_SET_DEFAULT_KEY_ID},
+        jwt::Jwt,
+    };
+
+    use super::TrustedIssuer;
+
+    fn jwt(issuer: Option<&str>) -> Jwt {
+        let jwks = JwkSet::default();
+        let mut header = JwsHeader::new();
+        header.set_algorithm("ES256");
+        header.set_key_id(JWK_SET_DEFAULT_KEY_ID);
+        let mut payload = JwtPayload::new();
+        if let Some(issuer) = issuer {
+            payload.set_issuer(issuer);
+        }
+        payload.set_audience(vec!["nebula"]);
+
+        Jwt::new(header, payload, jwks.get(JWK_SET_DEFAULT_KEY_ID).unwrap()).unwrap()
+    }
+
+    fn trusted_issuer(issuer: Option<&str>, audience: Option<&str>) -> TrustedIssuer {
+        TrustedIssuer::builder()
+            .maybe_issuer(issuer.map(str::to_owned))
+            .maybe_audience(audience.map(str::to_owned))
+            .jwks_discovery(Arc::new(StaticJwksDiscovery::new(JwkSet::default())))
+            .build()
+    }
+
+    #[test]
+    fn when_issuer_is_same_as_iss_then_trusted_issuer_is_issuer_of_jwt() {
+        let trusted_issuer = trusted_issuer(Some("https://idp.example.com/"), None);
+
+        assert!(trusted_issuer.is_issuer_of(&jwt(Some("https://idp.example.com/"))));
+        assert!(!trusted_issuer.is_issuer_of(&jwt(Some("https://idp.example.com"))));
+        assert!(!trusted_issuer.is_issuer_of(&jwt(None)));
+    }
+
+    #[test]
+    fn when_issuer_is_not_given_then_trusted_issuer_is_issuer_of_any_jwt() {
+        let trusted_issuer = trusted_issuer(None, None);
+
+        assert!(trusted_issuer.is_wildcard());
+        assert!(trusted_issuer.is_issuer_of(&jwt(Some("https://idp.example.com/"))));
+        assert!(trusted_issuer.is_issuer_of(&jwt(None)));
+    }
+
+    #[test]
+    fn when_audience_is_given_then_trusted_issuer_accepts_only_jwt_for_the_audience() {
+        let jwt = jwt(None);
+
+        assert!(trusted_issuer(None, Some("nebula")).is_audience_of(&jwt));
+        assert!(!trusted_issuer(None, Some("other")).is_audience_of(&jwt));
+        assert!(trusted_issuer(None, None).is_audience_of(&jwt));
+    }
+}
@@ -26,6 +26,8 @@ impl JwksDiscovery for StaticJwksDiscovery {
     }
 }
 
+const JWKS_REFRESH_FAILURES_TOTAL: &str = "jwks_refresh_failures_total";
+
 pub struct CachedRemoteJwksDiscovery {
     jwks: Arc<RwLock<Option<JwkSet>>>,
     client: reqwest::Client,
@@ -49,10 +51,17 @@ impl CachedRemoteJwksDiscovery {
             is_initialized: Mutex::new(false),
         }
     }
+
+    /// Replaces the HTTP client fetching the JWKS, e.g. with one configured for a private CA or mutual TLS.
+    pub fn client(mut self, client: reqwest::Client) -> Self {
+        self.client = client;
+        self
+    }
 }
 
+#[tracing::instrument(name = "jwks.fetch", skip_all)]
 pub async fn fetch_jwks(client: &reqwest::Client, jwks_url: impl IntoUrl) -> Result<JwkSet, super::error::AuthError> {
-    let response = client.get(jwks_url).send().await?;
+    let response = nebula_telemetry::trace::traced(client.get(jwks_url)).send().await?;
     let jwks = response.json::<JwkSet>().await?;
     Ok(jwks)
 }
@@ -69,7 +78,9 @@ impl JwksDiscovery for CachedRemoteJwksDiscovery {
             let mut is_initialized = self.is_initialized.lock().await;
             if !*is_initialized {
                 *is_initialized = true;
-                let jwks = fetch_jwks(&self.client, self.jwks_url.clone()).await?;
+                let jwks = fetch_jwks(&self.client, self.jwks_url.clone()).await.inspect_err(|_| {
+                    metrics::counter!(JWKS_REFRESH_FAILURES_TOTAL).increment(1);
+                })?;
                 *self.jwks.write().await = Some(jwks);
                 *self.expiration.write().await = std::time::Instant::now() + self.refresh_interval;
             }
@@ -82,9 +93,12 @@ impl JwksDiscovery for CachedRemoteJwksDiscovery {
                 let expiration_write = self.expiration.clone();
                 let refresh_interval = self.refresh_interval;
                 tokio::spawn(async move {
-                    if let Ok(jwks) = fetch_jwks(&client, jwks_url).await {
-                        *jwks_write.write().await = Some(jwks);
-                        *expiration_write.write().await = std::time::Instant::now() + refresh_interval;
+                    match fetch_jwks(&client, jwks_url).await {
+                        Ok(jwks) => {
+                            *jwks_write.write().await = Some(jwks);
+                            *expiration_write.write().await = std::time::Instant::now() + refresh_interval;
+                        }
+                        Err(_) => metrics::counter!(JWKS_REFRESH_FAILURES_TOTAL).increment(1),
                     }
                 });
             }
@@ -3,33 +3,65 @@ use std::sync::Arc;
 use bon::Builder;
 use tower::Layer;
 
-use crate::{jwk::jwk_set::JWK_SET_DEFAULT_KEY_ID, jwt::Jwt};
+use crate::{
+    claim::{NebulaClaim, AUTHORIZATION_SERVER_ISSUER},
+    jwt::Jwt,
+};
 
 use super::{
     error::AuthError,
     extractor::{AuthHeaderTokenExtractor, TokenExtractor},
+    issuer::TrustedIssuer,
     jwks_discovery::JwksDiscovery,
     service::NebulaAuthService,
 };
 
 #[derive(Builder, Clone)]
 pub struct NebulaAuthLayer {
-    pub jwk_discovery: Arc<dyn JwksDiscovery + Send + Sync>,
+    /// JWKS of the authorization server, which is trusted for its own tokens, and for tokens of any issuer not in
+    /// `issuers` unless one of them accepts any issuer.
+    pub jwk_discovery: Option<Arc<dyn JwksDiscovery + Send + Sync>>,
+
+    /// Issuers trusted in addition to the authorization server. An issuer matching the `iss` claim exactly takes
+    /// precedence over the one accepting any issuer, of which there should be at most one.
+    #[builder(default)]
+    pub issuers: Vec<TrustedIssuer>,
 
     #[builder(default = Arc::new(AuthHeaderTokenExtractor))]
     pub token_extractor: Arc<dyn TokenExtractor + Send + Sync>,
 }
 
 impl NebulaAuthLayer {
-    pub async fn validate_token(&self, token: &str) -> Result<Jwt, AuthError> {
-        let jwks = self.jwk_discovery.jwks().await?;
+    pub async fn validate_token(&self, token: &str) -> Result<(Jwt, NebulaClaim), AuthError> {
         let jwt = Jwt::decode_without_verification(token).map_err(AuthError::DecodeJwt)?;
-        let jwk = jwks.get(jwt.kid().unwrap_or(JWK_SET_DEFAULT_KEY_ID)).ok_or(AuthError::NoJwk)?;
-        jwt.verify(jwk).map_err(AuthError::VerifyJwt)?;
-        match jwt.is_expired() {
-            true => Err(AuthError::ExpiredJwt),
-            false => Ok(jwt),
+        let issuer = self.trusted_issuer(&jwt).ok_or(AuthError::UntrustedIssuer)?;
+        issuer.verify(&jwt).await?;
+        let claim = issuer.claim_mapping.claim(jwt.payload()).map_err(AuthError::ParseClaim)?;
+        if !issuer.is_workspace_allowed(&claim.workspace_name) {
+            return Err(AuthError::WorkspaceNotAllowed);
         }
+
+        Ok((jwt, claim))
+    }
+
+    /// The `iss` claim isn't verified yet, so a token is only ever verified with the issuer it names: an exact match
+    /// first, then the authorization server for its own tokens, and the issuer accepting any issuer last.
+    fn trusted_issuer(&self, jwt: &Jwt) -> Option<TrustedIssuer> {
+        let authorization_server = || {
+            self.jwk_discovery
+                .clone()
+                .map(|jwks_discovery| TrustedIssuer::builder().jwks_discovery(jwks_discovery).build())
+        };
+
+        if let Some(issuer) = self.issuers.iter().find(|issuer| !issuer.is_wildcard() && issuer.is_issuer_of(jwt)) {
+            return Some(issuer.clone());
+        }
+        if jwt.payload().issuer() == Some(AUTHORIZATION_SERVER_ISSUER) {
+            if let Some(issuer) = authorization_server() {
+                return Some(issuer);
+            }
+        }
+        self.issuers.iter().find(|issuer| issuer.is_wildcard()).cloned().or_else(authorization_server)
     }
 }
 
@@ -40,3 +72,137 @@ impl<S> Layer<S> for NebulaAuthLayer {
         NebulaAuthService::new(inner, self)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::{
+        sync::Arc,
+        time::{Duration, SystemTime},
+    };
+
+    use josekit::{jws::JwsHeader, jwt::JwtPayload};
+    use serde_json::json;
+
+    use crate::{
+        auth::{error::AuthError, issuer::TrustedIssuer, jwks_discovery::StaticJwksDiscovery},
+        claim::AUTHORIZATION_SERVER_ISSUER,
+        jwk::jwk_set::{JwkSet, JWK_SET_DEFAULT_KEY_ID},
+        jwt::Jwt,
+    };
+
+    use super::NebulaAuthLayer;
+
+    fn token(jwks: &JwkSet, issuer: &str, audience: &str, workspace_name: Option<&str>) -> String {
+        let mut header = JwsHeader::new();
+        header.set_algorithm("ES256");
+        header.set_key_id(JWK_SET_DEFAULT_KEY_ID);
+        let mut payload = JwtPayload::new();
+        payload.set_issuer(issuer);
+        payload.set_audience(vec![audience]);
+        payload.set_subject("alice");
+        payload.set_expires_at(&(SystemTime::now() + Duration::from_secs(60)));
+        if let Some(workspace_name) = workspace_name {
+            payload.set_claim("wmn", Some(json!(workspace_name))).unwrap();
+        }
+        payload.set_claim("attributes", Some(json!({}))).unwrap();
+        payload.set_claim("role", Some(json!("member"))).unwrap();
+
+        Jwt::new(header, payload, jwks.get(JWK_SET_DEFAULT_KEY_ID).unwrap()).unwrap().serialized_repr
+    }
+
+    fn trusted_issuer(jwks: &JwkSet, issuer: Option<&str>, audience: Option<&str>) -> TrustedIssuer {
+        TrustedIssuer::builder()
+            .maybe_issuer(issuer.map(str::to_owned))
+            .maybe_audience(audience.map(str::to_owned))
+            .jwks_discovery(Arc::new(StaticJwksDiscovery::new(jwks.clone())))
+            .build()
+    }
+
+    #[tokio::test]
+    async fn when_token_is_issued_by_trusted_issuer_then_layer_returns_claim() {
+        let jwks = JwkSet::default();
+        let layer = NebulaAuthLayer::builder()
+            .issuers(vec![trusted_issuer(&jwks, Some("https://idp.example.com/"), Some("nebula"))])
+            .build();
+
+        let (_, claim) =
+            layer.validate_token(&token(&jwks, "https://idp.example.com/", "nebula", Some("finance"))).await.unwrap();
+
+        assert_eq!(claim.gid, "alice");
+        assert_eq!(claim.workspace_name, "finance");
+    }
+
+    #[tokio::test]
+    async fn when_token_is_issued_by_other_issuer_then_layer_returns_untrusted_issuer_err() {
+        let jwks = JwkSet::default();
+        let layer = NebulaAuthLayer::builder()
+            .issuers(vec![trusted_issuer(&jwks, Some("https://idp.example.com/"), None)])
+            .build();
+
+        let result = layer.validate_token(&token(&jwks, "https://other.example.com/", "nebula", Some("finance"))).await;
+
+        assert!(matches!(result, Err(AuthError::UntrustedIssuer)));
+    }
+
+    #[tokio::test]
+    async fn when_token_is_for_other_audience_then_layer_returns_invalid_audience_err() {
+        let jwks = JwkSet::default();
+        let layer = NebulaAuthLayer::builder()
+            .issuers(vec![trusted_issuer(&jwks, Some("https://idp.example.com/"), Some("nebula"))])
+            .build();
+
+        let result = layer.validate_token(&token(&jwks, "https://idp.example.com/", "other", Some("finance"))).await;
+
+        assert!(matches!(result, Err(AuthError::InvalidAudience)));
+    }
+
+    #[tokio::test]
+    async fn when_token_misses_claim_then_layer_returns_parse_claim_err() {
+        let jwks = JwkSet::default();
+        let layer = NebulaAuthLayer::builder()
+            .issuers(vec![trusted_issuer(&jwks, Some("https://idp.example.com/"), None)])
+            .build();
+
+        let result = layer.validate_token(&token(&jwks, "https://idp.example.com/", "nebula", None)).await;
+
+        assert!(matches!(result, Err(AuthError::ParseClaim(_))));
+    }
+
+    #[tokio::test]
+    async fn when_wildcard_issuer_is_listed_first_then_layer_verifies_token_with_exact_issuer() {
+        let wildcard_jwks = JwkSet::default();
+        let jwks = JwkSet::default();
+        let layer = NebulaAuthLayer::builder()
+            .issuers(vec![
+                trusted_issuer(&wildcard_jwks, None, None),
+                trusted_issuer(&jwks, Some("https://idp.example.com/"), None),
+            ])
+            .build();
+
+        let exact = layer.validate_token(&token(&jwks, "https://idp.example.com/", "nebula", Some("finance"))).await;
+        let other =
+            layer.validate_token(&token(&wildcard_jwks, "https://other.example.com/", "nebula", Some("finance"))).await;
+
+        assert!(exact.is_ok());
+        assert!(other.is_ok());
+    }
+
+    #[tokio::test]
+    async fn when_wildcard_issuer_is_configured_then_layer_verifies_authorization_server_token_with_its_jwks() {
+        let wildcard_jwks = JwkSet::default();
+        let authorization_server_jwks = JwkSet::default();
+        let layer = NebulaAuthLayer::builder()
+            .jwk_discovery(Arc::new(StaticJwksDiscovery::new(authorization_server_jwks.clone())))
+            .issuers(vec![trusted_issuer(&wildcard_jwks, None, None)])
+            .build();
+
+        let authorization_server = layer
+            .validate_token(&token(&authorization_server_jwks, AUTHORIZATION_SERVER_ISSUER, "nebula", Some("finance")))
+            .await;
+        let forged =
+            layer.validate_token(&token(&wildcard_jwks, AUTHORIZATION_SERVER_ISSUER, "nebula", Some("finance"))).await;
+
+        assert!(authorization_server.is_ok());
+        assert!(matches!(forged, Err(AuthError::VerifyJwt(_))));
+    }
+}
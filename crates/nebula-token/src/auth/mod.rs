@@ -1,5 +1,6 @@
 pub mod error;
 pub mod extractor;
+pub mod issuer;
 pub mod jwks_discovery;
 pub mod layer;
 pub mod service;
@@ -8,6 +8,8 @@ use crate::error::JWTError;
 pub const WORKSPACE_NAME_CLAIM: &str = "wmn";
 pub const ATTRIBUTES_CLAIM: &str = "attributes";
 pub const ROLE_CLAIM: &str = "role";
+/// `iss` claim of the tokens issued by the authorization server.
+pub const AUTHORIZATION_SERVER_ISSUER: &str = "nebula-authorization";
 
 #[derive(Debug, Clone)]
 pub struct NebulaClaim {
@@ -21,19 +23,59 @@ impl TryFrom<&JwtPayload> for NebulaClaim {
     type Error = JWTError;
 
     fn try_from(payload: &JwtPayload) -> Result<Self, Self::Error> {
-        let gid = payload.subject().ok_or(JWTError::MissingClaim("sub"))?.to_string();
-        let workspace_name =
-            match payload.claim(WORKSPACE_NAME_CLAIM).ok_or(JWTError::MissingClaim(WORKSPACE_NAME_CLAIM))? {
-                Value::String(ref s) => s.clone(),
-                _ => return Err(JWTError::InvalidJwtFormat("wnm is not a string".to_string())),
-            };
-        let attributes = match payload.claim(ATTRIBUTES_CLAIM).ok_or(JWTError::MissingClaim(ATTRIBUTES_CLAIM))? {
-            Value::Object(ref map) => map.clone(),
-            _ => return Err(JWTError::InvalidJwtFormat("attributes is not a map".to_string())),
+        ClaimMapping::default().claim(payload)
+    }
+}
+
+/// Names of the claims which a [`NebulaClaim`] is read from, so that tokens of issuers other than the authorization
+/// server can be accepted. Defaults to the claims of the tokens issued by the authorization server.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct ClaimMapping {
+    pub gid: String,
+    pub workspace_name: String,
+    /// Claim holding a map of string attributes.
+    pub attributes: String,
+    pub role: String,
+    /// Role of the tokens without the role claim. Such tokens are rejected if not given.
+    pub default_role: Option<Role>,
+}
+
+impl Default for ClaimMapping {
+    fn default() -> Self {
+        Self {
+            gid: "sub".to_owned(),
+            workspace_name: WORKSPACE_NAME_CLAIM.to_owned(),
+            attributes: ATTRIBUTES_CLAIM.to_owned(),
+            role: ROLE_CLAIM.to_owned(),
+            default_role: None,
+        }
+    }
+}
+
+impl ClaimMapping {
+    pub fn claim(&self, payload: &JwtPayload) -> Result<NebulaClaim, JWTError> {
+        let gid = match payload.claim(&self.gid).ok_or_else(|| JWTError::MissingClaim(self.gid.clone()))? {
+            Value::String(ref s) => s.clone(),
+            _ => return Err(JWTError::InvalidJwtFormat(format!("{} is not a string", self.gid))),
         };
-        let role = match payload.claim(ROLE_CLAIM).ok_or(JWTError::MissingClaim(ROLE_CLAIM))? {
-            Value::String(ref s) => Role::from(s.clone()),
-            _ => return Err(JWTError::InvalidJwtFormat("role is not a string".to_string())),
+        let workspace_name = match payload
+            .claim(&self.workspace_name)
+            .ok_or_else(|| JWTError::MissingClaim(self.workspace_name.clone()))?
+        {
+            Value::String(ref s) => s.clone(),
+            _ => return Err(JWTError::InvalidJwtFormat(format!("{} is not a string", self.workspace_name))),
+        };
+        let attributes =
+            match payload.claim(&self.attributes).ok_or_else(|| JWTError::MissingClaim(self.attributes.clone()))? {
+                Value::Object(ref map) => map.clone(),
+                _ => return Err(JWTError::InvalidJwtFormat(format!("{} is not a map", self.attributes))),
+            };
+        let role = match (payload.claim(&self.role), &self.default_role) {
+            (Some(Value::String(s)), _) => Role::from(s.clone()),
+            (Some(_), _) => return Err(JWTError::InvalidJwtFormat(format!("{} is not a string", self.role))),
+            (None, Some(default_role)) => default_role.clone(),
+            (None, None) => return Err(JWTError::MissingClaim(self.role.clone())),
         };
 
         Ok(NebulaClaim {
@@ -46,18 +88,51 @@ impl TryFrom<&JwtPayload> for NebulaClaim {
                     Some((k, v))
                 })
                 .collect::<Option<_>>()
-                .ok_or(JWTError::InvalidJwtFormat("attributes is not a map of strings".to_string()))?,
+                .ok_or_else(|| JWTError::InvalidJwtFormat(format!("{} is not a map of strings", self.attributes)))?,
             role,
         })
     }
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[serde(from = "String", into = "String")]
 pub enum Role {
-    #[serde(rename = "admin")]
     Admin,
-    #[serde(rename = "member")]
     Member,
+    /// Read-only role which can see metadata and audit logs but can't change anything.
+    Auditor,
+    /// Role defined by the workspace, whose permissions are resolved by the service receiving the claim.
+    Custom(String),
+}
+
+impl Role {
+    /// Permissions granted to the built-in roles, or `None` for a custom role.
+    pub fn builtin_permissions(&self) -> Option<&'static [Permission]> {
+        match self {
+            Role::Admin => Some(Permission::ALL),
+            Role::Member => Some(&[
+                Permission::WorkspaceRead,
+                Permission::SecretRead,
+                Permission::SecretWrite,
+                Permission::PathRead,
+                Permission::PathWrite,
+                Permission::PolicyRead,
+                Permission::AuthorityRead,
+            ]),
+            Role::Auditor => Some(&[
+                Permission::WorkspaceRead,
+                Permission::PathRead,
+                Permission::PolicyRead,
+                Permission::AuthorityRead,
+                Permission::AuditRead,
+            ]),
+            Role::Custom(_) => None,
+        }
+    }
+
+    pub fn is_builtin(&self) -> bool {
+        !matches!(self, Role::Custom(_))
+    }
 }
 
 impl From<String> for Role {
@@ -65,7 +140,8 @@ impl From<String> for Role {
         match s.as_str() {
             "admin" => Role::Admin,
             "member" => Role::Member,
-            _ => Role::Member,
+            "auditor" => Role::Auditor,
+            _ => Role::Custom(s),
         }
     }
 }
@@ -75,6 +151,176 @@ impl From<Role> for String {
         match role {
             Role::Admin => "admin".to_string(),
             Role::Member => "member".to_string(),
+            Role::Auditor => "auditor".to_string(),
+            Role::Custom(name) => name,
+        }
+    }
+}
+
+impl std::fmt::Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&String::from(self.clone()))
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Permission {
+    #[serde(rename = "workspace:read")]
+    WorkspaceRead,
+    #[serde(rename = "workspace:manage")]
+    WorkspaceManage,
+    #[serde(rename = "secret:read")]
+    SecretRead,
+    #[serde(rename = "secret:write")]
+    SecretWrite,
+    #[serde(rename = "path:read")]
+    PathRead,
+    #[serde(rename = "path:write")]
+    PathWrite,
+    #[serde(rename = "policy:read")]
+    PolicyRead,
+    #[serde(rename = "policy:write")]
+    PolicyWrite,
+    #[serde(rename = "authority:read")]
+    AuthorityRead,
+    #[serde(rename = "authority:manage")]
+    AuthorityManage,
+    #[serde(rename = "parameter:manage")]
+    ParameterManage,
+    #[serde(rename = "role:manage")]
+    RoleManage,
+    #[serde(rename = "audit:read")]
+    AuditRead,
+}
+
+impl Permission {
+    pub const ALL: &'static [Permission] = &[
+        Permission::WorkspaceRead,
+        Permission::WorkspaceManage,
+        Permission::SecretRead,
+        Permission::SecretWrite,
+        Permission::PathRead,
+        Permission::PathWrite,
+        Permission::PolicyRead,
+        Permission::PolicyWrite,
+        Permission::AuthorityRead,
+        Permission::AuthorityManage,
+        Permission::ParameterManage,
+        Permission::RoleManage,
+        Permission::AuditRead,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Permission::WorkspaceRead => "workspace:read",
+            Permission::WorkspaceManage => "workspace:manage",
+            Permission::SecretRead => "secret:read",
+            Permission::SecretWrite => "secret:write",
+            Permission::PathRead => "path:read",
+            Permission::PathWrite => "path:write",
+            Permission::PolicyRead => "policy:read",
+            Permission::PolicyWrite => "policy:write",
+            Permission::AuthorityRead => "authority:read",
+            Permission::AuthorityManage => "authority:manage",
+            Permission::ParameterManage => "parameter:manage",
+            Permission::RoleManage => "role:manage",
+            Permission::AuditRead => "audit:read",
         }
     }
 }
+
+impl std::str::FromStr for Permission {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Permission::ALL
+            .iter()
+            .find(|permission| permission.as_str() == s)
+            .copied()
+            .ok_or_else(|| format!("unknown permission: {s}"))
+    }
+}
+
+impl std::fmt::Display for Permission {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use josekit::{jwt::JwtPayload, Value};
+    use serde_json::json;
+
+    use crate::error::JWTError;
+
+    use super::{ClaimMapping, Role};
+
+    fn payload(claims: Value) -> JwtPayload {
+        JwtPayload::from_map(claims.as_object().unwrap().clone()).unwrap()
+    }
+
+    #[test]
+    fn when_claims_are_given_then_default_claim_mapping_returns_nebula_claim() {
+        let payload =
+            payload(json!({ "sub": "alice", "wmn": "finance", "attributes": { "team": "payment" }, "role": "admin" }));
+
+        let claim = ClaimMapping::default().claim(&payload).unwrap();
+
+        assert_eq!(claim.gid, "alice");
+        assert_eq!(claim.workspace_name, "finance");
+        assert_eq!(claim.attributes.get("team").map(String::as_str), Some("payment"));
+        assert_eq!(claim.role, Role::Admin);
+    }
+
+    #[test]
+    fn when_claims_are_mapped_then_claim_mapping_reads_mapped_claims() {
+        let claim_mapping = ClaimMapping {
+            gid: "email".to_owned(),
+            workspace_name: "nebula_workspace".to_owned(),
+            attributes: "nebula_attributes".to_owned(),
+            default_role: Some(Role::Member),
+            ..Default::default()
+        };
+        let payload = payload(json!({
+            "sub": "0001",
+            "email": "alice@example.com",
+            "nebula_workspace": "finance",
+            "nebula_attributes": {}
+        }));
+
+        let claim = claim_mapping.claim(&payload).unwrap();
+
+        assert_eq!(claim.gid, "alice@example.com");
+        assert_eq!(claim.workspace_name, "finance");
+        assert_eq!(claim.role, Role::Member);
+    }
+
+    #[test]
+    fn when_claim_is_missing_then_claim_mapping_returns_missing_claim_err() {
+        let payload = payload(json!({ "sub": "alice", "attributes": {}, "role": "admin" }));
+
+        let result = ClaimMapping::default().claim(&payload);
+
+        assert!(matches!(result, Err(JWTError::MissingClaim(claim)) if claim == "wmn"));
+    }
+
+    #[test]
+    fn when_role_claim_is_missing_without_default_role_then_claim_mapping_returns_missing_claim_err() {
+        let payload = payload(json!({ "sub": "alice", "wmn": "finance", "attributes": {} }));
+
+        let result = ClaimMapping::default().claim(&payload);
+
+        assert!(matches!(result, Err(JWTError::MissingClaim(claim)) if claim == "role"));
+    }
+
+    #[test]
+    fn when_attributes_are_not_strings_then_claim_mapping_returns_invalid_jwt_format_err() {
+        let payload =
+            payload(json!({ "sub": "alice", "wmn": "finance", "attributes": { "level": 3 }, "role": "member" }));
+
+        let result = ClaimMapping::default().claim(&payload);
+
+        assert!(matches!(result, Err(JWTError::InvalidJwtFormat(_))));
+    }
+}
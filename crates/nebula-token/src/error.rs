@@ -25,5 +25,5 @@ pub enum JWTError {
     InvalidSignature(JoseError),
 
     #[error("missing claim '{0}'")]
-    MissingClaim(&'static str),
+    MissingClaim(String),
 }
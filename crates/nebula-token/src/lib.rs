@@ -4,3 +4,4 @@ pub mod claim;
 pub mod error;
 pub mod jwk;
 pub mod jwt;
+pub mod rate_limit;
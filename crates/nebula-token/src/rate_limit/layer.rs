@@ -0,0 +1,26 @@
+use std::{collections::HashMap, sync::Arc};
+
+use tower::Layer;
+
+use super::{service::NebulaRateLimitService, RateLimitQuota, RateLimiter};
+
+/// Rate limits requests per `(workspace, gid)` of the [`NebulaClaim`](crate::claim::NebulaClaim).
+/// It must be applied inside of the `NebulaAuthLayer` so that the claim is available.
+#[derive(Clone)]
+pub struct NebulaRateLimitLayer {
+    pub limiter: Arc<RateLimiter>,
+}
+
+impl NebulaRateLimitLayer {
+    pub fn new(default_quota: RateLimitQuota, workspace_quotas: HashMap<String, RateLimitQuota>) -> Self {
+        Self { limiter: Arc::new(RateLimiter::new(default_quota, workspace_quotas)) }
+    }
+}
+
+impl<S> Layer<S> for NebulaRateLimitLayer {
+    type Service = NebulaRateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        NebulaRateLimitService::new(inner, self)
+    }
+}
@@ -0,0 +1,214 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use serde::Deserialize;
+
+pub mod layer;
+pub mod service;
+
+pub use layer::NebulaRateLimitLayer;
+
+/// Buckets tracked at most, beyond which the least recently used bucket is dropped for a new one.
+const MAX_TRACKED_BUCKETS: usize = 10_000;
+
+/// Token bucket quota applied to each `(workspace, gid)` pair.
+/// A `requests_per_second` of `0` disables rate limiting.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitQuota {
+    pub requests_per_second: u32,
+    pub burst: Option<u32>,
+}
+
+impl RateLimitQuota {
+    fn capacity(&self) -> f64 {
+        self.burst.unwrap_or(self.requests_per_second).max(1) as f64
+    }
+}
+
+struct Bucket {
+    tokens: f64,
+    last_refilled_at: Instant,
+}
+
+impl Bucket {
+    /// Refills the tokens for the time elapsed since the last refill and takes one in a single step.
+    fn take(&mut self, now: Instant, rate: f64, capacity: f64) -> Result<(), Duration> {
+        let elapsed = now.saturating_duration_since(self.last_refilled_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * rate).min(capacity);
+        self.last_refilled_at = self.last_refilled_at.max(now);
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - self.tokens) / rate))
+        }
+    }
+}
+
+pub struct RateLimiter {
+    default_quota: RateLimitQuota,
+    workspace_quotas: HashMap<String, RateLimitQuota>,
+    buckets: Mutex<HashMap<(String, String), Bucket>>,
+}
+
+impl RateLimiter {
+    pub fn new(default_quota: RateLimitQuota, workspace_quotas: HashMap<String, RateLimitQuota>) -> Self {
+        Self { default_quota, workspace_quotas, buckets: Mutex::new(HashMap::new()) }
+    }
+
+    pub fn quota(&self, workspace_name: &str) -> &RateLimitQuota {
+        self.workspace_quotas.get(workspace_name).unwrap_or(&self.default_quota)
+    }
+
+    /// Takes a token from the bucket of `(workspace_name, gid)`.
+    /// Returns the duration to wait before the next request is allowed when the bucket is empty.
+    /// The bucket is looked up, refilled and taken from while holding the lock, so concurrent requests never take more
+    /// tokens than the bucket holds.
+    pub fn acquire(&self, workspace_name: &str, gid: &str) -> Result<(), Duration> {
+        self.acquire_at(workspace_name, gid, Instant::now())
+    }
+
+    fn acquire_at(&self, workspace_name: &str, gid: &str, now: Instant) -> Result<(), Duration> {
+        let quota = *self.quota(workspace_name);
+        if quota.requests_per_second == 0 {
+            return Ok(());
+        }
+
+        let rate = quota.requests_per_second as f64;
+        let capacity = quota.capacity();
+
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        let key = (workspace_name.to_owned(), gid.to_owned());
+        if !buckets.contains_key(&key) && buckets.len() >= MAX_TRACKED_BUCKETS {
+            self.evict(&mut buckets, now);
+        }
+
+        buckets
+            .entry(key)
+            .or_insert_with(|| Bucket { tokens: capacity, last_refilled_at: now })
+            .take(now, rate, capacity)
+    }
+
+    /// Drops the buckets idle long enough to be full again, which are the same as new ones. If none is, the least
+    /// recently used bucket is dropped, so that requests of many distinct users can't grow the buckets without bound.
+    fn evict(&self, buckets: &mut HashMap<(String, String), Bucket>, now: Instant) {
+        buckets.retain(|(workspace_name, _), bucket| {
+            let quota = self.quota(workspace_name);
+            let refill_duration = quota.capacity() / quota.requests_per_second.max(1) as f64;
+            now.saturating_duration_since(bucket.last_refilled_at).as_secs_f64() < refill_duration
+        });
+
+        if buckets.len() >= MAX_TRACKED_BUCKETS {
+            let least_recently_used =
+                buckets.iter().min_by_key(|(_, bucket)| bucket.last_refilled_at).map(|(key, _)| key.clone());
+            if let Some(least_recently_used) = least_recently_used {
+                buckets.remove(&least_recently_used);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        collections::HashMap,
+        sync::atomic::{AtomicUsize, Ordering},
+        time::{Duration, Instant},
+    };
+
+    use super::{RateLimitQuota, RateLimiter, MAX_TRACKED_BUCKETS};
+
+    fn limiter(requests_per_second: u32, burst: Option<u32>) -> RateLimiter {
+        RateLimiter::new(RateLimitQuota { requests_per_second, burst }, HashMap::new())
+    }
+
+    #[test]
+    fn when_burst_is_used_up_then_request_is_rejected_until_next_token() {
+        let limiter = limiter(2, Some(3));
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            assert!(limiter.acquire_at("workspace", "gid", now).is_ok());
+        }
+        let retry_after = limiter.acquire_at("workspace", "gid", now).unwrap_err();
+
+        assert_eq!(retry_after, Duration::from_millis(500));
+    }
+
+    #[test]
+    fn when_time_passes_then_tokens_are_refilled_up_to_burst() {
+        let limiter = limiter(2, Some(3));
+        let now = Instant::now();
+        for _ in 0..3 {
+            limiter.acquire_at("workspace", "gid", now).unwrap();
+        }
+
+        assert!(limiter.acquire_at("workspace", "gid", now + Duration::from_millis(500)).is_ok());
+        assert!(limiter.acquire_at("workspace", "gid", now + Duration::from_millis(500)).is_err());
+
+        let later = now + Duration::from_secs(60);
+        for _ in 0..3 {
+            assert!(limiter.acquire_at("workspace", "gid", later).is_ok());
+        }
+        assert!(limiter.acquire_at("workspace", "gid", later).is_err());
+    }
+
+    #[test]
+    fn when_users_or_workspaces_differ_then_buckets_are_separate() {
+        let limiter = RateLimiter::new(
+            RateLimitQuota { requests_per_second: 1, burst: None },
+            HashMap::from([("unlimited".to_owned(), RateLimitQuota { requests_per_second: 0, burst: None })]),
+        );
+        let now = Instant::now();
+
+        assert!(limiter.acquire_at("workspace", "gid", now).is_ok());
+        assert!(limiter.acquire_at("workspace", "gid", now).is_err());
+        assert!(limiter.acquire_at("workspace", "other", now).is_ok());
+        assert!(limiter.acquire_at("other", "gid", now).is_ok());
+        for _ in 0..10 {
+            assert!(limiter.acquire_at("unlimited", "gid", now).is_ok());
+        }
+    }
+
+    #[test]
+    fn when_requests_are_concurrent_then_no_more_than_burst_is_allowed() {
+        let limiter = limiter(1, Some(50));
+        let now = Instant::now();
+        let allowed = AtomicUsize::new(0);
+
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| {
+                    for _ in 0..20 {
+                        if limiter.acquire_at("workspace", "gid", now).is_ok() {
+                            allowed.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                });
+            }
+        });
+
+        assert_eq!(allowed.load(Ordering::Relaxed), 50);
+    }
+
+    #[test]
+    fn when_buckets_of_busy_users_reach_limit_then_least_recently_used_bucket_is_dropped() {
+        let limiter = limiter(1, None);
+        let now = Instant::now();
+        for i in 0..MAX_TRACKED_BUCKETS {
+            limiter.acquire_at("workspace", &format!("gid-{i}"), now + Duration::from_micros(i as u64)).unwrap();
+        }
+
+        assert!(limiter.acquire_at("workspace", "new", now + Duration::from_millis(100)).is_ok());
+
+        let buckets = limiter.buckets.lock().unwrap();
+        assert_eq!(buckets.len(), MAX_TRACKED_BUCKETS);
+        assert!(!buckets.contains_key(&("workspace".to_owned(), "gid-0".to_owned())));
+        assert!(buckets.contains_key(&("workspace".to_owned(), "gid-1".to_owned())));
+        assert!(buckets.contains_key(&("workspace".to_owned(), "new".to_owned())));
+    }
+}
@@ -0,0 +1,63 @@
+use std::{
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use axum::{
+    body::Body,
+    extract::Request,
+    http::{header::RETRY_AFTER, StatusCode},
+    response::IntoResponse,
+};
+use futures_util::future::BoxFuture;
+
+use crate::claim::NebulaClaim;
+
+use super::layer::NebulaRateLimitLayer;
+
+#[derive(Clone)]
+pub struct NebulaRateLimitService<S> {
+    inner: S,
+    layer: NebulaRateLimitLayer,
+}
+
+impl<S> NebulaRateLimitService<S> {
+    pub fn new(inner: S, layer: &NebulaRateLimitLayer) -> Self {
+        Self { inner, layer: layer.clone() }
+    }
+}
+
+impl<S> tower::Service<Request<Body>> for NebulaRateLimitService<S>
+where
+    S: tower::Service<Request<Body>, Response = axum::response::Response> + Send + Clone + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        let result = match request.extensions().get::<NebulaClaim>() {
+            Some(claim) => self.layer.limiter.acquire(&claim.workspace_name, &claim.gid),
+            None => Ok(()),
+        };
+
+        match result {
+            Ok(()) => {
+                let mut inner = self.inner.clone();
+                Box::pin(async move { inner.call(request).await })
+            }
+            Err(retry_after) => Box::pin(async move { Ok(too_many_requests(retry_after)) }),
+        }
+    }
+}
+
+fn too_many_requests(retry_after: Duration) -> axum::response::Response {
+    let retry_after_seconds = retry_after.as_secs_f64().ceil().max(1.0) as u64;
+    (StatusCode::TOO_MANY_REQUESTS, [(RETRY_AFTER, retry_after_seconds.to_string())], "Too many requests")
+        .into_response()
+}
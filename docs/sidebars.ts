@@ -98,6 +98,11 @@ const sidebars: SidebarsConfig = {
           label: "Authority server",
           id: "config/authority",
         },
+        {
+          type: "doc",
+          label: "All-in-one server",
+          id: "config/server",
+        },
       ],
     },
   ],